@@ -2,15 +2,6 @@ use std::marker::PhantomData;
 
 use crate::{reporter::ErrorReporter, EDeserialize};
 
-#[derive(Debug)]
-pub struct MissingFieldError(&'static str);
-
-impl std::fmt::Display for MissingFieldError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "missing field `{}`", self.0)
-    }
-}
-
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Default)]
 pub enum MaybeInvalidOrMissing<T> {
     Valid(PhantomData<T>),
@@ -22,11 +13,70 @@ pub enum MaybeInvalidOrMissing<T> {
 impl<T> MaybeInvalidOrMissing<T> {
     pub fn push_error_if_missing(&self, field_name: &'static str) {
         if let Self::Missing = self {
-            ErrorReporter::report(MissingFieldError(field_name));
+            ErrorReporter::report_missing_field(field_name);
+        }
+    }
+
+    /// Like [`push_error_if_missing`](Self::push_error_if_missing), but mirrors `serde`'s
+    /// handling of missing fields: types that accept an absent value, e.g. `Option<T>`,
+    /// don't trigger an error.
+    ///
+    /// Can't be used for fields with a custom `deserialize_with` function, since `serde`
+    /// reports those as missing unconditionally.
+    pub fn push_error_if_missing_and_required<'de>(&self, field_name: &'static str)
+    where
+        T: serde::Deserialize<'de>,
+    {
+        if let Self::Missing = self {
+            if T::deserialize(MissingFieldDeserializer(field_name)).is_err() {
+                ErrorReporter::report_missing_field(field_name);
+            }
         }
     }
 }
 
+/// Used by `#[eserde(code = "..")]` fields.
+///
+/// Assigns `code` to all errors reported by `f`, including the ones about the field
+/// being missing, unless a nested field already assigned its own.
+pub fn with_code<R>(code: &'static str, f: impl FnOnce() -> R) -> R {
+    let n_errors = ErrorReporter::n_errors();
+    let output = f();
+    ErrorReporter::assign_code(n_errors, code);
+    output
+}
+
+/// A deserializer for a missing field, equivalent to the one `serde` uses
+/// internally.
+///
+/// It deserializes `Option`s as `None` and fails with a "missing field" error
+/// for everything else.
+struct MissingFieldDeserializer(&'static str);
+
+impl<'de> serde::Deserializer<'de> for MissingFieldDeserializer {
+    type Error = serde::de::value::Error;
+
+    fn deserialize_any<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        Err(serde::de::Error::missing_field(self.0))
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        visitor.visit_none()
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
 /// Used by `#[eserde(compat)]` fields (NO `#[serde(default)]`).
 impl<'de, T> serde::Deserialize<'de> for MaybeInvalidOrMissing<T>
 where
@@ -78,6 +128,9 @@ impl<T> MaybeInvalid<T> {
     /// between `MaybeInvalid` and `MaybeInvalidOrMissing`.
     /// To be removed in the future.
     pub fn push_error_if_missing(&self, _field_name: &'static str) {}
+
+    /// See [`push_error_if_missing`](Self::push_error_if_missing).
+    pub fn push_error_if_missing_and_required(&self, _field_name: &'static str) {}
 }
 
 /// Used by `#[eserde(compat)]` `#[serde(default)]` fields.
@@ -100,6 +153,32 @@ where
     }
 }
 
+/// Used by `#[serde(flatten)]` fields (NO `#[serde(default)]`).
+///
+/// `serde` deserializes flattened fields out of the entries it buffered for the
+/// parent, bypassing the path tracking of the parent's deserializer: the entries
+/// are tracked again, so that errors point inside the flattened value.
+pub fn flattened_maybe_invalid_or_missing<'de, D, T>(
+    deserializer: D,
+) -> Result<MaybeInvalidOrMissing<T>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: EDeserialize<'de>,
+{
+    maybe_invalid_or_missing(crate::path::Deserializer::new(deserializer))
+}
+
+/// Used by `#[serde(flatten)]` `#[serde(default)]` fields.
+///
+/// See [`flattened_maybe_invalid_or_missing`] for more details.
+pub fn flattened_maybe_invalid<'de, D, T>(deserializer: D) -> Result<MaybeInvalid<T>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: EDeserialize<'de>,
+{
+    maybe_invalid(crate::path::Deserializer::new(deserializer))
+}
+
 /// Used by `#[serde(default, deserialize_with = "..")]` fields.
 pub fn maybe_invalid<'de, D, T>(deserializer: D) -> Result<MaybeInvalid<T>, D::Error>
 where
@@ -63,6 +63,9 @@ impl std::error::Error for DeserializationErrors {}
 pub struct DeserializationError {
     pub(crate) path: Option<Path>,
     pub(crate) details: String,
+    pub(crate) code: Option<&'static str>,
+    pub(crate) missing_field: Option<&'static str>,
+    pub(crate) syntax: bool,
 }
 
 impl DeserializationError {
@@ -78,6 +81,31 @@ impl DeserializationError {
     pub fn path(&self) -> Option<&Path> {
         self.path.as_ref()
     }
+
+    /// The machine code assigned to the error via `#[eserde(code = "..")]`, if any.
+    ///
+    /// It is set for all errors reported while deserializing an annotated field,
+    /// including the ones nested inside its value.
+    pub fn code(&self) -> Option<&str> {
+        self.code
+    }
+
+    /// The name of the required field that was missing from the input, if that's
+    /// what the error is about.
+    ///
+    /// The path points at the value lacking the field, e.g. `bar` if the sub-field
+    /// `foo` of the top-level field `bar` is missing.
+    pub fn missing_field(&self) -> Option<&str> {
+        self.missing_field
+    }
+
+    /// Returns `true` if the error is about the input not being well-formed,
+    /// e.g. a JSON document lacking its closing brace, rather than about its content.
+    ///
+    /// Only the deserializers of the [`json`](crate::json) module tell syntax errors apart.
+    pub fn is_syntax(&self) -> bool {
+        self.syntax
+    }
 }
 
 impl std::fmt::Display for DeserializationError {
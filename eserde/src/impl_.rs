@@ -273,10 +273,11 @@ macro_rules! impl_edeserialize_seq {
                     Ok(vec) => {
                         $(
                             if vec.len() != $n {
+                                // Same message as `serde`'s, so that it's recognized as a length error.
                                 $crate::reporter::ErrorReporter::report(::std::format!(
-                                    "expected sequence of {} elements, found {} elements.",
-                                    $n,
+                                    "invalid length {}, expected an array of length {}",
                                     vec.len(),
+                                    $n,
                                 ));
                             }
                         )?
@@ -339,6 +340,104 @@ impl_edeserialize_seq! {
     Vec<T> {T},
 }
 
+/// Implements [`crate::EDeserialize`] on tuples by falling back to the `EDeserialize` implementation
+/// of each element, reporting the tuple's length if it doesn't match.
+macro_rules! impl_edeserialize_tuple {
+    ( $( $len:literal => ( $( $n:literal $g:ident )+ ) )+ ) => {
+        $(
+            impl<'de, $( $g ),+ > $crate::EDeserialize<'de> for ( $( $g , )+ )
+            where
+                $( $g : $crate::EDeserialize<'de>, )+
+            {
+                fn deserialize_for_errors<D>(deserializer: D) -> Result<(), ()>
+                where
+                    D: $crate::_serde::Deserializer<'de>,
+                {
+                    // Wrapper which always succeeds but reports errors.
+                    struct Wrapper<T>(::std::marker::PhantomData<T>);
+                    impl<'de, T> $crate::_serde::de::Deserialize<'de> for Wrapper<T>
+                    where
+                        T: $crate::EDeserialize<'de>,
+                    {
+                        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+                        where
+                            D: $crate::_serde::Deserializer<'de>,
+                        {
+                            let _ = T::deserialize_for_errors(deserializer);
+                            Ok(Self(::std::marker::PhantomData))
+                        }
+                    }
+
+                    struct TupleVisitor< $( $g ),+ >(::std::marker::PhantomData<( $( $g , )+ )>);
+                    impl<'de, $( $g ),+ > $crate::_serde::de::Visitor<'de> for TupleVisitor< $( $g ),+ >
+                    where
+                        $( $g : $crate::EDeserialize<'de>, )+
+                    {
+                        type Value = ();
+
+                        fn expecting(&self, formatter: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                            write!(formatter, "a tuple of size {}", $len)
+                        }
+
+                        fn visit_seq<A>(self, mut seq: A) -> Result<(), A::Error>
+                        where
+                            A: $crate::_serde::de::SeqAccess<'de>,
+                        {
+                            let mut len = 0usize;
+                            $(
+                                if len == $n && seq.next_element::<Wrapper< $g >>()?.is_some() {
+                                    len += 1;
+                                }
+                            )+
+                            // Count the extra elements, if any.
+                            if len == $len {
+                                while seq.next_element::<$crate::_serde::de::IgnoredAny>()?.is_some() {
+                                    len += 1;
+                                }
+                            }
+                            if len != $len {
+                                $crate::reporter::ErrorReporter::report(
+                                    <$crate::_serde::de::value::Error as $crate::_serde::de::Error>::invalid_length(len, &self),
+                                );
+                            }
+                            Ok(())
+                        }
+                    }
+
+                    let n_errors = $crate::reporter::ErrorReporter::n_errors();
+                    if let Err(err) = deserializer.deserialize_tuple($len, TupleVisitor::<$( $g ),+>(::std::marker::PhantomData)) {
+                        $crate::reporter::ErrorReporter::report(err);
+                    }
+                    if $crate::reporter::ErrorReporter::n_errors() > n_errors {
+                        Err(())
+                    } else {
+                        Ok(())
+                    }
+                }
+            }
+        )+
+    };
+}
+
+impl_edeserialize_tuple! {
+    1 => (0 T0)
+    2 => (0 T0 1 T1)
+    3 => (0 T0 1 T1 2 T2)
+    4 => (0 T0 1 T1 2 T2 3 T3)
+    5 => (0 T0 1 T1 2 T2 3 T3 4 T4)
+    6 => (0 T0 1 T1 2 T2 3 T3 4 T4 5 T5)
+    7 => (0 T0 1 T1 2 T2 3 T3 4 T4 5 T5 6 T6)
+    8 => (0 T0 1 T1 2 T2 3 T3 4 T4 5 T5 6 T6 7 T7)
+    9 => (0 T0 1 T1 2 T2 3 T3 4 T4 5 T5 6 T6 7 T7 8 T8)
+    10 => (0 T0 1 T1 2 T2 3 T3 4 T4 5 T5 6 T6 7 T7 8 T8 9 T9)
+    11 => (0 T0 1 T1 2 T2 3 T3 4 T4 5 T5 6 T6 7 T7 8 T8 9 T9 10 T10)
+    12 => (0 T0 1 T1 2 T2 3 T3 4 T4 5 T5 6 T6 7 T7 8 T8 9 T9 10 T10 11 T11)
+    13 => (0 T0 1 T1 2 T2 3 T3 4 T4 5 T5 6 T6 7 T7 8 T8 9 T9 10 T10 11 T11 12 T12)
+    14 => (0 T0 1 T1 2 T2 3 T3 4 T4 5 T5 6 T6 7 T7 8 T8 9 T9 10 T10 11 T11 12 T12 13 T13)
+    15 => (0 T0 1 T1 2 T2 3 T3 4 T4 5 T5 6 T6 7 T7 8 T8 9 T9 10 T10 11 T11 12 T12 13 T13 14 T14)
+    16 => (0 T0 1 T1 2 T2 3 T3 4 T4 5 T5 6 T6 7 T7 8 T8 9 T9 10 T10 11 T11 12 T12 13 T13 14 T14 15 T15)
+}
+
 /// Implements [`crate::EDeserialize`] on the given sequence type by falling back to `T`'s `EDeserialize` implementation.
 macro_rules! impl_edeserialize_map {
     () => {};
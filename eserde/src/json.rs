@@ -57,6 +57,10 @@
 //! us to perform two passes over the input.\
 //! We are restricted to input types that are buffered in memory (byte slices,
 //! string slices, etc.).
+use std::cell::Cell;
+
+use serde::de::{DeserializeSeed, IgnoredAny, Visitor};
+
 use crate::{
     impl_edeserialize_compat, path, reporter::ErrorReporter, DeserializationError,
     DeserializationErrors, EDeserialize,
@@ -64,6 +68,8 @@ use crate::{
 
 /// Deserialize an instance of type `T` from a string of JSON text.
 ///
+/// Like [`serde_json::from_str`], it fails if anything but whitespace follows the value.
+///
 /// # Example
 ///
 /// ```rust
@@ -90,7 +96,7 @@ where
     T: EDeserialize<'a>,
 {
     let mut de = serde_json::Deserializer::from_str(s);
-    let error = match T::deserialize(&mut de) {
+    let error = match T::deserialize(&mut de).and_then(|v| de.end().map(|()| v)) {
         Ok(v) => {
             return Ok(v);
         }
@@ -99,16 +105,23 @@ where
     let _guard = ErrorReporter::start_deserialization();
 
     let mut de = serde_json::Deserializer::from_str(s);
-    let de = path::Deserializer::new(&mut de);
+    let syntax = Cell::new(false);
+    let de = path::Deserializer::new(WatchSyntax {
+        de: &mut de,
+        syntax: &syntax,
+    });
 
     let errors = match T::deserialize_for_errors(de) {
         Ok(_) => vec![],
-        Err(_) => ErrorReporter::take_errors(),
+        Err(_) => take_errors(syntax.get() && is_malformed(s.as_bytes())),
     };
     let errors = if errors.is_empty() {
         vec![DeserializationError {
             path: None,
             details: error.to_string(),
+            code: None,
+            missing_field: None,
+            syntax: is_syntax(&error),
         }]
     } else {
         errors
@@ -119,6 +132,8 @@ where
 
 /// Deserialize an instance of type `T` from bytes of JSON text.
 ///
+/// Like [`serde_json::from_slice`], it fails if anything but whitespace follows the value.
+///
 /// # Example
 ///
 /// ```rust
@@ -145,7 +160,7 @@ where
     T: EDeserialize<'a>,
 {
     let mut de = serde_json::Deserializer::from_slice(s);
-    let error = match T::deserialize(&mut de) {
+    let error = match T::deserialize(&mut de).and_then(|v| de.end().map(|()| v)) {
         Ok(v) => {
             return Ok(v);
         }
@@ -154,16 +169,23 @@ where
     let _guard = ErrorReporter::start_deserialization();
 
     let mut de = serde_json::Deserializer::from_slice(s);
-    let de = path::Deserializer::new(&mut de);
+    let syntax = Cell::new(false);
+    let de = path::Deserializer::new(WatchSyntax {
+        de: &mut de,
+        syntax: &syntax,
+    });
 
     let errors = match T::deserialize_for_errors(de) {
         Ok(_) => vec![],
-        Err(_) => ErrorReporter::take_errors(),
+        Err(_) => take_errors(syntax.get() && is_malformed(s)),
     };
     let errors = if errors.is_empty() {
         vec![DeserializationError {
             path: None,
             details: error.to_string(),
+            code: None,
+            missing_field: None,
+            syntax: is_syntax(&error),
         }]
     } else {
         errors
@@ -172,8 +194,244 @@ where
     Err(DeserializationErrors::from(errors))
 }
 
+/// Deserialize an instance of type `T` from bytes of JSON text,
+/// stopping at the first error.
+///
+/// Unlike [`from_slice`], it doesn't walk the input a second time to accumulate
+/// errors on failure: the outcome contains a single error, located via its path.
+/// It's a cheaper option for hot paths, when reporting every problem at once
+/// isn't worth the extra work.
+///
+/// # Example
+///
+/// ```rust
+/// #[derive(eserde::Deserialize, Debug)]
+/// struct User {
+///     fingerprint: String,
+///     age: u8,
+/// }
+///
+/// # fn main() {
+/// let j = br#"{ "fingerprint": 1, "age": -1 }"#;
+///
+/// let errors = eserde::json::from_slice_fail_fast::<User>(j).unwrap_err();
+/// assert_eq!(errors.len(), 1);
+/// assert_eq!(errors.iter().next().unwrap().path().unwrap().to_string(), "fingerprint");
+/// # }
+/// ```
+pub fn from_slice_fail_fast<'a, T>(s: &'a [u8]) -> Result<T, DeserializationErrors>
+where
+    T: EDeserialize<'a>,
+{
+    let _guard = ErrorReporter::start_deserialization();
+
+    let mut de = serde_json::Deserializer::from_slice(s);
+
+    match T::deserialize(path::Deserializer::new(&mut de)).and_then(|v| de.end().map(|()| v)) {
+        Ok(v) => Ok(v),
+        Err(e) => {
+            let syntax = is_syntax(&e);
+            ErrorReporter::report(e);
+            Err(DeserializationErrors::from(take_errors(syntax)))
+        }
+    }
+}
+
+/// Deserialize a `S::Value` from bytes of JSON text via `seed`, a [`DeserializeSeed`]
+/// carrying the context the value depends on—e.g. the set of values a field accepts.
+///
+/// The seed is only invoked once. If it fails, the input is visited a second time, like
+/// in [`from_slice`], to accumulate the errors that don't depend on the seed via the
+/// [`EDeserialize`] implementation of `S::Value`. The error of the seed comes first,
+/// unless it's one of them: it's the first problem the seed ran into, while the
+/// context-free pass can't spot more problems than the seed itself.
+///
+/// # Example
+///
+/// ```rust
+/// use serde::de::{DeserializeSeed, Deserializer, Error};
+///
+/// /// Deserialize a `u8` no greater than the given maximum.
+/// struct AtMost(u8);
+///
+/// impl<'de> DeserializeSeed<'de> for AtMost {
+///     type Value = u8;
+///
+///     fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<u8, D::Error> {
+///         let value: u8 = serde::Deserialize::deserialize(deserializer)?;
+///         if value > self.0 {
+///             return Err(D::Error::custom(format!("expected at most {}", self.0)));
+///         }
+///         Ok(value)
+///     }
+/// }
+///
+/// # fn main() {
+/// assert_eq!(eserde::json::from_slice_seed(AtMost(10), b"7").unwrap(), 7);
+///
+/// let errors = eserde::json::from_slice_seed(AtMost(10), b"12").unwrap_err();
+/// assert_eq!(errors.iter().next().unwrap().message(), "expected at most 10");
+/// # }
+/// ```
+pub fn from_slice_seed<'a, S>(seed: S, s: &'a [u8]) -> Result<S::Value, DeserializationErrors>
+where
+    S: DeserializeSeed<'a>,
+    S::Value: EDeserialize<'a>,
+{
+    let _guard = ErrorReporter::start_deserialization();
+
+    // The seed can't be invoked twice: track the path right away, to locate its error.
+    let mut de = serde_json::Deserializer::from_slice(s);
+    let error = match seed.deserialize(path::Deserializer::new(&mut de)) {
+        Ok(v) => match de.end() {
+            Ok(()) => return Ok(v),
+            // Trailing data: the value itself is fine, there's nothing else to look for.
+            Err(e) => {
+                return Err(DeserializationErrors::from(vec![DeserializationError {
+                    path: None,
+                    details: e.to_string(),
+                    code: None,
+                    missing_field: None,
+                    syntax: is_syntax(&e),
+                }]));
+            }
+        },
+        Err(e) => {
+            let syntax = is_syntax(&e);
+            ErrorReporter::report(e);
+            let mut errors = take_errors(syntax);
+            errors
+                .pop()
+                .expect("The error of the seed was just reported")
+        }
+    };
+
+    let mut de = serde_json::Deserializer::from_slice(s);
+    let syntax = Cell::new(false);
+    let de = path::Deserializer::new(WatchSyntax {
+        de: &mut de,
+        syntax: &syntax,
+    });
+    let mut errors = match S::Value::deserialize_for_errors(de) {
+        Ok(_) => vec![],
+        Err(_) => take_errors(syntax.get() && is_malformed(s)),
+    };
+    let seen = errors
+        .iter()
+        .any(|e| e.path == error.path && e.details == error.details);
+    if !seen {
+        errors.insert(0, error);
+    }
+
+    Err(DeserializationErrors::from(errors))
+}
+
+/// Take the errors reported so far, flagging the last one as a syntax error if `syntax` is set.
+///
+/// `serde_json` can't go past a syntax error: it aborts deserialization, hence it's
+/// the last error to be reported.
+fn take_errors(syntax: bool) -> Vec<DeserializationError> {
+    let mut errors = ErrorReporter::take_errors();
+    if let Some(last) = errors.last_mut() {
+        last.syntax = syntax;
+    }
+    errors
+}
+
+/// Whether `s` isn't well-formed JSON.
+///
+/// Once it has recovered from an error, the pass collecting errors may lose track of the
+/// structure of the input and trip over a well-formed document: its syntax errors are
+/// confirmed by a pass that doesn't look at the content.
+fn is_malformed(s: &[u8]) -> bool {
+    serde_json::from_slice::<IgnoredAny>(s).is_err()
+}
+
+fn is_syntax(error: &serde_json::Error) -> bool {
+    error.is_syntax() || error.is_eof()
+}
+
+/// Forwards to the top-level `serde_json` deserializer, recording whether
+/// the error it fails with, if any, is a syntax error.
+///
+/// `eserde` recovers from the other errors along the way: the ones reaching the top
+/// level are those that aborted deserialization.
+struct WatchSyntax<'a, D> {
+    de: D,
+    syntax: &'a Cell<bool>,
+}
+
+macro_rules! watch_syntax {
+    ($($method:ident($($arg:ident: $ty:ty),*);)*) => {
+        $(
+            fn $method<V>(self, $($arg: $ty,)* visitor: V) -> Result<V::Value, Self::Error>
+            where
+                V: Visitor<'de>,
+            {
+                let result = self.de.$method($($arg,)* visitor);
+                if let Err(error) = &result {
+                    self.syntax.set(is_syntax(error));
+                }
+                result
+            }
+        )*
+    };
+}
+
+impl<'de, D> serde::Deserializer<'de> for WatchSyntax<'_, D>
+where
+    D: serde::Deserializer<'de, Error = serde_json::Error>,
+{
+    type Error = serde_json::Error;
+
+    watch_syntax! {
+        deserialize_any();
+        deserialize_bool();
+        deserialize_i8();
+        deserialize_i16();
+        deserialize_i32();
+        deserialize_i64();
+        deserialize_i128();
+        deserialize_u8();
+        deserialize_u16();
+        deserialize_u32();
+        deserialize_u64();
+        deserialize_u128();
+        deserialize_f32();
+        deserialize_f64();
+        deserialize_char();
+        deserialize_str();
+        deserialize_string();
+        deserialize_bytes();
+        deserialize_byte_buf();
+        deserialize_option();
+        deserialize_unit();
+        deserialize_unit_struct(name: &'static str);
+        deserialize_newtype_struct(name: &'static str);
+        deserialize_seq();
+        deserialize_tuple(len: usize);
+        deserialize_tuple_struct(name: &'static str, len: usize);
+        deserialize_map();
+        deserialize_struct(name: &'static str, fields: &'static [&'static str]);
+        deserialize_enum(name: &'static str, variants: &'static [&'static str]);
+        deserialize_identifier();
+        deserialize_ignored_any();
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.de.is_human_readable()
+    }
+}
+
 impl_edeserialize_compat! {
     serde_json::value::Number,
     serde_json::value::Value,
     serde_json::value::Map<String, serde_json::value::Value>,
 }
+
+// Raw values capture any valid JSON, they can't fail on their own.
+#[cfg(feature = "raw_value")]
+impl_edeserialize_compat! {
+    Box<serde_json::value::RawValue>,
+    &'de serde_json::value::RawValue,
+}
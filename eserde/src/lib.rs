@@ -153,6 +153,10 @@
 //! `eserde::json` doesn't support deserializing from a reader, i.e. there is no equivalent to
 //! `serde_json::from_reader`.
 //!
+//! Enable the `raw_value` feature to capture fields as `Box<RawValue>` (or `&RawValue`),
+//! keeping their raw JSON for later re-parsing. Raw values accept any valid JSON,
+//! so only the surrounding structure can fail to deserialize.
+//!
 //! There is also an `axum` integration, [`eserde_axum`](https://docs.rs/eserde_axum).
 //! It provides an `eserde`-powered JSON extractor as a drop-in replacement for `axum`'s built-in
 //! one.
@@ -250,6 +254,9 @@
 //!         vec![DeserializationError {
 //!             path: None,
 //!             details: error.to_string(),
+//!             code: None,
+//!             missing_field: None,
+//!             syntax: false,
 //!         }]
 //!     } else {
 //!         errors
@@ -307,6 +314,10 @@ pub mod json;
 #[cfg_attr(docsrs, doc(cfg(feature = "toml")))]
 pub mod toml;
 
+#[cfg(feature = "urlencoded")]
+#[cfg_attr(docsrs, doc(cfg(feature = "urlencoded")))]
+pub mod urlencoded;
+
 mod errors;
 mod impl_;
 pub mod path;
@@ -440,6 +451,26 @@ pub mod _macro_impl;
 /// There's a catch, though: you'll get at most one deserialization error from a field annotated
 /// with `#[eserde(compat)]`, since we can't rely on [`EDeserialize`]'s error machinery.
 ///
+/// ## Error codes
+///
+/// Use `#[eserde(code = "...")]` to pin the machine code of the errors reported for a field,
+/// whatever went wrong—e.g. for an API contract that expects `AGE_INVALID` for all `age` failures:
+///
+/// ```rust
+/// #[derive(Debug, eserde::Deserialize)]
+/// struct Signup {
+///     #[eserde(code = "AGE_INVALID")]
+///     age: u8,
+/// }
+///
+/// let errors = eserde::json::from_str::<Signup>(r#"{"age": 300}"#).unwrap_err();
+/// let error = errors.iter().next().unwrap();
+/// assert_eq!(error.code(), Some("AGE_INVALID"));
+/// ```
+///
+/// The code is exposed via [`DeserializationError::code`]. It also applies to errors nested
+/// inside the field's value, unless they come from a field with a code of its own.
+///
 /// ## Limitations
 ///
 /// `eserde` doesn't support _all_ `serde` attributes (yet).
@@ -529,3 +560,102 @@ pub trait EDeserialize<'de>: Sized + serde::Deserialize<'de> {
     where
         D: serde::Deserializer<'de>;
 }
+
+/// Deserialize an instance of type `T` with a custom `serde` deserializer.
+///
+/// Use it for data formats that don't have a dedicated module in this crate,
+/// or to combine multiple formats into a single document.
+/// `deserializer` is invoked once to deserialize the input and, if that fails,
+/// a second time to collect all the errors: both invocations must visit the same input.
+///
+/// # Example
+///
+/// ```rust
+/// #[derive(eserde::Deserialize, Debug)]
+/// struct Point {
+///     x: f64,
+///     y: f64,
+/// }
+///
+/// # fn main() {
+/// // `serde_json::Value` is a `serde` deserializer too.
+/// let value = serde_json::json!({"x": "1", "y": null});
+/// let errors = eserde::from_deserializer::<Point, _>(|| value.clone()).unwrap_err();
+/// assert_eq!(errors.len(), 2);
+/// # }
+/// ```
+pub fn from_deserializer<'de, T, D>(
+    mut deserializer: impl FnMut() -> D,
+) -> Result<T, DeserializationErrors>
+where
+    T: EDeserialize<'de>,
+    D: serde::Deserializer<'de>,
+{
+    let error = match T::deserialize(deserializer()) {
+        Ok(v) => {
+            return Ok(v);
+        }
+        Err(e) => e,
+    };
+    let _guard = reporter::ErrorReporter::start_deserialization();
+
+    let de = path::Deserializer::new(deserializer());
+
+    let errors = match T::deserialize_for_errors(de) {
+        Ok(_) => vec![],
+        Err(_) => reporter::ErrorReporter::take_errors(),
+    };
+    let errors = if errors.is_empty() {
+        vec![DeserializationError {
+            path: None,
+            details: error.to_string(),
+            code: None,
+            missing_field: None,
+            syntax: false,
+        }]
+    } else {
+        errors
+    };
+
+    Err(DeserializationErrors::from(errors))
+}
+
+/// Deserialize an instance of type `T` with a custom `serde` deserializer,
+/// stopping at the first error.
+///
+/// Unlike [`from_deserializer`], the input is visited only once, hence `deserializer`
+/// is taken by value: at most one error is reported, with its path.
+///
+/// # Example
+///
+/// ```rust
+/// #[derive(eserde::Deserialize, Debug)]
+/// struct Point {
+///     x: f64,
+///     y: f64,
+/// }
+///
+/// # fn main() {
+/// let value = serde_json::json!({"x": "1", "y": null});
+/// let errors = eserde::from_deserializer_fail_fast::<Point, _>(value).unwrap_err();
+/// assert_eq!(errors.len(), 1);
+/// assert_eq!(errors.iter().next().unwrap().path().unwrap().to_string(), "x");
+/// # }
+/// ```
+pub fn from_deserializer_fail_fast<'de, T, D>(deserializer: D) -> Result<T, DeserializationErrors>
+where
+    T: EDeserialize<'de>,
+    D: serde::Deserializer<'de>,
+{
+    let _guard = reporter::ErrorReporter::start_deserialization();
+
+    match T::deserialize(path::Deserializer::new(deserializer)) {
+        Ok(v) => Ok(v),
+        Err(e) => {
+            reporter::ErrorReporter::report(e);
+            Err(DeserializationErrors::from(
+                reporter::ErrorReporter::take_errors(),
+            ))
+        }
+    }
+}
@@ -286,7 +286,12 @@ where
     where
         V: de::MapAccess<'de>,
     {
-        self.delegate.visit_map(MapAccess::new(visitor))
+        let mut map = MapAccess::new(visitor);
+        let outcome = self.delegate.visit_map(&mut map);
+        if outcome.is_err() {
+            map.skip_remaining_entries();
+        }
+        outcome
     }
 
     fn visit_enum<V>(self, visitor: V) -> Result<Self::Value, V::Error>
@@ -484,17 +489,30 @@ where
         V: DeserializeSeed<'de>,
     {
         let mut variant = None;
-        let outcome = self
-            .delegate
-            .variant_seed(CaptureKey::new(seed, &mut variant));
+        let mut map_key = false;
+        let outcome = self.delegate.variant_seed(DetectMapKey::new(
+            CaptureKey::new(seed, &mut variant),
+            &mut map_key,
+        ));
 
+        if outcome.is_err() {
+            // The variant itself is the problem (e.g. it's unknown):
+            // the error points at the enum, not at the variant.
+            PathTracker::stash_current_path_for_error();
+            // A bare string, e.g. a unit variant or the tag of an adjacently tagged enum,
+            // has been read in full. A map key is followed by the content of the variant,
+            // which is still unread.
+            match (&variant, map_key) {
+                (Some(_), false) => PathTracker::mark_value_consumed(),
+                (Some(_), true) => PathTracker::mark_value_unread(),
+                (None, _) => {}
+            }
+        }
+        let variant = variant.filter(|_| outcome.is_ok());
         let pop_path_segment_before_exit = variant.is_some();
         if let Some(variant) = variant {
             PathTracker::push(Segment::Enum { variant });
         }
-        if outcome.is_err() {
-            PathTracker::stash_current_path_for_error();
-        }
 
         outcome.map(move |(v, vis)| (v, WrapVariant::new(vis, pop_path_segment_before_exit)))
     }
@@ -553,6 +571,36 @@ where
     }
 }
 
+// Seed that records whether the variant of an enum is read as the key of a map,
+// i.e. the `{"Variant": ...}` form of externally tagged enums.
+struct DetectMapKey<'a, X> {
+    delegate: X,
+    map_key: &'a mut bool,
+}
+
+impl<'a, X> DetectMapKey<'a, X> {
+    fn new(delegate: X, map_key: &'a mut bool) -> Self {
+        DetectMapKey { delegate, map_key }
+    }
+}
+
+impl<'de, X> DeserializeSeed<'de> for DetectMapKey<'_, X>
+where
+    X: DeserializeSeed<'de>,
+{
+    type Value = X::Value;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<X::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        // `serde_json` reads that form through its dedicated map key deserializer,
+        // while bare strings are read by the deserializer the enum was requested from.
+        *self.map_key = std::any::type_name::<D>().starts_with("serde_json::de::MapKey<");
+        self.delegate.deserialize(deserializer)
+    }
+}
+
 // Seed that saves the string into the given optional during `visit_str` and
 // `visit_string`.
 struct CaptureKey<'a, X> {
@@ -1107,15 +1155,49 @@ where
     }
 }
 
+// Seed that records whether the underlying deserializer got as far as invoking it,
+// e.g. to tell the errors of a sequence element apart from those of the sequence itself.
+struct Reached<'a, X> {
+    seed: X,
+    reached: &'a mut bool,
+}
+
+impl<'a, X> Reached<'a, X> {
+    fn new(seed: X, reached: &'a mut bool) -> Self {
+        Reached { seed, reached }
+    }
+}
+
+impl<'de, X> DeserializeSeed<'de> for Reached<'_, X>
+where
+    X: DeserializeSeed<'de>,
+{
+    type Value = X::Value;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<X::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        *self.reached = true;
+        self.seed.deserialize(deserializer)
+    }
+}
+
 // Seq visitor that tracks the index of its elements.
 struct SeqAccess<X> {
     delegate: X,
     index: usize,
+    /// The index of the element that was rejected before being read in full, if any.
+    failed: Option<usize>,
 }
 
 impl<X> SeqAccess<X> {
     fn new(delegate: X) -> Self {
-        SeqAccess { delegate, index: 0 }
+        SeqAccess {
+            delegate,
+            index: 0,
+            failed: None,
+        }
     }
 }
 
@@ -1131,12 +1213,31 @@ where
         T: DeserializeSeed<'de>,
     {
         PathTracker::push(Segment::Seq { index: self.index });
-        self.index += 1;
-        let outcome = self.delegate.next_element_seed(TrackedSeed::new(seed));
+        // Sequences don't skip past failed elements: don't let the flag reach the enclosing map.
+        let consumed = PathTracker::track_value_consumed();
+        let mut reached = false;
+        let outcome = self
+            .delegate
+            .next_element_seed(Reached::new(TrackedSeed::new(seed), &mut reached));
+        PathTracker::pop();
+        if consumed.unread() {
+            self.failed = Some(self.index);
+        }
+        drop(consumed);
         if outcome.is_err() {
-            PathTracker::stash_current_path_for_error();
+            match self.failed.filter(|_| !reached) {
+                // The sequence can't be read past an element that wasn't read in full:
+                // the error is about that element, and the sequence is left unread too.
+                Some(index) => {
+                    PathTracker::stash_nested_path_for_error(Segment::Seq { index });
+                    PathTracker::mark_value_unread();
+                }
+                None => {
+                    PathTracker::stash_nested_path_for_error(Segment::Seq { index: self.index })
+                }
+            }
         }
-        PathTracker::pop();
+        self.index += 1;
         outcome
     }
 
@@ -1151,6 +1252,10 @@ struct MapAccess<X> {
     delegate: X,
     key: Option<String>,
     pop_path_segment_on_value: bool,
+    /// Set if the underlying map failed to yield a key or a value.
+    failed: bool,
+    /// The path segment of the value that was rejected before being read in full, if any.
+    failed_value: Option<Segment>,
 }
 
 impl<X> MapAccess<X> {
@@ -1159,10 +1264,40 @@ impl<X> MapAccess<X> {
             delegate,
             key: None,
             pop_path_segment_on_value: false,
+            failed: false,
+            failed_value: None,
         }
     }
 }
 
+impl<'de, X> MapAccess<X>
+where
+    X: de::MapAccess<'de>,
+{
+    /// Consume the rest of the map after its visitor bailed out halfway through,
+    /// e.g. on a duplicate field.
+    ///
+    /// The enclosing value can then carry on looking for errors past the end of the map,
+    /// rather than tripping over its leftovers.
+    /// Nothing is skipped if the underlying map itself failed, since the input may be
+    /// malformed—unless the failing value was read in full, e.g. an unknown variant.
+    /// Errors encountered along the way are ignored: the visitor's own error
+    /// is the one worth reporting.
+    fn skip_remaining_entries(&mut self) {
+        if self.failed {
+            return;
+        }
+        if self.pop_path_segment_on_value {
+            // The error is about the key we just read: preserve its path.
+            PathTracker::stash_current_path_for_error();
+            let _ = self.delegate.next_value::<de::IgnoredAny>();
+            PathTracker::pop();
+            self.pop_path_segment_on_value = false;
+        }
+        while let Ok(Some((de::IgnoredAny, de::IgnoredAny))) = self.delegate.next_entry() {}
+    }
+}
+
 impl<'de, X> de::MapAccess<'de> for MapAccess<X>
 where
     X: de::MapAccess<'de>,
@@ -1173,28 +1308,48 @@ where
     where
         K: DeserializeSeed<'de>,
     {
-        let key = &mut self.key;
-        let outcome = self.delegate.next_key_seed(CaptureKey::new(seed, key));
-        if let Some(key) = key.take() {
-            PathTracker::push(Segment::Map { key });
-            self.pop_path_segment_on_value = true;
-        }
-        if outcome.is_err() {
-            PathTracker::stash_current_path_for_error();
-        }
-        outcome
+        PathTracker::in_map_key(|| {
+            let key = &mut self.key;
+            let outcome = self.delegate.next_key_seed(CaptureKey::new(seed, key));
+            if let Some(key) = key.take() {
+                PathTracker::push(Segment::Map { key });
+                self.pop_path_segment_on_value = true;
+            }
+            if outcome.is_err() {
+                match self
+                    .failed_value
+                    .clone()
+                    .filter(|_| !self.pop_path_segment_on_value)
+                {
+                    // The map can't be read past a value that wasn't read in full:
+                    // the error is about that value, and the map is left unread too.
+                    Some(segment) => {
+                        PathTracker::stash_nested_path_for_error(segment);
+                        PathTracker::mark_value_unread();
+                    }
+                    None => PathTracker::stash_current_path_for_error(),
+                }
+                self.failed = true;
+            }
+            outcome
+        })
     }
 
     fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, X::Error>
     where
         V: DeserializeSeed<'de>,
     {
+        let consumed = PathTracker::track_value_consumed();
         let outcome = self.delegate.next_value_seed(TrackedSeed::new(seed));
         if outcome.is_err() {
             PathTracker::stash_current_path_for_error();
+            self.failed = !consumed.get();
         }
         if self.pop_path_segment_on_value {
-            PathTracker::pop();
+            let segment = PathTracker::pop();
+            if consumed.unread() {
+                self.failed_value = segment;
+            }
             self.pop_path_segment_on_value = false;
         }
         outcome
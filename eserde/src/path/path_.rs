@@ -10,14 +10,18 @@ use std::slice;
 /// Use `path.to_string()` to get a string representation of the path with
 /// segments separated by periods, or use [`path.iter()`](Path::iter) to iterate over
 /// individual segments of the path.
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct Path {
     segments: Vec<Segment>,
+    map_key: bool,
 }
 
 impl From<Vec<Segment>> for Path {
     fn from(segments: Vec<Segment>) -> Self {
-        Self { segments }
+        Self {
+            segments,
+            map_key: false,
+        }
     }
 }
 
@@ -57,6 +61,20 @@ impl Path {
         }
     }
 
+    /// Returns `true` if the error is located in one of the keys of the map
+    /// this path points at, rather than in the map itself or in one of its values.
+    ///
+    /// E.g. a non-numeric key when deserializing a `HashMap<u32, _>`.
+    /// The offending key can't be part of the path, since it couldn't be deserialized.
+    pub fn is_map_key(&self) -> bool {
+        self.map_key
+    }
+
+    pub(crate) fn map_key(mut self, map_key: bool) -> Self {
+        self.map_key = map_key;
+        self
+    }
+
     pub(crate) fn segments(&self) -> &[Segment] {
         &self.segments
     }
@@ -1,4 +1,4 @@
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 
 use super::{Path, Segment};
 
@@ -17,17 +17,29 @@ impl PathTracker {
         CURRENT_PATH.with_borrow_mut(|path| path.as_mut().map(|segments| segments.push(segment)));
     }
 
-    pub fn pop() {
-        CURRENT_PATH.with_borrow_mut(|path| {
-            path.as_mut().map(|segments| {
-                segments.pop();
-            })
-        });
+    pub fn pop() -> Option<Segment> {
+        CURRENT_PATH.with_borrow_mut(|path| path.as_mut().and_then(|segments| segments.pop()))
     }
 
     pub fn current_path() -> Option<Path> {
         let segments = CURRENT_PATH.with_borrow(|segments| segments.clone());
-        segments.map(|segments| segments.into())
+        segments.map(|segments| Path::from(segments).map_key(IN_MAP_KEY.get()))
+    }
+
+    /// Run `f` while deserializing a map key, to flag all errors
+    /// reported in the meantime as [targeting a key](Path::is_map_key).
+    pub fn in_map_key<R>(f: impl FnOnce() -> R) -> R {
+        /// Restores the previous flag on drop, even if `f` panics.
+        struct Restore(bool);
+
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                let _ = IN_MAP_KEY.try_with(|in_map_key| in_map_key.set(self.0));
+            }
+        }
+
+        let _restore = Restore(IN_MAP_KEY.replace(true));
+        f()
     }
 
     /// Stashes the current path for error handling.
@@ -52,6 +64,43 @@ impl PathTracker {
         });
     }
 
+    /// Flag the value being deserialized as read in full, even though it was rejected—e.g.
+    /// an unknown enum variant.
+    ///
+    /// The enclosing map can then skip its remaining entries, since the input is
+    /// well-formed up to that point.
+    pub fn mark_value_consumed() {
+        VALUE_CONSUMED.set(Some(true));
+    }
+
+    /// Flag the value being deserialized as rejected before it was read in full—e.g.
+    /// an unknown enum variant followed by its content.
+    ///
+    /// The enclosing map or sequence can't be read any further: the errors it reports
+    /// next are about that value.
+    pub fn mark_value_unread() {
+        VALUE_CONSUMED.set(Some(false));
+    }
+
+    /// Start deserializing a value, clearing the flag set by [`PathTracker::mark_value_consumed`]
+    /// or [`PathTracker::mark_value_unread`].
+    ///
+    /// The flag is cleared again when the returned guard is dropped, whatever the exit path,
+    /// so that it never leaks to the enclosing values.
+    pub fn track_value_consumed() -> ValueConsumed {
+        VALUE_CONSUMED.set(None);
+        ValueConsumed
+    }
+
+    /// Stashes the path to `segment`, nested in the current path, for error handling.
+    ///
+    /// See [`PathTracker::stash_current_path_for_error`] for the conditions.
+    pub fn stash_nested_path_for_error(segment: Segment) {
+        Self::push(segment);
+        Self::stash_current_path_for_error();
+        Self::pop();
+    }
+
     pub fn unstash_current_path_for_error() -> Option<Path> {
         PATH_ON_ERROR.take()
     }
@@ -62,6 +111,29 @@ impl PathTracker {
                 *s = None;
             }
         });
+        let _ = IN_MAP_KEY.try_with(|in_map_key| in_map_key.set(false));
+        let _ = VALUE_CONSUMED.try_with(|consumed| consumed.set(None));
+    }
+}
+
+/// The guard returned by [`PathTracker::track_value_consumed`].
+pub struct ValueConsumed;
+
+impl ValueConsumed {
+    /// Whether [`PathTracker::mark_value_consumed`] was invoked for the value being tracked.
+    pub fn get(&self) -> bool {
+        VALUE_CONSUMED.get() == Some(true)
+    }
+
+    /// Whether [`PathTracker::mark_value_unread`] was invoked for the value being tracked.
+    pub fn unread(&self) -> bool {
+        VALUE_CONSUMED.get() == Some(false)
+    }
+}
+
+impl Drop for ValueConsumed {
+    fn drop(&mut self) {
+        let _ = VALUE_CONSUMED.try_with(|consumed| consumed.set(None));
     }
 }
 
@@ -69,6 +141,13 @@ thread_local! {
     /// The path to the value we're currently trying to deserialize.
     static CURRENT_PATH: RefCell<Option<Vec<Segment>>> = const { RefCell::new(None) };
 
+    /// Whether we're currently trying to deserialize a map key.
+    static IN_MAP_KEY: Cell<bool> = const { Cell::new(false) };
+
+    /// Whether the last rejected value was read in full, see [`PathTracker::mark_value_consumed`]
+    /// and [`PathTracker::mark_value_unread`].
+    static VALUE_CONSUMED: Cell<Option<bool>> = const { Cell::new(None) };
+
     /// A snapshot of the current path, captured when an error occurred.
     ///
     /// For types that implement [`EDeserialize`], this is not necessary
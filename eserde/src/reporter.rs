@@ -44,13 +44,29 @@ impl ErrorReporter {
     /// This method will panic if called outside of a deserialization operation.
     /// Check out [`ErrorReporter::start_deserialization`] for more information.
     pub fn report<E: Display>(e: E) {
+        Self::push(e.to_string(), None);
+    }
+
+    /// Report that the required field `name` is missing from the value being deserialized.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if called outside of a deserialization operation.
+    pub(crate) fn report_missing_field(name: &'static str) {
+        Self::push(format!("missing field `{name}`"), Some(name));
+    }
+
+    fn push(details: String, missing_field: Option<&'static str>) {
         let path = match PathTracker::unstash_current_path_for_error() {
             Some(p) => Some(p),
             None => PathTracker::current_path(),
         };
         let error = DeserializationError {
             path,
-            details: e.to_string(),
+            details,
+            code: None,
+            missing_field,
+            syntax: false,
         };
         let success = DESERIALIZATION_ERRORS.with_borrow_mut(|v| {
             if let Some(v) = v {
@@ -73,6 +89,16 @@ impl ErrorReporter {
         };
     }
 
+    /// Assign `code` to the errors reported after the first `n_errors` ones,
+    /// unless they already carry a code of their own.
+    pub(crate) fn assign_code(n_errors: usize, code: &'static str) {
+        DESERIALIZATION_ERRORS.with_borrow_mut(|v| {
+            for error in v.iter_mut().flat_map(|v| v.iter_mut().skip(n_errors)) {
+                error.code.get_or_insert(code);
+            }
+        });
+    }
+
     /// Retrieve all errors that occurred during deserialization up to this point.
     ///
     /// The buffer is cleared after this call—i.e. subsequent calls to this method will return
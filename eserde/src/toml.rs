@@ -50,6 +50,9 @@ where
         vec![DeserializationError {
             path: None,
             details: error.to_string(),
+            code: None,
+            missing_field: None,
+            syntax: false,
         }]
     } else {
         errors
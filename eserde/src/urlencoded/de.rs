@@ -0,0 +1,629 @@
+//! A `serde` deserializer for `application/x-www-form-urlencoded` data
+//! that groups repeated keys together.
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use serde::de::{
+    self,
+    value::{BorrowedStrDeserializer, Error},
+    DeserializeSeed, EnumAccess, IntoDeserializer, MapAccess, SeqAccess, Unexpected, VariantAccess,
+    Visitor,
+};
+
+/// The decoded key-value pairs, with the values of repeated keys grouped together.
+///
+/// Keys are kept in the order of their first occurrence.
+#[derive(Default)]
+pub(crate) struct Entries<'a> {
+    nodes: Vec<(Cow<'a, str>, Node<'a>)>,
+    /// The position of each key in `nodes`, to group repeated keys
+    /// without scanning all the previous ones.
+    positions: HashMap<Cow<'a, str>, usize>,
+}
+
+/// Everything associated with a key: its values and,
+/// for keys using the bracket syntax, its nested entries.
+#[derive(Default)]
+struct Node<'a> {
+    values: Vec<Cow<'a, str>>,
+    children: Entries<'a>,
+}
+
+impl<'a> Entries<'a> {
+    pub(crate) fn parse(input: &'a [u8]) -> Self {
+        Self::from_pairs(form_urlencoded::parse(input))
+    }
+
+    pub(crate) fn from_pairs(pairs: impl Iterator<Item = (Cow<'a, str>, Cow<'a, str>)>) -> Self {
+        let mut entries = Self::default();
+        for (key, value) in pairs {
+            entries.entry(key).values.push(value);
+        }
+        entries
+    }
+
+    /// Like [`parse`](Self::parse), but keys using the bracket syntax
+    /// (e.g. `filter[status]`) are nested under their parent key.
+    ///
+    /// A trailing `[]` (e.g. `tag[]`) is ignored: repeated values are grouped
+    /// together, whether the key uses it or not.
+    pub(crate) fn parse_nested(input: &'a [u8]) -> Self {
+        let mut entries = Self::default();
+        for (key, value) in form_urlencoded::parse(input) {
+            let Some(mut ranges) = bracket_ranges(&key) else {
+                entries.entry(key).values.push(value);
+                continue;
+            };
+            if ranges.len() > 1 && ranges.last().is_some_and(|range| range.is_empty()) {
+                ranges.pop();
+            }
+            let segment = |range: std::ops::Range<usize>| match &key {
+                Cow::Borrowed(key) => Cow::Borrowed(&key[range]),
+                Cow::Owned(key) => Cow::Owned(key[range].to_owned()),
+            };
+            let mut ranges = ranges.into_iter();
+            let mut node = entries.entry(segment(ranges.next().unwrap()));
+            for range in ranges {
+                node = node.children.entry(segment(range));
+            }
+            node.values.push(value);
+        }
+        entries
+    }
+
+    fn entry(&mut self, key: Cow<'a, str>) -> &mut Node<'a> {
+        let position = match self.positions.get(&key) {
+            Some(position) => *position,
+            None => {
+                let position = self.nodes.len();
+                self.positions.insert(key.clone(), position);
+                self.nodes.push((key, Node::default()));
+                position
+            }
+        };
+        &mut self.nodes[position].1
+    }
+}
+
+/// Split a key using the bracket syntax into the ranges of its segments,
+/// e.g. `filter[price][min]` into `filter`, `price` and `min`.
+///
+/// Returns `None` if the key doesn't follow the syntax, in which case it's taken literally.
+fn bracket_ranges(key: &str) -> Option<Vec<std::ops::Range<usize>>> {
+    let open = key.find('[').filter(|open| *open > 0)?;
+    let mut ranges = Vec::new();
+    ranges.push(0..open);
+    let mut rest = open;
+    while rest < key.len() {
+        let segment = key[rest..].strip_prefix('[')?;
+        let close = segment.find(']')?;
+        if segment[..close].contains('[') {
+            return None;
+        }
+        ranges.push(rest + 1..rest + 1 + close);
+        rest += close + 2;
+    }
+    Some(ranges)
+}
+
+/// Knobs tweaking how values are parsed.
+#[derive(Clone, Copy, Default)]
+pub(crate) struct Options {
+    /// Accept `1`/`0`, `yes`/`no`, `on`/`off` and an empty value (e.g. a presence-only
+    /// `?active` flag, meaning `true`) for booleans, rather than `true`/`false` only.
+    pub(crate) flexible_booleans: bool,
+}
+
+/// Visit a decoded string, borrowing from the input whenever possible.
+fn visit_cow<'de, V>(s: &Cow<'de, str>, visitor: V) -> Result<V::Value, Error>
+where
+    V: Visitor<'de>,
+{
+    match s {
+        Cow::Borrowed(s) => visitor.visit_borrowed_str(s),
+        Cow::Owned(s) => visitor.visit_str(s),
+    }
+}
+
+/// A `serde` deserializer for a [`Document`](super::Document), visited as a map.
+pub struct Deserializer<'a, 'de> {
+    entries: &'a Entries<'de>,
+    options: Options,
+}
+
+impl<'a, 'de> Deserializer<'a, 'de> {
+    pub(crate) fn new(entries: &'a Entries<'de>, options: Options) -> Self {
+        Self { entries, options }
+    }
+}
+
+impl<'de> de::Deserializer<'de> for Deserializer<'_, 'de> {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_map(EntriesAccess {
+            entries: self.entries.nodes.iter(),
+            values: None,
+            options: self.options,
+        })
+    }
+
+    fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        if self.entries.nodes.is_empty() {
+            visitor.visit_unit()
+        } else {
+            self.deserialize_any(visitor)
+        }
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+struct EntriesAccess<'a, 'de> {
+    entries: std::slice::Iter<'a, (Cow<'de, str>, Node<'de>)>,
+    values: Option<&'a (Cow<'de, str>, Node<'de>)>,
+    options: Options,
+}
+
+impl<'de> MapAccess<'de> for EntriesAccess<'_, 'de> {
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        let Some(entry) = self.entries.next() else {
+            return Ok(None);
+        };
+        self.values = Some(entry);
+        seed.deserialize(Part(&entry.0, self.options)).map(Some)
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let (key, node) = self
+            .values
+            .take()
+            .ok_or_else(|| de::Error::custom("value is missing"))?;
+        seed.deserialize(Values {
+            key,
+            node,
+            options: self.options,
+        })
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.entries.len())
+    }
+}
+
+/// All the values associated with a key.
+///
+/// They are visited as a sequence if the target type asks for one,
+/// as a single value otherwise.
+/// Nested entries are visited as a map.
+struct Values<'a, 'de> {
+    key: &'a Cow<'de, str>,
+    node: &'a Node<'de>,
+    options: Options,
+}
+
+impl<'a, 'de> Values<'a, 'de> {
+    fn single<E>(&self, expected: &dyn de::Expected) -> Result<Part<'a, 'de>, E>
+    where
+        E: de::Error,
+    {
+        match (
+            self.node.values.as_slice(),
+            self.node.children.nodes.is_empty(),
+        ) {
+            ([value], true) => Ok(Part(value, self.options)),
+            ([], false) => Err(E::invalid_type(Unexpected::Map, expected)),
+            _ => Err(self.duplicate()),
+        }
+    }
+
+    /// The nested entries, if there are any.
+    fn nested<E>(&self) -> Result<Option<Deserializer<'a, 'de>>, E>
+    where
+        E: de::Error,
+    {
+        if self.node.children.nodes.is_empty() {
+            Ok(None)
+        } else if self.node.values.is_empty() {
+            Ok(Some(Deserializer::new(&self.node.children, self.options)))
+        } else {
+            // The key was given both a value and nested entries.
+            Err(self.duplicate())
+        }
+    }
+
+    fn duplicate<E>(&self) -> E
+    where
+        E: de::Error,
+    {
+        E::custom(format_args!("duplicate field `{}`", self.key))
+    }
+}
+
+macro_rules! forward_to_single {
+    ($($method:ident)*) => {
+        $(
+            fn $method<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+            where
+                V: Visitor<'de>,
+            {
+                de::Deserializer::$method(self.single::<Self::Error>(&visitor)?, visitor)
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for Values<'_, 'de> {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        if let Some(nested) = self.nested()? {
+            return nested.deserialize_any(visitor);
+        }
+        match self.node.values.as_slice() {
+            [value] => Part(value, self.options).deserialize_any(visitor),
+            _ => self.deserialize_seq(visitor),
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        if let Some(nested) = self.nested()? {
+            return nested.deserialize_any(visitor);
+        }
+        visitor.visit_seq(ValuesAccess(self.node.values.iter(), self.options))
+    }
+
+    fn deserialize_tuple<V>(self, _len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_unit_struct<V>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.single::<Self::Error>(&visitor)?
+            .deserialize_unit_struct(name, visitor)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.single::<Self::Error>(&visitor)?
+            .deserialize_enum(name, variants, visitor)
+    }
+
+    fn deserialize_map<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        if let Some(nested) = self.nested()? {
+            return nested.deserialize_any(visitor);
+        }
+        self.single::<Self::Error>(&visitor)?
+            .deserialize_map(visitor)
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        if let Some(nested) = self.nested()? {
+            return nested.deserialize_any(visitor);
+        }
+        self.single::<Self::Error>(&visitor)?
+            .deserialize_struct(name, fields, visitor)
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    forward_to_single! {
+        deserialize_bool deserialize_i8 deserialize_i16 deserialize_i32 deserialize_i64
+        deserialize_i128 deserialize_u8 deserialize_u16 deserialize_u32 deserialize_u64
+        deserialize_u128 deserialize_f32 deserialize_f64 deserialize_char deserialize_str
+        deserialize_string deserialize_bytes deserialize_byte_buf deserialize_unit
+        deserialize_identifier
+    }
+}
+
+struct ValuesAccess<'a, 'de>(std::slice::Iter<'a, Cow<'de, str>>, Options);
+
+impl<'de> SeqAccess<'de> for ValuesAccess<'_, 'de> {
+    type Error = Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        match self.0.next() {
+            Some(value) => seed.deserialize(Part(value, self.1)).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.0.len())
+    }
+}
+
+/// A single decoded key or value.
+///
+/// Primitives are parsed from their textual representation.
+struct Part<'a, 'de>(&'a Cow<'de, str>, Options);
+
+macro_rules! parse_primitive {
+    ($($method:ident => $visit:ident,)*) => {
+        $(
+            fn $method<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+            where
+                V: Visitor<'de>,
+            {
+                match self.0.parse() {
+                    Ok(v) => visitor.$visit(v),
+                    Err(_) => Err(de::Error::invalid_type(Unexpected::Str(self.0.as_ref()), &visitor)),
+                }
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for Part<'_, 'de> {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visit_cow(self.0, visitor)
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_bool<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        let value = self.0.as_ref();
+        let parsed = if self.1.flexible_booleans {
+            match value.to_ascii_lowercase().as_str() {
+                "true" | "1" | "yes" | "on" | "" => Some(true),
+                "false" | "0" | "no" | "off" => Some(false),
+                _ => None,
+            }
+        } else {
+            value.parse().ok()
+        };
+        match parsed {
+            Some(v) => visitor.visit_bool(v),
+            None => Err(de::Error::invalid_type(Unexpected::Str(value), &visitor)),
+        }
+    }
+
+    fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        if self.0.is_empty() {
+            visitor.visit_unit()
+        } else {
+            Err(de::Error::invalid_type(
+                Unexpected::Str(self.0.as_ref()),
+                &visitor,
+            ))
+        }
+    }
+
+    fn deserialize_unit_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_enum(self)
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    parse_primitive! {
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_i128 => visit_i128,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_u128 => visit_u128,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+        deserialize_char => visit_char,
+    }
+
+    serde::forward_to_deserialize_any! {
+        str string bytes byte_buf seq tuple tuple_struct map struct identifier
+    }
+}
+
+/// Unit variants can be selected by name, e.g. `role=admin`.
+impl<'de> EnumAccess<'de> for Part<'_, 'de> {
+    type Error = Error;
+    type Variant = UnitOnly;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant), Self::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let variant = match self.0 {
+            Cow::Borrowed(s) => seed.deserialize(BorrowedStrDeserializer::new(s))?,
+            Cow::Owned(s) => seed.deserialize(s.as_str().into_deserializer())?,
+        };
+        Ok((variant, UnitOnly))
+    }
+}
+
+struct UnitOnly;
+
+impl<'de> VariantAccess<'de> for UnitOnly {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T>(self, _seed: T) -> Result<T::Value, Self::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        Err(de::Error::invalid_type(
+            Unexpected::UnitVariant,
+            &"newtype variant",
+        ))
+    }
+
+    fn tuple_variant<V>(self, _len: usize, _visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        Err(de::Error::invalid_type(
+            Unexpected::UnitVariant,
+            &"tuple variant",
+        ))
+    }
+
+    fn struct_variant<V>(
+        self,
+        _fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        Err(de::Error::invalid_type(
+            Unexpected::UnitVariant,
+            &"struct variant",
+        ))
+    }
+}
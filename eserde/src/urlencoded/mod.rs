@@ -0,0 +1,246 @@
+//! Deserialize `application/x-www-form-urlencoded` data, e.g. HTML form submissions
+//! or query strings.
+//!
+//! # Example
+//!
+//! ```rust
+//! #[derive(eserde::Deserialize, Debug)]
+//! struct Search {
+//!     query: String,
+//!     page: u32,
+//!     #[serde(rename = "tag")]
+//!     tags: Vec<String>,
+//! }
+//!
+//! # fn main() {
+//! let search: Search = eserde::urlencoded::from_str("query=rust&page=2&tag=serde&tag=web").unwrap();
+//! assert_eq!(search.tags, ["serde", "web"]);
+//! # }
+//! ```
+//!
+//! # Repeated keys
+//!
+//! Unlike [`serde_urlencoded`](https://crates.io/crates/serde_urlencoded), repeated keys
+//! are grouped together: all their values are visited, in order, when the target field
+//! is a sequence (e.g. a `Vec<T>`).
+//! Errors for individual values carry the index of the offending value within the
+//! sequence—e.g. `tag[1]`.
+//!
+//! A repeated key is reported as a duplicate field if the target field expects a single value.
+//!
+//! # Nested keys
+//!
+//! [`from_str_nested`] and [`from_bytes_nested`] also understand the bracket syntax
+//! commonly used in query strings: `filter[status]=open&filter[price][min]=10` is visited
+//! as a `filter` map holding a `status` key and a nested `price` map.
+//! A trailing `[]`, e.g. `tag[]=a&tag[]=b`, is accepted and ignored, since
+//! repeated keys are grouped together anyway.
+//! Keys that don't follow the syntax (e.g. `a[b`) are taken literally.
+//!
+//! # Implementation
+//!
+//! This module relies on [`form_urlencoded`](https://crates.io/crates/form_urlencoded) to
+//! split and percent-decode the input.
+//! Values are parsed from their textual representation according to the type
+//! requested by the target field, e.g. `age=42` for `age: u8`.
+use std::borrow::Cow;
+
+use crate::{
+    path, reporter::ErrorReporter, DeserializationError, DeserializationErrors, EDeserialize,
+};
+
+mod de;
+
+pub use de::Deserializer;
+
+/// Decoded `application/x-www-form-urlencoded` data, visited as a map by its [`Deserializer`].
+///
+/// Most users should prefer [`from_str`] and friends.
+/// `Document` is meant for urlencoded data embedded in a larger input,
+/// to be deserialized via [`crate::from_deserializer`].
+#[derive(Default)]
+pub struct Document<'a> {
+    entries: de::Entries<'a>,
+    options: de::Options,
+}
+
+impl<'a> Document<'a> {
+    /// Decode `input`, grouping repeated keys together like [`from_bytes`] does.
+    pub fn parse(input: &'a [u8]) -> Self {
+        Self::new(de::Entries::parse(input))
+    }
+
+    /// Decode `input`, nesting the keys that use the bracket syntax
+    /// like [`from_bytes_nested`] does.
+    pub fn parse_nested(input: &'a [u8]) -> Self {
+        Self::new(de::Entries::parse_nested(input))
+    }
+
+    fn new(entries: de::Entries<'a>) -> Self {
+        Self {
+            entries,
+            options: de::Options::default(),
+        }
+    }
+
+    /// Build a document out of key-value pairs that have already been decoded,
+    /// e.g. the parameters captured from a URL path.
+    ///
+    /// Keys are taken literally and repeated keys are grouped together.
+    pub fn from_pairs<K, V>(pairs: impl IntoIterator<Item = (K, V)>) -> Self
+    where
+        K: Into<Cow<'a, str>>,
+        V: Into<Cow<'a, str>>,
+    {
+        Self::new(de::Entries::from_pairs(
+            pairs
+                .into_iter()
+                .map(|(key, value)| (key.into(), value.into())),
+        ))
+    }
+
+    /// Parse booleans flexibly, as commonly expected for query flags.
+    ///
+    /// Besides `true` and `false`, `1`/`0`, `yes`/`no` and `on`/`off` are accepted
+    /// (ignoring case), as well as an empty value, meaning `true`: `?active` sets
+    /// an `active: bool` field.
+    /// Any other value is reported as an invalid type.
+    ///
+    /// Disabled by default.
+    pub fn flexible_booleans(mut self, enabled: bool) -> Self {
+        self.options.flexible_booleans = enabled;
+        self
+    }
+
+    /// A deserializer visiting the document as a map.
+    pub fn deserializer(&self) -> Deserializer<'_, 'a> {
+        Deserializer::new(&self.entries, self.options)
+    }
+}
+
+/// Deserialize an instance of type `T` from a `application/x-www-form-urlencoded` string.
+///
+/// # Example
+///
+/// ```rust
+/// #[derive(eserde::Deserialize, Debug)]
+/// struct User {
+///     fingerprint: String,
+///     location: String,
+/// }
+///
+/// # fn main() {
+/// let data = "fingerprint=0xF9BA143B95FF6D82&location=Menlo+Park%2C+CA";
+///
+/// let u: User = eserde::urlencoded::from_str(data).unwrap();
+/// println!("{:#?}", u);
+/// # }
+/// ```
+pub fn from_str<'a, T>(s: &'a str) -> Result<T, DeserializationErrors>
+where
+    T: EDeserialize<'a>,
+{
+    from_bytes(s.as_bytes())
+}
+
+/// Deserialize an instance of type `T` from bytes of `application/x-www-form-urlencoded` data.
+///
+/// # Example
+///
+/// ```rust
+/// #[derive(eserde::Deserialize, Debug)]
+/// struct User {
+///     fingerprint: String,
+///     location: String,
+/// }
+///
+/// # fn main() {
+/// let data = b"fingerprint=0xF9BA143B95FF6D82&location=Menlo+Park%2C+CA";
+///
+/// let u: User = eserde::urlencoded::from_bytes(data).unwrap();
+/// println!("{:#?}", u);
+/// # }
+/// ```
+pub fn from_bytes<'a, T>(s: &'a [u8]) -> Result<T, DeserializationErrors>
+where
+    T: EDeserialize<'a>,
+{
+    from_entries(&de::Entries::parse(s))
+}
+
+/// Deserialize an instance of type `T` from a `application/x-www-form-urlencoded` string,
+/// nesting the keys that use the bracket syntax—e.g. `filter[status]`.
+///
+/// See [the module documentation](self#nested-keys) for more details.
+///
+/// # Example
+///
+/// ```rust
+/// #[derive(eserde::Deserialize, Debug)]
+/// struct Search {
+///     filter: Filter,
+/// }
+///
+/// #[derive(eserde::Deserialize, Debug)]
+/// struct Filter {
+///     status: String,
+///     min_price: Option<u32>,
+/// }
+///
+/// # fn main() {
+/// let data = "filter[status]=open&filter[min_price]=10";
+///
+/// let search: Search = eserde::urlencoded::from_str_nested(data).unwrap();
+/// assert_eq!(search.filter.min_price, Some(10));
+/// # }
+/// ```
+pub fn from_str_nested<'a, T>(s: &'a str) -> Result<T, DeserializationErrors>
+where
+    T: EDeserialize<'a>,
+{
+    from_bytes_nested(s.as_bytes())
+}
+
+/// Deserialize an instance of type `T` from bytes of `application/x-www-form-urlencoded` data,
+/// nesting the keys that use the bracket syntax—e.g. `filter[status]`.
+///
+/// See [the module documentation](self#nested-keys) for more details.
+pub fn from_bytes_nested<'a, T>(s: &'a [u8]) -> Result<T, DeserializationErrors>
+where
+    T: EDeserialize<'a>,
+{
+    from_entries(&de::Entries::parse_nested(s))
+}
+
+fn from_entries<'a, T>(entries: &de::Entries<'a>) -> Result<T, DeserializationErrors>
+where
+    T: EDeserialize<'a>,
+{
+    let error = match T::deserialize(de::Deserializer::new(entries, de::Options::default())) {
+        Ok(v) => {
+            return Ok(v);
+        }
+        Err(e) => e,
+    };
+    let _guard = ErrorReporter::start_deserialization();
+
+    let de = path::Deserializer::new(de::Deserializer::new(entries, de::Options::default()));
+
+    let errors = match T::deserialize_for_errors(de) {
+        Ok(_) => vec![],
+        Err(_) => ErrorReporter::take_errors(),
+    };
+    let errors = if errors.is_empty() {
+        vec![DeserializationError {
+            path: None,
+            details: error.to_string(),
+            code: None,
+            missing_field: None,
+            syntax: false,
+        }]
+    } else {
+        errors
+    };
+
+    Err(DeserializationErrors::from(errors))
+}
@@ -0,0 +1,119 @@
+#[derive(eserde::Deserialize, Debug)]
+#[allow(dead_code)]
+struct Signup {
+    #[eserde(code = "AGE_INVALID")]
+    #[serde(deserialize_with = "deserialize_age")]
+    age: u8,
+    #[eserde(compat, code = "LOCALE_INVALID")]
+    locale: String,
+    #[eserde(code = "ADDRESS_INVALID")]
+    address: Address,
+    contact: Contact,
+}
+
+#[derive(eserde::Deserialize, Debug)]
+#[allow(dead_code)]
+struct Address {
+    city: String,
+    #[eserde(code = "ZIP_INVALID")]
+    zip: u32,
+}
+
+#[derive(eserde::Deserialize, Debug)]
+#[allow(dead_code)]
+enum Contact {
+    Email {
+        #[eserde(code = "EMAIL_INVALID")]
+        value: String,
+    },
+    Phone {
+        #[eserde(code = "PHONE_INVALID")]
+        value: String,
+    },
+}
+
+fn deserialize_age<'de, D>(deserializer: D) -> Result<u8, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    serde::Deserialize::deserialize(deserializer)
+}
+
+fn codes(input: &str) -> Vec<(String, Option<String>)> {
+    eserde::json::from_str::<Signup>(input)
+        .unwrap_err()
+        .iter()
+        .map(|error| {
+            (
+                error.path().map(ToString::to_string).unwrap_or_default(),
+                error.code().map(ToOwned::to_owned),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn test_code_overrides() {
+    let codes = codes(
+        r#"{"age": 300, "locale": 1, "address": {"city": 1, "zip": "x"}, "contact": {"Phone": {"value": 1}}}"#,
+    );
+    let codes: Vec<_> = codes
+        .iter()
+        .map(|(path, code)| (path.as_str(), code.as_deref()))
+        .collect();
+    assert_eq!(
+        codes,
+        [
+            ("age", Some("AGE_INVALID")),
+            ("locale", Some("LOCALE_INVALID")),
+            ("address.city", Some("ADDRESS_INVALID")),
+            ("address.zip", Some("ZIP_INVALID")),
+            ("contact.Phone.value", Some("PHONE_INVALID")),
+        ]
+    );
+}
+
+#[test]
+fn test_code_missing_field() {
+    let codes = codes(r#"{"locale": "en", "contact": {"Email": {}}}"#);
+    let codes: Vec<_> = codes.iter().map(|(_, code)| code.as_deref()).collect();
+    assert_eq!(
+        codes,
+        [
+            Some("EMAIL_INVALID"),
+            Some("AGE_INVALID"),
+            Some("ADDRESS_INVALID")
+        ]
+    );
+}
+
+#[derive(eserde::Deserialize, Debug)]
+#[allow(dead_code)]
+struct Profile {
+    #[eserde(compat, code = "NICKNAME_INVALID")]
+    #[serde(deserialize_with = "deserialize_nickname")]
+    nickname: String,
+}
+
+fn deserialize_nickname<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let nickname = <String as serde::Deserialize>::deserialize(deserializer)?;
+    if nickname.is_empty() {
+        return Err(serde::de::Error::custom("the nickname is empty"));
+    }
+    Ok(nickname)
+}
+
+#[test]
+fn test_code_compat_deserialize_with() {
+    assert!(eserde::json::from_str::<Profile>(r#"{"nickname": "Al"}"#).is_ok());
+
+    let errors = eserde::json::from_str::<Profile>(r#"{"nickname": ""}"#).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    let error = errors.iter().next().unwrap();
+    assert_eq!(error.path().unwrap().to_string(), "nickname");
+    assert_eq!(error.code(), Some("NICKNAME_INVALID"));
+    assert!(error.message().starts_with("the nickname is empty"));
+}
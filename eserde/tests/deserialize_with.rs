@@ -72,3 +72,54 @@ fn test_fail() {
     - foo: unknown field `foo`, expected `number` or `ip` at line 1 column 37
     "###);
 }
+
+#[derive(eserde::Deserialize, Debug)]
+#[allow(dead_code)]
+struct Event {
+    name: String,
+    #[serde(with = "date")]
+    starts_on: (u16, u8, u8),
+    attendees: Vec<Attendee>,
+}
+
+#[derive(eserde::Deserialize, Debug)]
+#[allow(dead_code)]
+struct Attendee {
+    #[serde(deserialize_with = "date::deserialize")]
+    born_on: (u16, u8, u8),
+}
+
+mod date {
+    use serde::de::Error;
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<(u16, u8, u8), D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s: String = serde::de::Deserialize::deserialize(deserializer)?;
+        let parse = || {
+            let mut parts = s.splitn(3, '-');
+            Some((
+                parts.next()?.parse().ok()?,
+                parts.next()?.parse().ok()?,
+                parts.next()?.parse().ok()?,
+            ))
+        };
+        parse().ok_or_else(|| D::Error::custom(format!("`{s}` is not a valid YYYY-MM-DD date")))
+    }
+}
+
+/// Errors raised by custom deserializers must be attributed to the field
+/// they were invoked for, no matter how deeply nested it is.
+#[test]
+fn test_with_path() {
+    let errs = eserde::json::from_str::<Event>(
+        r#"{"name": "RustConf", "starts_on": "next week", "attendees": [{"born_on": "1990-01-01"}, {"born_on": 1990}]}"#,
+    )
+    .unwrap_err();
+    insta::assert_snapshot!(errs, @r###"
+    Something went wrong during deserialization:
+    - starts_on: `next week` is not a valid YYYY-MM-DD date
+    - attendees[1].born_on: invalid type: integer `1990`, expected a string at line 1 column 104
+    "###);
+}
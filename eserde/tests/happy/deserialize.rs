@@ -123,3 +123,72 @@ fn deser_for_errors() {
     let error_repr = value.unwrap_err().to_string();
     insta::assert_snapshot!(error_repr, @"invalid value: integer `-5`, expected u32 at line 2 column 19");
 }
+
+#[test]
+fn trailing_characters() {
+    let payload = r#"{"a": 1} {"a": 2}"#;
+
+    let error = eserde::json::from_str::<serde_json::Value>(payload).unwrap_err();
+    let error_repr = error.into_iter().map(|e| e.to_string()).join("\n");
+    insta::assert_snapshot!(error_repr, @"trailing characters at line 1 column 10");
+
+    let error =
+        eserde::json::from_slice_fail_fast::<serde_json::Value>(payload.as_bytes()).unwrap_err();
+    let error_repr = error.into_iter().map(|e| e.to_string()).join("\n");
+    insta::assert_snapshot!(error_repr, @"trailing characters at line 1 column 10");
+
+    assert!(eserde::json::from_str::<serde_json::Value>("{\"a\": 1}\n\t ").is_ok());
+}
+
+#[test]
+fn syntax_errors() {
+    #[derive(Debug, eserde::Deserialize)]
+    struct User {
+        name: String,
+        age: u8,
+    }
+
+    let syntax = |payload: &str| {
+        let errors = eserde::json::from_str::<User>(payload).unwrap_err();
+        let fail_fast = eserde::json::from_slice_fail_fast::<User>(payload.as_bytes()).unwrap_err();
+        [errors, fail_fast].map(|errors| errors.iter().map(|e| e.is_syntax()).collect_vec())
+    };
+
+    // The syntax error comes last, after the errors that could be recovered from.
+    assert_eq!(
+        syntax(r#"{"age": "30", "name": "#),
+        [vec![false, false, true], vec![false]]
+    );
+    assert_eq!(
+        syntax(r#"{"name": "Alice" "age": 30}"#),
+        [vec![true], vec![true]]
+    );
+    assert_eq!(
+        syntax(r#"{"name": "Alice", "age": 30} []"#),
+        [vec![true], vec![true]]
+    );
+    assert_eq!(
+        syntax(r#"{"name": 1, "age": 30}"#),
+        [vec![false], vec![false]]
+    );
+}
+
+#[test]
+fn syntax_errors_of_well_formed_documents() {
+    #[derive(Debug, eserde::Deserialize)]
+    enum Role {
+        Admin { since: u32 },
+    }
+
+    #[derive(Debug, eserde::Deserialize)]
+    struct User {
+        role: Role,
+        age: u8,
+    }
+
+    // The content of the unknown variant is left unread, and the parser then trips over it.
+    let errors =
+        eserde::json::from_str::<User>(r#"{"role": {"Guest": {}}, "age": 30}"#).unwrap_err();
+    assert_eq!(errors.len(), 2);
+    assert!(errors.iter().all(|e| !e.is_syntax()));
+}
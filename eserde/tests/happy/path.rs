@@ -320,3 +320,108 @@ fn test_adjacent_tagged_enum() {
 
     test::<TestEnum>(j, "content");
 }
+
+#[test]
+fn test_unknown_variant_followed_by_errors() {
+    #[derive(Debug, Deserialize)]
+    enum Status {
+        Active,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct Subscription {
+        status: Status,
+        seats: u32,
+    }
+
+    let j = r#"{"status": "Pending", "seats": "x"}"#;
+
+    test_many::<Subscription>(j, &["status", "seats"]);
+}
+
+#[test]
+fn test_adjacent_tagged_enum_unknown_tag() {
+    #[derive(Debug, Deserialize)]
+    #[serde(tag = "type", content = "content")]
+    pub enum TestEnum {
+        A(u32),
+        B(u64),
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct Wrapper {
+        value: TestEnum,
+        name: String,
+    }
+
+    let j = r#"
+    {
+        "value": {
+            "type": "C",
+            "content": 500
+        },
+        "name": 1
+    }"#;
+
+    test_many::<Wrapper>(j, &["value.type", "name"]);
+}
+
+#[test]
+fn test_unknown_variant_in_sequence() {
+    #[derive(Debug, Deserialize)]
+    enum Status {
+        Active,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct Subscription {
+        statuses: Vec<Status>,
+        seats: u32,
+    }
+
+    let j = r#"{"statuses": ["Pending", "Active"], "seats": "x"}"#;
+
+    test_many::<Subscription>(j, &["statuses[0]", "seats"]);
+}
+
+#[test]
+fn test_external_tagged_enum_unknown_variant() {
+    #[derive(Debug, Deserialize)]
+    pub enum TestEnum {
+        A { x: u32 },
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct Wrapper {
+        value: TestEnum,
+        name: String,
+    }
+
+    // The content of the variant is left unread: so is the rest of the document.
+    let j = r#"
+    {
+        "value": {
+            "C": {"x": 1}
+        },
+        "name": 1
+    }"#;
+
+    test_many::<Wrapper>(j, &["value", "value"]);
+}
+
+#[test]
+fn test_external_tagged_enum_unknown_variant_in_seq() {
+    #[derive(Debug, Deserialize)]
+    pub enum TestEnum {
+        A { x: u32 },
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct Wrapper {
+        values: Vec<TestEnum>,
+    }
+
+    let j = r#"{"values": [{"C": {"x": 1}}, {"A": {"x": "2"}}]}"#;
+
+    test_many::<Wrapper>(j, &["values[0]", "values[0]", "values"]);
+}
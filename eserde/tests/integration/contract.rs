@@ -15,9 +15,15 @@ fn struct_deny_unknown_fields() {
                     path: Some(
                         Path {
                             segments: [],
+                            map_key: false,
                         },
                     ),
                     details: "missing field `write_only`",
+                    code: None,
+                    missing_field: Some(
+                        "write_only",
+                    ),
+                    syntax: false,
                 },
             ],
         ),
@@ -38,17 +44,29 @@ fn struct_allow_unknown_fields() {
                     path: Some(
                         Path {
                             segments: [],
+                            map_key: false,
                         },
                     ),
                     details: "missing field `write_only`",
+                    code: None,
+                    missing_field: Some(
+                        "write_only",
+                    ),
+                    syntax: false,
                 },
                 DeserializationError {
                     path: Some(
                         Path {
                             segments: [],
+                            map_key: false,
                         },
                     ),
                     details: "missing field `skip_serializing_if`",
+                    code: None,
+                    missing_field: Some(
+                        "skip_serializing_if",
+                    ),
+                    syntax: false,
                 },
             ],
         ),
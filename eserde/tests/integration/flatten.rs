@@ -45,7 +45,7 @@ fn flattened_value() {
             value: JsonValue(
                 Object {
                     "rh6rGx64R5z8bXM53JB": Null,
-                    "tKCHK": Number(0.4519912601301124),
+                    "tKCHK": Number(0.45199126013011237),
                     "zRNzOrvBGBkR": Number(-942429839.0),
                     "zvPAta": Number(-1473060449.0),
                 },
@@ -98,3 +98,36 @@ fn flattened_map() {
     )
     "#);
 }
+
+#[test]
+fn flattened_struct_invalid() {
+    #[derive(eserde::Deserialize, Debug)]
+    #[allow(dead_code)]
+    struct Order {
+        items: Vec<Item>,
+    }
+
+    #[derive(eserde::Deserialize, Debug)]
+    #[allow(dead_code)]
+    struct Item {
+        id: u32,
+        #[serde(flatten)]
+        details: Details,
+    }
+
+    #[derive(eserde::Deserialize, Debug)]
+    #[allow(dead_code)]
+    struct Details {
+        tags: Vec<String>,
+    }
+
+    let errors = eserde::json::from_str::<Order>(
+        r#"{"items": [{"id": 1, "tags": []}, {"id": 2, "tags": ["a", 3]}]}"#,
+    )
+    .unwrap_err();
+    // Errors within flattened fields point inside them.
+    insta::assert_snapshot!(errors, @r"
+    Something went wrong during deserialization:
+    - items[1].tags[1]: invalid type: integer `3`, expected a string
+    ");
+}
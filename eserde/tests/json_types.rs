@@ -20,12 +20,59 @@ fn test() {
 
     let result = eserde::json::from_str::<HashMap<String, Number>>(PAYLOAD);
     let errors = result.unwrap_err();
-    // Note the errors degrade after the `"d": {` due to the curly brace.
+    // The rejected map under `"d"` is skipped, so that `"e"` is checked too.
     insta::assert_snapshot!(errors, @r###"
     Something went wrong during deserialization:
     - b: invalid type: boolean `true`, expected a JSON number at line 3 column 17
-    - d: invalid type: map, expected a JSON number at line 5 column 15
-    - expected `,` or `}` at line 5 column 16
+    - d: invalid type: map, expected a JSON number at line 5 column 28
+    - e: invalid type: string "foo", expected a JSON number at line 6 column 18
     "###
     );
 }
+
+#[derive(eserde::Deserialize, Debug)]
+struct Event {
+    kind: String,
+    payload: Box<serde_json::value::RawValue>,
+}
+
+#[derive(eserde::Deserialize, Debug)]
+struct BorrowedEvent<'a> {
+    kind: String,
+    #[serde(borrow)]
+    payload: &'a serde_json::value::RawValue,
+}
+
+#[test]
+fn test_raw_value() {
+    const PAYLOAD: &str = r#"{"kind": "signup", "payload": { "name":"Alice",  "tags": [1, 2] }}"#;
+
+    let event = eserde::json::from_str::<Event>(PAYLOAD).unwrap();
+    assert_eq!(event.kind, "signup");
+    assert_eq!(
+        event.payload.get(),
+        r#"{ "name":"Alice",  "tags": [1, 2] }"#
+    );
+
+    let event = eserde::json::from_str::<BorrowedEvent>(PAYLOAD).unwrap();
+    assert_eq!(
+        event.payload.get(),
+        r#"{ "name":"Alice",  "tags": [1, 2] }"#
+    );
+}
+
+#[test]
+fn test_raw_value_surrounding_errors() {
+    // The raw payload itself is fine, only its siblings are reported.
+    let errors = eserde::json::from_str::<Event>(r#"{"kind": 1, "payload": [true]}"#).unwrap_err();
+    insta::assert_snapshot!(errors, @r###"
+    Something went wrong during deserialization:
+    - kind: invalid type: integer `1`, expected a string at line 1 column 10
+    "###);
+
+    let errors = eserde::json::from_str::<Event>(r#"{"kind": "signup"}"#).unwrap_err();
+    insta::assert_snapshot!(errors, @r###"
+    Something went wrong during deserialization:
+    - missing field `payload`
+    "###);
+}
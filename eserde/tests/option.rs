@@ -0,0 +1,65 @@
+#[derive(eserde::Deserialize, Debug, PartialEq, Eq)]
+struct Outer {
+    inner: Inner,
+    items: Vec<Inner>,
+}
+
+#[derive(eserde::Deserialize, Debug, PartialEq, Eq)]
+struct Inner {
+    required: u32,
+    optional: Option<u32>,
+}
+
+#[test]
+fn test_null_option() {
+    assert_eq!(
+        Outer {
+            inner: Inner {
+                required: 1,
+                optional: None,
+            },
+            items: vec![Inner {
+                required: 2,
+                optional: None,
+            }],
+        },
+        eserde::json::from_str(
+            r#"{"inner": {"required": 1, "optional": null}, "items": [{"required": 2}]}"#
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn test_null_required() {
+    let errs = eserde::json::from_str::<Outer>(
+        r#"{"inner": {"required": null, "optional": null}, "items": [{"required": 1}, {"required": null}]}"#,
+    )
+    .unwrap_err();
+    insta::assert_snapshot!(errs, @r###"
+    Something went wrong during deserialization:
+    - inner.required: invalid type: null, expected u32 at line 1 column 27
+    - items[1].required: invalid type: null, expected u32 at line 1 column 92
+    "###);
+}
+
+#[test]
+fn test_null_required_struct() {
+    let errs = eserde::json::from_str::<Outer>(r#"{"inner": null, "items": [null]}"#).unwrap_err();
+    insta::assert_snapshot!(errs, @r###"
+    Something went wrong during deserialization:
+    - inner: invalid type: null, expected struct __ImplEDeserializeForInner at line 1 column 14
+    - items[0]: invalid type: null, expected struct __ImplEDeserializeForInner at line 1 column 30
+    "###);
+}
+
+#[test]
+fn test_missing_option_with_other_errors() {
+    // The missing `optional` field must not be reported, just like `serde` accepts it.
+    let errs = eserde::json::from_str::<Outer>(r#"{"inner": {"required": "1"}, "items": []}"#)
+        .unwrap_err();
+    insta::assert_snapshot!(errs, @r###"
+    Something went wrong during deserialization:
+    - inner.required: invalid type: string "1", expected u32 at line 1 column 26
+    "###);
+}
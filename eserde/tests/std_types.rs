@@ -114,7 +114,7 @@ fn test_seqs() {
     - [3].c: invalid type: integer `8`, expected a string at line 20 column 18
     - [3]: missing field `b`
     - [3]: missing field `d`
-    - expected sequence of 3 elements, found 4 elements.
+    - invalid length 4, expected an array of length 3
     "###);
 
     // Input is too short.
@@ -129,7 +129,32 @@ fn test_seqs() {
     - [3].c: invalid type: integer `8`, expected a string at line 20 column 18
     - [3]: missing field `b`
     - [3]: missing field `d`
-    - expected sequence of 5 elements, found 4 elements.
+    - invalid length 4, expected an array of length 5
+    "###);
+}
+
+#[test]
+fn test_tuples() {
+    let errors = eserde::json::from_str::<(u8, String, bool)>(r#"[-1, "ok", 3]"#).unwrap_err();
+    insta::assert_snapshot!(errors, @r###"
+    Something went wrong during deserialization:
+    - [0]: invalid value: integer `-1`, expected u8 at line 1 column 3
+    - [2]: invalid type: integer `3`, expected a boolean at line 1 column 12
+    "###);
+
+    // Input is too short.
+    let errors = eserde::json::from_str::<(u8, String, bool)>(r#"[-1, "ok"]"#).unwrap_err();
+    insta::assert_snapshot!(errors, @r###"
+    Something went wrong during deserialization:
+    - [0]: invalid value: integer `-1`, expected u8 at line 1 column 3
+    - invalid length 2, expected a tuple of size 3
+    "###);
+
+    // Input is too long.
+    let errors = eserde::json::from_str::<(u8, String)>(r#"[1, "ok", true]"#).unwrap_err();
+    insta::assert_snapshot!(errors, @r###"
+    Something went wrong during deserialization:
+    - invalid length 3, expected a tuple of size 2
     "###);
 }
 
@@ -198,3 +223,19 @@ fn test_map_nested() {
         "###);
     }
 }
+
+#[test]
+fn test_map_bad_key() {
+    const PAYLOAD: &str = r#"{"1": 5, "abc": 7}"#;
+
+    insta::allow_duplicates! {
+        check(eserde::json::from_str::<std::collections::HashMap<u32, u64>>(PAYLOAD));
+        check(eserde::json::from_str::<std::collections::BTreeMap<u32, u64>>(PAYLOAD));
+    }
+    fn check<T: std::fmt::Debug>(x: Result<T, DeserializationErrors>) {
+        let errs = x.unwrap_err();
+        let first = errs.iter().next().unwrap();
+        assert!(first.path().unwrap().is_map_key());
+        insta::assert_snapshot!(first, @"invalid value: expected key to be a number in quotes at line 1 column 10");
+    }
+}
@@ -0,0 +1,166 @@
+#![cfg(feature = "urlencoded")]
+#[derive(eserde::Deserialize, Debug, PartialEq, Eq)]
+struct Search {
+    query: String,
+    page: Option<u32>,
+    #[serde(rename = "tag", default)]
+    tags: Vec<u32>,
+    order: Order,
+}
+
+#[derive(eserde::Deserialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+enum Order {
+    Asc,
+    Desc,
+}
+
+#[test]
+fn test_happy() {
+    assert_eq!(
+        Search {
+            query: "hello world".to_owned(),
+            page: None,
+            tags: vec![],
+            order: Order::Asc,
+        },
+        eserde::urlencoded::from_str("query=hello+world&order=asc").unwrap()
+    );
+
+    assert_eq!(
+        Search {
+            query: "a&b".to_owned(),
+            page: Some(2),
+            tags: vec![1, 2, 3],
+            order: Order::Desc,
+        },
+        eserde::urlencoded::from_str("tag=1&query=a%26b&tag=2&page=2&order=desc&tag=3").unwrap()
+    );
+}
+
+#[test]
+fn test_fail() {
+    let x = eserde::urlencoded::from_str::<Search>("tag=1&tag=x&page=two&page=3&order=asc");
+    assert!(x.is_err(), "Expected Err: {:?}", x);
+    let errs = x.unwrap_err();
+    insta::assert_snapshot!(errs, @r#"
+    Something went wrong during deserialization:
+    - tag[1]: invalid type: string "x", expected u32
+    - page: duplicate field `page`
+    - missing field `query`
+    "#);
+}
+
+#[derive(eserde::Deserialize, Debug, PartialEq, Eq)]
+struct Listing {
+    filter: Filter,
+    #[serde(default)]
+    tag: Vec<String>,
+}
+
+#[derive(eserde::Deserialize, Debug, PartialEq, Eq)]
+struct Filter {
+    status: Order,
+    price: Option<PriceRange>,
+}
+
+#[derive(eserde::Deserialize, Debug, PartialEq, Eq)]
+struct PriceRange {
+    min: u32,
+    max: u32,
+}
+
+#[test]
+fn test_nested_happy() {
+    assert_eq!(
+        Listing {
+            filter: Filter {
+                status: Order::Asc,
+                price: Some(PriceRange { min: 10, max: 20 }),
+            },
+            tag: vec!["a".to_owned(), "b".to_owned()],
+        },
+        eserde::urlencoded::from_str_nested(
+            "filter[status]=asc&tag[]=a&filter%5Bprice%5D%5Bmin%5D=10&filter[price][max]=20&tag[]=b"
+        )
+        .unwrap()
+    );
+
+    // Brackets are taken literally by the flat parser.
+    let x = eserde::urlencoded::from_str::<Listing>("filter[status]=asc");
+    assert!(x.is_err(), "Expected Err: {:?}", x);
+}
+
+#[test]
+fn test_nested_fail() {
+    // `filter[status]` is given both a value and a nested key.
+    let x = eserde::urlencoded::from_str_nested::<Listing>(
+        "filter[price][min]=x&filter[price][max]=20&filter[status]=asc&filter[status][a]=b&tag[a]=b",
+    );
+    assert!(x.is_err(), "Expected Err: {:?}", x);
+    let errs = x.unwrap_err();
+    insta::assert_snapshot!(errs, @r#"
+    Something went wrong during deserialization:
+    - filter.price.min: invalid type: string "x", expected u32
+    - filter.status: duplicate field `status`
+    - tag: invalid type: map, expected a sequence
+    "#);
+}
+
+#[test]
+fn test_document_from_pairs() {
+    // Pairs are taken literally: neither percent-decoded nor split on brackets.
+    let document = eserde::urlencoded::Document::from_pairs([
+        ("query", "a%26b"),
+        ("tag", "1"),
+        ("tag", "x"),
+        ("order", "asc"),
+    ]);
+    let x = eserde::from_deserializer::<Search, _>(|| document.deserializer());
+    assert!(x.is_err(), "Expected Err: {:?}", x);
+    let errs = x.unwrap_err();
+    insta::assert_snapshot!(errs, @r#"
+    Something went wrong during deserialization:
+    - tag[1]: invalid type: string "x", expected u32
+    "#);
+}
+
+#[derive(eserde::Deserialize, Debug, PartialEq, Eq)]
+struct Flags {
+    active: bool,
+    #[serde(default)]
+    archived: bool,
+}
+
+#[test]
+fn test_flexible_booleans() {
+    let flags = |input: &'static str| {
+        let document =
+            eserde::urlencoded::Document::parse(input.as_bytes()).flexible_booleans(true);
+        eserde::from_deserializer::<Flags, _>(|| document.deserializer())
+    };
+    assert_eq!(
+        Flags {
+            active: true,
+            archived: false
+        },
+        flags("active&archived=OFF").unwrap()
+    );
+    assert_eq!(
+        Flags {
+            active: false,
+            archived: true
+        },
+        flags("active=0&archived=yes").unwrap()
+    );
+
+    let errs = flags("active=maybe").unwrap_err();
+    insta::assert_snapshot!(errs, @r#"
+    Something went wrong during deserialization:
+    - active: invalid type: string "maybe", expected a boolean
+    "#);
+
+    // Only `true` and `false` are accepted by default.
+    let x = eserde::urlencoded::from_str::<Flags>("active=1");
+    assert!(x.is_err(), "Expected Err: {:?}", x);
+}
@@ -0,0 +1,107 @@
+//! Compare the happy path of `eserde_axum::Json` against `axum::Json`.
+//!
+//! Run with `cargo bench -p eserde_axum --bench json`.
+use axum::body::Body;
+use axum::extract::{FromRequest, Request};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use std::hint::black_box;
+
+#[derive(eserde::Deserialize)]
+#[allow(dead_code)]
+struct Order {
+    id: u64,
+    customer: Customer,
+    items: Vec<Item>,
+    notes: Option<String>,
+}
+
+#[derive(eserde::Deserialize)]
+#[allow(dead_code)]
+struct Customer {
+    name: String,
+    email: String,
+}
+
+#[derive(eserde::Deserialize)]
+#[allow(dead_code)]
+struct Item {
+    sku: String,
+    quantity: u32,
+    price: f64,
+}
+
+fn payload(n_items: usize) -> String {
+    let items: Vec<_> = (0..n_items)
+        .map(|i| format!(r#"{{"sku": "SKU-{i}", "quantity": {i}, "price": 9.99}}"#))
+        .collect();
+    format!(
+        r#"{{"id": 42, "customer": {{"name": "Alice", "email": "alice@example.com"}}, "items": [{}], "notes": null}}"#,
+        items.join(", ")
+    )
+}
+
+fn request(body: &str) -> Request {
+    Request::builder()
+        .method("POST")
+        .uri("/")
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_owned()))
+        .unwrap()
+}
+
+fn from_bytes(c: &mut Criterion) {
+    let mut group = c.benchmark_group("from_bytes");
+    for n_items in [1, 100] {
+        let payload = payload(n_items);
+        group.throughput(Throughput::Bytes(payload.len() as u64));
+        group.bench_with_input(BenchmarkId::new("axum", n_items), &payload, |b, payload| {
+            b.iter(|| axum::Json::<Order>::from_bytes(black_box(payload.as_bytes())).unwrap())
+        });
+        group.bench_with_input(
+            BenchmarkId::new("eserde_axum", n_items),
+            &payload,
+            |b, payload| {
+                b.iter(|| {
+                    eserde_axum::Json::<Order>::from_bytes(black_box(payload.as_bytes())).unwrap()
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+fn from_request(c: &mut Criterion) {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+    let mut group = c.benchmark_group("from_request");
+    for n_items in [1, 100] {
+        let payload = payload(n_items);
+        group.throughput(Throughput::Bytes(payload.len() as u64));
+        group.bench_with_input(BenchmarkId::new("axum", n_items), &payload, |b, payload| {
+            b.iter(|| {
+                runtime
+                    .block_on(axum::Json::<Order>::from_request(request(payload), &()))
+                    .unwrap()
+            })
+        });
+        group.bench_with_input(
+            BenchmarkId::new("eserde_axum", n_items),
+            &payload,
+            |b, payload| {
+                b.iter(|| {
+                    runtime
+                        .block_on(eserde_axum::Json::<Order>::from_request(
+                            request(payload),
+                            &(),
+                        ))
+                        .unwrap()
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, from_bytes, from_request);
+criterion_main!(benches);
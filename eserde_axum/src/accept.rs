@@ -0,0 +1,289 @@
+//! Negotiate the media type of the response via the `Accept` header.
+//!
+//! See [`AcceptNegotiation`] for more details.
+use std::borrow::Cow;
+use std::sync::Arc;
+
+use axum_core::extract::FromRequestParts;
+use axum_core::response::{IntoResponse, Response};
+use http::request::Parts;
+use http::{HeaderMap, StatusCode};
+
+use crate::config::Config;
+use crate::details::ProblemDetails;
+
+#[doc(hidden)]
+macro_rules! __log_rejection {
+    (
+        rejection_type = $ty:ident,
+        status = $status:expr,
+    ) => {
+        {
+            tracing::event!(
+                target: "eserde_axum::accept::rejection",
+                tracing::Level::TRACE,
+                status = $status.as_u16(),
+                rejection_type = ::std::any::type_name::<$ty>(),
+                "rejecting request",
+            );
+        }
+    };
+}
+
+/// Extractor for the media type the response should be encoded with,
+/// e.g. `text/csv`, chosen out of the `Accept` header of the request.
+///
+/// The candidates are the media types listed via [`Config::producible_media_types`].
+/// The extractor picks the one with the highest quality value in the `Accept` header,
+/// matching it against the most specific media range (e.g. `text/csv` before
+/// `text/*` before `*/*`); ties are broken by the order of the producible types.
+/// Requests without an `Accept` header get the first producible type.
+///
+/// The request will be rejected (and a [`NotAcceptable`] will be returned) if none
+/// of the producible types is acceptable, e.g. because they all have `q=0`.
+///
+/// Media type parameters other than `q` are ignored.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{routing::get, Extension, Router};
+/// use eserde_axum::{AcceptNegotiation, Config};
+///
+/// async fn export_users(accept: AcceptNegotiation) -> String {
+///     format!("Exporting users as {}", accept.as_str())
+/// }
+///
+/// let app = Router::new()
+///     .route("/users", get(export_users))
+///     .layer(Extension(
+///         Config::new().producible_media_types(["application/json", "text/csv"]),
+///     ));
+/// # let _: Router = app;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AcceptNegotiation(Cow<'static, str>);
+
+impl AcceptNegotiation {
+    /// The chosen media type, as listed via [`Config::producible_media_types`].
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl PartialEq<str> for AcceptNegotiation {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for AcceptNegotiation {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+impl<S> FromRequestParts<S> for AcceptNegotiation
+where
+    S: Send + Sync,
+{
+    type Rejection = NotAcceptable;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let config = Config::from_request(parts);
+        let producible = &config.settings.producible_media_types;
+        match negotiate(&parts.headers, producible) {
+            Some(media_type) => Ok(Self(media_type.clone())),
+            None => Err(NotAcceptable {
+                producible: producible.clone(),
+                config,
+            }),
+        }
+    }
+}
+
+/// A media range listed in the `Accept` header, e.g. `text/*;q=0.5`.
+pub(crate) struct MediaRange<'a> {
+    type_: &'a str,
+    subtype: &'a str,
+    pub(crate) quality: f32,
+}
+
+/// The media ranges listed in the `Accept` header of a request, in order.
+///
+/// Ranges that aren't of the form `type/subtype` are skipped,
+/// while a malformed quality value counts as `q=0`.
+pub(crate) fn media_ranges(headers: &HeaderMap) -> impl Iterator<Item = MediaRange<'_>> {
+    headers
+        .get_all(http::header::ACCEPT)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .filter_map(|range| {
+            let mut params = range.split(';').map(str::trim);
+            let (type_, subtype) = params.next()?.split_once('/')?;
+            let quality = params
+                .find_map(|param| param.strip_prefix("q="))
+                .map_or(Some(1.0), |q| q.parse::<f32>().ok())
+                .unwrap_or(0.0);
+            Some(MediaRange {
+                type_: type_.trim(),
+                subtype: subtype.trim(),
+                quality,
+            })
+        })
+}
+
+impl MediaRange<'_> {
+    /// Whether the range names the given media type, rather than matching it via a wildcard.
+    #[cfg(feature = "cbor")]
+    pub(crate) fn is(&self, type_: &str, subtype: &str) -> bool {
+        self.specificity(type_, subtype) == Some(2)
+    }
+
+    /// How specifically the range matches the given media type, if it does:
+    /// `*/*` is the least specific, an exact match the most.
+    fn specificity(&self, type_: &str, subtype: &str) -> Option<u8> {
+        if self.type_ == "*" {
+            return Some(0);
+        }
+        if !self.type_.eq_ignore_ascii_case(type_) {
+            return None;
+        }
+        if self.subtype == "*" {
+            return Some(1);
+        }
+        self.subtype.eq_ignore_ascii_case(subtype).then_some(2)
+    }
+}
+
+/// The producible media type preferred by the client, if any is acceptable.
+fn negotiate<'a>(
+    headers: &HeaderMap,
+    producible: &'a [Cow<'static, str>],
+) -> Option<&'a Cow<'static, str>> {
+    let ranges: Vec<_> = media_ranges(headers).collect();
+    if ranges.is_empty() {
+        return producible.first();
+    }
+
+    let mut best: Option<(&Cow<'static, str>, f32)> = None;
+    for media_type in producible {
+        let (type_, subtype) = media_type.split_once('/').unwrap_or((media_type, ""));
+        // The quality of the most specific range matching the media type.
+        let quality = ranges
+            .iter()
+            .filter_map(|range| Some((range.specificity(type_, subtype)?, range.quality)))
+            .max_by(|(a, a_quality), (b, b_quality)| a.cmp(b).then(a_quality.total_cmp(b_quality)))
+            .map_or(0.0, |(_, quality)| quality);
+        if quality > 0.0 && best.is_none_or(|(_, best)| quality > best) {
+            best = Some((media_type, quality));
+        }
+    }
+    best.map(|(media_type, _)| media_type)
+}
+
+#[derive(Debug)]
+#[non_exhaustive]
+/// Rejection type for [`AcceptNegotiation`].
+///
+/// This rejection is used if none of the producible media types
+/// is acceptable according to the `Accept` header of the request.
+/// It is rendered as a `406 Not Acceptable` problem, listing the producible
+/// media types in the `producible_types` member.
+pub struct NotAcceptable {
+    pub(crate) producible: Arc<[Cow<'static, str>]>,
+    pub(crate) config: Config,
+}
+
+impl NotAcceptable {
+    /// The media types the extractor could choose from.
+    pub fn producible(&self) -> impl Iterator<Item = &str> {
+        self.producible.iter().map(|media_type| media_type.as_ref())
+    }
+}
+
+#[derive(serde::Serialize)]
+struct ProducibleTypes {
+    producible_types: Arc<[Cow<'static, str>]>,
+}
+
+impl IntoResponse for NotAcceptable {
+    fn into_response(self) -> Response {
+        let status = StatusCode::NOT_ACCEPTABLE;
+        let detail = self.to_string();
+        let problem = ProblemDetails {
+            extensions: Some(ProducibleTypes {
+                producible_types: self.producible,
+            }),
+            ..ProblemDetails::new(
+                "not_acceptable",
+                status,
+                "The requested media type is not available",
+                detail,
+            )
+        }
+        .configure(&self.config);
+        __log_rejection!(rejection_type = NotAcceptable, status = status,);
+        problem.into_response()
+    }
+}
+
+impl std::fmt::Display for NotAcceptable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "None of the media types listed in the `Accept` header can be produced"
+        )
+    }
+}
+
+impl std::error::Error for NotAcceptable {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils;
+    use http::Request;
+
+    async fn extract(accept: &str) -> Result<AcceptNegotiation, NotAcceptable> {
+        let request = Request::builder()
+            .uri("/users")
+            .header(http::header::ACCEPT, accept)
+            .extension(Config::new().producible_media_types(["application/json", "text/csv"]))
+            .body(())
+            .unwrap();
+        let (mut parts, _) = request.into_parts();
+        AcceptNegotiation::from_request_parts(&mut parts, &()).await
+    }
+
+    #[tokio::test]
+    async fn test_acceptable() {
+        assert_eq!(extract("text/csv").await.unwrap(), "text/csv");
+        assert_eq!(
+            extract("text/csv;q=0.5, application/*").await.unwrap(),
+            "application/json"
+        );
+        // The most specific range wins, ties go to the first producible type.
+        assert_eq!(
+            extract("*/*, application/json;q=0").await.unwrap(),
+            "text/csv"
+        );
+        assert_eq!(extract("*/*").await.unwrap(), "application/json");
+    }
+
+    #[tokio::test]
+    async fn test_not_acceptable() {
+        let rejection = extract("text/html, application/json;q=0")
+            .await
+            .unwrap_err();
+
+        let (status, body) = test_utils::problem(rejection).await;
+        assert_eq!(status, 406);
+        assert_eq!(body["type"], "not_acceptable");
+        assert_eq!(
+            body["producible_types"],
+            serde_json::json!(["application/json", "text/csv"])
+        );
+    }
+}
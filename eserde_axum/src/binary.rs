@@ -0,0 +1,607 @@
+//! Extract a fixed-size binary header, followed by arbitrary bytes.
+//!
+//! See [`Binary`] for more details.
+use std::fmt::Display;
+
+use axum_core::extract::rejection::BytesRejection;
+use axum_core::extract::{FromRequest, Request};
+use axum_core::response::{IntoResponse, Response};
+use bytes::Bytes;
+use http::header::CONTENT_TYPE;
+use http::{HeaderMap, StatusCode};
+
+use crate::config::Config;
+use crate::details::{
+    bytes_rejection_response, InvalidRequest, ProblemDetails, Source, UnsupportedMediaType,
+    ValidationError, ValidationErrors,
+};
+
+#[doc(hidden)]
+macro_rules! __log_rejection {
+    (
+        rejection_type = $ty:ident,
+        status = $status:expr,
+    ) => {
+        {
+            tracing::event!(
+                target: "eserde_axum::binary::rejection",
+                tracing::Level::TRACE,
+                status = $status.as_u16(),
+                rejection_type = ::std::any::type_name::<$ty>(),
+                "rejecting request",
+            );
+        }
+    };
+}
+
+/// A fixed-size header at the start of a binary request body, see [`Binary`].
+///
+/// # Example
+///
+/// ```rust
+/// use eserde_axum::binary::BinaryHeader;
+///
+/// /// A version byte, followed by a big-endian sequence number.
+/// struct Frame {
+///     version: u8,
+///     sequence: u32,
+/// }
+///
+/// impl BinaryHeader for Frame {
+///     const LEN: usize = 5;
+///     type Error = String;
+///
+///     fn parse(bytes: &[u8]) -> Result<Self, Self::Error> {
+///         let version = bytes[0];
+///         if version != 1 {
+///             return Err(format!("Unsupported frame version {version}"));
+///         }
+///         let sequence = u32::from_be_bytes(bytes[1..5].try_into().unwrap());
+///         Ok(Frame { version, sequence })
+///     }
+/// }
+/// ```
+pub trait BinaryHeader: Sized {
+    /// The length of the header, in bytes.
+    const LEN: usize;
+
+    /// The error returned if the header is malformed.
+    ///
+    /// It's used as the `detail` of the problem returned to the client.
+    type Error: Display;
+
+    /// Parse the header out of the first [`LEN`](Self::LEN) bytes of the body.
+    ///
+    /// `bytes` is always exactly [`LEN`](Self::LEN) bytes long.
+    fn parse(bytes: &[u8]) -> Result<Self, Self::Error>;
+}
+
+/// Extractor for `application/octet-stream` request bodies made of a fixed-size
+/// binary header, followed by arbitrary bytes—for binary protocols that can't
+/// use multipart.
+///
+/// The first [`H::LEN`](BinaryHeader::LEN) bytes of the body are parsed via
+/// [`BinaryHeader::parse`], while the rest is handed over as is.
+///
+/// The request will be rejected (and a [`BinaryRejection`] will be returned) if:
+///
+/// - The request doesn't have a `Content-Type: application/octet-stream` header.
+/// - The body is shorter than the header.
+/// - The header can't be parsed.
+/// - Buffering the request body fails.
+///
+/// ⚠️ Since parsing the header requires consuming the request body, the `Binary`
+/// extractor must be *last* if there are multiple extractors in a handler.
+/// See ["the order of extractors"][order-of-extractors]
+///
+/// [order-of-extractors]: https://docs.rs/axum/latest/axum/extract/index.html#the-order-of-extractors
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{routing::post, Router};
+/// use eserde_axum::binary::{Binary, BinaryHeader};
+///
+/// struct Frame {
+///     sequence: u32,
+/// }
+///
+/// impl BinaryHeader for Frame {
+///     const LEN: usize = 4;
+///     type Error = std::convert::Infallible;
+///
+///     fn parse(bytes: &[u8]) -> Result<Self, Self::Error> {
+///         Ok(Frame {
+///             sequence: u32::from_be_bytes(bytes.try_into().unwrap()),
+///         })
+///     }
+/// }
+///
+/// async fn ingest(Binary(frame, payload): Binary<Frame>) -> String {
+///     format!("Frame #{} carries {} bytes", frame.sequence, payload.len())
+/// }
+///
+/// let app = Router::new().route("/frames", post(ingest));
+/// # let _: Router = app;
+/// ```
+#[derive(Debug, Clone, Default)]
+#[must_use]
+pub struct Binary<H>(pub H, pub Bytes);
+
+impl<H, S> FromRequest<S> for Binary<H>
+where
+    H: BinaryHeader,
+    S: Send + Sync,
+{
+    type Rejection = BinaryRejection;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let config = Config::from_request(&req);
+        check_octet_stream_content_type(req.headers(), &config)?;
+        let (bytes, _permit) =
+            crate::body::read_body::<_, Self::Rejection>(req, state, &config).await?;
+        Self::from_bytes_with_config(bytes, &config)
+    }
+}
+
+impl<H> Binary<H>
+where
+    H: BinaryHeader,
+{
+    /// Split a buffered body into its header and its trailing bytes.
+    ///
+    /// Most users should prefer to use the `FromRequest` impl.
+    pub fn from_bytes(bytes: Bytes) -> Result<Self, BinaryRejection> {
+        Self::from_bytes_with_config(bytes, &Config::default())
+    }
+
+    fn from_bytes_with_config(mut bytes: Bytes, config: &Config) -> Result<Self, BinaryRejection> {
+        if bytes.len() < H::LEN {
+            return Err(BodyTooShort {
+                expected: H::LEN,
+                actual: bytes.len(),
+                config: config.clone(),
+            }
+            .into());
+        }
+        let header = bytes.split_to(H::LEN);
+        match H::parse(&header) {
+            Ok(header) => Ok(Self(header, bytes)),
+            Err(e) => Err(InvalidBinaryHeader {
+                detail: e.to_string(),
+                config: config.clone(),
+            }
+            .into()),
+        }
+    }
+}
+
+/// Check that the `Content-Type` header is set to `application/octet-stream`.
+/// Parameters don't matter.
+///
+/// Return an error otherwise.
+fn check_octet_stream_content_type(
+    headers: &HeaderMap,
+    config: &Config,
+) -> Result<(), BinaryRejection> {
+    let Some(content_type) = headers
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+    else {
+        return Err(MissingBinaryContentType {
+            config: config.clone(),
+        }
+        .into());
+    };
+    let essence = content_type.split(';').next().unwrap_or_default().trim();
+    if !essence.eq_ignore_ascii_case("application/octet-stream") {
+        return Err(BinaryContentTypeMismatch {
+            actual: content_type.to_owned(),
+            config: config.clone(),
+        }
+        .into());
+    }
+    Ok(())
+}
+
+#[derive(Debug, Default)]
+#[non_exhaustive]
+/// Rejection type for [`Binary`] used if the `Content-Type` header is missing.
+pub struct MissingBinaryContentType {
+    pub(crate) config: Config,
+}
+
+impl IntoResponse for MissingBinaryContentType {
+    fn into_response(self) -> Response {
+        let error = ValidationError::new(
+            Source::Header {
+                name: CONTENT_TYPE.as_str().into(),
+            },
+            "missing_content_type",
+            "Expected request with `Content-Type: application/octet-stream`, but no `Content-Type` header was found",
+        );
+        let response = InvalidRequest::new(ValidationErrors {
+            errors: vec![error],
+            by_pointer: false,
+        })
+        .configure(&self.config);
+        __log_rejection!(
+            rejection_type = MissingBinaryContentType,
+            status = InvalidRequest::status(),
+        );
+        response.into_response()
+    }
+}
+
+impl std::fmt::Display for MissingBinaryContentType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Expected request with `Content-Type: application/octet-stream`")
+    }
+}
+
+impl std::error::Error for MissingBinaryContentType {}
+
+#[derive(Debug)]
+#[non_exhaustive]
+/// Rejection type for [`Binary`] used if the `Content-Type` header has an incorrect value.
+///
+/// It results in a `415 Unsupported Media Type` problem, listing the accepted
+/// media types in the `expected` member and the header value in the `received` one.
+pub struct BinaryContentTypeMismatch {
+    pub(crate) actual: String,
+    pub(crate) config: Config,
+}
+
+/// The media types accepted by [`Binary`], as listed in a [`BinaryContentTypeMismatch`] problem.
+const EXPECTED_CONTENT_TYPES: &[&str] = &["application/octet-stream"];
+
+impl BinaryContentTypeMismatch {
+    /// The media types accepted by the extractor.
+    pub fn expected(&self) -> &'static [&'static str] {
+        EXPECTED_CONTENT_TYPES
+    }
+
+    /// The value of the `Content-Type` header of the request.
+    pub fn received(&self) -> &str {
+        &self.actual
+    }
+}
+
+impl IntoResponse for BinaryContentTypeMismatch {
+    fn into_response(self) -> Response {
+        let message = self.to_string();
+        let response = UnsupportedMediaType::mismatch(
+            EXPECTED_CONTENT_TYPES,
+            self.actual,
+            message,
+            "The request body must be binary data",
+        )
+        .configure(&self.config);
+        __log_rejection!(
+            rejection_type = BinaryContentTypeMismatch,
+            status = UnsupportedMediaType::status(),
+        );
+        response.into_response()
+    }
+}
+
+impl std::fmt::Display for BinaryContentTypeMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Expected request with `Content-Type: application/octet-stream`, but found `{}`",
+            self.actual
+        )
+    }
+}
+
+impl std::error::Error for BinaryContentTypeMismatch {}
+
+#[derive(Debug)]
+#[non_exhaustive]
+/// Rejection type for [`Binary`] used if the request body is shorter than
+/// the [`BinaryHeader`].
+pub struct BodyTooShort {
+    pub(crate) expected: usize,
+    pub(crate) actual: usize,
+    pub(crate) config: Config,
+}
+
+impl IntoResponse for BodyTooShort {
+    fn into_response(self) -> Response {
+        let status = StatusCode::BAD_REQUEST;
+        let problem: ProblemDetails<()> = ProblemDetails::new(
+            "body_too_short",
+            status,
+            "The request body is too short to hold its header",
+            self.to_string(),
+        )
+        .configure(&self.config);
+        __log_rejection!(rejection_type = BodyTooShort, status = status,);
+        problem.into_response()
+    }
+}
+
+impl std::fmt::Display for BodyTooShort {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Expected a header of {} bytes, but the request body is only {} bytes long",
+            self.expected, self.actual
+        )
+    }
+}
+
+impl std::error::Error for BodyTooShort {}
+
+#[derive(Debug)]
+#[non_exhaustive]
+/// Rejection type for [`Binary`] used if the header can't be parsed.
+///
+/// The error returned by [`BinaryHeader::parse`] is used as the `detail`
+/// of the problem.
+pub struct InvalidBinaryHeader {
+    pub(crate) detail: String,
+    pub(crate) config: Config,
+}
+
+impl IntoResponse for InvalidBinaryHeader {
+    fn into_response(self) -> Response {
+        let status = StatusCode::BAD_REQUEST;
+        let problem: ProblemDetails<()> = ProblemDetails::new(
+            "invalid_binary_header",
+            status,
+            "The header of the request body couldn't be parsed",
+            self.detail,
+        )
+        .configure(&self.config);
+        __log_rejection!(rejection_type = InvalidBinaryHeader, status = status,);
+        problem.into_response()
+    }
+}
+
+impl std::fmt::Display for InvalidBinaryHeader {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Failed to parse the header of the request body: {}",
+            self.detail
+        )
+    }
+}
+
+impl std::error::Error for InvalidBinaryHeader {}
+
+/// Rejection used for [`Binary`].
+///
+/// Contains one variant for each way the [`Binary`] extractor
+/// can fail.
+///
+/// All error responses follow the problem details specification,
+/// as outlined in [RFC 9457](https://www.rfc-editor.org/rfc/rfc9457.html).
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum BinaryRejection {
+    #[allow(missing_docs)]
+    MissingBinaryContentType(MissingBinaryContentType),
+    #[allow(missing_docs)]
+    BinaryContentTypeMismatch(BinaryContentTypeMismatch),
+    #[allow(missing_docs)]
+    BodyTooShort(BodyTooShort),
+    #[allow(missing_docs)]
+    InvalidBinaryHeader(InvalidBinaryHeader),
+    #[allow(missing_docs)]
+    BytesRejection(BytesRejection),
+    #[allow(missing_docs)]
+    ContentTooLarge(crate::body::ContentTooLarge),
+    #[allow(missing_docs)]
+    Overloaded(crate::body::Overloaded),
+    #[allow(missing_docs)]
+    InvalidContentLength(crate::body::InvalidContentLength),
+    #[cfg(feature = "timeout")]
+    #[allow(missing_docs)]
+    RequestTimeout(crate::timeout::RequestTimeout),
+}
+
+impl IntoResponse for BinaryRejection {
+    fn into_response(self) -> Response {
+        match self {
+            Self::MissingBinaryContentType(inner) => inner.into_response(),
+            Self::BinaryContentTypeMismatch(inner) => inner.into_response(),
+            Self::BodyTooShort(inner) => inner.into_response(),
+            Self::InvalidBinaryHeader(inner) => inner.into_response(),
+            Self::BytesRejection(inner) => bytes_rejection_response(inner),
+            Self::ContentTooLarge(inner) => inner.into_response(),
+            Self::Overloaded(inner) => inner.into_response(),
+            Self::InvalidContentLength(inner) => inner.into_response(),
+            #[cfg(feature = "timeout")]
+            Self::RequestTimeout(inner) => inner.into_response(),
+        }
+    }
+}
+
+impl From<MissingBinaryContentType> for BinaryRejection {
+    fn from(inner: MissingBinaryContentType) -> Self {
+        Self::MissingBinaryContentType(inner)
+    }
+}
+impl From<BinaryContentTypeMismatch> for BinaryRejection {
+    fn from(inner: BinaryContentTypeMismatch) -> Self {
+        Self::BinaryContentTypeMismatch(inner)
+    }
+}
+impl From<BodyTooShort> for BinaryRejection {
+    fn from(inner: BodyTooShort) -> Self {
+        Self::BodyTooShort(inner)
+    }
+}
+impl From<InvalidBinaryHeader> for BinaryRejection {
+    fn from(inner: InvalidBinaryHeader) -> Self {
+        Self::InvalidBinaryHeader(inner)
+    }
+}
+impl From<BytesRejection> for BinaryRejection {
+    fn from(inner: BytesRejection) -> Self {
+        Self::BytesRejection(inner)
+    }
+}
+impl From<crate::body::ContentTooLarge> for BinaryRejection {
+    fn from(inner: crate::body::ContentTooLarge) -> Self {
+        Self::ContentTooLarge(inner)
+    }
+}
+impl From<crate::body::Overloaded> for BinaryRejection {
+    fn from(inner: crate::body::Overloaded) -> Self {
+        Self::Overloaded(inner)
+    }
+}
+impl From<crate::body::InvalidContentLength> for BinaryRejection {
+    fn from(inner: crate::body::InvalidContentLength) -> Self {
+        Self::InvalidContentLength(inner)
+    }
+}
+#[cfg(feature = "timeout")]
+impl From<crate::timeout::RequestTimeout> for BinaryRejection {
+    fn from(inner: crate::timeout::RequestTimeout) -> Self {
+        Self::RequestTimeout(inner)
+    }
+}
+impl std::fmt::Display for BinaryRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingBinaryContentType(inner) => write!(f, "{inner}"),
+            Self::BinaryContentTypeMismatch(inner) => write!(f, "{inner}"),
+            Self::BodyTooShort(inner) => write!(f, "{inner}"),
+            Self::InvalidBinaryHeader(inner) => write!(f, "{inner}"),
+            Self::BytesRejection(inner) => write!(f, "{inner}"),
+            Self::ContentTooLarge(inner) => write!(f, "{inner}"),
+            Self::Overloaded(inner) => write!(f, "{inner}"),
+            Self::InvalidContentLength(inner) => write!(f, "{inner}"),
+            #[cfg(feature = "timeout")]
+            Self::RequestTimeout(inner) => write!(f, "{inner}"),
+        }
+    }
+}
+impl std::error::Error for BinaryRejection {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::MissingBinaryContentType(inner) => inner.source(),
+            Self::BinaryContentTypeMismatch(inner) => inner.source(),
+            Self::BodyTooShort(inner) => inner.source(),
+            Self::InvalidBinaryHeader(inner) => inner.source(),
+            Self::BytesRejection(inner) => inner.source(),
+            Self::ContentTooLarge(inner) => inner.source(),
+            Self::Overloaded(inner) => inner.source(),
+            Self::InvalidContentLength(inner) => inner.source(),
+            #[cfg(feature = "timeout")]
+            Self::RequestTimeout(inner) => inner.source(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils;
+    use axum_core::body::Body;
+
+    /// A version byte, followed by a big-endian sequence number.
+    #[derive(Debug, PartialEq)]
+    struct Frame {
+        version: u8,
+        sequence: u32,
+    }
+
+    impl BinaryHeader for Frame {
+        const LEN: usize = 5;
+        type Error = String;
+
+        fn parse(bytes: &[u8]) -> Result<Self, Self::Error> {
+            let version = bytes[0];
+            if version != 1 {
+                return Err(format!("Unsupported frame version {version}"));
+            }
+            let sequence = u32::from_be_bytes(bytes[1..5].try_into().unwrap());
+            Ok(Frame { version, sequence })
+        }
+    }
+
+    fn request(body: &'static [u8]) -> Request {
+        Request::builder()
+            .method("POST")
+            .uri("/")
+            .header(CONTENT_TYPE, "application/octet-stream")
+            .body(Body::from(body))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_header_then_payload() {
+        let Binary(frame, payload) =
+            Binary::<Frame>::from_request(request(b"\x01\x00\x00\x00\x2a\xde\xad"), &())
+                .await
+                .unwrap();
+        assert_eq!(
+            frame,
+            Frame {
+                version: 1,
+                sequence: 42
+            }
+        );
+        assert_eq!(&payload[..], b"\xde\xad");
+    }
+
+    #[tokio::test]
+    async fn test_body_too_short() {
+        let rejection = Binary::<Frame>::from_request(request(b"\x01\x00"), &())
+            .await
+            .unwrap_err();
+        assert!(matches!(rejection, BinaryRejection::BodyTooShort(_)));
+
+        let (status, body) = test_utils::problem(rejection).await;
+        assert_eq!(status, 400);
+        assert_eq!(body["type"], "body_too_short");
+        assert_eq!(
+            body["detail"],
+            "Expected a header of 5 bytes, but the request body is only 2 bytes long"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_invalid_header() {
+        let rejection = Binary::<Frame>::from_request(request(b"\x02\x00\x00\x00\x2a"), &())
+            .await
+            .unwrap_err();
+
+        let (status, body) = test_utils::problem(rejection).await;
+        assert_eq!(status, 400);
+        assert_eq!(body["type"], "invalid_binary_header");
+        assert_eq!(body["detail"], "Unsupported frame version 2");
+    }
+
+    #[tokio::test]
+    async fn test_content_type_mismatch() {
+        let request = Request::builder()
+            .method("POST")
+            .uri("/")
+            .header(CONTENT_TYPE, "text/plain")
+            .body(Body::from("\x01\x00\x00\x00\x2a"))
+            .unwrap();
+        let rejection = Binary::<Frame>::from_request(request, &())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            rejection,
+            BinaryRejection::BinaryContentTypeMismatch(_)
+        ));
+
+        let (status, body) = test_utils::problem(rejection).await;
+        assert_eq!(status, 415);
+        assert_eq!(body["errors"][0]["code"], "content_type_mismatch");
+        assert_eq!(
+            body["expected"],
+            serde_json::json!(["application/octet-stream"])
+        );
+        assert_eq!(body["received"], "text/plain");
+    }
+}
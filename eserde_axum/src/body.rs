@@ -0,0 +1,408 @@
+//! Supporting types for buffering request bodies.
+//!
+//! See [`Config::max_body_size`](crate::Config::max_body_size) for more details.
+use axum_core::body::Body;
+use axum_core::extract::rejection::BytesRejection;
+use axum_core::extract::Request;
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum_core::response::{IntoResponse, Response};
+use bytes::Bytes;
+use http::header::{CONTENT_LENGTH, RETRY_AFTER};
+use http::{HeaderMap, HeaderValue, StatusCode};
+use http_body_util::Limited;
+
+use crate::config::Config;
+use crate::details::ProblemDetails;
+
+#[doc(hidden)]
+macro_rules! __log_rejection {
+    (
+        rejection_type = $ty:ident,
+        status = $status:expr,
+    ) => {
+        {
+            tracing::event!(
+                target: "eserde_axum::body::rejection",
+                tracing::Level::TRACE,
+                status = $status.as_u16(),
+                rejection_type = ::std::any::type_name::<$ty>(),
+                "rejecting request",
+            );
+        }
+    };
+}
+
+/// Buffer the request body, honoring the limits specified in the [`Config`].
+///
+/// Requests announcing a body larger than [`Config::max_body_size`] are rejected
+/// right away: the body isn't polled, so clients waiting for a `100 Continue`
+/// (because of an `Expect: 100-continue` header) never get to send it.
+///
+/// The returned permit counts against [`Config::max_concurrent_extractions`]:
+/// hold it until the body has been deserialized.
+pub(crate) async fn read_body<S, R>(
+    mut req: Request,
+    state: &S,
+    config: &Config,
+) -> Result<(Bytes, ExtractionPermit), R>
+where
+    S: Send + Sync,
+    R: From<BytesRejection>
+        + From<ContentTooLarge>
+        + From<InvalidContentLength>
+        + From<Overloaded>
+        + FromTimeout,
+{
+    let length = content_length(req.headers()).map_err(|()| InvalidContentLength {
+        config: config.clone(),
+    })?;
+    if let Some(max) = config.settings.max_body_size {
+        if let Some(length) = length.filter(|length| *length > max as u64) {
+            return Err(ContentTooLarge {
+                length,
+                max,
+                config: config.clone(),
+            }
+            .into());
+        }
+        // The announced length may be missing (or wrong), enforce the limit while buffering too.
+        req = req.map(|body| Body::new(Limited::new(body, max)));
+    }
+    let permit = ExtractionPermit::acquire(config)?;
+    #[cfg(feature = "timeout")]
+    let bytes = crate::timeout::buffer::<_, R>(req, state, config).await?;
+    #[cfg(not(feature = "timeout"))]
+    let bytes = {
+        use axum_core::extract::FromRequest;
+
+        Bytes::from_request(req, state).await?
+    };
+    Ok((bytes, permit))
+}
+
+/// Counts the extractions in flight, for [`Config::max_concurrent_extractions`].
+#[derive(Debug)]
+pub(crate) struct ExtractionLimiter {
+    in_flight: AtomicUsize,
+    max: NonZeroUsize,
+    retry_after: HeaderValue,
+}
+
+impl ExtractionLimiter {
+    pub(crate) fn new(max: NonZeroUsize, retry_after: Duration) -> Self {
+        let seconds = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+        Self {
+            in_flight: AtomicUsize::new(0),
+            max,
+            retry_after: seconds.into(),
+        }
+    }
+}
+
+/// A slot among the [`Config::max_concurrent_extractions`], released on drop.
+///
+/// The default permit doesn't count against any limit.
+#[derive(Debug, Default)]
+pub(crate) struct ExtractionPermit(Option<Arc<ExtractionLimiter>>);
+
+impl ExtractionPermit {
+    /// Take a slot, if the extractions are bounded, without waiting for one to free up.
+    pub(crate) fn acquire(config: &Config) -> Result<Self, Overloaded> {
+        let Some(limiter) = &config.settings.extraction_limiter else {
+            return Ok(Self(None));
+        };
+        limiter
+            .in_flight
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |in_flight| {
+                (in_flight < limiter.max.get()).then_some(in_flight + 1)
+            })
+            .map_err(|_| Overloaded {
+                config: config.clone(),
+            })?;
+        Ok(Self(Some(limiter.clone())))
+    }
+}
+
+impl Drop for ExtractionPermit {
+    fn drop(&mut self) {
+        if let Some(limiter) = &self.0 {
+            limiter.in_flight.fetch_sub(1, Ordering::AcqRel);
+        }
+    }
+}
+
+/// Rejections that can be built out of a
+/// [`RequestTimeout`](crate::timeout::RequestTimeout), if the `timeout` feature is enabled.
+#[cfg(feature = "timeout")]
+pub(crate) trait FromTimeout: From<crate::timeout::RequestTimeout> {}
+
+#[cfg(feature = "timeout")]
+impl<R: From<crate::timeout::RequestTimeout>> FromTimeout for R {}
+
+/// Rejections that can be built out of a `RequestTimeout`, if the `timeout` feature is enabled.
+#[cfg(not(feature = "timeout"))]
+pub(crate) trait FromTimeout {}
+
+#[cfg(not(feature = "timeout"))]
+impl<R> FromTimeout for R {}
+
+/// The value of the `Content-Length` header, if any.
+///
+/// It fails if the value isn't a sequence of digits fitting in a `u64`, or if
+/// several headers disagree on the length.
+/// The length is only ever compared against the limits, never used to allocate.
+fn content_length(headers: &HeaderMap) -> Result<Option<u64>, ()> {
+    let mut length = None;
+    for value in headers.get_all(CONTENT_LENGTH) {
+        let bytes = value.as_bytes();
+        if bytes.is_empty() || !bytes.iter().all(u8::is_ascii_digit) {
+            return Err(());
+        }
+        // Only digits, hence valid UTF-8: parsing fails on overflow alone.
+        let value: u64 = std::str::from_utf8(bytes)
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .ok_or(())?;
+        if length.is_some_and(|length| length != value) {
+            return Err(());
+        }
+        length = Some(value);
+    }
+    Ok(length)
+}
+
+#[derive(Debug)]
+#[non_exhaustive]
+/// Rejection type used by the extractors of this crate if the `Content-Length` of the
+/// request exceeds [`Config::max_body_size`](crate::Config::max_body_size).
+pub struct ContentTooLarge {
+    pub(crate) length: u64,
+    pub(crate) max: usize,
+    pub(crate) config: Config,
+}
+
+impl IntoResponse for ContentTooLarge {
+    fn into_response(self) -> Response {
+        let status = StatusCode::PAYLOAD_TOO_LARGE;
+        let problem: ProblemDetails<()> = ProblemDetails::new(
+            "content_too_large",
+            status,
+            "The content is too large",
+            self.to_string(),
+        )
+        .configure(&self.config);
+        __log_rejection!(rejection_type = ContentTooLarge, status = status,);
+        problem.into_response()
+    }
+}
+
+impl std::fmt::Display for ContentTooLarge {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "The request body is {} bytes long, but at most {} bytes are accepted",
+            self.length, self.max
+        )
+    }
+}
+
+impl std::error::Error for ContentTooLarge {}
+
+#[derive(Debug)]
+#[non_exhaustive]
+/// Rejection type used by the extractors of this crate if the `Content-Length` header
+/// of the request isn't a valid length, e.g. because it overflows a `u64`.
+pub struct InvalidContentLength {
+    pub(crate) config: Config,
+}
+
+impl IntoResponse for InvalidContentLength {
+    fn into_response(self) -> Response {
+        let status = StatusCode::BAD_REQUEST;
+        let problem: ProblemDetails<()> = ProblemDetails::new(
+            "invalid_content_length",
+            status,
+            "The content length is invalid",
+            self.to_string(),
+        )
+        .configure(&self.config);
+        __log_rejection!(rejection_type = InvalidContentLength, status = status,);
+        problem.into_response()
+    }
+}
+
+impl std::fmt::Display for InvalidContentLength {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "The `Content-Length` header must be a single non-negative integer, fitting in 64 bits"
+        )
+    }
+}
+
+impl std::error::Error for InvalidContentLength {}
+
+#[derive(Debug)]
+#[non_exhaustive]
+/// Rejection type used by the extractors of this crate if
+/// [`Config::max_concurrent_extractions`](crate::Config::max_concurrent_extractions)
+/// request bodies are already being extracted.
+///
+/// It is rendered as a `503 Service Unavailable` problem, with a `Retry-After` header.
+pub struct Overloaded {
+    pub(crate) config: Config,
+}
+
+impl IntoResponse for Overloaded {
+    fn into_response(self) -> Response {
+        let status = StatusCode::SERVICE_UNAVAILABLE;
+        let retry_after = self
+            .config
+            .settings
+            .extraction_limiter
+            .as_ref()
+            .map(|limiter| limiter.retry_after.clone());
+        let problem: ProblemDetails<()> = ProblemDetails::new(
+            "overloaded",
+            status,
+            "The server is overloaded",
+            self.to_string(),
+        )
+        .configure(&self.config);
+        __log_rejection!(rejection_type = Overloaded, status = status,);
+        let mut response = problem.into_response();
+        if let Some(retry_after) = retry_after {
+            response.headers_mut().insert(RETRY_AFTER, retry_after);
+        }
+        response
+    }
+}
+
+impl std::fmt::Display for Overloaded {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Too many requests are being processed right now, please try again later"
+        )
+    }
+}
+
+impl std::error::Error for Overloaded {}
+
+#[cfg(all(test, feature = "json"))]
+mod tests {
+    use axum_core::extract::FromRequest;
+
+    use super::*;
+    use crate::test_utils::{self, Untouchable};
+    use crate::{Json, JsonRejection};
+
+    #[tokio::test]
+    async fn test_expect_continue_with_oversized_length() {
+        let request = Request::builder()
+            .method("POST")
+            .uri("/")
+            .header(http::header::CONTENT_TYPE, "application/json")
+            .header(http::header::EXPECT, "100-continue")
+            .header(CONTENT_LENGTH, "5000000")
+            .extension(Config::new().max_body_size(1024))
+            .body(Body::new(Untouchable))
+            .unwrap();
+        let rejection = Json::<serde_json::Value>::from_request(request, &())
+            .await
+            .unwrap_err();
+        assert!(matches!(rejection, JsonRejection::ContentTooLarge(_)));
+
+        let (status, body) = test_utils::problem(rejection).await;
+        assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(body["type"], "content_too_large");
+        assert_eq!(
+            body["detail"],
+            "The request body is 5000000 bytes long, but at most 1024 bytes are accepted"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_overflowing_length() {
+        for length in ["99999999999999999999", "-1", "+5", "12, 13"] {
+            let request = Request::builder()
+                .method("POST")
+                .uri("/")
+                .header(http::header::CONTENT_TYPE, "application/json")
+                .header(CONTENT_LENGTH, length)
+                .body(Body::new(Untouchable))
+                .unwrap();
+            let rejection = Json::<serde_json::Value>::from_request(request, &())
+                .await
+                .unwrap_err();
+            assert!(matches!(rejection, JsonRejection::InvalidContentLength(_)));
+
+            let (status, body) = test_utils::problem(rejection).await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+            assert_eq!(body["type"], "invalid_content_length");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_oversized_body_without_length() {
+        let request = test_utils::json_request(r#"{"name": "Alice"}"#)
+            .extension(Config::new().max_body_size(8));
+        let rejection = Json::<serde_json::Value>::from_request(request.body(), &())
+            .await
+            .unwrap_err();
+
+        let (status, body) = test_utils::problem(rejection).await;
+        assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(body["type"], "content_too_large");
+    }
+
+    #[tokio::test]
+    async fn test_overloaded() {
+        let config = Config::new().max_concurrent_extractions(1, Duration::from_millis(1500));
+        let request = test_utils::json_request(r#"{"name": "Alice"}"#)
+            .extension(config.clone())
+            .body();
+        // The first request holds the only slot until it's deserialized.
+        let (_, permit) = read_body::<_, JsonRejection>(request, &(), &config)
+            .await
+            .unwrap();
+
+        let request = test_utils::json_request(r#"{"name": "Bob"}"#).extension(config.clone());
+        let rejection = Json::<serde_json::Value>::from_request(request.body(), &())
+            .await
+            .unwrap_err();
+        assert!(matches!(rejection, JsonRejection::Overloaded(_)));
+        let response = rejection.into_response();
+        assert_eq!(response.headers()[RETRY_AFTER], "2");
+        let (status, body) = test_utils::problem(response).await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(body["type"], "overloaded");
+
+        drop(permit);
+        let request = test_utils::json_request(r#"{"name": "Bob"}"#).extension(config);
+        let Json(value) = Json::<serde_json::Value>::from_request(request.body(), &())
+            .await
+            .unwrap();
+        assert_eq!(value["name"], "Bob");
+    }
+
+    #[test]
+    #[should_panic(expected = "must be non-zero")]
+    fn test_zero_concurrent_extractions() {
+        let _ = Config::new().max_concurrent_extractions(0, Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn test_body_within_limit() {
+        let request = test_utils::json_request(r#"{"name": "Alice"}"#)
+            .extension(Config::new().max_body_size(1024));
+        let Json(value) = Json::<serde_json::Value>::from_request(request.body(), &())
+            .await
+            .unwrap();
+        assert_eq!(value["name"], "Alice");
+    }
+}
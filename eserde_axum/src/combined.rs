@@ -0,0 +1,549 @@
+//! Extract the path parameters, the query string and the JSON body of a request
+//! into a single value.
+//!
+//! See [`Combined`] for more details.
+use std::ops::{Deref, DerefMut};
+
+use axum::extract::rejection::RawPathParamsRejection;
+use axum::extract::RawPathParams;
+use axum_core::extract::{FromRequest, FromRequestParts, Request};
+use axum_core::response::{IntoResponse, Response};
+use eserde::urlencoded::Document;
+use eserde::{DeserializationErrors, EDeserialize};
+use serde::de::value::BorrowedStrDeserializer;
+use serde::de::DeserializeOwned;
+use serde::de::{self, DeserializeSeed, MapAccess, Visitor};
+
+use crate::config::Config;
+use crate::details::{InvalidRequest, ProblemDetails, ValidationError, ValidationErrors};
+use crate::json::{check_document, check_json_headers, JsonRejection};
+
+#[doc(hidden)]
+macro_rules! __log_rejection {
+    (
+        rejection_type = $ty:ident,
+        status = $status:expr,
+    ) => {
+        {
+            tracing::event!(
+                target: "eserde_axum::combined::rejection",
+                tracing::Level::TRACE,
+                status = $status.as_u16(),
+                rejection_type = ::std::any::type_name::<$ty>(),
+                "rejecting request",
+            );
+        }
+    };
+    (
+        rejection_type = $ty:ident,
+        status = $status:expr,
+        config = $config:expr,
+    ) => {
+        {
+            let (method, target) = $config.request_target();
+            tracing::event!(
+                target: "eserde_axum::combined::rejection",
+                tracing::Level::TRACE,
+                status = $status.as_u16(),
+                rejection_type = ::std::any::type_name::<$ty>(),
+                http.method = method,
+                http.target = target,
+                "rejecting request",
+            );
+        }
+    };
+}
+
+/// Extractor for the path parameters, the query string and the JSON body of a request,
+/// deserialized in one go.
+///
+/// Each field of the target type is deserialized from the part of the request it's
+/// marked with, via [`Combine`]:
+///
+/// - `#[combined(path)]` fields are deserialized from the path parameters of the route,
+///   like axum's `Path` would do for a struct.
+/// - `#[combined(query)]` fields are deserialized from the query string, like
+///   [`Query`](crate::Query) would do.
+/// - `#[combined(body)]` fields are deserialized from the request body, like
+///   [`Json`](crate::Json) would do.
+///
+/// Unlike a handler taking the three extractors separately, the request is rejected (and a
+/// [`CombinedRejection`] is returned) with all the errors found in the three parts at once.
+/// Each error points at its own part of the request: `source` is `path`, `query` or
+/// `body`, alongside the offending parameter or JSON pointer.
+///
+/// The requirements on the body are the same as [`Json`](crate::Json)'s: the `Content-Type`
+/// must be `application/json` and the body must honor the limits set in the [`Config`].
+///
+/// ⚠️ Since parsing the body requires consuming it, the `Combined` extractor must be
+/// *last* if there are multiple extractors in a handler.
+/// See ["the order of extractors"][order-of-extractors]
+///
+/// [order-of-extractors]: https://docs.rs/axum/latest/axum/extract/index.html#the-order-of-extractors
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{routing::put, Router};
+/// use eserde_axum::{Combine, Combined};
+///
+/// #[derive(eserde::Deserialize, Combine)]
+/// struct UpdateUser {
+///     #[combined(path)]
+///     user: UserPath,
+///     #[combined(query)]
+///     options: Notify,
+///     #[combined(body)]
+///     changes: UserChanges,
+/// }
+///
+/// #[derive(eserde::Deserialize)]
+/// struct UserPath {
+///     id: u64,
+/// }
+///
+/// #[derive(eserde::Deserialize)]
+/// struct Notify {
+///     #[serde(default)]
+///     notify: bool,
+/// }
+///
+/// #[derive(eserde::Deserialize)]
+/// struct UserChanges {
+///     name: String,
+/// }
+///
+/// // e.g. `PUT /users/42?notify=true` with `{"name": "Alice"}` as body
+/// async fn update_user(Combined(update): Combined<UpdateUser>) {
+///     // update is a `UpdateUser`
+/// }
+///
+/// let app = Router::new().route("/users/{id}", put(update_user));
+/// # let _: Router = app;
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(docsrs, doc(cfg(feature = "combined")))]
+#[must_use]
+pub struct Combined<T>(pub T);
+
+/// The request part each field of a [`Combined`] target is deserialized from.
+///
+/// Derive it via `#[derive(Combine)]`, marking each field with `#[combined(path)]`,
+/// `#[combined(query)]` or `#[combined(body)]`.
+/// The name of the field is the one `serde` knows it by, taking `#[serde(rename = "...")]`
+/// into account; `#[serde(rename_all = "...")]` isn't supported.
+#[cfg_attr(docsrs, doc(cfg(feature = "combined")))]
+pub trait Combine {
+    /// The name of each field, alongside the request part it's deserialized from.
+    const FIELDS: &'static [(&'static str, Part)];
+}
+
+pub use eserde_axum_derive::Combine;
+
+/// A part of the request a field of a [`Combined`] target can be deserialized from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+#[cfg_attr(docsrs, doc(cfg(feature = "combined")))]
+pub enum Part {
+    /// The path parameters of the route.
+    Path,
+    /// The query string.
+    Query,
+    /// The JSON body.
+    Body,
+}
+
+impl<T, S> FromRequest<S> for Combined<T>
+where
+    T: Combine,
+    T: DeserializeOwned,
+    T: for<'de> EDeserialize<'de>,
+    S: Send + Sync,
+{
+    type Rejection = CombinedRejection;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let config = Config::from_request(&req);
+        let (mut parts, body) = req.into_parts();
+        let params = RawPathParams::from_request_parts(&mut parts, state)
+            .await
+            .map_err(|rejection| InvalidPathParams {
+                rejection,
+                config: config.clone(),
+            })?;
+        check_json_headers(&parts.headers, &config)?;
+        let query = parts.uri.query().unwrap_or_default().to_owned();
+        let req = Request::from_parts(parts, body);
+        let (bytes, _permit) =
+            crate::body::read_body::<_, JsonRejection>(req, state, &config).await?;
+        check_document(&bytes, &config)?;
+
+        let path = Document::from_pairs(params.iter());
+        let query = Document::parse_nested(query.as_bytes())
+            .flexible_booleans(config.settings.flexible_query_booleans);
+        let sections = Sections {
+            fields: T::FIELDS,
+            path: &path,
+            query: &query,
+            body: &bytes,
+        };
+        match eserde::from_deserializer(|| sections) {
+            Ok(value) => Ok(Combined(value)),
+            Err(errors) => Err(CombinedError {
+                errors,
+                fields: T::FIELDS,
+                config,
+            }
+            .into()),
+        }
+    }
+}
+
+impl<T> Deref for Combined<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Combined<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T> From<T> for Combined<T> {
+    fn from(inner: T) -> Self {
+        Self(inner)
+    }
+}
+
+/// The request parts, visited as a map with one entry per field of the target type.
+#[derive(Clone, Copy)]
+struct Sections<'a> {
+    fields: &'static [(&'static str, Part)],
+    path: &'a Document<'a>,
+    query: &'a Document<'a>,
+    body: &'a [u8],
+}
+
+impl<'de> de::Deserializer<'de> for Sections<'de> {
+    type Error = serde_json::Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_map(SectionsAccess {
+            sections: self,
+            next: 0,
+        })
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+struct SectionsAccess<'a> {
+    sections: Sections<'a>,
+    /// The index, in [`Sections::fields`], of the next field to be visited.
+    next: usize,
+}
+
+impl<'de> MapAccess<'de> for SectionsAccess<'de> {
+    type Error = serde_json::Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        let Some((name, _)) = self.sections.fields.get(self.next) else {
+            return Ok(None);
+        };
+        seed.deserialize(BorrowedStrDeserializer::new(name))
+            .map(Some)
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let (_, part) = self.sections.fields[self.next];
+        self.next += 1;
+        match part {
+            Part::Path => seed
+                .deserialize(self.sections.path.deserializer())
+                .map_err(de::Error::custom),
+            Part::Query => seed
+                .deserialize(self.sections.query.deserializer())
+                .map_err(de::Error::custom),
+            Part::Body => {
+                let mut de = serde_json::Deserializer::from_slice(self.sections.body);
+                let value = seed.deserialize(&mut de)?;
+                de.end()?;
+                Ok(value)
+            }
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.sections.fields.len() - self.next)
+    }
+}
+
+#[derive(Debug)]
+#[cfg_attr(docsrs, doc(cfg(feature = "combined")))]
+/// Rejection type for [`Combined`].
+///
+/// This rejection is used if the path parameters, the query string or the body
+/// couldn't be deserialized into the target type.
+pub struct CombinedError {
+    pub(crate) errors: DeserializationErrors,
+    /// The fields of the target type, to tell which part each error belongs to.
+    pub(crate) fields: &'static [(&'static str, Part)],
+    pub(crate) config: Config,
+}
+
+impl IntoResponse for CombinedError {
+    fn into_response(self) -> Response {
+        let errors = self
+            .errors
+            .iter()
+            .map(|error| ValidationError::combined(error, self.fields))
+            .collect();
+        let response = InvalidRequest::new(ValidationErrors {
+            errors,
+            by_pointer: false,
+        })
+        .configure(&self.config);
+        __log_rejection!(
+            rejection_type = CombinedError,
+            status = InvalidRequest::status(),
+            config = self.config,
+        );
+        response.into_response()
+    }
+}
+
+impl std::fmt::Display for CombinedError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Failed to deserialize the request into the target schema:\n")?;
+        for e in self.errors.iter() {
+            writeln!(f, "- {}", e)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for CombinedError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.errors)
+    }
+}
+
+#[derive(Debug)]
+#[cfg_attr(docsrs, doc(cfg(feature = "combined")))]
+/// Rejection type for [`Combined`] used if the path parameters couldn't be extracted,
+/// e.g. because one of them isn't valid UTF-8 once percent-decoded.
+pub struct InvalidPathParams {
+    pub(crate) rejection: RawPathParamsRejection,
+    pub(crate) config: Config,
+}
+
+impl IntoResponse for InvalidPathParams {
+    fn into_response(self) -> Response {
+        let status = self.rejection.status();
+        let problem: ProblemDetails<()> = ProblemDetails::new(
+            "invalid_path_parameters",
+            status,
+            "The path parameters couldn't be extracted",
+            self.rejection.body_text(),
+        )
+        .configure(&self.config);
+        __log_rejection!(rejection_type = InvalidPathParams, status = status,);
+        problem.into_response()
+    }
+}
+
+impl std::fmt::Display for InvalidPathParams {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.rejection)
+    }
+}
+
+impl std::error::Error for InvalidPathParams {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.rejection)
+    }
+}
+
+/// Rejection used for [`Combined`].
+///
+/// Contains one variant for each way the [`Combined`] extractor
+/// can fail.
+///
+/// All error responses follow the problem details specification,
+/// as outlined in [RFC 9457](https://www.rfc-editor.org/rfc/rfc9457.html).
+#[derive(Debug)]
+#[non_exhaustive]
+#[cfg_attr(docsrs, doc(cfg(feature = "combined")))]
+pub enum CombinedRejection {
+    #[allow(missing_docs)]
+    CombinedError(CombinedError),
+    #[allow(missing_docs)]
+    InvalidPathParams(InvalidPathParams),
+    /// The body was rejected before deserialization, e.g. because of its `Content-Type`.
+    JsonRejection(JsonRejection),
+}
+
+impl IntoResponse for CombinedRejection {
+    fn into_response(self) -> Response {
+        match self {
+            Self::CombinedError(inner) => inner.into_response(),
+            Self::InvalidPathParams(inner) => inner.into_response(),
+            Self::JsonRejection(inner) => inner.into_response(),
+        }
+    }
+}
+
+impl From<CombinedError> for CombinedRejection {
+    fn from(inner: CombinedError) -> Self {
+        Self::CombinedError(inner)
+    }
+}
+impl From<InvalidPathParams> for CombinedRejection {
+    fn from(inner: InvalidPathParams) -> Self {
+        Self::InvalidPathParams(inner)
+    }
+}
+impl From<JsonRejection> for CombinedRejection {
+    fn from(inner: JsonRejection) -> Self {
+        Self::JsonRejection(inner)
+    }
+}
+
+impl std::fmt::Display for CombinedRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::CombinedError(inner) => write!(f, "{inner}"),
+            Self::InvalidPathParams(inner) => write!(f, "{inner}"),
+            Self::JsonRejection(inner) => write!(f, "{inner}"),
+        }
+    }
+}
+
+impl std::error::Error for CombinedRejection {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::CombinedError(inner) => inner.source(),
+            Self::InvalidPathParams(inner) => inner.source(),
+            Self::JsonRejection(inner) => inner.source(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils;
+    use axum::routing::put;
+    use axum::Router;
+    use axum_core::body::Body;
+    use http::header::CONTENT_TYPE;
+    use tower::ServiceExt;
+
+    #[derive(Debug, eserde::Deserialize, Combine)]
+    #[allow(dead_code)]
+    struct UpdateUser {
+        #[combined(path)]
+        path: UserPath,
+        #[combined(query)]
+        #[serde(rename = "options")]
+        query: Notify,
+        #[combined(body)]
+        changes: UserChanges,
+    }
+
+    #[derive(Debug, eserde::Deserialize)]
+    #[allow(dead_code)]
+    struct UserPath {
+        id: u64,
+    }
+
+    #[derive(Debug, eserde::Deserialize)]
+    #[allow(dead_code)]
+    struct Notify {
+        #[serde(default)]
+        notify: bool,
+    }
+
+    #[derive(Debug, eserde::Deserialize)]
+    #[allow(dead_code)]
+    struct UserChanges {
+        name: String,
+    }
+
+    async fn update_user(uri: &str, body: &'static str) -> Response {
+        async fn handler(Combined(update): Combined<UpdateUser>) -> String {
+            format!(
+                "{} {} {}",
+                update.path.id, update.query.notify, update.changes.name
+            )
+        }
+
+        let app = Router::new().route("/users/{id}", put(handler));
+        let request = http::Request::builder()
+            .method("PUT")
+            .uri(uri)
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(body))
+            .unwrap();
+        app.oneshot(request).await.unwrap()
+    }
+
+    #[test]
+    fn test_fields() {
+        assert_eq!(
+            UpdateUser::FIELDS,
+            [
+                ("path", Part::Path),
+                ("options", Part::Query),
+                ("changes", Part::Body)
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_valid_request() {
+        let response = update_user("/users/42?notify=true", r#"{"name": "Alice"}"#).await;
+        assert_eq!(response.status(), 200);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(body, "42 true Alice");
+    }
+
+    #[tokio::test]
+    async fn test_errors_across_parts() {
+        let response = update_user("/users/abc?notify=maybe", r#"{"name": 42}"#).await;
+
+        let (status, body) = test_utils::problem(response).await;
+        assert_eq!(status, 400);
+        assert_eq!(body["type"], "invalid_request");
+        let errors = body["errors"].as_array().unwrap();
+        assert_eq!(errors.len(), 3);
+        assert_eq!(errors[0]["source"], "path");
+        assert_eq!(errors[0]["parameter"], "id");
+        assert_eq!(errors[0]["code"], "invalid_type");
+        assert_eq!(errors[1]["source"], "query");
+        assert_eq!(errors[1]["parameter"], "notify");
+        assert_eq!(errors[1]["code"], "invalid_type");
+        assert_eq!(errors[2]["source"], "body");
+        assert_eq!(errors[2]["pointer"], "/name");
+        assert_eq!(errors[2]["code"], "invalid_type");
+    }
+}
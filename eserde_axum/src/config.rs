@@ -0,0 +1,1235 @@
+//! Configuration for the extractors provided by this crate.
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::num::NonZeroUsize;
+use std::sync::{Arc, OnceLock};
+
+/// Knobs to customize the behaviour of the extractors provided by this crate.
+///
+/// The extractors look for a `Config` in the request extensions, falling back
+/// to [`Config::default`] if there is none.
+/// The easiest way to install it is [`axum::Extension`], either for the whole
+/// router or for a subset of routes:
+///
+/// ```rust,no_run
+/// use axum::{routing::post, Extension, Router};
+/// use eserde_axum::{Config, Json};
+///
+/// #[derive(eserde::Deserialize)]
+/// struct CreateUser {
+///     email: String,
+/// }
+///
+/// async fn create_user(Json(payload): Json<CreateUser>) {}
+///
+/// let config = Config::new().max_object_members(64);
+/// let app = Router::new()
+///     .route("/users", post(create_user))
+///     .layer(Extension(config));
+/// # let _: Router = app;
+/// ```
+///
+/// [`axum::Extension`]: https://docs.rs/axum/latest/axum/struct.Extension.html
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct Config {
+    /// Behind an `Arc` since the configuration is cloned for every request
+    /// and carried around by rejections.
+    pub(crate) settings: Arc<Settings>,
+    /// The `Accept-Language` header of the current request, if a
+    /// [`Catalog`](crate::i18n::Catalog) has been installed.
+    ///
+    /// A boxed string rather than a `HeaderValue`, to keep rejections small.
+    #[cfg(feature = "i18n")]
+    pub(crate) accept_language: Option<Box<str>>,
+    /// The method, path and origin of the current request, if [`Config::log_request_target`]
+    /// or [`Config::resolve_relative_types`] is enabled.
+    pub(crate) request_target: Option<Arc<RequestTarget>>,
+    /// Set if the `Accept` header of the current request prefers CBOR problems
+    /// over JSON ones.
+    #[cfg(feature = "cbor")]
+    pub(crate) prefers_cbor: bool,
+}
+
+/// The endpoint hit by a request, as attached to the rejection events.
+#[derive(Debug)]
+pub(crate) struct RequestTarget {
+    #[cfg(any(feature = "json", feature = "form", feature = "query"))]
+    pub(crate) method: http::Method,
+    #[cfg(any(feature = "json", feature = "form", feature = "query"))]
+    pub(crate) path: Box<str>,
+    /// The scheme and host the request was sent to, e.g. `https://example.com`,
+    /// if [`Config::resolve_relative_types`] is enabled and a valid host was found.
+    pub(crate) origin: Option<Box<str>>,
+}
+
+/// The fallback `500` problem of a request, honoring [`Config::internal_server_error`]
+/// and [`Config::type_prefix`].
+///
+/// Problem responses carry one around in case they can't be serialized, which is
+/// seldom the case: it's only rendered if needed.
+#[derive(Debug, Clone)]
+pub(crate) struct Fallback {
+    settings: Arc<Settings>,
+    request_target: Option<Arc<RequestTarget>>,
+}
+
+impl Fallback {
+    /// The body of the fallback `500` problem, if it differs from the default one.
+    ///
+    /// It's rendered anew if its type has to be resolved against the origin of the request.
+    pub(crate) fn body(&self) -> Option<bytes::Bytes> {
+        let origin = self
+            .request_target
+            .as_ref()
+            .and_then(|target| target.origin.as_deref());
+        match origin {
+            Some(origin) if self.settings.type_prefix.starts_with('/') => {
+                Some(self.settings.internal_server_error_problem(origin))
+            }
+            _ => self.settings.internal_server_error.clone(),
+        }
+    }
+
+    /// The fallback `500 Internal Server Error` response.
+    pub(crate) fn response(&self) -> axum_core::response::Response {
+        use axum_core::response::IntoResponse;
+
+        match self.body() {
+            Some(body) => (
+                http::StatusCode::INTERNAL_SERVER_ERROR,
+                [(
+                    http::header::CONTENT_TYPE,
+                    crate::details::APPLICATION_PROBLEM_JSON,
+                )],
+                body,
+            )
+                .into_response(),
+            None => crate::details::INTERNAL_SERVER_ERROR.into_response(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Settings {
+    pub(crate) max_object_members: Option<NonZeroUsize>,
+    pub(crate) max_string_length: Option<NonZeroUsize>,
+    pub(crate) max_depth: Option<NonZeroUsize>,
+    /// The header trusted to advertise the depth of JSON request bodies, if any.
+    pub(crate) depth_header: Option<http::HeaderName>,
+    pub(crate) strict_json: bool,
+    pub(crate) fail_fast: bool,
+    pub(crate) case_insensitive_variants: bool,
+    /// The types accepted for the top-level value of JSON documents,
+    /// if restricted.
+    #[cfg(feature = "json")]
+    pub(crate) json_root_types: Option<Vec<crate::json::JsonType>>,
+    #[cfg(feature = "query")]
+    pub(crate) flexible_query_booleans: bool,
+    #[cfg(feature = "form")]
+    pub(crate) nested_form_fields: bool,
+    pub(crate) about_blank: bool,
+    pub(crate) status_as_string: bool,
+    pub(crate) omit_errors: bool,
+    pub(crate) list_missing_fields: bool,
+    pub(crate) coalesce_invalid_types: bool,
+    pub(crate) unprocessable_entity: bool,
+    pub(crate) errors_by_pointer: bool,
+    pub(crate) error_order: ErrorOrder,
+    /// URL templates for the documentation of each error code.
+    pub(crate) documentation: HashMap<Cow<'static, str>, String>,
+    pub(crate) problem_type_header: bool,
+    pub(crate) debug_raw_body: Option<usize>,
+    pub(crate) log_request_target: bool,
+    /// Falls back to [`DEFAULT_MAX_DETAIL_LENGTH`] if unset.
+    pub(crate) max_detail_length: Option<usize>,
+    pub(crate) max_body_size: Option<usize>,
+    /// Shared by all the clones of the configuration, if the extractions are bounded.
+    pub(crate) extraction_limiter: Option<Arc<crate::body::ExtractionLimiter>>,
+    /// The body of the fallback `500` problem, if overridden—either via
+    /// [`Config::internal_server_error`] or via [`Config::type_prefix`].
+    pub(crate) internal_server_error: Option<bytes::Bytes>,
+    /// The title and detail of the fallback `500` problem, if overridden.
+    pub(crate) internal_server_error_text: Option<(Cow<'static, str>, Cow<'static, str>)>,
+    /// Prepended to the `type` of every problem, empty by default.
+    pub(crate) type_prefix: Cow<'static, str>,
+    pub(crate) resolve_relative_types: bool,
+    pub(crate) api_version_segment: usize,
+    pub(crate) supported_api_versions: Arc<[Cow<'static, str>]>,
+    pub(crate) producible_media_types: Arc<[Cow<'static, str>]>,
+    #[cfg(feature = "i18n")]
+    pub(crate) catalog: Option<Arc<crate::i18n::Catalog>>,
+    #[cfg(feature = "timeout")]
+    pub(crate) body_read_timeout: Option<std::time::Duration>,
+    /// Falls back to [`DEFAULT_CHANNEL_CAPACITY`] if unset.
+    #[cfg(feature = "channel")]
+    pub(crate) channel_capacity: Option<usize>,
+    /// Falls back to [`DEFAULT_MAX_MULTIPART_PARTS`] if unset.
+    #[cfg(feature = "multipart")]
+    pub(crate) max_multipart_parts: Option<NonZeroUsize>,
+    /// The identity of the `invalid_request` problems, if overridden
+    /// by the current extractor.
+    #[cfg(feature = "json")]
+    pub(crate) identity: Option<crate::json::identity::Identity>,
+}
+
+impl Settings {
+    /// Serialize the body of the fallback `500` problem, if it differs from the default one.
+    fn render_internal_server_error(&mut self) {
+        self.internal_server_error = (self.internal_server_error_text.is_some()
+            || !self.type_prefix.is_empty())
+        .then(|| self.internal_server_error_problem(""));
+    }
+
+    /// Serialize the body of the fallback `500` problem, with `origin` in front
+    /// of its type.
+    fn internal_server_error_problem(&self, origin: &str) -> bytes::Bytes {
+        let (title, detail) = self.internal_server_error_text.clone().unwrap_or((
+            crate::details::INTERNAL_SERVER_ERROR_TITLE.into(),
+            crate::details::INTERNAL_SERVER_ERROR_DETAIL.into(),
+        ));
+        crate::details::internal_server_error_problem(
+            &format!("{origin}{}", self.type_prefix),
+            title,
+            detail,
+        )
+    }
+}
+
+/// The default for [`Config::max_detail_length`].
+const DEFAULT_MAX_DETAIL_LENGTH: usize = 1024;
+
+/// The default capacity of the channel of a [`JsonChannel`](crate::JsonChannel).
+#[cfg(feature = "channel")]
+const DEFAULT_CHANNEL_CAPACITY: usize = 16;
+
+/// The default for [`Config::max_multipart_parts`].
+#[cfg(feature = "multipart")]
+const DEFAULT_MAX_MULTIPART_PARTS: usize = 100;
+
+/// The order of the validation errors in problem responses,
+/// as specified via [`Config::error_order`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ErrorOrder {
+    /// The order in which the problems were found, i.e. their position in the document.
+    #[default]
+    DocumentOrder,
+    /// Sorted by location (a JSON pointer, a header or a field name),
+    /// compared as plain strings: `/items/10` comes before `/items/2`.
+    PointerLexical,
+    /// The most severe problems first, then sorted by location like [`ErrorOrder::PointerLexical`].
+    ///
+    /// From the most to the least severe: problems with the structure of the payload
+    /// (`missing_field`, `unknown_field`, `duplicate_field`, `invalid_type`, `invalid_length`),
+    /// then problems with the values themselves (`invalid_value`, `out_of_range`,
+    /// `unknown_variant`, `invalid_format`), then everything else.
+    SeverityThenPointer,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        // Share the default settings, rather than allocating them anew
+        // for every request that doesn't carry a `Config`.
+        static DEFAULT: OnceLock<Config> = OnceLock::new();
+        DEFAULT
+            .get_or_init(|| Config {
+                settings: Arc::default(),
+                #[cfg(feature = "i18n")]
+                accept_language: None,
+                request_target: None,
+                #[cfg(feature = "cbor")]
+                prefers_cbor: false,
+            })
+            .clone()
+    }
+}
+
+impl Config {
+    /// Create a new configuration, with the default settings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the maximum number of members a JSON object in the request body can have.
+    ///
+    /// The limit applies to every object in the document, no matter how deeply nested.
+    /// It is enforced before the body is deserialized into the target type, thus guarding
+    /// against payloads with a huge number of distinct keys.
+    ///
+    /// There is no limit by default.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max` is zero.
+    pub fn max_object_members(mut self, max: usize) -> Self {
+        self.settings_mut().max_object_members =
+            Some(NonZeroUsize::new(max).expect("The maximum number of members must be non-zero"));
+        self
+    }
+
+    /// Set the maximum length, in characters, of the strings in a JSON request body.
+    ///
+    /// The limit applies to every string value in the document, no matter how deeply nested
+    /// (object keys are not affected).
+    /// It is enforced before the body is deserialized into the target type: an over-long
+    /// string is reported with a `string_too_long` error pointing at it, without ever
+    /// being allocated as part of the target type.
+    ///
+    /// There is no limit by default.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max` is zero.
+    pub fn max_string_length(mut self, max: usize) -> Self {
+        self.settings_mut().max_string_length =
+            Some(NonZeroUsize::new(max).expect("The maximum string length must be non-zero"));
+        self
+    }
+
+    /// Set the maximum nesting depth of the objects and arrays in a JSON request body.
+    ///
+    /// A top-level object (or array) is at depth 1, its nested objects at depth 2, and so on.
+    /// The limit is enforced before the body is deserialized into the target type:
+    /// the first container beyond the limit is reported with a `too_deep` error pointing at it.
+    ///
+    /// There is no limit by default, besides the recursion limit of `serde_json` (128 levels).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max` is zero.
+    pub fn max_depth(mut self, max: usize) -> Self {
+        self.settings_mut().max_depth =
+            Some(NonZeroUsize::new(max).expect("The maximum depth must be non-zero"));
+        self
+    }
+
+    /// Trust the `name` header to advertise the nesting depth of JSON request bodies,
+    /// e.g. an `X-Json-Depth: 40` header set by a gateway that already parsed them.
+    ///
+    /// Requests advertising a depth beyond [`Config::max_depth`] are rejected right away,
+    /// with a `too_deep` problem: the body isn't even read.
+    /// The advertised depth is only used to short-circuit: bodies advertised within the
+    /// limit (or without a valid header) are still checked once buffered.
+    ///
+    /// ⚠️ Clients can set this header too: only enable it in deployments where it's
+    /// set (or stripped) by a trusted party.
+    ///
+    /// Disabled by default.
+    pub fn trust_depth_header(mut self, name: http::HeaderName) -> Self {
+        self.settings_mut().depth_header = Some(name);
+        self
+    }
+
+    /// Set the maximum size, in bytes, of the request bodies buffered by the extractors.
+    ///
+    /// Requests whose `Content-Length` exceeds the limit are rejected with a `413 Content Too Large`
+    /// problem before their body is read. In particular, clients that sent an
+    /// `Expect: 100-continue` header are turned down without being asked for the body.
+    /// Bodies without a `Content-Length` are rejected as soon as they grow past the limit.
+    ///
+    /// The limit applies on top of axum's `DefaultBodyLimit`: the lower of the two wins.
+    ///
+    /// There is no limit by default, other than axum's.
+    pub fn max_body_size(mut self, max: usize) -> Self {
+        self.settings_mut().max_body_size = Some(max);
+        self
+    }
+
+    /// Set the maximum number of request bodies buffered and deserialized at once,
+    /// across all the requests sharing this configuration, to shed excess load.
+    ///
+    /// Extractions beyond the limit don't wait for a slot: they're rejected right away
+    /// with a `503 Service Unavailable` problem, of type `overloaded`, asking clients
+    /// to come back after `retry_after` via the `Retry-After` header
+    /// (rounded up to the next second).
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use eserde_axum::Config;
+    ///
+    /// let config = Config::new().max_concurrent_extractions(64, Duration::from_secs(1));
+    /// # let _ = config;
+    /// ```
+    ///
+    /// [`JsonStream`](crate::JsonStream) and [`JsonChannel`](crate::JsonChannel) count
+    /// as in flight until the stream is dropped, or until the channel's parsing task ends.
+    ///
+    /// There is no limit by default.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max` is zero.
+    pub fn max_concurrent_extractions(
+        mut self,
+        max: usize,
+        retry_after: std::time::Duration,
+    ) -> Self {
+        let max =
+            NonZeroUsize::new(max).expect("The maximum number of extractions must be non-zero");
+        self.settings_mut().extraction_limiter = Some(Arc::new(
+            crate::body::ExtractionLimiter::new(max, retry_after),
+        ));
+        self
+    }
+
+    /// Reject JSON request bodies that `serde_json` would tolerate,
+    /// but which are wasteful to process:
+    ///
+    /// - bodies starting with a UTF-8 byte order mark (BOM);
+    /// - bodies with more than 1 KiB of whitespace before or after the JSON document;
+    /// - content types with repeated or unknown parameters (only `charset` and `profile`
+    ///   are accepted), or with a charset other than UTF-8. They are rejected with a
+    ///   `415 Unsupported Media Type` problem.
+    ///
+    /// Disabled by default.
+    pub fn strict_json(mut self, enabled: bool) -> Self {
+        self.settings_mut().strict_json = enabled;
+        self
+    }
+
+    /// Stop deserializing JSON request bodies at the first error, rather than
+    /// accumulating all of them.
+    ///
+    /// By default, a request body that fails to deserialize is walked a second
+    /// time to report every problem at once. That's wasted work on hot endpoints,
+    /// where a single error is enough: in fail-fast mode, the `invalid_request`
+    /// problem lists only the first error that was encountered.
+    ///
+    /// Disabled by default.
+    pub fn fail_fast(mut self, enabled: bool) -> Self {
+        self.settings_mut().fail_fast = enabled;
+        self
+    }
+
+    /// Match the variants of enums in JSON request bodies regardless of their ASCII case,
+    /// e.g. to accept `"Active"` for a variant named `active`.
+    ///
+    /// Exact matches win, hence variants differing only by their case are still told apart.
+    /// Values matching no variant are rejected with an `unknown_variant` error,
+    /// listing the expected variants.
+    /// Only externally tagged enums (the default representation) are affected:
+    /// the tags of internally tagged, adjacently tagged and untagged enums are
+    /// matched exactly.
+    ///
+    /// Disabled by default.
+    pub fn case_insensitive_variants(mut self, enabled: bool) -> Self {
+        self.settings_mut().case_insensitive_variants = enabled;
+        self
+    }
+
+    /// Only accept JSON request bodies whose top-level value has one of the given types,
+    /// e.g. to reject arrays and scalars on endpoints expecting an object.
+    ///
+    /// The check happens before the body is deserialized into the target type,
+    /// and mismatches get a dedicated `invalid_root_type` problem.
+    ///
+    /// ```rust
+    /// use eserde_axum::{json::JsonType, Config};
+    ///
+    /// let config = Config::new().json_root_types([JsonType::Object]);
+    /// # let _ = config;
+    /// ```
+    ///
+    /// Every type is accepted by default.
+    #[cfg(feature = "json")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+    pub fn json_root_types(
+        mut self,
+        types: impl IntoIterator<Item = crate::json::JsonType>,
+    ) -> Self {
+        self.settings_mut().json_root_types = Some(types.into_iter().collect());
+        self
+    }
+
+    /// Parse the booleans of query strings flexibly, since clients don't agree
+    /// on how to spell flags.
+    ///
+    /// Besides `true` and `false`, `1`/`0`, `yes`/`no` and `on`/`off` are accepted
+    /// (ignoring case), and presence-only flags are `true`: `?active` sets an
+    /// `active: bool` field. Any other value is reported as an invalid type,
+    /// pointing at the offending parameter.
+    ///
+    /// It applies to `Query` and to the query section of `Combined`.
+    ///
+    /// Disabled by default.
+    #[cfg(feature = "query")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "query")))]
+    pub fn flexible_query_booleans(mut self, enabled: bool) -> Self {
+        self.settings_mut().flexible_query_booleans = enabled;
+        self
+    }
+
+    /// Nest the form fields using the bracket syntax (e.g. `user[name]=Alice&user[age]=30`)
+    /// under their parent key, to deserialize `Form` bodies into nested structs.
+    /// Errors for nested fields are reported with the same syntax—e.g. `user[age]`.
+    ///
+    /// Brackets are taken literally when disabled: `user[name]` is a key of its own.
+    ///
+    /// Disabled by default.
+    #[cfg(feature = "form")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "form")))]
+    pub fn nested_form_fields(mut self, enabled: bool) -> Self {
+        self.settings_mut().nested_form_fields = enabled;
+        self
+    }
+
+    /// Emit minimal problem responses, as described in
+    /// [RFC 9457](https://www.rfc-editor.org/rfc/rfc9457.html#name-aboutblank).
+    ///
+    /// When enabled, the `type` of the problems returned by the extractors
+    /// is set to `about:blank` and their `title` to the reason phrase of
+    /// the HTTP status code (e.g. `Bad Request`).
+    /// All other members are left untouched.
+    ///
+    /// Disabled by default: each problem gets a dedicated `type`, e.g. `invalid_request`.
+    pub fn about_blank(mut self, enabled: bool) -> Self {
+        self.settings_mut().about_blank = enabled;
+        self
+    }
+
+    /// Serialize the `status` member of problem responses as a string
+    /// (e.g. `"400"`) rather than as a number.
+    ///
+    /// [RFC 9457](https://www.rfc-editor.org/rfc/rfc9457.html#name-status) mandates a number:
+    /// only enable this for clients that can't cope with it.
+    /// The status code of the responses themselves is unaffected.
+    ///
+    /// Disabled by default.
+    pub fn status_as_string(mut self, enabled: bool) -> Self {
+        self.settings_mut().status_as_string = enabled;
+        self
+    }
+
+    /// Omit the `errors` member from `invalid_request` problems, leaving a minimal
+    /// object with just the standard [RFC 9457](https://www.rfc-editor.org/rfc/rfc9457.html)
+    /// members (`type`, `status`, `title` and `detail`).
+    ///
+    /// Use it for clients that reject unknown members. The validation errors are
+    /// still available to your own code, e.g. via `JsonError::errors`.
+    ///
+    /// Disabled by default.
+    pub fn omit_errors(mut self, enabled: bool) -> Self {
+        self.settings_mut().omit_errors = enabled;
+        self
+    }
+
+    /// Add a `missing` member to `invalid_request` problems, listing the JSON pointers
+    /// of the required fields that are missing from the request body:
+    ///
+    /// ```json
+    /// { "missing": ["/name", "/contact/email"] }
+    /// ```
+    ///
+    /// It's a shortcut for clients that highlight the missing fields of a form:
+    /// the problems are still reported in full via the `errors` member, as
+    /// `missing_field` errors pointing at the object that lacks the field.
+    /// The member is included even if [`Config::omit_errors`] is enabled, and
+    /// left out if there are no missing fields.
+    ///
+    /// Disabled by default.
+    pub fn list_missing_fields(mut self, enabled: bool) -> Self {
+        self.settings_mut().list_missing_fields = enabled;
+        self
+    }
+
+    /// Merge the `invalid_type` errors reported for the same value, at the same location,
+    /// into a single error listing every type that was attempted:
+    ///
+    /// ```text
+    /// invalid type: string "x", expected one of: u64, a boolean
+    /// ```
+    ///
+    /// Types that accept several alternatives for the same input, like untagged enums,
+    /// may otherwise report one error per alternative, flooding clients with
+    /// near-identical problems.
+    /// `#[derive(eserde::Deserialize)]` doesn't support `#[serde(untagged)]`, so this
+    /// only affects hand-written [`EDeserialize`](eserde::EDeserialize) implementations
+    /// that report a failure for each alternative.
+    ///
+    /// Disabled by default.
+    pub fn coalesce_invalid_types(mut self, enabled: bool) -> Self {
+        self.settings_mut().coalesce_invalid_types = enabled;
+        self
+    }
+
+    /// Serialize the `errors` member of problem responses as an object, rather than as an array.
+    ///
+    /// Each key is the location of the problems—a JSON pointer for request bodies
+    /// (e.g. `/contact/email`), the name of the field or header otherwise—mapped to
+    /// the `detail` of each problem found there:
+    ///
+    /// ```json
+    /// { "errors": { "/contact/email": ["invalid type: integer `5`, expected a string"] } }
+    /// ```
+    ///
+    /// The other members of validation errors (e.g. `code`) are left out.
+    ///
+    /// Disabled by default.
+    pub fn errors_by_pointer(mut self, enabled: bool) -> Self {
+        self.settings_mut().errors_by_pointer = enabled;
+        self
+    }
+
+    /// Tell malformed JSON request bodies apart from well-formed ones that don't match
+    /// the target type.
+    ///
+    /// Bodies that aren't syntactically valid JSON (e.g. truncated ones) are rejected with a
+    /// `400 Bad Request` problem of type `malformed_request`, while the others are rejected
+    /// with a `422 Unprocessable Content` problem of type `unprocessable_entity`.
+    /// Both list the same validation errors as the `invalid_request` problem they replace.
+    ///
+    /// Disabled by default: every deserialization failure is a `400 Bad Request`
+    /// problem of type `invalid_request`.
+    pub fn unprocessable_entity(mut self, enabled: bool) -> Self {
+        self.settings_mut().unprocessable_entity = enabled;
+        self
+    }
+
+    /// Choose the order of the validation errors in problem responses.
+    ///
+    /// Errors that compare equal keep the order in which they were found.
+    /// The order is applied to [`Config::errors_by_pointer`] groups as well.
+    ///
+    /// Defaults to [`ErrorOrder::DocumentOrder`].
+    pub fn error_order(mut self, order: ErrorOrder) -> Self {
+        self.settings_mut().error_order = order;
+        self
+    }
+
+    /// Link the documentation page for an error code.
+    ///
+    /// Every validation error with the given `code` gets a `documentation` member,
+    /// pointing at `url`. Occurrences of `{code}` in `url` are replaced with the code
+    /// itself, so the same template can be registered for multiple codes.
+    /// Errors whose code has no registered documentation don't get the member.
+    ///
+    /// ```rust
+    /// use eserde_axum::Config;
+    ///
+    /// let config = Config::new()
+    ///     .documentation("invalid_type", "https://docs.example.com/errors/{code}")
+    ///     .documentation("missing_field", "https://docs.example.com/errors/{code}");
+    /// # let _ = config;
+    /// ```
+    pub fn documentation(
+        mut self,
+        code: impl Into<Cow<'static, str>>,
+        url: impl Into<String>,
+    ) -> Self {
+        self.settings_mut()
+            .documentation
+            .insert(code.into(), url.into());
+        self
+    }
+
+    /// Mirror the `type` member of problem responses in an `X-Problem-Type` header,
+    /// for intermediaries and dashboards that only look at headers.
+    ///
+    /// The header carries the final `type`, i.e. after [`Config::type_prefix`] and
+    /// [`Config::about_blank`] are applied.
+    ///
+    /// Disabled by default.
+    pub fn problem_type_header(mut self, enabled: bool) -> Self {
+        self.settings_mut().problem_type_header = enabled;
+        self
+    }
+
+    /// Attach the method and the path of the request, as `http.method` and `http.target`,
+    /// to the events logged when a request is rejected because it failed to deserialize.
+    ///
+    /// Handy to tell which endpoint was hit from the logs alone, without setting up
+    /// a span for every request.
+    ///
+    /// Disabled by default.
+    pub fn log_request_target(mut self, enabled: bool) -> Self {
+        self.settings_mut().log_request_target = enabled;
+        self
+    }
+
+    /// Include the raw request body in the problems returned when it fails
+    /// to deserialize, as a `_debug_raw` member.
+    ///
+    /// The body is truncated to `max_len` bytes, with an ellipsis marking the cut.
+    /// Handy during development, to see exactly what the server received.
+    ///
+    /// **This setting only has effect in debug builds**: it's ignored when
+    /// `debug_assertions` are disabled, so that request bodies can't be echoed
+    /// back to clients in production, even if it has been left on by mistake.
+    ///
+    /// Disabled by default.
+    pub fn debug_raw_body(mut self, max_len: usize) -> Self {
+        self.settings_mut().debug_raw_body = Some(max_len);
+        self
+    }
+
+    /// Set the maximum length, in characters, of the `detail` of problems
+    /// and of their validation errors.
+    ///
+    /// Longer details (e.g. `unknown_variant` errors listing a huge number of variants)
+    /// are cut short and followed by an ellipsis, with a `truncated: true` member
+    /// flagging the problem or the validation error.
+    ///
+    /// Defaults to 1024 characters.
+    pub fn max_detail_length(mut self, max_len: usize) -> Self {
+        self.settings_mut().max_detail_length = Some(max_len);
+        self
+    }
+
+    /// Set the title and the detail of the `500 Internal Server Error` problem
+    /// sent when a problem response can't be serialized, e.g. to translate them
+    /// or to match the tone of your API.
+    ///
+    /// The fallback is still a well-formed `application/problem+json` document,
+    /// of type `internal_server_error`: only its title and detail change.
+    /// It is serialized once, when calling this method.
+    ///
+    /// Defaults to a generic "Internal Server Error" problem.
+    pub fn internal_server_error(
+        mut self,
+        title: impl Into<Cow<'static, str>>,
+        detail: impl Into<Cow<'static, str>>,
+    ) -> Self {
+        let settings = self.settings_mut();
+        settings.internal_server_error_text = Some((title.into(), detail.into()));
+        settings.render_internal_server_error();
+        self
+    }
+
+    /// Prepend `prefix` to the `type` of every problem produced by this crate,
+    /// e.g. `myapi:` to turn `invalid_request` into `myapi:invalid_request`.
+    ///
+    /// The prefix applies to the fallback `500` problem too, but not to
+    /// the `about:blank` type enabled via [`Config::about_blank`].
+    /// Error codes (e.g. `invalid_type`) and the types matched by
+    /// [`Catalog::header`](crate::i18n::Catalog::header) are left untouched.
+    ///
+    /// ```rust
+    /// use eserde_axum::Config;
+    ///
+    /// let config = Config::new().type_prefix("myapi:");
+    /// # let _ = config;
+    /// ```
+    ///
+    /// Empty by default.
+    pub fn type_prefix(mut self, prefix: impl Into<Cow<'static, str>>) -> Self {
+        let settings = self.settings_mut();
+        settings.type_prefix = prefix.into();
+        settings.render_internal_server_error();
+        self
+    }
+
+    /// Resolve the problem types that are relative references, i.e. starting with `/`,
+    /// against the scheme and host the request was sent to.
+    ///
+    /// Combined with a [`Config::type_prefix`] such as `/errors/`, it turns
+    /// `invalid_request` into e.g. `https://api.example.com/errors/invalid_request`.
+    ///
+    /// The scheme is taken from the `X-Forwarded-Proto` header, falling back to the
+    /// request URI and then to `http`. The host is taken from the `X-Forwarded-Host`
+    /// header, falling back to `Host` and then to the request URI.
+    /// Types are left relative if no (valid) host can be found.
+    ///
+    /// ⚠️ Clients can set the `X-Forwarded-*` headers too: only enable it in deployments
+    /// where they're set (or stripped) by a trusted proxy.
+    ///
+    /// ```rust
+    /// use eserde_axum::Config;
+    ///
+    /// let config = Config::new()
+    ///     .type_prefix("/errors/")
+    ///     .resolve_relative_types(true);
+    /// # let _ = config;
+    /// ```
+    ///
+    /// Disabled by default.
+    pub fn resolve_relative_types(mut self, enabled: bool) -> Self {
+        self.settings_mut().resolve_relative_types = enabled;
+        self
+    }
+
+    /// Set the API versions accepted by the [`ApiVersion`](crate::ApiVersion) extractor.
+    ///
+    /// No version is supported by default: every request is rejected until
+    /// the supported versions are specified.
+    ///
+    /// ```rust
+    /// use eserde_axum::Config;
+    ///
+    /// let config = Config::new().supported_api_versions(["v1", "v2"]);
+    /// # let _ = config;
+    /// ```
+    pub fn supported_api_versions<V>(mut self, versions: impl IntoIterator<Item = V>) -> Self
+    where
+        V: Into<Cow<'static, str>>,
+    {
+        self.settings_mut().supported_api_versions = versions.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Set the media types the [`AcceptNegotiation`](crate::AcceptNegotiation) extractor
+    /// can choose from, in order of preference.
+    ///
+    /// No media type is producible by default: every request is rejected until
+    /// the producible types are specified.
+    ///
+    /// ```rust
+    /// use eserde_axum::Config;
+    ///
+    /// let config = Config::new().producible_media_types(["application/json", "text/csv"]);
+    /// # let _ = config;
+    /// ```
+    pub fn producible_media_types<M>(mut self, media_types: impl IntoIterator<Item = M>) -> Self
+    where
+        M: Into<Cow<'static, str>>,
+    {
+        self.settings_mut().producible_media_types =
+            media_types.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Set the position of the path segment holding the API version,
+    /// starting from zero.
+    ///
+    /// Defaults to the first segment, e.g. `v1` in `/v1/users`.
+    pub fn api_version_segment(mut self, index: usize) -> Self {
+        self.settings_mut().api_version_segment = index;
+        self
+    }
+
+    /// Reject requests whose body isn't fully received within `timeout`,
+    /// guarding against clients trickling it to hold connections open.
+    ///
+    /// The clock starts when the extractor starts reading the body.
+    /// Late requests get a `408 Request Timeout` problem, of type `request_timeout`.
+    /// Install a different configuration on the routes that expect large uploads,
+    /// to give them more time.
+    ///
+    /// There is no timeout by default.
+    #[cfg(feature = "timeout")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "timeout")))]
+    pub fn body_read_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.settings_mut().body_read_timeout = Some(timeout);
+        self
+    }
+
+    /// The number of elements a [`JsonChannel`](crate::JsonChannel) buffers for the handler,
+    /// before it stops reading the request body.
+    ///
+    /// A capacity of `0` is treated as `1`.
+    ///
+    /// 16 by default.
+    #[cfg(feature = "channel")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "channel")))]
+    pub fn channel_capacity(mut self, capacity: usize) -> Self {
+        self.settings_mut().channel_capacity = Some(capacity.max(1));
+        self
+    }
+
+    /// Set the maximum number of parts a [`MultipartJsonBatch`](crate::MultipartJsonBatch)
+    /// request body can have.
+    ///
+    /// Bodies with more parts are rejected, with a `too_many_parts` error, before any
+    /// of them is deserialized.
+    ///
+    /// 100 by default.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max` is zero.
+    #[cfg(feature = "multipart")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "multipart")))]
+    pub fn max_multipart_parts(mut self, max: usize) -> Self {
+        self.settings_mut().max_multipart_parts =
+            Some(NonZeroUsize::new(max).expect("The maximum number of parts must be non-zero"));
+        self
+    }
+
+    /// Translate the `detail` of validation errors with the given catalog,
+    /// according to the `Accept-Language` header of the request,
+    /// and attach the headers it lists to problem responses of the matching type.
+    /// Problem responses carry a `Vary: Accept-Language` header, for the sake of caches,
+    /// if the catalog has at least one locale.
+    ///
+    /// See [`Catalog`](crate::i18n::Catalog) for more details.
+    #[cfg(feature = "i18n")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "i18n")))]
+    pub fn catalog(mut self, catalog: crate::i18n::Catalog) -> Self {
+        self.settings_mut().catalog = Some(Arc::new(catalog));
+        self
+    }
+
+    /// The maximum length of problem details, as specified via [`Config::max_detail_length`].
+    pub(crate) fn detail_limit(&self) -> usize {
+        self.settings
+            .max_detail_length
+            .unwrap_or(DEFAULT_MAX_DETAIL_LENGTH)
+    }
+
+    /// The capacity of the channel of a [`JsonChannel`](crate::JsonChannel).
+    #[cfg(feature = "channel")]
+    pub(crate) fn channel_limit(&self) -> usize {
+        self.settings
+            .channel_capacity
+            .unwrap_or(DEFAULT_CHANNEL_CAPACITY)
+    }
+
+    /// The maximum number of parts of a [`MultipartJsonBatch`](crate::MultipartJsonBatch)
+    /// body, as specified via [`Config::max_multipart_parts`].
+    #[cfg(feature = "multipart")]
+    pub(crate) fn multipart_part_limit(&self) -> usize {
+        self.settings
+            .max_multipart_parts
+            .map_or(DEFAULT_MAX_MULTIPART_PARTS, NonZeroUsize::get)
+    }
+
+    /// The (truncated) raw body to include in problem responses,
+    /// if [`Config::debug_raw_body`] is enabled and this is a debug build.
+    #[cfg(any(feature = "json", feature = "form", feature = "query"))]
+    pub(crate) fn debug_raw(&self, body: &[u8]) -> Option<String> {
+        if !cfg!(debug_assertions) {
+            return None;
+        }
+        let max_len = self.settings.debug_raw_body?;
+        let mut raw = String::from_utf8_lossy(&body[..body.len().min(max_len)]).into_owned();
+        if body.len() > max_len {
+            raw.push('…');
+        }
+        Some(raw)
+    }
+
+    pub(crate) fn settings_mut(&mut self) -> &mut Settings {
+        Arc::make_mut(&mut self.settings)
+    }
+
+    /// The documentation URL for the given error code, if any.
+    pub(crate) fn documentation_for(&self, code: &str) -> Option<String> {
+        self.settings
+            .documentation
+            .get(code)
+            .map(|template| template.replace("{code}", code))
+    }
+
+    /// Returns `true` if at least one structural limit on JSON documents
+    /// has been configured.
+    #[cfg(feature = "json")]
+    pub(crate) fn has_json_limits(&self) -> bool {
+        self.settings.max_object_members.is_some()
+            || self.settings.max_string_length.is_some()
+            || self.settings.max_depth.is_some()
+    }
+
+    /// The bundles to translate error details with, if a catalog is installed
+    /// and the request has an `Accept-Language` header.
+    #[cfg(feature = "i18n")]
+    pub(crate) fn localizer(&self) -> Option<crate::i18n::Localizer<'_>> {
+        let catalog = self.settings.catalog.as_ref()?;
+        Some(catalog.localizer(self.accept_language.as_deref()?))
+    }
+
+    /// The fallback `500 Internal Server Error` problem, honoring
+    /// [`Config::internal_server_error`] and [`Config::type_prefix`].
+    #[cfg(any(feature = "catch-unwind", feature = "anyhow"))]
+    pub(crate) fn internal_server_error_response(&self) -> axum_core::response::Response {
+        self.fallback().response()
+    }
+
+    /// The fallback `500` problem for the current request, to be rendered on demand.
+    pub(crate) fn fallback(&self) -> Fallback {
+        Fallback {
+            settings: self.settings.clone(),
+            request_target: self.request_target.clone(),
+        }
+    }
+
+    /// The method and path of the current request, if captured.
+    #[cfg(any(feature = "json", feature = "form", feature = "query"))]
+    pub(crate) fn request_target(&self) -> (Option<&str>, Option<&str>) {
+        match &self.request_target {
+            Some(target) if self.settings.log_request_target => {
+                (Some(target.method.as_str()), Some(&target.path))
+            }
+            _ => (None, None),
+        }
+    }
+
+    /// The origin the relative problem types are resolved against, if any.
+    pub(crate) fn origin(&self) -> Option<&str> {
+        self.request_target.as_ref()?.origin.as_deref()
+    }
+
+    /// Retrieve the configuration from the request extensions,
+    /// falling back to the default one.
+    ///
+    /// The request headers are used to localize the rejections, if enabled,
+    /// and to pick the format of problem responses.
+    pub(crate) fn from_request(request: &impl RequestHead) -> Self {
+        let mut config = request
+            .extensions()
+            .get::<Self>()
+            .cloned()
+            .unwrap_or_default();
+        #[cfg(feature = "i18n")]
+        if config.settings.catalog.is_some() {
+            config.accept_language = request
+                .headers()
+                .get(http::header::ACCEPT_LANGUAGE)
+                .and_then(|value| value.to_str().ok())
+                .map(Box::from);
+        }
+        #[cfg(feature = "cbor")]
+        {
+            config.prefers_cbor = crate::details::prefers_cbor(request.headers());
+        }
+        if config.settings.log_request_target || config.settings.resolve_relative_types {
+            config.request_target = Some(Arc::new(RequestTarget {
+                origin: config
+                    .settings
+                    .resolve_relative_types
+                    .then(|| origin(request))
+                    .flatten()
+                    .map(Box::from),
+                #[cfg(any(feature = "json", feature = "form", feature = "query"))]
+                method: request.method().clone(),
+                #[cfg(any(feature = "json", feature = "form", feature = "query"))]
+                path: request.uri().path().into(),
+            }));
+        }
+        config
+    }
+}
+
+/// The scheme and host the request was sent to, e.g. `https://example.com`,
+/// honoring the `X-Forwarded-Proto` and `X-Forwarded-Host` headers.
+fn origin(request: &impl RequestHead) -> Option<String> {
+    /// The first value of a (possibly comma-separated) forwarding header.
+    fn forwarded<'a>(request: &'a impl RequestHead, name: &str) -> Option<&'a str> {
+        let value = request.headers().get(name)?.to_str().ok()?;
+        value.split(',').next().map(str::trim)
+    }
+
+    let scheme = forwarded(request, "x-forwarded-proto")
+        .or_else(|| request.uri().scheme_str())
+        .unwrap_or("http");
+    let scheme = if scheme.eq_ignore_ascii_case("https") {
+        "https"
+    } else if scheme.eq_ignore_ascii_case("http") {
+        "http"
+    } else {
+        return None;
+    };
+    let host = forwarded(request, "x-forwarded-host")
+        .or_else(|| {
+            request
+                .headers()
+                .get(http::header::HOST)
+                .and_then(|value| value.to_str().ok())
+        })
+        .or_else(|| {
+            request
+                .uri()
+                .authority()
+                .map(|authority| authority.as_str())
+        })?;
+    // Guard against hosts that would alter the path of the resolved type.
+    let valid = !host.is_empty()
+        && host
+            .bytes()
+            .all(|byte| byte.is_ascii_alphanumeric() || b".-:[]".contains(&byte));
+    valid.then(|| format!("{scheme}://{host}"))
+}
+
+/// The parts of a request [`Config::from_request`] looks at,
+/// for both full requests and their [`Parts`](http::request::Parts).
+pub(crate) trait RequestHead {
+    #[cfg(any(feature = "json", feature = "form", feature = "query"))]
+    fn method(&self) -> &http::Method;
+    fn uri(&self) -> &http::Uri;
+    fn extensions(&self) -> &http::Extensions;
+    fn headers(&self) -> &http::HeaderMap;
+}
+
+impl<B> RequestHead for http::Request<B> {
+    #[cfg(any(feature = "json", feature = "form", feature = "query"))]
+    fn method(&self) -> &http::Method {
+        http::Request::method(self)
+    }
+
+    fn uri(&self) -> &http::Uri {
+        http::Request::uri(self)
+    }
+
+    fn extensions(&self) -> &http::Extensions {
+        http::Request::extensions(self)
+    }
+
+    fn headers(&self) -> &http::HeaderMap {
+        http::Request::headers(self)
+    }
+}
+
+impl RequestHead for http::request::Parts {
+    #[cfg(any(feature = "json", feature = "form", feature = "query"))]
+    fn method(&self) -> &http::Method {
+        &self.method
+    }
+
+    fn uri(&self) -> &http::Uri {
+        &self.uri
+    }
+
+    fn extensions(&self) -> &http::Extensions {
+        &self.extensions
+    }
+
+    fn headers(&self) -> &http::HeaderMap {
+        &self.headers
+    }
+}
+
+/// A [`Layer`](tower_layer::Layer) that installs a [`Config`] in the extensions of every request,
+/// so that all the extractors of the wrapped routes pick it up.
+///
+/// It's equivalent to `axum::Extension(config)`, spelled out as the one place where the
+/// crate is configured for an application. A `Config` installed closer to a route
+/// (e.g. via [`Router::route_layer`]) still takes precedence over this one.
+///
+/// ```rust,no_run
+/// use axum::{routing::post, Router};
+/// use eserde_axum::{Config, ConfigLayer, Json};
+///
+/// #[derive(eserde::Deserialize)]
+/// struct CreateUser {
+///     email: String,
+/// }
+///
+/// async fn create_user(Json(payload): Json<CreateUser>) {}
+///
+/// let app = Router::new()
+///     .route("/users", post(create_user))
+///     .layer(ConfigLayer::new(Config::new().max_body_size(64 * 1024)));
+/// # let _: Router = app;
+/// ```
+///
+/// Apply it outside of [`MethodNotAllowedLayer`](crate::MethodNotAllowedLayer), for that
+/// layer's problems to honor the configuration too.
+///
+/// [`Router::route_layer`]: https://docs.rs/axum/latest/axum/struct.Router.html#method.route_layer
+#[derive(Debug, Clone, Default)]
+#[cfg(feature = "layers")]
+#[cfg_attr(docsrs, doc(cfg(feature = "layers")))]
+pub struct ConfigLayer {
+    config: Config,
+}
+
+#[cfg(feature = "layers")]
+impl ConfigLayer {
+    /// Create a layer installing `config`.
+    pub fn new(config: Config) -> Self {
+        Self { config }
+    }
+}
+
+#[cfg(feature = "layers")]
+impl<S> tower_layer::Layer<S> for ConfigLayer {
+    type Service = ConfigService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ConfigService {
+            inner,
+            config: self.config.clone(),
+        }
+    }
+}
+
+/// The [`Service`](tower_service::Service) returned by [`ConfigLayer`].
+#[derive(Debug, Clone)]
+#[cfg(feature = "layers")]
+#[cfg_attr(docsrs, doc(cfg(feature = "layers")))]
+pub struct ConfigService<S> {
+    inner: S,
+    config: Config,
+}
+
+#[cfg(feature = "layers")]
+impl<S, B> tower_service::Service<http::Request<B>> for ConfigService<S>
+where
+    S: tower_service::Service<http::Request<B>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(
+        &mut self,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: http::Request<B>) -> Self::Future {
+        request.extensions_mut().insert(self.config.clone());
+        self.inner.call(request)
+    }
+}
+
+#[cfg(all(test, feature = "json", feature = "layers"))]
+mod tests {
+    use axum::{routing::post, Router};
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::{test_utils, Json};
+
+    #[tokio::test]
+    async fn test_config_layer() {
+        let app = Router::new()
+            .route("/", post(|Json(_): Json<serde_json::Value>| async {}))
+            .layer(ConfigLayer::new(Config::new().max_body_size(8)));
+
+        let request = test_utils::json_request(r#"{"name": "Alice"}"#).body();
+        let response = app.clone().oneshot(request).await.unwrap();
+        let (status, body) = test_utils::problem(response).await;
+        assert_eq!(status, 413);
+        assert_eq!(body["type"], "content_too_large");
+
+        let request = test_utils::json_request("{}").body();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), 200);
+    }
+
+    #[tokio::test]
+    async fn test_resolve_relative_types() {
+        let app = Router::new()
+            .route("/", post(|Json(_): Json<Vec<u8>>| async {}))
+            .layer(ConfigLayer::new(
+                Config::new()
+                    .type_prefix("/errors/")
+                    .resolve_relative_types(true),
+            ));
+
+        let request = test_utils::json_request("{}")
+            .header("host", "10.0.0.7:8080")
+            .header("x-forwarded-proto", "https")
+            .header("x-forwarded-host", "api.example.com, proxy.internal")
+            .body();
+        let (_, body) = test_utils::problem(app.clone().oneshot(request).await.unwrap()).await;
+        assert_eq!(
+            body["type"],
+            "https://api.example.com/errors/invalid_request"
+        );
+
+        let request = test_utils::json_request("{}")
+            .header("host", "10.0.0.7:8080")
+            .body();
+        let (_, body) = test_utils::problem(app.clone().oneshot(request).await.unwrap()).await;
+        assert_eq!(body["type"], "http://10.0.0.7:8080/errors/invalid_request");
+
+        // Hosts that would tamper with the path leave the type relative.
+        let request = test_utils::json_request("{}")
+            .header("x-forwarded-host", "evil.com/phishing?")
+            .body();
+        let (_, body) = test_utils::problem(app.oneshot(request).await.unwrap()).await;
+        assert_eq!(body["type"], "/errors/invalid_request");
+    }
+}
@@ -0,0 +1,187 @@
+//! Deserialize [RFC 3339](https://www.rfc-editor.org/rfc/rfc3339) date-times,
+//! e.g. `"2024-05-01T12:30:00Z"`, with actionable errors.
+//!
+//! The functions in this module are meant to be used with
+//! `#[serde(deserialize_with = "...")]`:
+//!
+//! ```rust
+//! #[derive(eserde::Deserialize)]
+//! struct Meeting {
+//!     #[serde(deserialize_with = "eserde_axum::datetime::chrono_rfc3339")]
+//!     starts_at: chrono::DateTime<chrono::Utc>,
+//!     #[serde(deserialize_with = "eserde_axum::datetime::time_rfc3339")]
+//!     ends_at: time::OffsetDateTime,
+//! }
+//! ```
+//!
+//! When the input isn't a valid RFC 3339 date-time, the reported error explains the
+//! expected format—pointing out whether the timezone offset is the only thing
+//! missing—and is classified with the `invalid_format` code.
+use std::fmt;
+
+use serde::de::{Deserializer, Error, Visitor};
+
+use crate::details::INVALID_FORMAT;
+
+/// An example of the expected format, shared by all the error messages.
+const EXAMPLES: &str = "e.g. `2024-05-01T12:30:00Z` or `2024-05-01T12:30:00+02:00`";
+
+/// The error for a string that isn't an RFC 3339 date-time.
+///
+/// `with_offset` tells whether the string is a valid date-time once an offset gets appended,
+/// i.e. whether the offset is the only thing missing.
+fn invalid_date_time<E: Error>(v: &str, with_offset: bool, cause: impl fmt::Display) -> E {
+    if with_offset {
+        E::custom(format_args!(
+            "{INVALID_FORMAT}expected an RFC 3339 date-time with a timezone offset ({EXAMPLES}), \
+            found `{v}`, which has no offset"
+        ))
+    } else {
+        E::custom(format_args!(
+            "{INVALID_FORMAT}expected an RFC 3339 date-time ({EXAMPLES}), found `{v}` ({cause})"
+        ))
+    }
+}
+
+/// Deserialize a [`chrono::DateTime`] from an RFC 3339 string.
+///
+/// The timezone offset is required, e.g. `2024-05-01T12:30:00Z`. The date-time can be
+/// deserialized into `DateTime<FixedOffset>`, to preserve the offset, or into `DateTime<Utc>`.
+#[cfg(feature = "chrono")]
+#[cfg_attr(docsrs, doc(cfg(feature = "chrono")))]
+pub fn chrono_rfc3339<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: From<chrono::DateTime<chrono::FixedOffset>>,
+{
+    struct ChronoVisitor;
+
+    impl Visitor<'_> for ChronoVisitor {
+        type Value = chrono::DateTime<chrono::FixedOffset>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            write!(formatter, "an RFC 3339 date-time ({EXAMPLES})")
+        }
+
+        fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+        where
+            E: Error,
+        {
+            chrono::DateTime::parse_from_rfc3339(v).map_err(|e| {
+                let with_offset = chrono::DateTime::parse_from_rfc3339(&format!("{v}Z")).is_ok();
+                invalid_date_time(v, with_offset, e)
+            })
+        }
+    }
+
+    deserializer.deserialize_str(ChronoVisitor).map(T::from)
+}
+
+/// Deserialize a [`time::OffsetDateTime`] from an RFC 3339 string.
+///
+/// The timezone offset is required, e.g. `2024-05-01T12:30:00Z`.
+#[cfg(feature = "time")]
+#[cfg_attr(docsrs, doc(cfg(feature = "time")))]
+pub fn time_rfc3339<'de, D>(deserializer: D) -> Result<time::OffsetDateTime, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    use time::format_description::well_known::Rfc3339;
+
+    struct TimeVisitor;
+
+    impl Visitor<'_> for TimeVisitor {
+        type Value = time::OffsetDateTime;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            write!(formatter, "an RFC 3339 date-time ({EXAMPLES})")
+        }
+
+        fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+        where
+            E: Error,
+        {
+            time::OffsetDateTime::parse(v, &Rfc3339).map_err(|e| {
+                let with_offset = time::OffsetDateTime::parse(&format!("{v}Z"), &Rfc3339).is_ok();
+                invalid_date_time(v, with_offset, e)
+            })
+        }
+    }
+
+    deserializer.deserialize_str(TimeVisitor)
+}
+
+#[cfg(all(test, feature = "chrono", feature = "time", feature = "json"))]
+mod tests {
+    use crate::Json;
+
+    #[derive(Debug, eserde::Deserialize)]
+    struct Meeting {
+        #[serde(deserialize_with = "super::chrono_rfc3339")]
+        starts_at: chrono::DateTime<chrono::Utc>,
+        #[serde(deserialize_with = "super::time_rfc3339")]
+        ends_at: time::OffsetDateTime,
+    }
+
+    fn errors(body: &[u8]) -> serde_json::Value {
+        let Err(crate::JsonRejection::JsonError(error)) = Json::<Meeting>::from_bytes(body) else {
+            panic!("Expected a deserialization error");
+        };
+        serde_json::to_value(error.validation_errors()).unwrap()["errors"].take()
+    }
+
+    #[test]
+    fn test_valid() {
+        let Json(meeting) = Json::<Meeting>::from_bytes(
+            br#"{"starts_at": "2024-05-01T12:30:00+02:00", "ends_at": "2024-05-01T11:00:00Z"}"#,
+        )
+        .unwrap();
+        assert_eq!(meeting.starts_at.to_rfc3339(), "2024-05-01T10:30:00+00:00");
+        assert_eq!(meeting.ends_at.unix_timestamp(), 1714561200);
+    }
+
+    #[test]
+    fn test_missing_offset() {
+        let errors = errors(
+            br#"{"starts_at": "2024-05-01T12:30:00", "ends_at": "2024-05-01T13:30:00.250"}"#,
+        );
+        assert_eq!(errors.as_array().unwrap().len(), 2);
+        for (error, pointer) in errors
+            .as_array()
+            .unwrap()
+            .iter()
+            .zip(["/starts_at", "/ends_at"])
+        {
+            assert_eq!(error["code"], "invalid_format");
+            assert_eq!(error["pointer"], pointer);
+            let detail = error["detail"].as_str().unwrap();
+            assert!(detail.contains("with a timezone offset"), "{detail}");
+            assert!(detail.contains("which has no offset"), "{detail}");
+        }
+    }
+
+    #[test]
+    fn test_malformed() {
+        let errors = errors(br#"{"starts_at": "next tuesday", "ends_at": "2024-13-01T12:00:00Z"}"#);
+        assert_eq!(errors.as_array().unwrap().len(), 2);
+        for (error, pointer) in errors
+            .as_array()
+            .unwrap()
+            .iter()
+            .zip(["/starts_at", "/ends_at"])
+        {
+            assert_eq!(error["code"], "invalid_format");
+            assert_eq!(error["pointer"], pointer);
+            let detail = error["detail"].as_str().unwrap();
+            assert!(
+                detail.contains("expected an RFC 3339 date-time ("),
+                "{detail}"
+            );
+            assert!(!detail.contains("no offset"), "{detail}");
+        }
+        assert!(errors[0]["detail"]
+            .as_str()
+            .unwrap()
+            .contains("found `next tuesday`"));
+    }
+}
@@ -2,67 +2,876 @@
 //!
 //! See [RFC 9457](https://www.rfc-editor.org/rfc/rfc9457.html) for more details.
 use std::borrow::Cow;
+use std::collections::hash_map::{Entry, HashMap};
 
-use bytes::{BufMut, BytesMut};
-use http::{header::CONTENT_TYPE, HeaderName, HeaderValue, StatusCode};
+use axum_core::extract::rejection::{BytesRejection, FailedToBufferBody};
+use axum_core::response::IntoResponse;
+use bytes::{BufMut, Bytes, BytesMut};
+use eserde::{DeserializationError, DeserializationErrors};
+use http::header::{CONTENT_LOCATION, CONTENT_TYPE, VARY, WWW_AUTHENTICATE};
+use http::{HeaderName, HeaderValue, StatusCode};
 
-#[derive(serde::Serialize)]
+use crate::config::{Config, ErrorOrder, Fallback};
+use crate::sink::ErrorSource;
+
+#[derive(serde::Serialize, Debug)]
 pub(crate) struct ProblemDetails<Extension> {
     #[serde(rename = "type")]
     pub(crate) type_: Cow<'static, str>,
-    pub(crate) status: u16,
+    pub(crate) status: Status,
     pub(crate) title: Cow<'static, str>,
     pub(crate) detail: Cow<'static, str>,
+    /// Set if `detail` was cut short, as specified via [`Config::max_detail_length`].
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub(crate) truncated: bool,
     #[serde(flatten)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub(crate) extensions: Option<Extension>,
+    /// The authentication challenge to send via the `WWW-Authenticate` header.
+    ///
+    /// Only emitted for `401 Unauthorized` problems, as prescribed by
+    /// [RFC 9110](https://www.rfc-editor.org/rfc/rfc9110#name-401-unauthorized).
+    #[serde(skip)]
+    pub(crate) challenge: Option<HeaderValue>,
+    /// The problem to respond with if this one can't be serialized,
+    /// as specified via [`Config::internal_server_error`].
+    #[serde(skip)]
+    pub(crate) fallback: Option<Fallback>,
+    /// The request headers the response was negotiated on, to send via the `Vary` header.
+    #[serde(skip)]
+    pub(crate) vary: Option<HeaderValue>,
+    /// The canonical resource the problem is about, to send via the `Content-Location` header.
+    #[serde(skip)]
+    pub(crate) content_location: Option<HeaderValue>,
+    /// The format to serialize the problem in, as negotiated via the `Accept` header.
+    #[serde(skip)]
+    pub(crate) format: Format,
+    /// The headers to attach to the response, e.g. those registered for the problem type
+    /// via [`Catalog::header`](crate::i18n::Catalog::header).
+    #[serde(skip)]
+    pub(crate) headers: Vec<(HeaderName, HeaderValue)>,
+}
+
+/// The serialization format of a problem response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Format {
+    /// `application/problem+json`, the default.
+    Json,
+    /// `application/problem+cbor`, if the `cbor` feature is enabled
+    /// and the client prefers it.
+    #[cfg(feature = "cbor")]
+    Cbor,
+}
+
+/// The HTTP status code of a problem.
+///
+/// Serialized as a number, as mandated by RFC 9457, unless
+/// [`Config::status_as_string`] is enabled.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Status {
+    pub(crate) code: u16,
+    pub(crate) as_string: bool,
+}
+
+impl From<u16> for Status {
+    fn from(code: u16) -> Self {
+        Self {
+            code,
+            as_string: false,
+        }
+    }
+}
+
+impl serde::Serialize for Status {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        if self.as_string {
+            serializer.collect_str(&self.code)
+        } else {
+            serializer.serialize_u16(self.code)
+        }
+    }
 }
 
-#[derive(serde::Serialize)]
 pub(crate) struct ValidationErrors {
     pub(crate) errors: Vec<ValidationError>,
+    /// Serialize `errors` as an object mapping each location to the details
+    /// of its problems, as specified via [`Config::errors_by_pointer`].
+    pub(crate) by_pointer: bool,
 }
 
-#[derive(serde::Serialize)]
+impl serde::Serialize for ValidationErrors {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+
+        let mut s = serializer.serialize_struct("ValidationErrors", 1)?;
+        if self.by_pointer {
+            s.serialize_field("errors", &ByPointer(&self.errors))?;
+        } else {
+            s.serialize_field("errors", &self.errors)?;
+        }
+        s.end()
+    }
+}
+
+/// Validation errors grouped by location, in order of first appearance.
+struct ByPointer<'a>(&'a [ValidationError]);
+
+impl serde::Serialize for ByPointer<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut groups: Vec<(&str, Vec<&str>)> = Vec::new();
+        // The position of each location in `groups`.
+        let mut positions: HashMap<&str, usize> = HashMap::new();
+        for error in self.0 {
+            let location = error.source.location();
+            match positions.entry(location) {
+                Entry::Occupied(entry) => groups[*entry.get()].1.push(&error.detail),
+                Entry::Vacant(entry) => {
+                    entry.insert(groups.len());
+                    groups.push((location, vec![&error.detail]));
+                }
+            }
+        }
+        serializer.collect_map(groups)
+    }
+}
+
+impl ValidationErrors {
+    /// Adjust the errors according to the settings specified in the [`Config`].
+    pub(crate) fn configure(mut self, config: &Config) -> Self {
+        self.by_pointer = config.settings.errors_by_pointer;
+        if config.settings.coalesce_invalid_types {
+            self.coalesce_invalid_types();
+        }
+        match config.settings.error_order {
+            ErrorOrder::DocumentOrder => {}
+            ErrorOrder::PointerLexical => self
+                .errors
+                .sort_by(|a, b| a.source.location().cmp(b.source.location())),
+            ErrorOrder::SeverityThenPointer => self.errors.sort_by(|a, b| {
+                (severity(&a.code), a.source.location())
+                    .cmp(&(severity(&b.code), b.source.location()))
+            }),
+        }
+        #[cfg(feature = "i18n")]
+        let localizer = config.localizer();
+        for error in &mut self.errors {
+            error.documentation = config.documentation_for(&error.code);
+            #[cfg(feature = "i18n")]
+            if let Some(detail) = localizer
+                .as_ref()
+                .and_then(|localizer| localizer.localize(&error.code, &error.detail))
+            {
+                error.detail = detail;
+            }
+            if let Some(detail) = truncate(&error.detail, config.detail_limit()) {
+                error.detail = detail;
+                error.truncated = true;
+            }
+        }
+        self
+    }
+
+    /// Merge the `invalid_type` errors reported for the same value, at the same location,
+    /// into a single error listing all the expected types, in order of appearance.
+    ///
+    /// It happens when several alternatives are attempted for the same input,
+    /// e.g. by a hand-written untagged enum.
+    fn coalesce_invalid_types(&mut self) {
+        // Each kept error, with the types it expects if it's an `invalid_type` error.
+        let mut coalesced: Vec<(ValidationError, Vec<String>)> = Vec::new();
+        for error in std::mem::take(&mut self.errors) {
+            let Some((unexpected, expects, _)) = split_invalid_type(&error) else {
+                coalesced.push((error, Vec::new()));
+                continue;
+            };
+            let expects = expects.to_owned();
+            let previous = coalesced.iter_mut().find(|(other, _)| {
+                split_invalid_type(other).is_some_and(|(other, _, _)| other == unexpected)
+                    && same_source(&other.source, &error.source)
+            });
+            match previous {
+                Some((_, expected)) => {
+                    if !expected.contains(&expects) {
+                        expected.push(expects);
+                    }
+                }
+                None => coalesced.push((error, vec![expects])),
+            }
+        }
+        self.errors = coalesced
+            .into_iter()
+            .map(|(mut error, expected)| {
+                if expected.len() > 1 {
+                    if let Some((unexpected, _, position)) = split_invalid_type(&error) {
+                        error.detail = format!(
+                            "invalid type: {unexpected}, expected one of: {}{position}",
+                            expected.join(", ")
+                        );
+                    }
+                }
+                error
+            })
+            .collect();
+    }
+
+    /// The JSON pointers to the fields reported as missing from the request body,
+    /// in the order they were found.
+    ///
+    /// `missing_field` errors point at the object lacking the field, whose name
+    /// is provided by `eserde` alongside the error.
+    pub(crate) fn missing_fields(&self) -> Vec<String> {
+        self.errors
+            .iter()
+            .filter_map(|error| {
+                let field = error.missing_field.as_deref()?;
+                let Source::Body {
+                    pointer: Some(pointer),
+                    ..
+                } = &error.source
+                else {
+                    return None;
+                };
+                Some(format!("{pointer}/{}", escape_token(field)))
+            })
+            .collect()
+    }
+}
+
+impl ValidationErrors {
+    /// Build errors for the request body out of a map from dotted field names
+    /// (e.g. `address.city`) to messages, as produced by custom validation logic.
+    ///
+    /// Each field name is turned into a JSON pointer (e.g. `/address/city`) and
+    /// errors are sorted by pointer, so that the outcome doesn't depend on the
+    /// iteration order of the map. The errors are reported with the `invalid` code.
+    ///
+    /// Exposed via [`ErrorSink::extend_field_messages`](crate::ErrorSink::extend_field_messages).
+    pub(crate) fn from_field_messages<K, V>(messages: impl IntoIterator<Item = (K, V)>) -> Self
+    where
+        K: AsRef<str>,
+        V: Into<String>,
+    {
+        let mut errors: Vec<_> = messages
+            .into_iter()
+            .map(|(field, message)| {
+                ValidationError::new(
+                    Source::Body {
+                        pointer: Some(pointer(field.as_ref().split('.').map(escape_token))),
+                        key: false,
+                    },
+                    "invalid",
+                    message,
+                )
+            })
+            .collect();
+        errors.sort_by(|a, b| a.source.location().cmp(b.source.location()));
+        Self {
+            errors,
+            by_pointer: false,
+        }
+    }
+}
+
+/// Escape a reference token of a JSON pointer, as prescribed by
+/// [RFC 6901](https://www.rfc-editor.org/rfc/rfc6901#section-3).
+pub(crate) fn escape_token(token: &str) -> Cow<'_, str> {
+    if token.contains(['~', '/']) {
+        token.replace('~', "~0").replace('/', "~1").into()
+    } else {
+        token.into()
+    }
+}
+
+/// Cut `detail` down to `max_len` characters, followed by an ellipsis.
+///
+/// Returns `None` if `detail` is short enough already.
+fn truncate(detail: &str, max_len: usize) -> Option<String> {
+    let (end, _) = detail.char_indices().nth(max_len)?;
+    let mut truncated = String::with_capacity(end + '…'.len_utf8());
+    truncated.push_str(&detail[..end]);
+    truncated.push('…');
+    Some(truncated)
+}
+
+/// Split the detail of an `invalid_type` error into the unexpected value,
+/// the expected type and the position of the error in the input, if any—e.g.
+/// `invalid type: string "x", expected u8 at line 1 column 11`.
+fn split_invalid_type(error: &ValidationError) -> Option<(&str, &str, &str)> {
+    if error.code != "invalid_type" {
+        return None;
+    }
+    let (unexpected, expected) = error
+        .detail
+        .strip_prefix("invalid type: ")?
+        .split_once(", expected ")?;
+    let at = expected.rfind(" at line ").unwrap_or(expected.len());
+    let (expected, position) = expected.split_at(at);
+    Some((unexpected, expected, position))
+}
+
+/// Whether two errors were reported at the same location of the same part of the request.
+fn same_source(a: &Source, b: &Source) -> bool {
+    std::mem::discriminant(a) == std::mem::discriminant(b)
+        && a.custom_name() == b.custom_name()
+        && a.location() == b.location()
+}
+
+/// The rank of an error code for [`ErrorOrder::SeverityThenPointer`],
+/// the most severe first.
+fn severity(code: &str) -> u8 {
+    match code {
+        "missing_field" | "unknown_field" | "duplicate_field" | "invalid_type"
+        | "invalid_length" => 0,
+        "invalid_value" | "out_of_range" | "unknown_variant" | "invalid_format" => 1,
+        _ => 2,
+    }
+}
+
+#[derive(serde::Serialize, Debug)]
 pub(crate) struct ValidationError {
     pub(crate) detail: String,
+    /// A machine-readable identifier for the kind of problem,
+    /// e.g. `invalid_type` or `out_of_range`.
+    pub(crate) code: Cow<'static, str>,
     #[serde(flatten)]
     pub(crate) source: Source,
+    /// The range of values accepted by the target type,
+    /// populated for `out_of_range` errors.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) range: Option<Range>,
+    /// The expected and actual number of elements of a sequence,
+    /// populated for `invalid_length` errors.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) length: Option<Length>,
+    /// A link to the documentation for this kind of problem,
+    /// as specified via [`Config::documentation`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) documentation: Option<String>,
+    /// Set if `detail` was cut short, as specified via [`Config::max_detail_length`].
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub(crate) truncated: bool,
+    /// The name of the missing field, for `missing_field` errors reported by `eserde`.
+    ///
+    /// The error itself points at the object lacking the field.
+    #[serde(skip)]
+    pub(crate) missing_field: Option<String>,
 }
 
-/// The request part where the problem occurred.
-#[derive(serde::Serialize)]
+impl ValidationError {
+    /// A validation error without a range, a length or a documentation link.
+    ///
+    /// Use the struct update syntax to set the other fields.
+    pub(crate) fn new(
+        source: Source,
+        code: impl Into<Cow<'static, str>>,
+        detail: impl Into<String>,
+    ) -> Self {
+        Self {
+            detail: detail.into(),
+            code: code.into(),
+            source,
+            range: None,
+            length: None,
+            documentation: None,
+            truncated: false,
+            missing_field: None,
+        }
+    }
+
+    /// Build a validation error for the request body out of an error
+    /// reported by `eserde`.
+    pub(crate) fn body(error: &DeserializationError) -> Self {
+        let pointer = error.path().map(path_pointer);
+        let key = error.path().is_some_and(|path| path.is_map_key());
+        let (code, range) = classify(error.message());
+        let length = (code == "invalid_length")
+            .then(|| length(error.message()))
+            .flatten();
+        // Fields annotated with `#[eserde(code = "..")]` take precedence over the derived code.
+        let code = match error.code() {
+            Some(code) => Cow::Owned(code.to_owned()),
+            None => code.into(),
+        };
+        ValidationError {
+            range,
+            length,
+            missing_field: error.missing_field().map(ToOwned::to_owned),
+            ..ValidationError::new(Source::Body { pointer, key }, code, error.message())
+        }
+    }
+
+    /// Prepend `prefix` to the JSON pointer of errors located in the request body.
+    #[cfg(feature = "json")]
+    pub(crate) fn pointer_prefix(mut self, prefix: &str) -> Self {
+        if let Source::Body { pointer, .. } = &mut self.source {
+            match pointer {
+                Some(pointer) => pointer.insert_str(0, prefix),
+                None if !prefix.is_empty() => *pointer = Some(prefix.to_owned()),
+                None => {}
+            }
+        }
+        self
+    }
+
+    /// Build a validation error for a form field out of an error
+    /// reported by `eserde`.
+    ///
+    /// A trailing sequence index, for repeated fields, is reported on its own.
+    #[cfg(feature = "form")]
+    pub(crate) fn form(error: &DeserializationError) -> Self {
+        let segments: Vec<_> = error
+            .path()
+            .into_iter()
+            .flat_map(|path| path.iter())
+            .collect();
+        let (index, segments) = match segments.split_last() {
+            Some((eserde::path::Segment::Seq { index }, rest)) => (Some(*index), rest),
+            _ => (None, &segments[..]),
+        };
+        let field = bracket_name(segments.iter().copied());
+        ValidationError {
+            source: Source::Form { field, index },
+            ..Self::body(error)
+        }
+    }
+
+    /// Build a validation error for a query parameter out of an error
+    /// reported by `eserde`.
+    #[cfg(feature = "query")]
+    pub(crate) fn query(error: &DeserializationError) -> Self {
+        let parameter = bracket_name(error.path().into_iter().flat_map(|path| path.iter()));
+        ValidationError {
+            source: Source::Query { parameter },
+            ..Self::body(error)
+        }
+    }
+
+    /// Build a validation error out of an error reported by `eserde` for
+    /// a [`Combined`](crate::Combined) document.
+    ///
+    /// The first segment of the path is the field of the target type, which tells
+    /// the request part the problem belongs to.
+    #[cfg(feature = "combined")]
+    pub(crate) fn combined(
+        error: &DeserializationError,
+        fields: &[(&str, crate::combined::Part)],
+    ) -> Self {
+        use crate::combined::Part;
+
+        let mut segments = error.path().into_iter().flat_map(|path| path.iter());
+        let part = segments.next().and_then(|field| {
+            let field = field.to_string();
+            fields
+                .iter()
+                .find(|(name, _)| *name == field)
+                .map(|(_, part)| *part)
+        });
+        let source = match part {
+            Some(Part::Path) => Source::Path {
+                parameter: segments.next().map(ToString::to_string),
+            },
+            Some(Part::Query) => Source::Query {
+                parameter: bracket_name(segments),
+            },
+            Some(Part::Body) => Source::Body {
+                pointer: Some(pointer(segments.map(segment_token))),
+                key: error.path().is_some_and(|path| path.is_map_key()),
+            },
+            None => return Self::body(error),
+        };
+        ValidationError {
+            source,
+            ..Self::body(error)
+        }
+    }
+
+    /// Build a validation error for an outgoing response out of an error
+    /// reported by `eserde`.
+    pub(crate) fn response(error: &DeserializationError) -> Self {
+        let pointer = error.path().map(path_pointer);
+        ValidationError {
+            source: Source::Response { pointer },
+            ..Self::body(error)
+        }
+    }
+}
+
+/// Name a query parameter or a form field using the bracket syntax for nested keys,
+/// e.g. `filter[price][min]`.
+#[cfg(any(feature = "form", feature = "query"))]
+fn bracket_name<'a>(
+    mut segments: impl Iterator<Item = &'a eserde::path::Segment>,
+) -> Option<String> {
+    let mut parameter = segments.next()?.to_string();
+    for segment in segments {
+        match segment {
+            eserde::path::Segment::Seq { .. } => parameter.push_str(&segment.to_string()),
+            _ => {
+                parameter.push('[');
+                parameter.push_str(&segment.to_string());
+                parameter.push(']');
+            }
+        }
+    }
+    Some(parameter)
+}
+
+/// Build a [JSON pointer](https://www.rfc-editor.org/info/rfc6901) out of the path
+/// reported by `eserde`.
+fn path_pointer(path: &eserde::path::Path) -> String {
+    pointer(path.iter().map(segment_token))
+}
+
+/// The reference token for a path segment: array indices are bare numbers,
+/// e.g. `/items/2` rather than `/items/[2]`, while keys are escaped.
+fn segment_token(segment: &eserde::path::Segment) -> Cow<'_, str> {
+    match segment {
+        eserde::path::Segment::Seq { index } => index.to_string().into(),
+        eserde::path::Segment::Map { key } | eserde::path::Segment::Enum { variant: key } => {
+            escape_token(key)
+        }
+    }
+}
+
+/// Build a [JSON pointer](https://www.rfc-editor.org/info/rfc6901) out of a sequence of
+/// reference tokens, escaped already.
+pub(crate) fn pointer<S: std::fmt::Display>(segments: impl Iterator<Item = S>) -> String {
+    segments.fold(String::new(), |mut acc, part| {
+        acc.push('/');
+        acc.push_str(&part.to_string());
+        acc
+    })
+}
+
+/// The prefix of the errors emitted by the custom deserializers of this crate
+/// when the input doesn't match the expected format.
+///
+/// Errors starting with it are classified with the `invalid_format` code.
+pub(crate) const INVALID_FORMAT: &str = "invalid format: ";
+
+/// The inclusive range of values accepted by an integer type.
+#[derive(serde::Serialize, Debug, PartialEq, Eq)]
+pub(crate) struct Range {
+    pub(crate) min: i128,
+    pub(crate) max: u128,
+}
+
+/// The expected and actual number of elements of a sequence, e.g. a tuple
+/// or a fixed-size array.
+#[derive(serde::Serialize, Debug, PartialEq, Eq)]
+pub(crate) struct Length {
+    pub(crate) expected: usize,
+    pub(crate) actual: usize,
+}
+
+/// Extract the lengths out of the message of an `invalid_length` error, e.g.
+/// `invalid length 2, expected an array of length 3 at line 1 column 10`.
+fn length(message: &str) -> Option<Length> {
+    let rest = message.strip_prefix("invalid length ")?;
+    let (actual, expected) = rest.split_once(", expected ")?;
+    // Drop the position appended by `serde_json`, if any.
+    let expected = expected.split(" at line ").next()?;
+    Some(Length {
+        expected: expected
+            .split_whitespace()
+            .rev()
+            .find_map(|word| word.parse().ok())?,
+        actual: actual.parse().ok()?,
+    })
+}
+
+/// Determine the error code for a deserialization error, based on
+/// the message emitted by `serde`.
+///
+/// `serde`'s error constructors (`invalid_type`, `invalid_value`, etc.)
+/// produce messages with a stable prefix, which is what we rely on here.
+/// Integers that don't fit into the target integer type are classified
+/// as `out_of_range` rather than `invalid_value`, alongside the range
+/// accepted by the target type.
+fn classify(message: &str) -> (&'static str, Option<Range>) {
+    const PREFIXES: &[(&str, &str)] = &[
+        ("invalid type: ", "invalid_type"),
+        ("invalid value: ", "invalid_value"),
+        ("invalid length ", "invalid_length"),
+        ("unknown variant ", "unknown_variant"),
+        ("unknown field ", "unknown_field"),
+        ("missing field ", "missing_field"),
+        ("duplicate field ", "duplicate_field"),
+        (INVALID_FORMAT, "invalid_format"),
+    ];
+
+    if let Some(rest) = message.strip_prefix("invalid value: integer `") {
+        if let Some(range) = rest
+            .split_once(", expected ")
+            .and_then(|(_, expected)| expected.split_whitespace().next())
+            .and_then(integer_range)
+        {
+            return ("out_of_range", Some(range));
+        }
+    }
+    let code = PREFIXES
+        .iter()
+        .find(|(prefix, _)| message.starts_with(prefix))
+        .map(|(_, code)| *code)
+        .unwrap_or("invalid");
+    (code, None)
+}
+
+/// The range of values accepted by a primitive integer type,
+/// given its name (e.g. `u8`).
+fn integer_range(type_name: &str) -> Option<Range> {
+    macro_rules! range {
+        ($($ty:ident),*) => {
+            match type_name {
+                $(stringify!($ty) => Some(Range {
+                    min: $ty::MIN as i128,
+                    max: $ty::MAX as u128,
+                }),)*
+                _ => None,
+            }
+        };
+    }
+    range!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize)
+}
+
+/// The request part where the problem occurred, or the response
+/// if it failed validation on its way out.
+#[derive(serde::Serialize, Debug)]
 #[serde(tag = "source", rename_all = "snake_case")]
 pub(crate) enum Source {
     Body {
         /// A [JSON pointer](https://www.rfc-editor.org/info/rfc6901) targeted
         /// at the problematic body property.
         pointer: Option<String>,
+        /// Set if the problem lies in one of the keys of the object at `pointer`,
+        /// rather than in the object itself.
+        #[serde(skip_serializing_if = "std::ops::Not::not")]
+        key: bool,
     },
     Header {
         /// The name of the problematic header.
         name: Cow<'static, str>,
     },
+    #[cfg(feature = "form")]
+    Form {
+        /// The name of the problematic form field, using the bracket syntax
+        /// for nested fields—e.g. `user[age]`.
+        field: Option<String>,
+        /// The position of the problematic value, if the field
+        /// was repeated to send multiple values.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        index: Option<usize>,
+    },
+    #[cfg(feature = "query")]
+    Query {
+        /// The problematic query parameter, using the bracket syntax
+        /// for nested parameters—e.g. `filter[price][min]`.
+        parameter: Option<String>,
+    },
+    #[cfg(feature = "combined")]
+    Path {
+        /// The name of the problematic path parameter.
+        parameter: Option<String>,
+    },
+    Response {
+        /// A [JSON pointer](https://www.rfc-editor.org/info/rfc6901) targeted
+        /// at the problematic response property.
+        pointer: Option<String>,
+    },
+    /// A source defined outside of this crate, reported via [`ErrorSink::push_error`].
+    ///
+    /// It emits its own `source` member, hence `untagged`—which serde only allows
+    /// on the last variants.
+    ///
+    /// [`ErrorSink::push_error`]: crate::ErrorSink::push_error
+    #[serde(untagged)]
+    Custom(CustomSource),
+}
+
+/// A boxed [`ErrorSource`], serialized as its `source` and `location` members.
+#[derive(Debug)]
+pub(crate) struct CustomSource(pub(crate) Box<dyn ErrorSource>);
+
+impl serde::Serialize for CustomSource {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeMap;
+
+        let mut map = serializer.serialize_map(Some(2))?;
+        map.serialize_entry("source", self.0.name())?;
+        map.serialize_entry("location", self.0.location())?;
+        map.end()
+    }
+}
+
+impl Source {
+    /// The location of the problem within its source: a JSON pointer for bodies
+    /// and responses, a name for headers, form fields, query and path parameters.
+    fn location(&self) -> &str {
+        match self {
+            Self::Body { pointer, .. } | Self::Response { pointer } => {
+                pointer.as_deref().unwrap_or_default()
+            }
+            Self::Header { name } => name,
+            #[cfg(feature = "form")]
+            Self::Form { field, .. } => field.as_deref().unwrap_or_default(),
+            #[cfg(feature = "query")]
+            Self::Query { parameter } => parameter.as_deref().unwrap_or_default(),
+            #[cfg(feature = "combined")]
+            Self::Path { parameter } => parameter.as_deref().unwrap_or_default(),
+            Self::Custom(source) => source.0.location(),
+        }
+    }
+
+    /// The discriminator of a custom source, if it is one.
+    fn custom_name(&self) -> Option<&str> {
+        match self {
+            Self::Custom(source) => Some(source.0.name()),
+            _ => None,
+        }
+    }
+}
+
+impl<Extension> ProblemDetails<Extension> {
+    /// A problem without extension members, to be served as JSON with no extra headers.
+    ///
+    /// Use the struct update syntax to set the other fields.
+    pub(crate) fn new(
+        type_: impl Into<Cow<'static, str>>,
+        status: StatusCode,
+        title: impl Into<Cow<'static, str>>,
+        detail: impl Into<Cow<'static, str>>,
+    ) -> Self {
+        Self {
+            type_: type_.into(),
+            status: status.as_u16().into(),
+            title: title.into(),
+            detail: detail.into(),
+            truncated: false,
+            extensions: None,
+            challenge: None,
+            fallback: None,
+            vary: None,
+            content_location: None,
+            format: Format::Json,
+            headers: Vec::new(),
+        }
+    }
+
+    /// Adjust the problem according to the settings specified in the [`Config`].
+    pub(crate) fn configure(mut self, config: &Config) -> Self {
+        self.status.as_string = config.settings.status_as_string;
+        self.fallback = Some(config.fallback());
+        // The details of validation errors are localized according to `Accept-Language`.
+        #[cfg(feature = "i18n")]
+        if let Some(catalog) = &config.settings.catalog {
+            if catalog.localizes() {
+                self.vary = Some(HeaderValue::from_static("Accept-Language"));
+            }
+            if let Some(headers) = catalog.headers(&self.type_) {
+                self.headers.extend_from_slice(headers);
+            }
+        }
+        #[cfg(feature = "cbor")]
+        if config.prefers_cbor {
+            self.format = Format::Cbor;
+        }
+        if let Some(detail) = truncate(&self.detail, config.detail_limit()) {
+            self.detail = detail.into();
+            self.truncated = true;
+        }
+        if !config.settings.type_prefix.is_empty() {
+            self.type_ = format!("{}{}", config.settings.type_prefix, self.type_).into();
+        }
+        if let Some(origin) = config.origin().filter(|_| self.type_.starts_with('/')) {
+            self.type_ = format!("{origin}{}", self.type_).into();
+        }
+        if config.settings.about_blank {
+            self.type_ = ABOUT_BLANK.into();
+            if let Some(reason) = StatusCode::from_u16(self.status.code)
+                .ok()
+                .and_then(|status| status.canonical_reason())
+            {
+                self.title = reason.into();
+            }
+        }
+        if config.settings.problem_type_header {
+            if let Ok(value) = HeaderValue::from_str(&self.type_) {
+                self.headers.push((X_PROBLEM_TYPE, value));
+            }
+        }
+        self
+    }
 }
 
+/// The header mirroring the `type` of a problem, as specified via [`Config::problem_type_header`].
+const X_PROBLEM_TYPE: HeaderName = HeaderName::from_static("x-problem-type");
+
+/// The default problem type, as defined by
+/// [RFC 9457](https://www.rfc-editor.org/rfc/rfc9457.html#name-aboutblank).
+const ABOUT_BLANK: &str = "about:blank";
+
 impl<Extension> axum_core::response::IntoResponse for ProblemDetails<Extension>
 where
     Extension: serde::Serialize,
 {
     fn into_response(self) -> axum_core::response::Response {
-        let status = StatusCode::try_from(self.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        let status =
+            StatusCode::try_from(self.status.code).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
 
         // Use a small initial capacity of 128 bytes like serde_json::to_vec
         // https://docs.rs/serde_json/1.0.82/src/serde_json/ser.rs.html#2189
         let mut buf = BytesMut::with_capacity(128).writer();
-        match serde_json::to_writer(&mut buf, &self) {
-            Ok(()) => (
+        let (serialized, content_type) = match self.format {
+            Format::Json => (
+                serde_json::to_writer(&mut buf, &self).is_ok(),
+                APPLICATION_PROBLEM_JSON,
+            ),
+            #[cfg(feature = "cbor")]
+            Format::Cbor => (
+                ciborium::into_writer(&self, &mut buf).is_ok(),
+                APPLICATION_PROBLEM_CBOR,
+            ),
+        };
+        if serialized {
+            let mut response = (
                 status,
-                [(CONTENT_TYPE, APPLICATION_PROBLEM_JSON)],
+                [(CONTENT_TYPE, content_type)],
                 buf.into_inner().freeze(),
             )
-                .into_response(),
-            Err(_) => INTERNAL_SERVER_ERROR.into_response(),
+                .into_response();
+            if let (StatusCode::UNAUTHORIZED, Some(challenge)) = (status, self.challenge) {
+                response.headers_mut().insert(WWW_AUTHENTICATE, challenge);
+            }
+            if let Some(vary) = self.vary {
+                response.headers_mut().append(VARY, vary);
+            }
+            if let Some(location) = self.content_location {
+                response.headers_mut().insert(CONTENT_LOCATION, location);
+            }
+            response.headers_mut().extend(self.headers);
+            // The format of the problem is negotiated on `Accept`.
+            #[cfg(feature = "cbor")]
+            response
+                .headers_mut()
+                .append(VARY, HeaderValue::from_static("Accept"));
+            response
+        } else {
+            match self.fallback {
+                Some(fallback) => fallback.response(),
+                None => INTERNAL_SERVER_ERROR.into_response(),
+            }
         }
     }
 }
@@ -70,6 +879,60 @@ where
 pub(crate) const APPLICATION_PROBLEM_JSON: HeaderValue =
     HeaderValue::from_static("application/problem+json");
 
+#[cfg(feature = "cbor")]
+pub(crate) const APPLICATION_PROBLEM_CBOR: HeaderValue =
+    HeaderValue::from_static("application/problem+cbor");
+
+/// Collect the body of a problem response and decode it: as CBOR if its `Content-Type`
+/// says so, as JSON otherwise.
+///
+/// Shared by the unit tests of this crate and by `testing::problem`.
+#[cfg(any(test, feature = "testing"))]
+pub(crate) async fn read_problem(
+    response: impl IntoResponse,
+) -> (http::response::Parts, serde_json::Value) {
+    use http_body_util::BodyExt;
+
+    let (parts, body) = response.into_response().into_parts();
+    let body = body
+        .collect()
+        .await
+        .expect("Failed to collect the body of the response")
+        .to_bytes();
+    #[cfg(feature = "cbor")]
+    if parts.headers.get(CONTENT_TYPE) == Some(&APPLICATION_PROBLEM_CBOR) {
+        let value = ciborium::from_reader(&body[..])
+            .unwrap_or_else(|e| panic!("The body of the problem response is not valid CBOR ({e})"));
+        return (parts, value);
+    }
+    let value = serde_json::from_slice(&body).unwrap_or_else(|e| {
+        panic!(
+            "The body of the problem response is not valid JSON ({e}): {}",
+            String::from_utf8_lossy(&body)
+        )
+    });
+    (parts, value)
+}
+
+/// Returns `true` if the `Accept` header ranks a CBOR media type
+/// (`application/problem+cbor` or `application/cbor`) higher than any JSON one.
+///
+/// Wildcards don't count towards JSON: a client naming CBOR explicitly gets CBOR,
+/// unless it names a JSON media type with the same quality or higher too.
+#[cfg(feature = "cbor")]
+pub(crate) fn prefers_cbor(headers: &http::HeaderMap) -> bool {
+    let mut cbor = 0.0_f32;
+    let mut json = 0.0_f32;
+    for range in crate::accept::media_ranges(headers) {
+        if range.is("application", "problem+cbor") || range.is("application", "cbor") {
+            cbor = cbor.max(range.quality);
+        } else if range.is("application", "problem+json") || range.is("application", "json") {
+            json = json.max(range.quality);
+        }
+    }
+    cbor > 0.0 && cbor > json
+}
+
 pub(crate) const INTERNAL_SERVER_ERROR: (StatusCode, [(HeaderName, HeaderValue); 1], &[u8]) = (
     StatusCode::INTERNAL_SERVER_ERROR,
     [(CONTENT_TYPE, APPLICATION_PROBLEM_JSON)],
@@ -79,20 +942,229 @@ pub(crate) const INTERNAL_SERVER_ERROR: (StatusCode, [(HeaderName, HeaderValue);
 pub(crate) const INTERNAL_SERVER_ERROR_PROBLEM: &[u8] = br#"{
     "type": "internal_server_error",
     "title": "Internal Server Error",
-    "detail": "Something went wrong when processing your request. Please try again later."
+    "detail": "Something went wrong when processing your request. Please try again later.",
     "status": 500
 }"#;
 
-pub(crate) struct InvalidRequest(ProblemDetails<ValidationErrors>);
+/// The title of [`INTERNAL_SERVER_ERROR_PROBLEM`].
+pub(crate) const INTERNAL_SERVER_ERROR_TITLE: &str = "Internal Server Error";
+
+/// The detail of [`INTERNAL_SERVER_ERROR_PROBLEM`].
+pub(crate) const INTERNAL_SERVER_ERROR_DETAIL: &str =
+    "Something went wrong when processing your request. Please try again later.";
+
+/// Serialize the body of a `500 Internal Server Error` problem
+/// with the given type prefix, title and detail.
+pub(crate) fn internal_server_error_problem(
+    type_prefix: &str,
+    title: Cow<'static, str>,
+    detail: Cow<'static, str>,
+) -> Bytes {
+    let problem: ProblemDetails<()> = ProblemDetails::new(
+        format!("{type_prefix}internal_server_error"),
+        StatusCode::INTERNAL_SERVER_ERROR,
+        title,
+        detail,
+    );
+    serde_json::to_vec(&problem)
+        .expect("Serializing a problem without extensions can't fail")
+        .into()
+}
+
+/// Convert a failure to buffer the request body into a problem response.
+///
+/// Shared by all extractors that consume the request body.
+pub(crate) fn bytes_rejection_response(rejection: BytesRejection) -> axum_core::response::Response {
+    let mut response = None;
+    #[allow(clippy::single_match)]
+    match rejection {
+        BytesRejection::FailedToBufferBody(failed_to_buffer_body) => match failed_to_buffer_body {
+            FailedToBufferBody::LengthLimitError(length_limit_error) => {
+                let details: ProblemDetails<()> = ProblemDetails::new(
+                    "content_too_large",
+                    length_limit_error.status(),
+                    "The content is too large",
+                    length_limit_error.body_text(),
+                );
+                response = Some(details.into_response());
+            }
+            FailedToBufferBody::UnknownBodyError(unknown_body_error) => {
+                let details: ProblemDetails<()> = ProblemDetails::new(
+                    "body_buffering_error",
+                    unknown_body_error.status(),
+                    "Failed to buffer the body",
+                    unknown_body_error.body_text(),
+                );
+                response = Some(details.into_response());
+            }
+            _ => {}
+        },
+        _ => {}
+    }
+    response.unwrap_or_else(|| INTERNAL_SERVER_ERROR.into_response())
+}
+
+/// A `415 Unsupported Media Type` problem, listing the media types accepted by the
+/// extractor in the `expected` member and the `Content-Type` of the request in the
+/// `received` one.
+pub(crate) struct UnsupportedMediaType(ProblemDetails<UnsupportedMediaTypeMembers>);
+
+/// The extension members of an [`UnsupportedMediaType`] problem.
+#[derive(serde::Serialize)]
+pub(crate) struct UnsupportedMediaTypeMembers {
+    #[serde(flatten)]
+    errors: ValidationErrors,
+    expected: &'static [&'static str],
+    received: String,
+}
+
+impl UnsupportedMediaType {
+    /// Report a `Content-Type` header matching none of the `expected` media types.
+    ///
+    /// `message` describes the mismatch, `detail` what the request body must be.
+    pub(crate) fn mismatch(
+        expected: &'static [&'static str],
+        received: String,
+        message: String,
+        detail: &'static str,
+    ) -> Self {
+        Self::new("content_type_mismatch", message, detail, expected, received)
+    }
+
+    /// A problem holding a single error about the `Content-Type` header,
+    /// with the given `code` and `message`.
+    pub(crate) fn new(
+        code: &'static str,
+        message: String,
+        detail: &'static str,
+        expected: &'static [&'static str],
+        received: String,
+    ) -> Self {
+        let error = ValidationError::new(
+            Source::Header {
+                name: CONTENT_TYPE.as_str().into(),
+            },
+            code,
+            message,
+        );
+        Self(ProblemDetails {
+            extensions: Some(UnsupportedMediaTypeMembers {
+                errors: ValidationErrors {
+                    errors: vec![error],
+                    by_pointer: false,
+                },
+                expected,
+                received,
+            }),
+            ..ProblemDetails::new(
+                "unsupported_media_type",
+                Self::status(),
+                "The content type of the request is not supported",
+                detail,
+            )
+        })
+    }
+
+    /// Use a more specific problem type and title.
+    #[cfg(feature = "json")]
+    pub(crate) fn typed(mut self, type_: &'static str, title: &'static str) -> Self {
+        self.0.type_ = type_.into();
+        self.0.title = title.into();
+        self
+    }
+
+    pub(crate) fn status() -> StatusCode {
+        StatusCode::UNSUPPORTED_MEDIA_TYPE
+    }
+
+    /// Adjust the problem according to the settings specified in the [`Config`].
+    pub(crate) fn configure(self, config: &Config) -> ProblemDetails<UnsupportedMediaTypeMembers> {
+        let mut problem = self.0;
+        if let Some(members) = problem.extensions.take() {
+            problem.extensions = Some(UnsupportedMediaTypeMembers {
+                errors: members.errors.configure(config),
+                ..members
+            });
+        }
+        problem.configure(config)
+    }
+}
+
+pub(crate) struct InvalidRequest(ProblemDetails<InvalidRequestMembers>);
+
+/// The extension members of an [`InvalidRequest`] problem.
+#[derive(serde::Serialize)]
+pub(crate) struct InvalidRequestMembers {
+    /// Left out if [`Config::omit_errors`] is enabled.
+    #[serde(flatten)]
+    pub(crate) errors: Option<ValidationErrors>,
+    /// The raw request body, as specified via [`Config::debug_raw_body`].
+    #[serde(rename = "_debug_raw", skip_serializing_if = "Option::is_none")]
+    pub(crate) debug_raw: Option<String>,
+    /// The pointers to the missing fields, as specified via [`Config::list_missing_fields`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) missing: Option<Vec<String>>,
+    /// The OpenTelemetry trace ID of the span the problem was raised in, if any.
+    #[cfg(feature = "opentelemetry")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) trace_id: Option<String>,
+    /// Where the request body stopped being syntactically valid JSON, if it isn't.
+    #[cfg(feature = "json")]
+    #[serde(flatten)]
+    pub(crate) syntax_error: Option<SyntaxError>,
+}
+
+/// The position of a syntax error in a JSON request body.
+#[cfg(feature = "json")]
+#[derive(serde::Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct SyntaxError {
+    /// The line of the error, starting at 1.
+    pub(crate) line: usize,
+    /// The column of the error within its line, in bytes, starting at 1.
+    pub(crate) column: usize,
+    /// The offset, within the body, of the byte where parsing stopped, starting at 0.
+    pub(crate) offset: usize,
+}
+
+#[cfg(feature = "json")]
+impl SyntaxError {
+    /// Locate the syntax error reported by `serde_json` for `body`.
+    pub(crate) fn new(error: &serde_json::Error, body: &[u8]) -> Self {
+        let line_start = match error.line() {
+            0 | 1 => 0,
+            line => body
+                .iter()
+                .enumerate()
+                .filter(|(_, byte)| **byte == b'\n')
+                .nth(line - 2)
+                .map_or(body.len(), |(position, _)| position + 1),
+        };
+        Self {
+            line: error.line(),
+            column: error.column(),
+            offset: (line_start + error.column().saturating_sub(1)).min(body.len()),
+        }
+    }
+}
 
 impl InvalidRequest {
     pub(crate) fn new(errors: ValidationErrors) -> Self {
         Self(ProblemDetails {
-            type_: "invalid_request".into(),
-            status: Self::status().as_u16(),
-            title: "The request is invalid".into(),
-            extensions: Some(errors),
-            detail: "The request is either malformed or doesn't match the expected schema".into(),
+            extensions: Some(InvalidRequestMembers {
+                errors: Some(errors),
+                debug_raw: None,
+                missing: None,
+                #[cfg(feature = "opentelemetry")]
+                trace_id: current_trace_id(),
+                #[cfg(feature = "json")]
+                syntax_error: None,
+            }),
+            ..ProblemDetails::new(
+                "invalid_request",
+                Self::status(),
+                "The request is invalid",
+                "The request is either malformed or doesn't match the expected schema",
+            )
         })
     }
 
@@ -100,9 +1172,115 @@ impl InvalidRequest {
         StatusCode::BAD_REQUEST
     }
 
-    pub(crate) fn into_inner(self) -> ProblemDetails<ValidationErrors> {
+    /// Report a malformed request body (`400 Bad Request`) or a well-formed body that doesn't
+    /// match the expected schema (`422 Unprocessable Content`) with a dedicated problem type,
+    /// if [`Config::unprocessable_entity`] is enabled.
+    #[cfg(feature = "json")]
+    pub(crate) fn split_by_syntax(mut self, malformed: bool, config: &Config) -> Self {
+        if !config.settings.unprocessable_entity {
+            return self;
+        }
+        if malformed {
+            self.0.type_ = "malformed_request".into();
+            self.0.title = "The request body is malformed".into();
+            self.0.detail = "The request body is not syntactically valid".into();
+        } else {
+            self.0.type_ = "unprocessable_entity".into();
+            self.0.status = StatusCode::UNPROCESSABLE_ENTITY.as_u16().into();
+            self.0.title = "The request body is semantically invalid".into();
+            self.0.detail =
+                "The request body is well-formed, but it doesn't match the expected schema".into();
+        }
+        self
+    }
+
+    /// Attach the position of the syntax error in the request body to the problem, if any.
+    #[cfg(feature = "json")]
+    pub(crate) fn syntax_error(mut self, syntax_error: Option<SyntaxError>) -> Self {
+        if let Some(members) = &mut self.0.extensions {
+            members.syntax_error = syntax_error;
+        }
+        self
+    }
+
+    /// The status code of this problem, which isn't necessarily [`InvalidRequest::status`].
+    #[cfg(feature = "json")]
+    pub(crate) fn status_code(&self) -> StatusCode {
+        StatusCode::from_u16(self.0.status.code).unwrap_or_else(|_| Self::status())
+    }
+
+    pub(crate) fn into_inner(self) -> ProblemDetails<InvalidRequestMembers> {
         self.0
     }
+
+    /// Point at the canonical resource the problem is about,
+    /// via the `Content-Location` header.
+    pub(crate) fn content_location(mut self, location: Option<HeaderValue>) -> Self {
+        self.0.content_location = location;
+        self
+    }
+
+    /// Attach the raw request body to the problem, if any.
+    ///
+    /// See [`Config::debug_raw_body`].
+    #[cfg(any(feature = "json", feature = "form", feature = "query"))]
+    pub(crate) fn debug_raw(mut self, debug_raw: Option<String>) -> Self {
+        if let Some(members) = &mut self.0.extensions {
+            members.debug_raw = debug_raw;
+        }
+        self
+    }
+
+    /// Adjust the problem according to the settings specified in the [`Config`].
+    pub(crate) fn configure(self, config: &Config) -> Self {
+        #[allow(unused_mut)]
+        let mut problem = self.0;
+        #[cfg(feature = "json")]
+        if let Some(identity) = config.settings.identity {
+            problem.type_ = identity.type_.into();
+            problem.title = identity.title.into();
+            if let Some(detail) = identity.detail {
+                problem.detail = detail.into();
+            }
+        }
+        let problem = ProblemDetails {
+            extensions: problem.extensions.map(|members| InvalidRequestMembers {
+                missing: members
+                    .errors
+                    .as_ref()
+                    .filter(|_| config.settings.list_missing_fields)
+                    .map(ValidationErrors::missing_fields)
+                    .filter(|missing| !missing.is_empty()),
+                errors: members
+                    .errors
+                    .filter(|_| !config.settings.omit_errors)
+                    .map(|errors| errors.configure(config)),
+                ..members
+            }),
+            ..problem
+        };
+        Self(problem.configure(config))
+    }
+}
+
+/// The OpenTelemetry trace ID of the current span.
+///
+/// Returns `None` outside of a span, or if the span isn't part of a valid trace
+/// (e.g. because the `tracing-opentelemetry` layer isn't installed).
+#[cfg(feature = "opentelemetry")]
+fn current_trace_id() -> Option<String> {
+    use opentelemetry::trace::TraceContextExt;
+    use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+    let span = tracing::Span::current();
+    if span.is_none() {
+        return None;
+    }
+    let context = span.context();
+    let span_context = context.span().span_context().clone();
+    span_context
+        .is_valid()
+        .then(|| span_context.trace_id().to_string())
 }
 
 impl axum_core::response::IntoResponse for InvalidRequest {
@@ -111,37 +1289,840 @@ impl axum_core::response::IntoResponse for InvalidRequest {
     }
 }
 
+/// A response that failed validation before being sent to the client,
+/// e.g. data loaded from a cache that no longer matches the expected schema.
+///
+/// It lists the failures using the same model as request validation errors,
+/// tagged with a `response` source.
+/// When converted into a response, the failures are logged and the client gets
+/// a generic 500 problem, without any internal detail.
+///
+/// ```rust
+/// use axum::response::{IntoResponse, Response};
+/// use eserde_axum::InvalidResponse;
+///
+/// #[derive(eserde::Deserialize, serde::Serialize)]
+/// struct Profile {
+///     name: String,
+/// }
+///
+/// fn cached_profile(cached: &str) -> Response {
+///     match eserde::json::from_str::<Profile>(cached) {
+///         Ok(profile) => axum::Json(profile).into_response(),
+///         Err(errors) => InvalidResponse::new(&errors).into_response(),
+///     }
+/// }
+/// # let _ = cached_profile(r#"{"name": 1}"#);
+/// ```
+pub struct InvalidResponse(ProblemDetails<ValidationErrors>);
+
+impl InvalidResponse {
+    /// Build a new `InvalidResponse` out of the errors reported by `eserde`
+    /// when validating the outgoing data.
+    pub fn new(errors: &DeserializationErrors) -> Self {
+        let errors = errors.iter().map(ValidationError::response).collect();
+        Self(ProblemDetails {
+            extensions: Some(ValidationErrors {
+                errors,
+                by_pointer: false,
+            }),
+            ..ProblemDetails::new(
+                "invalid_response",
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "The response is invalid",
+                "The response doesn't match the expected schema",
+            )
+        })
+    }
+}
+
+impl axum_core::response::IntoResponse for InvalidResponse {
+    fn into_response(self) -> axum_core::response::Response {
+        match serde_json::to_string(&self.0) {
+            Ok(problem) => {
+                tracing::event!(
+                    target: "eserde_axum::invalid_response",
+                    tracing::Level::ERROR,
+                    problem = %problem,
+                    "the response failed validation",
+                );
+            }
+            Err(e) => {
+                tracing::event!(
+                    target: "eserde_axum::invalid_response",
+                    tracing::Level::ERROR,
+                    error.message = %e,
+                    "the response failed validation, but the failures couldn't be serialized",
+                );
+            }
+        }
+        INTERNAL_SERVER_ERROR.into_response()
+    }
+}
+
+/// A `401 Unauthorized` problem, for requests that lack valid authentication credentials.
+///
+/// The response carries a `WWW-Authenticate` header with the given challenge,
+/// telling the client how to authenticate.
+///
+/// ```rust
+/// use axum::http::HeaderValue;
+/// use axum::response::IntoResponse;
+/// use eserde_axum::Unauthorized;
+///
+/// let problem = Unauthorized::new(HeaderValue::from_static(r#"Bearer realm="api""#));
+/// let response = problem.into_response();
+/// assert_eq!(response.status(), 401);
+/// assert!(response.headers().contains_key("www-authenticate"));
+/// ```
+pub struct Unauthorized(pub(crate) ProblemDetails<()>);
+
+impl Unauthorized {
+    /// Build a new `Unauthorized` problem, with the challenge to send
+    /// via the `WWW-Authenticate` header.
+    pub fn new(challenge: HeaderValue) -> Self {
+        Self(ProblemDetails {
+            challenge: Some(challenge),
+            ..ProblemDetails::new(
+                "unauthorized",
+                StatusCode::UNAUTHORIZED,
+                "Authentication is required",
+                "The request lacks valid authentication credentials",
+            )
+        })
+    }
+
+    /// Adjust the problem according to the settings specified in the [`Config`].
+    pub fn configure(self, config: &Config) -> Self {
+        Self(self.0.configure(config))
+    }
+}
+
+impl axum_core::response::IntoResponse for Unauthorized {
+    fn into_response(self) -> axum_core::response::Response {
+        self.0.into_response()
+    }
+}
+
+impl ProblemDetails<ValidationErrors> {
+    /// A `400 Bad Request` problem, caused by a request with `count` (i.e. more than one)
+    /// `Content-Type` headers.
+    #[cfg(any(feature = "json", feature = "form", feature = "protobuf"))]
+    pub(crate) fn ambiguous_content_type(count: usize) -> Self {
+        let error = ValidationError::new(
+            Source::Header {
+                name: CONTENT_TYPE.as_str().into(),
+            },
+            "ambiguous_content_type",
+            format!("Expected a single `Content-Type` header, but found {count}"),
+        );
+        ProblemDetails {
+            extensions: Some(ValidationErrors {
+                errors: vec![error],
+                by_pointer: false,
+            }),
+            ..ProblemDetails::new(
+                "ambiguous_content_type",
+                StatusCode::BAD_REQUEST,
+                "The content type of the request is ambiguous",
+                "The request specifies more than one `Content-Type`",
+            )
+        }
+    }
+
+    /// A `409 Conflict` problem, caused by the request body property
+    /// at `pointer`—e.g. a value that must be unique but is already taken.
+    pub(crate) fn conflict(pointer: String, detail: String) -> Self {
+        let error = ValidationError::new(
+            Source::Body {
+                pointer: Some(pointer),
+                key: false,
+            },
+            "conflict",
+            detail,
+        );
+        ProblemDetails {
+            extensions: Some(ValidationErrors {
+                errors: vec![error],
+                by_pointer: false,
+            }),
+            ..ProblemDetails::new(
+                "conflict",
+                StatusCode::CONFLICT,
+                "The request conflicts with the current state of the resource",
+                "The request body contains values that conflict with existing data",
+            )
+        }
+    }
+}
+
+/// A `409 Conflict` problem, pointing at the property of the request body
+/// that conflicts with the current state of the server—e.g. when a
+/// unique constraint is violated.
+///
+/// The property is reported with the same model used for validation errors.
+///
+/// ```rust
+/// use axum::response::IntoResponse;
+/// use eserde_axum::Conflict;
+///
+/// let problem = Conflict::new("/email", "The email address is already registered");
+/// let response = problem.into_response();
+/// assert_eq!(response.status(), 409);
+/// ```
+pub struct Conflict(pub(crate) ProblemDetails<ValidationErrors>);
+
+impl Conflict {
+    /// Build a new `Conflict` problem for the request body property at `pointer`,
+    /// a [JSON pointer](https://www.rfc-editor.org/info/rfc6901) (e.g. `/email`).
+    pub fn new(pointer: impl Into<String>, detail: impl Into<String>) -> Self {
+        Self(ProblemDetails::conflict(pointer.into(), detail.into()))
+    }
+
+    /// Adjust the problem according to the settings specified in the [`Config`].
+    pub fn configure(self, config: &Config) -> Self {
+        let problem = ProblemDetails {
+            extensions: self.0.extensions.map(|errors| errors.configure(config)),
+            ..self.0
+        };
+        Self(problem.configure(config))
+    }
+
+    /// Point at the canonical URL of the conflicting resource, via the
+    /// `Content-Location` header.
+    pub fn content_location(mut self, location: HeaderValue) -> Self {
+        self.0.content_location = Some(location);
+        self
+    }
+}
+
+impl axum_core::response::IntoResponse for Conflict {
+    fn into_response(self) -> axum_core::response::Response {
+        self.0.into_response()
+    }
+}
+
+/// The extension members of a [`NotFound`] problem.
+#[derive(serde::Serialize)]
+pub(crate) struct NotFoundMembers {
+    /// The type of the missing resource, e.g. `user`.
+    resource: Cow<'static, str>,
+    /// The identifier of the missing resource.
+    id: String,
+}
+
+impl ProblemDetails<NotFoundMembers> {
+    /// A `404 Not Found` problem, for the `resource` identified by `id`.
+    pub(crate) fn not_found(resource: Cow<'static, str>, id: String) -> Self {
+        let detail = format!("There is no {resource} with id `{id}`");
+        ProblemDetails {
+            extensions: Some(NotFoundMembers { resource, id }),
+            ..ProblemDetails::new(
+                "not_found",
+                StatusCode::NOT_FOUND,
+                "The resource was not found",
+                detail,
+            )
+        }
+    }
+}
+
+/// A `404 Not Found` problem, identifying the resource that couldn't be found
+/// via the `resource` and `id` members.
+///
+/// ```rust
+/// use axum::response::IntoResponse;
+/// use eserde_axum::NotFound;
+///
+/// let problem = NotFound::new("user", 42);
+/// let response = problem.into_response();
+/// assert_eq!(response.status(), 404);
+/// ```
+pub struct NotFound(pub(crate) ProblemDetails<NotFoundMembers>);
+
+impl NotFound {
+    /// Build a new `NotFound` problem for the `resource` (e.g. `user`) identified by `id`.
+    pub fn new(resource: impl Into<Cow<'static, str>>, id: impl std::fmt::Display) -> Self {
+        Self(ProblemDetails::not_found(resource.into(), id.to_string()))
+    }
+
+    /// Adjust the problem according to the settings specified in the [`Config`].
+    pub fn configure(self, config: &Config) -> Self {
+        Self(self.0.configure(config))
+    }
+
+    /// Point at the canonical URL the resource would be found at, via the
+    /// `Content-Location` header.
+    pub fn content_location(mut self, location: HeaderValue) -> Self {
+        self.0.content_location = Some(location);
+        self
+    }
+}
+
+impl axum_core::response::IntoResponse for NotFound {
+    fn into_response(self) -> axum_core::response::Response {
+        self.0.into_response()
+    }
+}
+
+/// A `429 Too Many Requests` problem, for clients exceeding a rate limit.
+///
+/// Register a `Retry-After` header for the `too_many_requests` type
+/// via [`Catalog::header`](crate::i18n::Catalog::header) to tell clients when to try again.
+///
+/// ```rust
+/// use axum::response::IntoResponse;
+/// use eserde_axum::TooManyRequests;
+///
+/// let problem = TooManyRequests::new();
+/// let response = problem.into_response();
+/// assert_eq!(response.status(), 429);
+/// ```
+#[derive(Debug)]
+pub struct TooManyRequests(pub(crate) ProblemDetails<()>);
+
+impl TooManyRequests {
+    /// Build a new `TooManyRequests` problem.
+    pub fn new() -> Self {
+        Self(ProblemDetails::new(
+            "too_many_requests",
+            StatusCode::TOO_MANY_REQUESTS,
+            "Too many requests",
+            "The rate limit was exceeded, try again later",
+        ))
+    }
+
+    /// Adjust the problem according to the settings specified in the [`Config`].
+    pub fn configure(self, config: &Config) -> Self {
+        Self(self.0.configure(config))
+    }
+}
+
+impl Default for TooManyRequests {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl axum_core::response::IntoResponse for TooManyRequests {
+    fn into_response(self) -> axum_core::response::Response {
+        self.0.into_response()
+    }
+}
+
+/// A problem with an arbitrary status and type, e.g. to report the domain errors
+/// of your handlers.
+///
+/// Enable the `thiserror` feature to derive the conversion of an error enum into
+/// a `Problem`, see [`IntoProblem`](crate::IntoProblem).
+///
+/// ```rust
+/// use axum::response::IntoResponse;
+/// use eserde_axum::Problem;
+/// use http::StatusCode;
+///
+/// let problem = Problem::new(StatusCode::CONFLICT, "order_shipped", "The order was already shipped");
+/// let response = problem.into_response();
+/// assert_eq!(response.status(), 409);
+/// ```
+#[derive(Debug)]
+pub struct Problem(pub(crate) ProblemDetails<()>);
+
+impl Problem {
+    /// Build a new `Problem`, titled after the canonical reason of `status`
+    /// (e.g. `Conflict`) unless [another title](Self::title) is set.
+    pub fn new(
+        status: StatusCode,
+        type_: impl Into<Cow<'static, str>>,
+        detail: impl Into<Cow<'static, str>>,
+    ) -> Self {
+        Self(ProblemDetails::new(
+            type_,
+            status,
+            status.canonical_reason().unwrap_or_default(),
+            detail,
+        ))
+    }
+
+    /// Set the title of the problem, a short summary of its type.
+    pub fn title(mut self, title: impl Into<Cow<'static, str>>) -> Self {
+        self.0.title = title.into();
+        self
+    }
+
+    /// Adjust the problem according to the settings specified in the [`Config`].
+    pub fn configure(self, config: &Config) -> Self {
+        Self(self.0.configure(config))
+    }
+}
+
+impl axum_core::response::IntoResponse for Problem {
+    fn into_response(self) -> axum_core::response::Response {
+        self.0.into_response()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use axum_core::response::IntoResponse;
     use http::StatusCode;
 
+    #[test]
+    fn test_from_field_messages() {
+        let messages = std::collections::HashMap::from([
+            ("email".to_owned(), "The email is not valid".to_owned()),
+            ("address.city".to_owned(), "The city is required".to_owned()),
+        ]);
+        let errors = ValidationErrors::from_field_messages(messages);
+
+        let errors = serde_json::to_value(errors).unwrap();
+        assert_eq!(
+            errors,
+            serde_json::json!({
+                "errors": [
+                    {
+                        "detail": "The city is required",
+                        "code": "invalid",
+                        "source": "body",
+                        "pointer": "/address/city",
+                    },
+                    {
+                        "detail": "The email is not valid",
+                        "code": "invalid",
+                        "source": "body",
+                        "pointer": "/email",
+                    },
+                ]
+            })
+        );
+    }
+
     #[test]
     fn test_problem_details_status_code() {
         let problem = ProblemDetails {
-            type_: "test_error".into(),
-            status: 400,
-            title: "Test Error".into(),
-            detail: "This is a test error".into(),
             extensions: Option::<()>::None,
+            ..ProblemDetails::new(
+                "test_error",
+                StatusCode::BAD_REQUEST,
+                "Test Error",
+                "This is a test error",
+            )
         };
 
         let response = problem.into_response();
         assert_eq!(response.status(), StatusCode::BAD_REQUEST);
     }
 
+    #[test]
+    fn test_problem_details_challenge() {
+        let challenge = HeaderValue::from_static(r#"Bearer realm="api""#);
+        let problem = ProblemDetails {
+            extensions: Option::<()>::None,
+            challenge: Some(challenge.clone()),
+            ..ProblemDetails::new(
+                "unauthorized",
+                StatusCode::UNAUTHORIZED,
+                "Unauthorized",
+                "Missing credentials",
+            )
+        };
+
+        let response = problem.into_response();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(response.headers()[WWW_AUTHENTICATE], challenge);
+    }
+
+    #[test]
+    fn test_content_location() {
+        let location = HeaderValue::from_static("/users/42");
+        let response = Conflict::new("/email", "The email address is already registered")
+            .content_location(location.clone())
+            .into_response();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+        assert_eq!(response.headers()[CONTENT_LOCATION], location);
+
+        let response =
+            Conflict::new("/email", "The email address is already registered").into_response();
+        assert!(response.headers().get(CONTENT_LOCATION).is_none());
+    }
+
+    #[test]
+    fn test_challenge_ignored_for_other_statuses() {
+        let problem = ProblemDetails {
+            extensions: Option::<()>::None,
+            challenge: Some(HeaderValue::from_static("Bearer")),
+            ..ProblemDetails::new(
+                "forbidden",
+                StatusCode::FORBIDDEN,
+                "Forbidden",
+                "Insufficient permissions",
+            )
+        };
+
+        let response = problem.into_response();
+        assert!(!response.headers().contains_key(WWW_AUTHENTICATE));
+    }
+
+    #[tokio::test]
+    async fn test_conflict() {
+        let problem = Conflict::new("/email", "The email address is already registered");
+        let (status, body) = crate::test_utils::problem(problem).await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(body["type"], "conflict");
+        assert_eq!(body["status"], 409);
+        assert_eq!(body["errors"][0]["code"], "conflict");
+        assert_eq!(body["errors"][0]["source"], "body");
+        assert_eq!(body["errors"][0]["pointer"], "/email");
+        assert_eq!(
+            body["errors"][0]["detail"],
+            "The email address is already registered"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_truncated_detail() {
+        let variants = (0..500)
+            .map(|i| format!("`variant{i}`"))
+            .collect::<Vec<_>>();
+        let detail = format!(
+            "unknown variant `x`, expected one of {}",
+            variants.join(", ")
+        );
+
+        let problem = Conflict::new("/kind", detail.clone()).configure(&Config::default());
+        let (_, body) = crate::test_utils::problem(problem).await;
+        let error = &body["errors"][0];
+        assert_eq!(error["truncated"], true);
+        let truncated = error["detail"].as_str().unwrap();
+        assert_eq!(truncated.chars().count(), 1025);
+        assert!(detail.starts_with(truncated.strip_suffix('…').unwrap()));
+
+        let config = Config::new().max_detail_length(10);
+        let problem = Conflict::new("/kind", "é".repeat(11)).configure(&config);
+        let (_, body) = crate::test_utils::problem(problem).await;
+        assert_eq!(body["errors"][0]["detail"], format!("{}…", "é".repeat(10)));
+        // The problem's own detail is truncated too.
+        assert_eq!(body["detail"], "The reques…");
+        assert_eq!(body["truncated"], true);
+
+        let problem = Conflict::new("/kind", "é".repeat(10)).configure(&config);
+        let (_, body) = crate::test_utils::problem(problem).await;
+        assert!(body["errors"][0].get("truncated").is_none());
+    }
+
+    fn ordered(order: ErrorOrder) -> Vec<(String, String)> {
+        let errors = [
+            ("/items/2", "out_of_range"),
+            ("/name", "invalid"),
+            ("/items/10", "invalid_value"),
+            ("", "missing_field"),
+            ("/email", "invalid_type"),
+        ]
+        .into_iter()
+        .map(|(pointer, code)| {
+            ValidationError::new(
+                Source::Body {
+                    pointer: Some(pointer.into()),
+                    key: false,
+                },
+                code,
+                String::new(),
+            )
+        })
+        .collect();
+        let errors = ValidationErrors {
+            errors,
+            by_pointer: false,
+        }
+        .configure(&Config::new().error_order(order));
+        errors
+            .errors
+            .iter()
+            .map(|error| (error.source.location().to_owned(), error.code.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_document_order() {
+        let pointers: Vec<_> = ordered(ErrorOrder::DocumentOrder)
+            .into_iter()
+            .map(|(pointer, _)| pointer)
+            .collect();
+        assert_eq!(pointers, ["/items/2", "/name", "/items/10", "", "/email"]);
+    }
+
+    #[test]
+    fn test_pointer_lexical_order() {
+        let pointers: Vec<_> = ordered(ErrorOrder::PointerLexical)
+            .into_iter()
+            .map(|(pointer, _)| pointer)
+            .collect();
+        assert_eq!(pointers, ["", "/email", "/items/10", "/items/2", "/name"]);
+    }
+
+    #[test]
+    fn test_severity_then_pointer_order() {
+        let codes: Vec<_> = ordered(ErrorOrder::SeverityThenPointer)
+            .into_iter()
+            .map(|(pointer, code)| format!("{code} {pointer}"))
+            .collect();
+        assert_eq!(
+            codes,
+            [
+                "missing_field ",
+                "invalid_type /email",
+                "invalid_value /items/10",
+                "out_of_range /items/2",
+                "invalid /name"
+            ]
+        );
+    }
+
+    #[cfg(feature = "opentelemetry")]
+    #[test]
+    fn test_trace_id() {
+        use opentelemetry::trace::noop::NoopTracer;
+        use opentelemetry::trace::{
+            SpanContext, SpanId, TraceContextExt, TraceFlags, TraceId, TraceState,
+        };
+        use tracing_opentelemetry::OpenTelemetrySpanExt;
+        use tracing_subscriber::layer::SubscriberExt;
+
+        let problem = || {
+            let problem = InvalidRequest::new(ValidationErrors {
+                errors: vec![],
+                by_pointer: false,
+            });
+            serde_json::to_value(problem.into_inner()).unwrap()
+        };
+
+        // No span, no trace ID.
+        assert!(problem().get("trace_id").is_none());
+
+        let subscriber = tracing_subscriber::registry()
+            .with(tracing_opentelemetry::layer().with_tracer(NoopTracer::new()));
+        tracing::subscriber::with_default(subscriber, || {
+            let trace_id = TraceId::from_hex("4bf92f3577b34da6a3ce929d0e0e4736").unwrap();
+            let parent = opentelemetry::Context::new().with_remote_span_context(SpanContext::new(
+                trace_id,
+                SpanId::from_hex("00f067aa0ba902b7").unwrap(),
+                TraceFlags::SAMPLED,
+                true,
+                TraceState::default(),
+            ));
+            let span = tracing::info_span!("request");
+            let _ = span.set_parent(parent);
+            let _guard = span.enter();
+            assert_eq!(problem()["trace_id"], "4bf92f3577b34da6a3ce929d0e0e4736");
+        });
+    }
+
+    #[tokio::test]
+    async fn test_not_found() {
+        let problem = NotFound::new("user", 42);
+        let (status, body) = crate::test_utils::problem(problem).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body["type"], "not_found");
+        assert_eq!(body["status"], 404);
+        assert_eq!(body["resource"], "user");
+        assert_eq!(body["id"], "42");
+        assert_eq!(body["detail"], "There is no user with id `42`");
+    }
+
     #[test]
     fn test_problem_details_internal_server_error_status() {
         let problem = ProblemDetails {
-            type_: "server_error".into(),
-            status: 500,
-            title: "Server Error".into(),
-            detail: "This is a server error".into(),
             extensions: Option::<()>::None,
+            ..ProblemDetails::new(
+                "server_error",
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Server Error",
+                "This is a server error",
+            )
         };
 
         let response = problem.into_response();
         assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
     }
+
+    #[tokio::test]
+    async fn test_configured_fallback() {
+        struct Unserializable;
+
+        impl serde::Serialize for Unserializable {
+            fn serialize<S: serde::Serializer>(&self, _: S) -> Result<S::Ok, S::Error> {
+                Err(serde::ser::Error::custom("boom"))
+            }
+        }
+
+        let problem = |config: &Config| {
+            ProblemDetails {
+                extensions: Some(Unserializable),
+                ..ProblemDetails::new("conflict", StatusCode::CONFLICT, "Conflict", "Unreachable")
+            }
+            .configure(config)
+        };
+
+        let (status, body) = crate::test_utils::problem(problem(&Config::new())).await;
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(body["title"], "Internal Server Error");
+
+        let config = Config::new().internal_server_error(
+            "Erreur interne",
+            "Un problème est survenu, veuillez réessayer plus tard.",
+        );
+        let response = problem(&config).into_response();
+        assert_eq!(response.headers()[CONTENT_TYPE], APPLICATION_PROBLEM_JSON);
+        let (status, body) = crate::test_utils::problem(response).await;
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(body["type"], "internal_server_error");
+        assert_eq!(body["status"], 500);
+        assert_eq!(body["title"], "Erreur interne");
+        assert_eq!(
+            body["detail"],
+            "Un problème est survenu, veuillez réessayer plus tard."
+        );
+    }
+
+    #[test]
+    fn test_classify_out_of_range() {
+        let (code, range) =
+            classify("invalid value: integer `300`, expected u8 at line 1 column 10");
+        assert_eq!(code, "out_of_range");
+        assert_eq!(range, Some(Range { min: 0, max: 255 }));
+
+        let (code, range) = classify("invalid value: integer `-1`, expected u32");
+        assert_eq!(code, "out_of_range");
+        assert_eq!(
+            range,
+            Some(Range {
+                min: 0,
+                max: u32::MAX as u128
+            })
+        );
+    }
+
+    #[test]
+    fn test_length() {
+        assert_eq!(
+            length("invalid length 2, expected an array of length 3 at line 1 column 10"),
+            Some(Length {
+                expected: 3,
+                actual: 2
+            })
+        );
+        assert_eq!(
+            length("invalid length 4, expected a tuple of size 2"),
+            Some(Length {
+                expected: 2,
+                actual: 4
+            })
+        );
+        assert_eq!(length("invalid length 4, expected fewer elements"), None);
+    }
+
+    #[test]
+    fn test_classify_invalid_type() {
+        let (code, range) =
+            classify(r#"invalid type: string "x", expected u8 at line 1 column 11"#);
+        assert_eq!(code, "invalid_type");
+        assert_eq!(range, None);
+
+        let (code, range) = classify("invalid value: string \"x\", expected a valid email");
+        assert_eq!(code, "invalid_value");
+        assert_eq!(range, None);
+    }
+
+    #[cfg(feature = "json")]
+    #[tokio::test]
+    async fn test_invalid_response() {
+        #[derive(eserde::Deserialize, Debug)]
+        #[allow(dead_code)]
+        struct Profile {
+            name: String,
+        }
+
+        let errors = eserde::json::from_str::<Profile>(r#"{"name": 42}"#).unwrap_err();
+        let problem = InvalidResponse::new(&errors);
+
+        let details = serde_json::to_value(&problem.0).unwrap();
+        assert_eq!(details["status"], 500);
+        assert_eq!(details["errors"][0]["source"], "response");
+        assert_eq!(details["errors"][0]["pointer"], "/name");
+        assert_eq!(details["errors"][0]["code"], "invalid_type");
+
+        let (status, body) = crate::test_utils::problem(problem).await;
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(body["type"], "internal_server_error");
+        assert!(body.get("errors").is_none());
+        assert!(!body.to_string().contains("name"));
+    }
+
+    #[cfg(all(feature = "cbor", feature = "json"))]
+    #[tokio::test]
+    async fn test_cbor_problem() {
+        use axum_core::extract::FromRequest;
+        use http_body_util::BodyExt;
+
+        #[derive(eserde::Deserialize, Debug)]
+        #[allow(dead_code)]
+        struct Profile {
+            name: String,
+        }
+
+        let request = crate::test_utils::json_request(r#"{"name": 42}"#)
+            .header("accept", "application/problem+cbor");
+        let rejection = crate::Json::<Profile>::from_request(request.body(), &())
+            .await
+            .unwrap_err();
+        let response = rejection.into_response();
+        assert_eq!(response.headers()[CONTENT_TYPE], "application/problem+cbor");
+        assert_eq!(response.headers()[VARY], "Accept");
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let body: serde_json::Value = ciborium::from_reader(&body[..]).unwrap();
+        assert_eq!(body["status"], 400);
+        assert_eq!(body["type"], "invalid_request");
+        assert_eq!(body["errors"][0]["pointer"], "/name");
+    }
+
+    #[cfg(feature = "cbor")]
+    #[test]
+    fn test_prefers_cbor() {
+        let prefers_cbor = |accept: &'static str| {
+            let mut headers = http::HeaderMap::new();
+            headers.insert(http::header::ACCEPT, HeaderValue::from_static(accept));
+            super::prefers_cbor(&headers)
+        };
+        assert!(prefers_cbor("application/problem+cbor"));
+        assert!(prefers_cbor("application/cbor, */*"));
+        assert!(prefers_cbor(
+            "application/json;q=0.5, application/problem+cbor"
+        ));
+        assert!(!prefers_cbor(
+            "application/problem+json, application/problem+cbor"
+        ));
+        assert!(!prefers_cbor("application/problem+cbor;q=0, */*"));
+        assert!(!prefers_cbor("*/*"));
+    }
+
+    #[tokio::test]
+    async fn test_problem_type_header() {
+        use http_body_util::BodyExt;
+
+        let config = Config::new()
+            .problem_type_header(true)
+            .type_prefix("https://errors.example.com/");
+        let response = NotFound::new("user", 42).configure(&config).into_response();
+        let header = response.headers()["x-problem-type"].clone();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(header, "https://errors.example.com/not_found");
+        assert_eq!(body["type"], header.to_str().unwrap());
+
+        // Off by default.
+        let response = NotFound::new("user", 42).into_response();
+        assert!(response.headers().get("x-problem-type").is_none());
+    }
 }
@@ -0,0 +1,500 @@
+//! Extract and validate the `Content-Disposition` header of a request.
+//!
+//! See [`ContentDisposition`] for more details.
+use axum_core::extract::{FromRequestParts, OptionalFromRequestParts};
+use axum_core::response::{IntoResponse, Response};
+use http::header::CONTENT_DISPOSITION;
+use http::request::Parts;
+
+use crate::config::Config;
+use crate::details::{InvalidRequest, Source, ValidationError, ValidationErrors};
+
+#[doc(hidden)]
+macro_rules! __log_rejection {
+    (
+        rejection_type = $ty:ident,
+        status = $status:expr,
+    ) => {
+        {
+            tracing::event!(
+                target: "eserde_axum::disposition::rejection",
+                tracing::Level::TRACE,
+                status = $status.as_u16(),
+                rejection_type = ::std::any::type_name::<$ty>(),
+                "rejecting request",
+            );
+        }
+    };
+}
+
+/// Extractor for the `Content-Disposition` header of an upload, as defined by
+/// [RFC 6266](https://www.rfc-editor.org/rfc/rfc6266).
+///
+/// The filename is taken from the `filename*` parameter, if present, decoding its
+/// [RFC 5987](https://www.rfc-editor.org/rfc/rfc5987) encoded value (UTF-8 or ISO-8859-1),
+/// and from the `filename` parameter otherwise.
+/// Filenames are only accepted if they are safe to use as the name of a file:
+/// they can't be empty, contain path separators (`/` or `\`) or control characters,
+/// nor be `.` or `..`.
+///
+/// The request will be rejected (and a [`ContentDispositionRejection`] will be returned)
+/// if the header is missing or if it's malformed.
+/// Use `Option<ContentDisposition>` if the header is optional.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{routing::put, Router};
+/// use eserde_axum::ContentDisposition;
+///
+/// async fn upload(disposition: ContentDisposition) -> String {
+///     match disposition.filename() {
+///         Some(filename) => format!("Storing {filename}"),
+///         None => "Storing an unnamed file".into(),
+///     }
+/// }
+///
+/// let app = Router::new().route("/uploads", put(upload));
+/// # let _: Router = app;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContentDisposition {
+    disposition: String,
+    filename: Option<String>,
+}
+
+impl ContentDisposition {
+    /// The disposition type, in lowercase—e.g. `attachment` or `inline`.
+    pub fn disposition(&self) -> &str {
+        &self.disposition
+    }
+
+    /// Returns `true` if the disposition type is `attachment`.
+    pub fn is_attachment(&self) -> bool {
+        self.disposition == "attachment"
+    }
+
+    /// The name of the uploaded file, if the client sent one.
+    pub fn filename(&self) -> Option<&str> {
+        self.filename.as_deref()
+    }
+
+    /// Parse the value of a `Content-Disposition` header.
+    ///
+    /// Returns the reason why the value is malformed on failure.
+    fn parse(value: &str) -> Result<Self, &'static str> {
+        let mut cursor = Cursor(value);
+        let disposition = cursor.token();
+        if disposition.is_empty() {
+            return Err("the disposition type is missing");
+        }
+        let mut filename = None;
+        let mut extended_filename = None;
+        loop {
+            cursor.skip_whitespace();
+            if cursor.0.is_empty() {
+                break;
+            }
+            if !cursor.eat(';') {
+                return Err("parameters must be separated by `;`");
+            }
+            cursor.skip_whitespace();
+            // A trailing `;` is tolerated.
+            if cursor.0.is_empty() {
+                break;
+            }
+            let name = cursor.token();
+            cursor.skip_whitespace();
+            if name.is_empty() || !cursor.eat('=') {
+                return Err("parameters must have a name and a value");
+            }
+            cursor.skip_whitespace();
+            if name.eq_ignore_ascii_case("filename*") {
+                let value = decode_ext_value(cursor.token())
+                    .ok_or("the `filename*` parameter is not a valid RFC 5987 encoded value")?;
+                if extended_filename.replace(value).is_some() {
+                    return Err("the `filename*` parameter is repeated");
+                }
+            } else {
+                let value = match cursor.quoted_string() {
+                    Some(value) => value?,
+                    None => cursor.token().to_owned(),
+                };
+                if name.eq_ignore_ascii_case("filename") && filename.replace(value).is_some() {
+                    return Err("the `filename` parameter is repeated");
+                }
+            }
+        }
+        // `filename*` takes precedence, as recommended by RFC 6266.
+        let filename = extended_filename.or(filename);
+        if let Some(filename) = &filename {
+            check_filename(filename)?;
+        }
+        Ok(Self {
+            disposition: disposition.to_ascii_lowercase(),
+            filename,
+        })
+    }
+}
+
+/// The unparsed portion of a header value.
+struct Cursor<'a>(&'a str);
+
+impl<'a> Cursor<'a> {
+    fn skip_whitespace(&mut self) {
+        self.0 = self.0.trim_start_matches([' ', '\t']);
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        match self.0.strip_prefix(c) {
+            Some(rest) => {
+                self.0 = rest;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Consume a (possibly empty) token, as defined by
+    /// [RFC 9110](https://www.rfc-editor.org/rfc/rfc9110#name-tokens).
+    fn token(&mut self) -> &'a str {
+        let end = self
+            .0
+            .find(|c: char| !(c.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(c)))
+            .unwrap_or(self.0.len());
+        let (token, rest) = self.0.split_at(end);
+        self.0 = rest;
+        token
+    }
+
+    /// Consume a quoted string, resolving its escapes.
+    ///
+    /// Returns `None` if the value doesn't start with a quote.
+    fn quoted_string(&mut self) -> Option<Result<String, &'static str>> {
+        let rest = self.0.strip_prefix('"')?;
+        let mut value = String::new();
+        let mut chars = rest.char_indices();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '"' => {
+                    self.0 = &rest[i + 1..];
+                    return Some(Ok(value));
+                }
+                '\\' => match chars.next() {
+                    Some((_, escaped)) => value.push(escaped),
+                    None => break,
+                },
+                c => value.push(c),
+            }
+        }
+        Some(Err("a quoted parameter value is not terminated"))
+    }
+}
+
+/// Decode an RFC 5987 `ext-value`, e.g. `UTF-8''%e2%82%ac%20rates.pdf`.
+fn decode_ext_value(value: &str) -> Option<String> {
+    let mut parts = value.splitn(3, '\'');
+    let (charset, _language, encoded) = (parts.next()?, parts.next()?, parts.next()?);
+    let mut bytes = Vec::with_capacity(encoded.len());
+    let mut encoded = encoded.bytes();
+    while let Some(b) = encoded.next() {
+        if b == b'%' {
+            let hex = [encoded.next()?, encoded.next()?];
+            let hex = std::str::from_utf8(&hex).ok()?;
+            bytes.push(u8::from_str_radix(hex, 16).ok()?);
+        } else {
+            bytes.push(b);
+        }
+    }
+    if charset.eq_ignore_ascii_case("utf-8") {
+        String::from_utf8(bytes).ok()
+    } else if charset.eq_ignore_ascii_case("iso-8859-1") {
+        // ISO-8859-1 maps each byte to the Unicode code point with the same value.
+        Some(bytes.into_iter().map(char::from).collect())
+    } else {
+        None
+    }
+}
+
+/// Check that `filename` can be safely used as the name of a file.
+fn check_filename(filename: &str) -> Result<(), &'static str> {
+    if filename.is_empty() {
+        Err("the filename is empty")
+    } else if filename.contains(['/', '\\']) || filename == "." || filename == ".." {
+        Err("the filename must not be a path")
+    } else if filename.chars().any(char::is_control) {
+        Err("the filename must not contain control characters")
+    } else {
+        Ok(())
+    }
+}
+
+impl<S> FromRequestParts<S> for ContentDisposition
+where
+    S: Send + Sync,
+{
+    type Rejection = ContentDispositionRejection;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        match <Self as OptionalFromRequestParts<S>>::from_request_parts(parts, state).await? {
+            Some(disposition) => Ok(disposition),
+            None => Err(MissingContentDisposition {
+                config: Config::from_request(parts),
+            }
+            .into()),
+        }
+    }
+}
+
+impl<S> OptionalFromRequestParts<S> for ContentDisposition
+where
+    S: Send + Sync,
+{
+    type Rejection = ContentDispositionRejection;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        _state: &S,
+    ) -> Result<Option<Self>, Self::Rejection> {
+        let Some(value) = parts.headers.get(CONTENT_DISPOSITION) else {
+            return Ok(None);
+        };
+        // Clients often send UTF-8 filenames in the clear, rather than via `filename*`.
+        let parsed = std::str::from_utf8(value.as_bytes())
+            .map_err(|_| "the header is not valid UTF-8")
+            .and_then(Self::parse);
+        match parsed {
+            Ok(disposition) => Ok(Some(disposition)),
+            Err(reason) => Err(InvalidContentDisposition {
+                actual: String::from_utf8_lossy(value.as_bytes()).into_owned(),
+                reason,
+                config: Config::from_request(parts),
+            }
+            .into()),
+        }
+    }
+}
+
+fn content_disposition_error(detail: String, code: &'static str) -> ValidationError {
+    ValidationError::new(
+        Source::Header {
+            name: "Content-Disposition".into(),
+        },
+        code,
+        detail,
+    )
+}
+
+#[derive(Debug)]
+#[non_exhaustive]
+/// Rejection type for [`ContentDisposition`] used if the `Content-Disposition`
+/// header is missing.
+pub struct MissingContentDisposition {
+    pub(crate) config: Config,
+}
+
+impl IntoResponse for MissingContentDisposition {
+    fn into_response(self) -> Response {
+        let error = content_disposition_error(
+            "Expected request with a `Content-Disposition` header, but none was found".into(),
+            "missing_content_disposition",
+        );
+        let response = InvalidRequest::new(ValidationErrors {
+            errors: vec![error],
+            by_pointer: false,
+        })
+        .configure(&self.config);
+        __log_rejection!(
+            rejection_type = MissingContentDisposition,
+            status = InvalidRequest::status(),
+        );
+        response.into_response()
+    }
+}
+
+impl std::fmt::Display for MissingContentDisposition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Expected request with a `Content-Disposition` header")
+    }
+}
+
+impl std::error::Error for MissingContentDisposition {}
+
+#[derive(Debug)]
+#[non_exhaustive]
+/// Rejection type for [`ContentDisposition`] used if the `Content-Disposition` header
+/// is malformed or carries an unsafe filename.
+pub struct InvalidContentDisposition {
+    pub(crate) actual: String,
+    pub(crate) reason: &'static str,
+    pub(crate) config: Config,
+}
+
+impl IntoResponse for InvalidContentDisposition {
+    fn into_response(self) -> Response {
+        let error = content_disposition_error(
+            format!(
+                "Expected a valid `Content-Disposition` header (e.g. `attachment; filename=\"report.pdf\"`), but found `{}`: {}",
+                self.actual, self.reason
+            ),
+            "invalid_content_disposition",
+        );
+        let response = InvalidRequest::new(ValidationErrors {
+            errors: vec![error],
+            by_pointer: false,
+        })
+        .configure(&self.config);
+        __log_rejection!(
+            rejection_type = InvalidContentDisposition,
+            status = InvalidRequest::status(),
+        );
+        response.into_response()
+    }
+}
+
+impl std::fmt::Display for InvalidContentDisposition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Expected a valid `Content-Disposition` header, but found `{}`: {}",
+            self.actual, self.reason
+        )
+    }
+}
+
+impl std::error::Error for InvalidContentDisposition {}
+
+/// Rejection used for [`ContentDisposition`].
+///
+/// Contains one variant for each way the [`ContentDisposition`] extractor
+/// can fail.
+///
+/// All error responses follow the problem details specification,
+/// as outlined in [RFC 9457](https://www.rfc-editor.org/rfc/rfc9457.html).
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ContentDispositionRejection {
+    #[allow(missing_docs)]
+    MissingContentDisposition(MissingContentDisposition),
+    #[allow(missing_docs)]
+    InvalidContentDisposition(InvalidContentDisposition),
+}
+
+impl IntoResponse for ContentDispositionRejection {
+    fn into_response(self) -> Response {
+        match self {
+            Self::MissingContentDisposition(inner) => inner.into_response(),
+            Self::InvalidContentDisposition(inner) => inner.into_response(),
+        }
+    }
+}
+
+impl From<MissingContentDisposition> for ContentDispositionRejection {
+    fn from(inner: MissingContentDisposition) -> Self {
+        Self::MissingContentDisposition(inner)
+    }
+}
+impl From<InvalidContentDisposition> for ContentDispositionRejection {
+    fn from(inner: InvalidContentDisposition) -> Self {
+        Self::InvalidContentDisposition(inner)
+    }
+}
+impl std::fmt::Display for ContentDispositionRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingContentDisposition(inner) => write!(f, "{inner}"),
+            Self::InvalidContentDisposition(inner) => write!(f, "{inner}"),
+        }
+    }
+}
+impl std::error::Error for ContentDispositionRejection {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::MissingContentDisposition(inner) => inner.source(),
+            Self::InvalidContentDisposition(inner) => inner.source(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils;
+
+    async fn extract(
+        value: &'static str,
+    ) -> Result<ContentDisposition, ContentDispositionRejection> {
+        let (mut parts, _) = http::Request::builder()
+            .header(CONTENT_DISPOSITION, value)
+            .body(())
+            .unwrap()
+            .into_parts();
+        <ContentDisposition as FromRequestParts<()>>::from_request_parts(&mut parts, &()).await
+    }
+
+    #[tokio::test]
+    async fn test_plain_filename() {
+        let disposition = extract(r#"Attachment; filename="annual \"report\".pdf""#)
+            .await
+            .unwrap();
+        assert!(disposition.is_attachment());
+        assert_eq!(disposition.filename(), Some(r#"annual "report".pdf"#));
+
+        let disposition = extract("inline; filename=logo.png").await.unwrap();
+        assert_eq!(disposition.disposition(), "inline");
+        assert_eq!(disposition.filename(), Some("logo.png"));
+    }
+
+    #[tokio::test]
+    async fn test_encoded_filename() {
+        let disposition = extract(
+            "attachment; filename=\"EUR rates.pdf\"; filename*=UTF-8''%e2%82%ac%20rates.pdf",
+        )
+        .await
+        .unwrap();
+        assert_eq!(disposition.filename(), Some("€ rates.pdf"));
+
+        let disposition = extract("attachment; filename*=iso-8859-1'en'%A3%20rates.pdf")
+            .await
+            .unwrap();
+        assert_eq!(disposition.filename(), Some("£ rates.pdf"));
+    }
+
+    #[tokio::test]
+    async fn test_malformed() {
+        for value in [
+            "",
+            "attachment filename=a.pdf",
+            "attachment; filename=\"a.pdf",
+            "attachment; filename*=UTF-8''%zz.pdf",
+            "attachment; filename=\"../../etc/passwd\"",
+        ] {
+            let rejection = extract(value).await.unwrap_err();
+            assert!(
+                matches!(
+                    rejection,
+                    ContentDispositionRejection::InvalidContentDisposition(_)
+                ),
+                "{value}"
+            );
+
+            let (status, body) = test_utils::problem(rejection).await;
+            assert_eq!(status, 400);
+            assert_eq!(body["errors"][0]["source"], "header");
+            assert_eq!(body["errors"][0]["name"], "Content-Disposition");
+            assert_eq!(body["errors"][0]["code"], "invalid_content_disposition");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_missing() {
+        let (mut parts, _) = http::Request::new(()).into_parts();
+        let disposition = <ContentDisposition as OptionalFromRequestParts<()>>::from_request_parts(
+            &mut parts,
+            &(),
+        )
+        .await
+        .unwrap();
+        assert!(disposition.is_none());
+    }
+}
@@ -0,0 +1,277 @@
+use std::ops::Deref;
+use std::ops::DerefMut;
+
+use super::*;
+use crate::config::Config;
+use axum_core::extract::FromRequest;
+use axum_core::extract::Request;
+use eserde::EDeserialize;
+use http::header::HeaderMap;
+use serde::de::DeserializeOwned;
+
+/// URL encoded form extractor.
+///
+/// It deserializes `application/x-www-form-urlencoded` request bodies into some type that
+/// implements [`serde::de::DeserializeOwned`] and [`eserde::EDeserialize`].
+/// The request will be rejected (and a [`FormRejection`] will be returned) if:
+///
+/// - The request doesn't have a `Content-Type: application/x-www-form-urlencoded` header.
+/// - The body couldn't be deserialized into the target type.
+/// - Buffering the request body fails.
+///
+/// Repeated keys (e.g. `tag=a&tag=b`), as sent by HTML forms for multi-valued inputs,
+/// are collected into sequence fields—e.g. a `Vec<T>`.
+/// Errors for individual values point at their position within the field.
+///
+/// Keys using the bracket syntax (e.g. `user[name]=Alice&user[age]=30`) can be nested
+/// under their parent key, to deserialize into nested structs, via
+/// [`Config::nested_form_fields`].
+///
+/// ⚠️ Since parsing the form requires consuming the request body, the `Form` extractor must be
+/// *last* if there are multiple extractors in a handler.
+/// See ["the order of extractors"][order-of-extractors]
+///
+/// [order-of-extractors]: https://docs.rs/axum/latest/axum/extract/index.html#the-order-of-extractors
+///
+/// See [`FormRejection`] for more details.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{routing::post, Router};
+/// use eserde_axum::Form;
+///
+/// #[derive(eserde::Deserialize)]
+/// struct CreatePost {
+///     title: String,
+///     #[serde(rename = "tag", default)]
+///     tags: Vec<String>,
+/// }
+///
+/// async fn create_post(Form(payload): Form<CreatePost>) {
+///     // payload is a `CreatePost`
+/// }
+///
+/// let app = Router::new().route("/posts", post(create_post));
+/// # let _: Router = app;
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(docsrs, doc(cfg(feature = "form")))]
+#[must_use]
+pub struct Form<T>(pub T);
+
+impl<T, S> FromRequest<S> for Form<T>
+where
+    T: DeserializeOwned,
+    T: for<'de> EDeserialize<'de>,
+    S: Send + Sync,
+{
+    type Rejection = FormRejection;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let config = Config::from_request(&req);
+        check_form_content_type(req.headers(), &config)?;
+        let (bytes, _permit) =
+            crate::body::read_body::<_, Self::Rejection>(req, state, &config).await?;
+        Self::from_bytes_with_config(&bytes, &config)
+    }
+}
+
+/// Check that the `Content-Type` header is set to `application/x-www-form-urlencoded`.
+///
+/// Return an error otherwise.
+fn check_form_content_type(headers: &HeaderMap, config: &Config) -> Result<(), FormRejection> {
+    let missing = || {
+        MissingFormContentType {
+            config: config.clone(),
+        }
+        .into()
+    };
+    let count = headers.get_all(http::header::CONTENT_TYPE).iter().count();
+    if count > 1 {
+        return Err(AmbiguousFormContentType {
+            count,
+            config: config.clone(),
+        }
+        .into());
+    }
+    let Some(content_type) = headers.get(http::header::CONTENT_TYPE) else {
+        return Err(missing());
+    };
+    let Ok(content_type) = content_type.to_str() else {
+        return Err(missing());
+    };
+
+    let is_form_content_type = content_type
+        .parse::<mime::Mime>()
+        .is_ok_and(|mime| mime.essence_str() == mime::APPLICATION_WWW_FORM_URLENCODED.as_ref());
+    if !is_form_content_type {
+        return Err(FormContentTypeMismatch {
+            actual: content_type.to_string(),
+            config: config.clone(),
+        }
+        .into());
+    }
+    Ok(())
+}
+
+impl<T> Deref for Form<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Form<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T> From<T> for Form<T> {
+    fn from(inner: T) -> Self {
+        Self(inner)
+    }
+}
+
+impl<T> Form<T>
+where
+    T: DeserializeOwned,
+    T: for<'de> EDeserialize<'de>,
+{
+    /// Construct a `Form<T>` from a byte slice. Most users should prefer to use the `FromRequest` impl
+    /// but special cases may require first extracting a `Request` into `Bytes` then optionally
+    /// constructing a `Form<T>`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, FormRejection> {
+        Self::from_bytes_with_config(bytes, &Config::default())
+    }
+
+    /// Like [`from_bytes`](Self::from_bytes), but honoring the settings
+    /// specified in the [`Config`].
+    pub(crate) fn from_bytes_with_config(
+        bytes: &[u8],
+        config: &Config,
+    ) -> Result<Self, FormRejection> {
+        let result = if config.settings.nested_form_fields {
+            eserde::urlencoded::from_bytes_nested(bytes)
+        } else {
+            eserde::urlencoded::from_bytes(bytes)
+        };
+        match result {
+            Ok(value) => Ok(Form(value)),
+            Err(errors) => Err(FormError::new(errors, config.clone(), bytes).into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils;
+
+    #[derive(Debug, PartialEq, eserde::Deserialize)]
+    struct Post {
+        title: String,
+        #[serde(rename = "tag")]
+        tags: Vec<u32>,
+    }
+
+    #[tokio::test]
+    async fn test_repeated_keys() {
+        let request = test_utils::form_request("title=Hello&tag=1&tag=2");
+        let Form(post) = Form::<Post>::from_request(request.body(), &())
+            .await
+            .unwrap();
+        assert_eq!(
+            post,
+            Post {
+                title: "Hello".into(),
+                tags: vec![1, 2],
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_repeated_keys_error() {
+        let request = test_utils::form_request("title=Hello&tag=1&tag=x");
+        let rejection = Form::<Post>::from_request(request.body(), &())
+            .await
+            .unwrap_err();
+        assert!(matches!(rejection, FormRejection::FormError(_)));
+
+        let (status, body) = test_utils::problem(rejection).await;
+        assert_eq!(status, 400);
+        let error = &body["errors"][0];
+        assert_eq!(error["source"], "form");
+        assert_eq!(error["field"], "tag");
+        assert_eq!(error["index"], 1);
+        assert_eq!(error["code"], "invalid_type");
+    }
+
+    #[tokio::test]
+    async fn test_nested_fields() {
+        #[derive(Debug, PartialEq, eserde::Deserialize)]
+        struct Signup {
+            user: User,
+        }
+
+        #[derive(Debug, PartialEq, eserde::Deserialize)]
+        struct User {
+            name: String,
+            age: u8,
+        }
+
+        // Brackets are taken literally by default.
+        let rejection = Form::<Signup>::from_bytes(b"user[name]=Alice&user[age]=30").unwrap_err();
+        assert!(matches!(rejection, FormRejection::FormError(_)));
+
+        let config = Config::new().nested_form_fields(true);
+        let Form(signup) =
+            Form::<Signup>::from_bytes_with_config(b"user[name]=Alice&user[age]=30", &config)
+                .unwrap();
+        assert_eq!(
+            signup,
+            Signup {
+                user: User {
+                    name: "Alice".into(),
+                    age: 30
+                }
+            }
+        );
+
+        let request = test_utils::form_request("user[name]=A&user[age]=x").extension(config);
+        let rejection = Form::<Signup>::from_request(request.body(), &())
+            .await
+            .unwrap_err();
+        let (status, body) = test_utils::problem(rejection).await;
+        assert_eq!(status, 400);
+        let errors = body["errors"].as_array().unwrap();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0]["source"], "form");
+        assert_eq!(errors[0]["field"], "user[age]");
+        assert!(errors[0].get("index").is_none());
+        assert_eq!(errors[0]["code"], "invalid_type");
+    }
+
+    #[tokio::test]
+    async fn test_content_type_mismatch() {
+        let request = test_utils::json_request(r#"{"title": "Hello"}"#);
+        let rejection = Form::<Post>::from_request(request.body(), &())
+            .await
+            .unwrap_err();
+        let FormRejection::FormContentTypeMismatch(mismatch) = &rejection else {
+            panic!("Expected a content type mismatch, got {rejection:?}");
+        };
+        assert_eq!(mismatch.received(), "application/json");
+
+        let (status, body) = test_utils::problem(rejection).await;
+        assert_eq!(status, 415);
+        assert_eq!(body["type"], "unsupported_media_type");
+        assert_eq!(
+            body["expected"],
+            serde_json::json!(["application/x-www-form-urlencoded"])
+        );
+        assert_eq!(body["received"], "application/json");
+    }
+}
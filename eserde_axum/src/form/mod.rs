@@ -0,0 +1,7 @@
+//! Supporting types for the [`Form`] extractor.
+mod form_;
+mod rejections;
+
+#[doc(hidden)]
+pub use form_::Form;
+pub use rejections::*;
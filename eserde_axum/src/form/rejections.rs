@@ -0,0 +1,370 @@
+use axum_core::extract::rejection::BytesRejection;
+use eserde::DeserializationErrors;
+use http::{header::CONTENT_TYPE, StatusCode};
+
+use crate::config::Config;
+use crate::details::{
+    bytes_rejection_response, InvalidRequest, ProblemDetails, Source, UnsupportedMediaType,
+    ValidationError, ValidationErrors,
+};
+
+#[doc(hidden)]
+macro_rules! __log_rejection {
+    (
+        rejection_type = $ty:ident,
+        status = $status:expr,
+    ) => {
+        {
+            tracing::event!(
+                target: "eserde_axum::form::rejection",
+                tracing::Level::TRACE,
+                status = $status.as_u16(),
+                rejection_type = ::std::any::type_name::<$ty>(),
+                "rejecting request",
+            );
+        }
+    };
+    (
+        rejection_type = $ty:ident,
+        status = $status:expr,
+        config = $config:expr,
+    ) => {
+        {
+            let (method, target) = $config.request_target();
+            tracing::event!(
+                target: "eserde_axum::form::rejection",
+                tracing::Level::TRACE,
+                status = $status.as_u16(),
+                rejection_type = ::std::any::type_name::<$ty>(),
+                http.method = method,
+                http.target = target,
+                "rejecting request",
+            );
+        }
+    };
+}
+
+#[derive(Debug)]
+#[cfg_attr(docsrs, doc(cfg(feature = "form")))]
+/// Rejection type for [`Form`](super::Form).
+///
+/// This rejection is used if the request body couldn't be deserialized
+/// into the target type.
+pub struct FormError {
+    pub(crate) errors: DeserializationErrors,
+    pub(crate) config: Config,
+    pub(crate) debug_raw: Option<String>,
+}
+
+impl FormError {
+    pub(crate) fn new(errors: DeserializationErrors, config: Config, body: &[u8]) -> Self {
+        Self {
+            debug_raw: config.debug_raw(body),
+            errors,
+            config,
+        }
+    }
+
+    pub(crate) fn validation_errors(&self) -> ValidationErrors {
+        let errors = self.errors.iter().map(ValidationError::form).collect();
+        ValidationErrors {
+            errors,
+            by_pointer: false,
+        }
+    }
+}
+
+impl axum_core::response::IntoResponse for FormError {
+    fn into_response(self) -> axum_core::response::Response {
+        let response = InvalidRequest::new(self.validation_errors())
+            .debug_raw(self.debug_raw)
+            .configure(&self.config);
+        __log_rejection!(
+            rejection_type = FormError,
+            status = InvalidRequest::status(),
+            config = self.config,
+        );
+        response.into_response()
+    }
+}
+
+impl std::fmt::Display for FormError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Failed to deserialize the request form body into the target schema:\n")?;
+        for e in self.errors.iter() {
+            writeln!(f, "- {}", e)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for FormError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.errors)
+    }
+}
+
+#[derive(Debug, Default)]
+#[non_exhaustive]
+#[cfg_attr(docsrs, doc(cfg(feature = "form")))]
+/// Rejection type for [`Form`](super::Form) used if the `Content-Type`
+/// header is missing.
+pub struct MissingFormContentType {
+    pub(crate) config: Config,
+}
+
+impl axum_core::response::IntoResponse for MissingFormContentType {
+    fn into_response(self) -> axum_core::response::Response {
+        let error = ValidationError::new(
+            Source::Header {
+                name: CONTENT_TYPE.as_str().into(),
+            },
+            "missing_content_type",
+            "Expected request with `Content-Type: application/x-www-form-urlencoded`, but no `Content-Type` header was found",
+        );
+        let response = InvalidRequest::new(ValidationErrors {
+            errors: vec![error],
+            by_pointer: false,
+        })
+        .configure(&self.config);
+        __log_rejection!(
+            rejection_type = MissingFormContentType,
+            status = InvalidRequest::status(),
+        );
+        response.into_response()
+    }
+}
+
+impl std::fmt::Display for MissingFormContentType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Expected request with `Content-Type: application/x-www-form-urlencoded`"
+        )
+    }
+}
+
+impl std::error::Error for MissingFormContentType {}
+
+#[derive(Debug)]
+#[non_exhaustive]
+#[cfg_attr(docsrs, doc(cfg(feature = "form")))]
+/// Rejection type for [`Form`](super::Form) used if the `Content-Type`
+/// header has an incorrect value.
+///
+/// It results in a `415 Unsupported Media Type` problem, listing the accepted
+/// media types in the `expected` member and the header value in the `received` one.
+pub struct FormContentTypeMismatch {
+    pub(crate) actual: String,
+    pub(crate) config: Config,
+}
+
+/// The media types accepted by [`Form`](super::Form), as listed in a [`FormContentTypeMismatch`] problem.
+const EXPECTED_CONTENT_TYPES: &[&str] = &["application/x-www-form-urlencoded"];
+
+impl FormContentTypeMismatch {
+    /// The media types accepted by the extractor.
+    pub fn expected(&self) -> &'static [&'static str] {
+        EXPECTED_CONTENT_TYPES
+    }
+
+    /// The value of the `Content-Type` header of the request.
+    pub fn received(&self) -> &str {
+        &self.actual
+    }
+}
+
+impl axum_core::response::IntoResponse for FormContentTypeMismatch {
+    fn into_response(self) -> axum_core::response::Response {
+        let message = self.to_string();
+        let response = UnsupportedMediaType::mismatch(
+            EXPECTED_CONTENT_TYPES,
+            self.actual,
+            message,
+            "The request body must be URL-encoded form data",
+        )
+        .configure(&self.config);
+        __log_rejection!(
+            rejection_type = FormContentTypeMismatch,
+            status = UnsupportedMediaType::status(),
+        );
+        response.into_response()
+    }
+}
+
+impl std::fmt::Display for FormContentTypeMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Expected request with `Content-Type: application/x-www-form-urlencoded`, but found `{}`",
+            self.actual
+        )
+    }
+}
+
+impl std::error::Error for FormContentTypeMismatch {}
+
+#[derive(Debug)]
+#[non_exhaustive]
+#[cfg_attr(docsrs, doc(cfg(feature = "form")))]
+/// Rejection type for [`Form`](super::Form) used if the request has
+/// more than one `Content-Type` header, e.g. because of a misconfigured proxy.
+pub struct AmbiguousFormContentType {
+    pub(crate) count: usize,
+    pub(crate) config: Config,
+}
+
+impl axum_core::response::IntoResponse for AmbiguousFormContentType {
+    fn into_response(self) -> axum_core::response::Response {
+        let status = StatusCode::BAD_REQUEST;
+        let problem = ProblemDetails::ambiguous_content_type(self.count).configure(&self.config);
+        let problem = ProblemDetails {
+            extensions: problem
+                .extensions
+                .map(|errors| errors.configure(&self.config)),
+            ..problem
+        };
+        __log_rejection!(rejection_type = AmbiguousFormContentType, status = status,);
+        problem.into_response()
+    }
+}
+
+impl std::fmt::Display for AmbiguousFormContentType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Expected a single `Content-Type` header, but found {}",
+            self.count
+        )
+    }
+}
+
+impl std::error::Error for AmbiguousFormContentType {}
+
+/// Rejection used for [`Form`](super::Form).
+///
+/// Contains one variant for each way the [`Form`](super::Form) extractor
+/// can fail.
+///
+/// All error responses follow the problem details specification,
+/// as outlined in [RFC 9457](https://www.rfc-editor.org/rfc/rfc9457.html).
+#[derive(Debug)]
+#[non_exhaustive]
+#[cfg_attr(docsrs, doc(cfg(feature = "form")))]
+pub enum FormRejection {
+    #[allow(missing_docs)]
+    FormError(FormError),
+    #[allow(missing_docs)]
+    MissingFormContentType(MissingFormContentType),
+    #[allow(missing_docs)]
+    AmbiguousFormContentType(AmbiguousFormContentType),
+    #[allow(missing_docs)]
+    FormContentTypeMismatch(FormContentTypeMismatch),
+    #[allow(missing_docs)]
+    BytesRejection(BytesRejection),
+    #[allow(missing_docs)]
+    ContentTooLarge(crate::body::ContentTooLarge),
+    #[allow(missing_docs)]
+    Overloaded(crate::body::Overloaded),
+    #[allow(missing_docs)]
+    InvalidContentLength(crate::body::InvalidContentLength),
+    #[cfg(feature = "timeout")]
+    #[allow(missing_docs)]
+    RequestTimeout(crate::timeout::RequestTimeout),
+}
+
+impl axum_core::response::IntoResponse for FormRejection {
+    fn into_response(self) -> axum_core::response::Response {
+        match self {
+            Self::FormError(inner) => inner.into_response(),
+            Self::MissingFormContentType(inner) => inner.into_response(),
+            Self::AmbiguousFormContentType(inner) => inner.into_response(),
+            Self::FormContentTypeMismatch(inner) => inner.into_response(),
+            Self::BytesRejection(inner) => bytes_rejection_response(inner),
+            Self::ContentTooLarge(inner) => inner.into_response(),
+            Self::Overloaded(inner) => inner.into_response(),
+            Self::InvalidContentLength(inner) => inner.into_response(),
+            #[cfg(feature = "timeout")]
+            Self::RequestTimeout(inner) => inner.into_response(),
+        }
+    }
+}
+
+impl From<FormError> for FormRejection {
+    fn from(inner: FormError) -> Self {
+        Self::FormError(inner)
+    }
+}
+impl From<MissingFormContentType> for FormRejection {
+    fn from(inner: MissingFormContentType) -> Self {
+        Self::MissingFormContentType(inner)
+    }
+}
+impl From<AmbiguousFormContentType> for FormRejection {
+    fn from(inner: AmbiguousFormContentType) -> Self {
+        Self::AmbiguousFormContentType(inner)
+    }
+}
+impl From<FormContentTypeMismatch> for FormRejection {
+    fn from(inner: FormContentTypeMismatch) -> Self {
+        Self::FormContentTypeMismatch(inner)
+    }
+}
+impl From<BytesRejection> for FormRejection {
+    fn from(inner: BytesRejection) -> Self {
+        Self::BytesRejection(inner)
+    }
+}
+impl From<crate::body::ContentTooLarge> for FormRejection {
+    fn from(inner: crate::body::ContentTooLarge) -> Self {
+        Self::ContentTooLarge(inner)
+    }
+}
+impl From<crate::body::Overloaded> for FormRejection {
+    fn from(inner: crate::body::Overloaded) -> Self {
+        Self::Overloaded(inner)
+    }
+}
+impl From<crate::body::InvalidContentLength> for FormRejection {
+    fn from(inner: crate::body::InvalidContentLength) -> Self {
+        Self::InvalidContentLength(inner)
+    }
+}
+#[cfg(feature = "timeout")]
+impl From<crate::timeout::RequestTimeout> for FormRejection {
+    fn from(inner: crate::timeout::RequestTimeout) -> Self {
+        Self::RequestTimeout(inner)
+    }
+}
+impl std::fmt::Display for FormRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::FormError(inner) => write!(f, "{inner}"),
+            Self::MissingFormContentType(inner) => write!(f, "{inner}"),
+            Self::AmbiguousFormContentType(inner) => write!(f, "{inner}"),
+            Self::FormContentTypeMismatch(inner) => write!(f, "{inner}"),
+            Self::BytesRejection(inner) => write!(f, "{inner}"),
+            Self::ContentTooLarge(inner) => write!(f, "{inner}"),
+            Self::Overloaded(inner) => write!(f, "{inner}"),
+            Self::InvalidContentLength(inner) => write!(f, "{inner}"),
+            #[cfg(feature = "timeout")]
+            Self::RequestTimeout(inner) => write!(f, "{inner}"),
+        }
+    }
+}
+impl std::error::Error for FormRejection {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::FormError(inner) => inner.source(),
+            Self::MissingFormContentType(inner) => inner.source(),
+            Self::AmbiguousFormContentType(inner) => inner.source(),
+            Self::FormContentTypeMismatch(inner) => inner.source(),
+            Self::BytesRejection(inner) => inner.source(),
+            Self::ContentTooLarge(inner) => inner.source(),
+            Self::Overloaded(inner) => inner.source(),
+            Self::InvalidContentLength(inner) => inner.source(),
+            #[cfg(feature = "timeout")]
+            Self::RequestTimeout(inner) => inner.source(),
+        }
+    }
+}
@@ -0,0 +1,152 @@
+//! Deserialize human-readable durations and byte sizes, e.g. `"30s"` or `"10MB"`.
+//!
+//! The functions in this module are meant to be used with
+//! `#[serde(deserialize_with = "...")]`:
+//!
+//! ```rust
+//! use std::time::Duration;
+//!
+//! #[derive(eserde::Deserialize)]
+//! struct Settings {
+//!     #[serde(deserialize_with = "eserde_axum::humanize::duration")]
+//!     timeout: Duration,
+//!     #[serde(deserialize_with = "eserde_axum::humanize::byte_size")]
+//!     max_upload_size: bytesize::ByteSize,
+//! }
+//! ```
+//!
+//! When the input doesn't match the expected format, the reported error
+//! explains which format is accepted and is classified with the `invalid_format` code.
+use std::fmt;
+
+use serde::de::{Deserializer, Error, Visitor};
+
+use crate::details::INVALID_FORMAT;
+
+/// Deserialize a [`Duration`](std::time::Duration) from a human-readable string,
+/// e.g. `"30s"`, `"5m"` or `"1h 30m"`.
+///
+/// Check out [`humantime::parse_duration`] for the full list of supported units.
+#[cfg(feature = "humantime")]
+#[cfg_attr(docsrs, doc(cfg(feature = "humantime")))]
+pub fn duration<'de, D>(deserializer: D) -> Result<std::time::Duration, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    struct DurationVisitor;
+
+    impl Visitor<'_> for DurationVisitor {
+        type Value = std::time::Duration;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a human-readable duration, e.g. `30s`, `5m` or `1h 30m`")
+        }
+
+        fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+        where
+            E: Error,
+        {
+            humantime::parse_duration(v).map_err(|e| {
+                E::custom(format_args!(
+                    "{INVALID_FORMAT}expected a duration made of a number followed by a unit \
+                    (e.g. `30s`, `5m` or `1h 30m`), found `{v}` ({e})"
+                ))
+            })
+        }
+    }
+
+    deserializer.deserialize_str(DurationVisitor)
+}
+
+/// Deserialize a [`ByteSize`](bytesize::ByteSize) from a human-readable string,
+/// e.g. `"512KiB"` or `"10MB"`.
+///
+/// A plain integer is interpreted as a number of bytes.
+#[cfg(feature = "bytesize")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bytesize")))]
+pub fn byte_size<'de, D>(deserializer: D) -> Result<bytesize::ByteSize, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    struct ByteSizeVisitor;
+
+    impl Visitor<'_> for ByteSizeVisitor {
+        type Value = bytesize::ByteSize;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a human-readable byte size, e.g. `512KiB` or `10MB`")
+        }
+
+        fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+        where
+            E: Error,
+        {
+            Ok(bytesize::ByteSize::b(v))
+        }
+
+        fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+        where
+            E: Error,
+        {
+            v.parse().map_err(|_| {
+                E::custom(format_args!(
+                    "{INVALID_FORMAT}expected a byte size made of a number followed by a unit \
+                    (`B`, `KB`, `KiB`, `MB`, `MiB`, `GB`, `GiB`, ...), found `{v}`"
+                ))
+            })
+        }
+    }
+
+    deserializer.deserialize_any(ByteSizeVisitor)
+}
+
+#[cfg(all(test, feature = "humantime", feature = "bytesize"))]
+mod tests {
+    use crate::Json;
+
+    #[derive(Debug, eserde::Deserialize)]
+    #[allow(dead_code)]
+    struct Settings {
+        #[serde(deserialize_with = "super::duration")]
+        timeout: std::time::Duration,
+        #[serde(deserialize_with = "super::byte_size")]
+        size: bytesize::ByteSize,
+    }
+
+    fn errors(body: &[u8]) -> serde_json::Value {
+        let Err(crate::JsonRejection::JsonError(error)) = Json::<Settings>::from_bytes(body) else {
+            panic!("Expected a deserialization error");
+        };
+        serde_json::to_value(error.validation_errors()).unwrap()["errors"].take()
+    }
+
+    #[test]
+    fn test_valid() {
+        let Json(settings) =
+            Json::<Settings>::from_bytes(br#"{"timeout": "1m 30s", "size": "10MB"}"#).unwrap();
+        assert_eq!(settings.timeout, std::time::Duration::from_secs(90));
+        assert_eq!(settings.size, bytesize::ByteSize::mb(10));
+    }
+
+    #[test]
+    fn test_bad_duration() {
+        let errors = errors(br#"{"timeout": "30x", "size": 1024}"#);
+        assert_eq!(errors.as_array().unwrap().len(), 1);
+        assert_eq!(errors[0]["code"], "invalid_format");
+        assert_eq!(errors[0]["pointer"], "/timeout");
+        let detail = errors[0]["detail"].as_str().unwrap();
+        assert!(detail.contains("`30s`, `5m` or `1h 30m`"), "{detail}");
+        assert!(detail.contains("found `30x`"), "{detail}");
+    }
+
+    #[test]
+    fn test_bad_byte_size() {
+        let errors = errors(br#"{"timeout": "30s", "size": "10QB"}"#);
+        assert_eq!(errors.as_array().unwrap().len(), 1);
+        assert_eq!(errors[0]["code"], "invalid_format");
+        assert_eq!(errors[0]["pointer"], "/size");
+        let detail = errors[0]["detail"].as_str().unwrap();
+        assert!(detail.contains("`MiB`"), "{detail}");
+        assert!(detail.contains("found `10QB`"), "{detail}");
+    }
+}
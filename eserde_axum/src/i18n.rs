@@ -0,0 +1,386 @@
+//! Localize the validation errors reported by the extractors of this crate,
+//! and attach headers to problem responses depending on their type.
+//!
+//! See [`Catalog`] for more details.
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::{FluentArgs, FluentResource};
+use fluent_langneg::{negotiate_languages, parse_accepted_languages, NegotiationStrategy};
+use http::{HeaderName, HeaderValue};
+use unic_langid::LanguageIdentifier;
+
+/// A set of [Fluent](https://projectfluent.org/) bundles, one per locale, used to
+/// translate the `detail` of validation errors.
+///
+/// Each message is looked up by the `code` of the error (e.g. `invalid_type`).
+/// The original, English, detail is available to the message as the `$detail` variable.
+/// The locale is negotiated against the `Accept-Language` header of the request:
+/// errors keep their original detail if the header is missing, if none of the
+/// requested locales is available or if the chosen bundle lacks a message for their code.
+///
+/// Install the catalog via [`Config::catalog`](crate::Config::catalog).
+/// Embed the bundles at build time, with [`include_str!`], to avoid any runtime I/O:
+///
+/// ```rust,no_run
+/// use axum::{routing::post, Extension, Router};
+/// use eserde_axum::i18n::Catalog;
+/// use eserde_axum::{Config, Json};
+///
+/// #[derive(eserde::Deserialize)]
+/// struct CreateUser {
+///     email: String,
+/// }
+///
+/// async fn create_user(Json(payload): Json<CreateUser>) {}
+///
+/// // Usually `include_str!("../locales/fr.ftl")`.
+/// const FR: &str = "missing_field = Un champ obligatoire est manquant ({ $detail })";
+///
+/// let catalog = Catalog::new()
+///     .add_locale("fr", FR)
+///     .expect("Invalid French bundle");
+/// let app = Router::new()
+///     .route("/users", post(create_user))
+///     .layer(Extension(Config::new().catalog(catalog)));
+/// # let _: Router = app;
+/// ```
+///
+/// The catalog also lists the headers to attach to the problem responses of a given
+/// `type`, see [`Catalog::header`].
+#[cfg_attr(docsrs, doc(cfg(feature = "i18n")))]
+#[derive(Default)]
+pub struct Catalog {
+    locales: Vec<LanguageIdentifier>,
+    /// The bundle for each entry of `locales`, in the same order.
+    bundles: Vec<FluentBundle<FluentResource>>,
+    /// The headers to attach to problem responses, by problem type.
+    headers: HashMap<Cow<'static, str>, Vec<(HeaderName, HeaderValue)>>,
+}
+
+impl Catalog {
+    /// Create an empty catalog.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the messages of a Fluent bundle for the given locale, e.g. `fr` or `pt-BR`.
+    ///
+    /// A locale can be specified multiple times, to split its messages across multiple
+    /// bundles. Fails if `locale` isn't a valid language identifier, if `source` isn't
+    /// valid Fluent syntax or if it redefines a message already defined for `locale`.
+    pub fn add_locale(
+        mut self,
+        locale: &str,
+        source: impl Into<String>,
+    ) -> Result<Self, CatalogError> {
+        let Ok(id) = locale.parse::<LanguageIdentifier>() else {
+            return Err(CatalogError::InvalidLocale {
+                locale: locale.to_owned(),
+            });
+        };
+        let resource =
+            FluentResource::try_new(source.into()).map_err(|(_, errors)| CatalogError::Syntax {
+                locale: locale.to_owned(),
+                details: errors
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join("; "),
+            })?;
+        let position = match self.locales.iter().position(|l| *l == id) {
+            Some(position) => position,
+            None => {
+                let mut bundle = FluentBundle::new_concurrent(vec![id.clone()]);
+                // Isolation marks are meant for rendering, not for JSON payloads.
+                bundle.set_use_isolating(false);
+                self.locales.push(id);
+                self.bundles.push(bundle);
+                self.locales.len() - 1
+            }
+        };
+        self.bundles[position]
+            .add_resource(resource)
+            .map_err(|errors| CatalogError::Duplicate {
+                locale: locale.to_owned(),
+                details: errors
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join("; "),
+            })?;
+        Ok(self)
+    }
+
+    /// Attach a header to every problem response of the given `type`,
+    /// e.g. `Retry-After` for `too_many_requests` problems.
+    ///
+    /// Register the same `type` multiple times to attach several headers.
+    /// The headers are matched against the original type of the problem,
+    /// even if [`Config::about_blank`](crate::Config::about_blank) is enabled.
+    ///
+    /// ```rust
+    /// use eserde_axum::i18n::Catalog;
+    /// use http::header::{CONNECTION, RETRY_AFTER};
+    /// use http::HeaderValue;
+    ///
+    /// let catalog = Catalog::new()
+    ///     .header("too_many_requests", RETRY_AFTER, HeaderValue::from_static("60"))
+    ///     .header("content_too_large", CONNECTION, HeaderValue::from_static("close"));
+    /// # let _ = catalog;
+    /// ```
+    pub fn header(
+        mut self,
+        type_: impl Into<Cow<'static, str>>,
+        name: HeaderName,
+        value: HeaderValue,
+    ) -> Self {
+        self.headers
+            .entry(type_.into())
+            .or_default()
+            .push((name, value));
+        self
+    }
+
+    /// The headers registered for the given problem type, if any.
+    pub(crate) fn headers(&self, type_: &str) -> Option<&[(HeaderName, HeaderValue)]> {
+        self.headers.get(type_).map(Vec::as_slice)
+    }
+
+    /// Returns `true` if at least one locale has been added,
+    /// i.e. if responses may depend on `Accept-Language`.
+    pub(crate) fn localizes(&self) -> bool {
+        !self.locales.is_empty()
+    }
+
+    /// Negotiate the locales listed in `accept_language` against the available ones,
+    /// once for all the errors of a response.
+    pub(crate) fn localizer(&self, accept_language: &str) -> Localizer<'_> {
+        let requested = parse_accepted_languages(accept_language);
+        let bundles = negotiate_languages(
+            &requested,
+            &self.locales,
+            None,
+            NegotiationStrategy::Filtering,
+        )
+        .into_iter()
+        .filter_map(|locale| {
+            let position = self.locales.iter().position(|l| l == locale)?;
+            Some(&self.bundles[position])
+        })
+        .collect();
+        Localizer { bundles }
+    }
+}
+
+/// The bundles of a [`Catalog`] matching the `Accept-Language` header of a request,
+/// from the best match to the worst one.
+pub(crate) struct Localizer<'a> {
+    bundles: Vec<&'a FluentBundle<FluentResource>>,
+}
+
+impl Localizer<'_> {
+    /// Translate the detail of an error with the given `code`, using the best
+    /// bundle that has a message for it.
+    pub(crate) fn localize(&self, code: &str, detail: &str) -> Option<String> {
+        self.bundles.iter().find_map(|bundle| {
+            let pattern = bundle.get_message(code)?.value()?;
+            let mut args = FluentArgs::new();
+            args.set("detail", detail);
+            let mut errors = Vec::new();
+            let localized = bundle.format_pattern(pattern, Some(&args), &mut errors);
+            errors.is_empty().then(|| Cow::into_owned(localized))
+        })
+    }
+}
+
+impl std::fmt::Debug for Catalog {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Catalog")
+            .field("locales", &self.locales)
+            .field("headers", &self.headers)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The ways adding a bundle to a [`Catalog`] can fail.
+#[derive(Debug)]
+#[non_exhaustive]
+#[cfg_attr(docsrs, doc(cfg(feature = "i18n")))]
+pub enum CatalogError {
+    /// The locale isn't a valid language identifier.
+    InvalidLocale {
+        /// The offending locale.
+        locale: String,
+    },
+    /// The bundle isn't valid Fluent syntax.
+    Syntax {
+        /// The locale of the bundle.
+        locale: String,
+        /// The problems reported by the Fluent parser.
+        details: String,
+    },
+    /// The bundle redefines messages already defined for its locale.
+    Duplicate {
+        /// The locale of the bundle.
+        locale: String,
+        /// The redefined messages.
+        details: String,
+    },
+}
+
+impl std::fmt::Display for CatalogError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidLocale { locale } => write!(f, "`{locale}` is not a valid locale"),
+            Self::Syntax { locale, details } => {
+                write!(
+                    f,
+                    "The bundle for `{locale}` is not valid Fluent syntax: {details}"
+                )
+            }
+            Self::Duplicate { locale, details } => {
+                write!(
+                    f,
+                    "The bundle for `{locale}` redefines existing messages: {details}"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for CatalogError {}
+
+#[cfg(all(test, feature = "json"))]
+mod tests {
+    use axum_core::extract::FromRequest;
+    use axum_core::response::IntoResponse;
+    use http::header::VARY;
+
+    use super::*;
+    use crate::{test_utils, Config, Json, NotFound, TooManyRequests};
+
+    const FR: &str = "
+invalid_type = Le type de la valeur est incorrect ({ $detail })
+";
+
+    #[derive(Debug, eserde::Deserialize)]
+    #[allow(dead_code)]
+    struct User {
+        name: String,
+        age: u8,
+    }
+
+    fn config() -> Config {
+        Config::new().catalog(Catalog::new().add_locale("fr", FR).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_localized_detail() {
+        let request = test_utils::json_request(r#"{"age": "30"}"#)
+            .header("accept-language", "fr-CH, fr;q=0.9, en;q=0.8")
+            .extension(config());
+        let rejection = Json::<User>::from_request(request.body(), &())
+            .await
+            .unwrap_err();
+
+        let (_, body) = test_utils::problem(rejection).await;
+        let errors = body["errors"].as_array().unwrap();
+        assert_eq!(errors[0]["code"], "invalid_type");
+        assert_eq!(
+            errors[0]["detail"],
+            "Le type de la valeur est incorrect (invalid type: string \"30\", expected u8 at line 1 column 12)"
+        );
+        // There is no French message for missing fields.
+        assert_eq!(errors[1]["code"], "missing_field");
+        assert_eq!(errors[1]["detail"], "missing field `name`");
+    }
+
+    #[tokio::test]
+    async fn test_unavailable_locale() {
+        let request = test_utils::json_request(r#"{"name": "Alice", "age": "30"}"#)
+            .header("accept-language", "de")
+            .extension(config());
+        let rejection = Json::<User>::from_request(request.body(), &())
+            .await
+            .unwrap_err();
+
+        let (_, body) = test_utils::problem(rejection).await;
+        assert!(body["errors"][0]["detail"]
+            .as_str()
+            .unwrap()
+            .starts_with("invalid type"));
+    }
+
+    #[tokio::test]
+    async fn test_vary_header() {
+        let request = test_utils::json_request(r#"{"age": "30"}"#)
+            .header("accept-language", "fr")
+            .extension(config());
+        let rejection = Json::<User>::from_request(request.body(), &())
+            .await
+            .unwrap_err();
+        let response = rejection.into_response();
+        assert_eq!(response.headers()[VARY], "Accept-Language");
+
+        // The response doesn't depend on `Accept-Language` without a catalog.
+        let request = test_utils::json_request(r#"{"age": "30"}"#).header("accept-language", "fr");
+        let rejection = Json::<User>::from_request(request.body(), &())
+            .await
+            .unwrap_err();
+        let response = rejection.into_response();
+        assert!(response
+            .headers()
+            .get_all(VARY)
+            .iter()
+            .all(|vary| vary != "Accept-Language"));
+    }
+
+    #[tokio::test]
+    async fn test_problem_headers() {
+        let catalog = Catalog::new().header(
+            "too_many_requests",
+            http::header::RETRY_AFTER,
+            HeaderValue::from_static("120"),
+        );
+        let config = Config::new().catalog(catalog);
+        let response = TooManyRequests::new().configure(&config).into_response();
+        assert_eq!(response.status(), 429);
+        assert_eq!(response.headers()[http::header::RETRY_AFTER], "120");
+        // Without locales, the response doesn't depend on `Accept-Language`.
+        assert!(response
+            .headers()
+            .get_all(VARY)
+            .iter()
+            .all(|vary| vary != "Accept-Language"));
+
+        // Other problem types don't get the header.
+        let response = NotFound::new("user", 42).configure(&config).into_response();
+        assert!(response.headers().get(http::header::RETRY_AFTER).is_none());
+
+        // The original type is matched, even if it's replaced with `about:blank`.
+        let config = config.about_blank(true);
+        let response = TooManyRequests::new().configure(&config).into_response();
+        assert_eq!(response.headers()[http::header::RETRY_AFTER], "120");
+    }
+
+    #[test]
+    fn test_invalid_bundle() {
+        assert!(matches!(
+            Catalog::new().add_locale("not a locale!", FR),
+            Err(CatalogError::InvalidLocale { .. })
+        ));
+        assert!(matches!(
+            Catalog::new().add_locale("fr", "invalid_type ="),
+            Err(CatalogError::Syntax { .. })
+        ));
+        assert!(matches!(
+            Catalog::new()
+                .add_locale("fr", FR)
+                .unwrap()
+                .add_locale("fr", FR),
+            Err(CatalogError::Duplicate { .. })
+        ));
+    }
+}
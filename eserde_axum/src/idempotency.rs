@@ -0,0 +1,324 @@
+//! Extract and validate the `Idempotency-Key` header of a request.
+//!
+//! See [`IdempotencyKey`] for more details.
+use axum_core::extract::{FromRequestParts, OptionalFromRequestParts};
+use axum_core::response::{IntoResponse, Response};
+use http::header::HeaderName;
+use http::request::Parts;
+use uuid::Uuid;
+
+use crate::config::Config;
+use crate::details::{InvalidRequest, Source, ValidationError, ValidationErrors};
+
+#[doc(hidden)]
+macro_rules! __log_rejection {
+    (
+        rejection_type = $ty:ident,
+        status = $status:expr,
+    ) => {
+        {
+            tracing::event!(
+                target: "eserde_axum::idempotency::rejection",
+                tracing::Level::TRACE,
+                status = $status.as_u16(),
+                rejection_type = ::std::any::type_name::<$ty>(),
+                "rejecting request",
+            );
+        }
+    };
+}
+
+/// The `Idempotency-Key` request header, as defined by the
+/// [IETF draft](https://datatracker.ietf.org/doc/draft-ietf-httpapi-idempotency-key-header/).
+const IDEMPOTENCY_KEY: HeaderName = HeaderName::from_static("idempotency-key");
+
+/// Extractor for the key sent via the `Idempotency-Key` header, used by clients
+/// to safely retry non-idempotent requests (e.g. `POST`).
+///
+/// The key must be a UUID, in any of the formats accepted by [`Uuid::parse_str`],
+/// e.g. `67e55044-10b1-426f-9247-bb680e5fe0c8`.
+/// The request will be rejected (and an [`IdempotencyKeyRejection`] will be returned)
+/// if the header is missing or if it isn't a valid UUID.
+/// Use `Option<IdempotencyKey>` if the header is optional.
+///
+/// Detecting replays is up to the handler: the extractor only hands over the parsed key.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{routing::post, Router};
+/// use eserde_axum::IdempotencyKey;
+///
+/// async fn create_payment(key: IdempotencyKey) -> String {
+///     // Look up the key before charging the customer...
+///     format!("Processing payment {}", key.uuid())
+/// }
+///
+/// let app = Router::new().route("/payments", post(create_payment));
+/// # let _: Router = app;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct IdempotencyKey(pub Uuid);
+
+impl IdempotencyKey {
+    /// The key sent by the client.
+    pub fn uuid(&self) -> Uuid {
+        self.0
+    }
+}
+
+impl From<IdempotencyKey> for Uuid {
+    fn from(key: IdempotencyKey) -> Self {
+        key.0
+    }
+}
+
+impl<S> FromRequestParts<S> for IdempotencyKey
+where
+    S: Send + Sync,
+{
+    type Rejection = IdempotencyKeyRejection;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        match <Self as OptionalFromRequestParts<S>>::from_request_parts(parts, state).await? {
+            Some(key) => Ok(key),
+            None => Err(MissingIdempotencyKey {
+                config: Config::from_request(parts),
+            }
+            .into()),
+        }
+    }
+}
+
+impl<S> OptionalFromRequestParts<S> for IdempotencyKey
+where
+    S: Send + Sync,
+{
+    type Rejection = IdempotencyKeyRejection;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        _state: &S,
+    ) -> Result<Option<Self>, Self::Rejection> {
+        let Some(value) = parts.headers.get(IDEMPOTENCY_KEY) else {
+            return Ok(None);
+        };
+        let key = value
+            .to_str()
+            .ok()
+            .and_then(|value| Uuid::parse_str(value.trim()).ok());
+        match key {
+            Some(key) => Ok(Some(Self(key))),
+            None => Err(InvalidIdempotencyKey {
+                actual: String::from_utf8_lossy(value.as_bytes()).into_owned(),
+                config: Config::from_request(parts),
+            }
+            .into()),
+        }
+    }
+}
+
+fn idempotency_key_error(detail: String, code: &'static str) -> ValidationError {
+    ValidationError::new(
+        Source::Header {
+            name: "Idempotency-Key".into(),
+        },
+        code,
+        detail,
+    )
+}
+
+#[derive(Debug)]
+#[non_exhaustive]
+/// Rejection type for [`IdempotencyKey`] used if the `Idempotency-Key` header is missing.
+pub struct MissingIdempotencyKey {
+    pub(crate) config: Config,
+}
+
+impl IntoResponse for MissingIdempotencyKey {
+    fn into_response(self) -> Response {
+        let error = idempotency_key_error(
+            "Expected request with an `Idempotency-Key` header, but none was found".into(),
+            "missing_idempotency_key",
+        );
+        let response = InvalidRequest::new(ValidationErrors {
+            errors: vec![error],
+            by_pointer: false,
+        })
+        .configure(&self.config);
+        __log_rejection!(
+            rejection_type = MissingIdempotencyKey,
+            status = InvalidRequest::status(),
+        );
+        response.into_response()
+    }
+}
+
+impl std::fmt::Display for MissingIdempotencyKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Expected request with an `Idempotency-Key` header")
+    }
+}
+
+impl std::error::Error for MissingIdempotencyKey {}
+
+#[derive(Debug)]
+#[non_exhaustive]
+/// Rejection type for [`IdempotencyKey`] used if the `Idempotency-Key` header
+/// is not a valid UUID.
+pub struct InvalidIdempotencyKey {
+    pub(crate) actual: String,
+    pub(crate) config: Config,
+}
+
+impl IntoResponse for InvalidIdempotencyKey {
+    fn into_response(self) -> Response {
+        let error = idempotency_key_error(
+            format!(
+                "Expected an `Idempotency-Key` header holding a UUID (e.g. `67e55044-10b1-426f-9247-bb680e5fe0c8`), but found `{}`",
+                self.actual
+            ),
+            "invalid_idempotency_key",
+        );
+        let response = InvalidRequest::new(ValidationErrors {
+            errors: vec![error],
+            by_pointer: false,
+        })
+        .configure(&self.config);
+        __log_rejection!(
+            rejection_type = InvalidIdempotencyKey,
+            status = InvalidRequest::status(),
+        );
+        response.into_response()
+    }
+}
+
+impl std::fmt::Display for InvalidIdempotencyKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Expected an `Idempotency-Key` header holding a UUID, but found `{}`",
+            self.actual
+        )
+    }
+}
+
+impl std::error::Error for InvalidIdempotencyKey {}
+
+/// Rejection used for [`IdempotencyKey`].
+///
+/// Contains one variant for each way the [`IdempotencyKey`] extractor
+/// can fail.
+///
+/// All error responses follow the problem details specification,
+/// as outlined in [RFC 9457](https://www.rfc-editor.org/rfc/rfc9457.html).
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum IdempotencyKeyRejection {
+    #[allow(missing_docs)]
+    MissingIdempotencyKey(MissingIdempotencyKey),
+    #[allow(missing_docs)]
+    InvalidIdempotencyKey(InvalidIdempotencyKey),
+}
+
+impl IntoResponse for IdempotencyKeyRejection {
+    fn into_response(self) -> Response {
+        match self {
+            Self::MissingIdempotencyKey(inner) => inner.into_response(),
+            Self::InvalidIdempotencyKey(inner) => inner.into_response(),
+        }
+    }
+}
+
+impl From<MissingIdempotencyKey> for IdempotencyKeyRejection {
+    fn from(inner: MissingIdempotencyKey) -> Self {
+        Self::MissingIdempotencyKey(inner)
+    }
+}
+impl From<InvalidIdempotencyKey> for IdempotencyKeyRejection {
+    fn from(inner: InvalidIdempotencyKey) -> Self {
+        Self::InvalidIdempotencyKey(inner)
+    }
+}
+impl std::fmt::Display for IdempotencyKeyRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingIdempotencyKey(inner) => write!(f, "{inner}"),
+            Self::InvalidIdempotencyKey(inner) => write!(f, "{inner}"),
+        }
+    }
+}
+impl std::error::Error for IdempotencyKeyRejection {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::MissingIdempotencyKey(inner) => inner.source(),
+            Self::InvalidIdempotencyKey(inner) => inner.source(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils;
+
+    async fn extract(
+        value: Option<&'static str>,
+    ) -> Result<IdempotencyKey, IdempotencyKeyRejection> {
+        let mut request = http::Request::builder();
+        if let Some(value) = value {
+            request = request.header(IDEMPOTENCY_KEY, value);
+        }
+        let (mut parts, _) = request.body(()).unwrap().into_parts();
+        <IdempotencyKey as FromRequestParts<()>>::from_request_parts(&mut parts, &()).await
+    }
+
+    #[tokio::test]
+    async fn test_valid() {
+        let key = extract(Some("67e55044-10b1-426f-9247-bb680e5fe0c8"))
+            .await
+            .unwrap();
+        assert_eq!(
+            key.uuid(),
+            Uuid::parse_str("67e55044-10b1-426f-9247-bb680e5fe0c8").unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_malformed() {
+        for value in ["", "not-a-uuid", "67e55044-10b1-426f-9247"] {
+            let rejection = extract(Some(value)).await.unwrap_err();
+            assert!(
+                matches!(rejection, IdempotencyKeyRejection::InvalidIdempotencyKey(_)),
+                "{value}"
+            );
+
+            let (status, body) = test_utils::problem(rejection).await;
+            assert_eq!(status, 400);
+            assert_eq!(body["errors"][0]["source"], "header");
+            assert_eq!(body["errors"][0]["name"], "Idempotency-Key");
+            assert_eq!(body["errors"][0]["code"], "invalid_idempotency_key");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_missing() {
+        let (mut parts, _) = http::Request::new(()).into_parts();
+        let key =
+            <IdempotencyKey as OptionalFromRequestParts<()>>::from_request_parts(&mut parts, &())
+                .await
+                .unwrap();
+        assert!(key.is_none());
+
+        let rejection = extract(None).await.unwrap_err();
+        assert!(matches!(
+            rejection,
+            IdempotencyKeyRejection::MissingIdempotencyKey(_)
+        ));
+
+        let (status, body) = test_utils::problem(rejection).await;
+        assert_eq!(status, 400);
+        assert_eq!(body["errors"][0]["name"], "Idempotency-Key");
+        assert_eq!(body["errors"][0]["code"], "missing_idempotency_key");
+    }
+}
@@ -0,0 +1,115 @@
+//! The errors shared by the extractors deserializing a batch of JSON documents,
+//! e.g. [`NdJson`](crate::NdJson) and [`MultipartJsonBatch`](crate::MultipartJsonBatch).
+use eserde::{DeserializationErrors, EDeserialize};
+
+use crate::config::Config;
+
+/// A document of a batch that couldn't be deserialized, located by its position `P`
+/// within the batch.
+///
+/// It carries the errors reported by `eserde` rather than the validation errors
+/// of a problem response: like [`JsonError::errors`](super::JsonError::errors), they
+/// can be inspected by the handler, and a single document can have several of them.
+#[derive(Debug)]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "ndjson", feature = "multipart"))))]
+pub struct DocumentError<P> {
+    position: P,
+    errors: DeserializationErrors,
+}
+
+impl<P> DocumentError<P> {
+    /// The errors encountered while deserializing the document.
+    pub fn errors(&self) -> &DeserializationErrors {
+        &self.errors
+    }
+}
+
+/// The number of a line of an [`NdJson`](crate::NdJson) body, starting from 1.
+#[derive(Debug, Clone, Copy)]
+#[cfg(feature = "ndjson")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ndjson")))]
+pub struct LineNumber(usize);
+
+#[cfg(feature = "ndjson")]
+impl DocumentError<LineNumber> {
+    pub(crate) fn line_error(line: usize, errors: DeserializationErrors) -> Self {
+        Self {
+            position: LineNumber(line),
+            errors,
+        }
+    }
+
+    /// The number of the offending line, starting from 1.
+    pub fn line(&self) -> usize {
+        self.position.0
+    }
+}
+
+#[cfg(feature = "ndjson")]
+impl std::fmt::Display for LineNumber {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}", self.0)
+    }
+}
+
+/// The index of a part of a [`MultipartJsonBatch`](crate::MultipartJsonBatch) body,
+/// starting from 0.
+#[derive(Debug, Clone, Copy)]
+#[cfg(feature = "multipart")]
+#[cfg_attr(docsrs, doc(cfg(feature = "multipart")))]
+pub struct PartIndex(usize);
+
+#[cfg(feature = "multipart")]
+impl DocumentError<PartIndex> {
+    pub(crate) fn part_error(index: usize, errors: DeserializationErrors) -> Self {
+        Self {
+            position: PartIndex(index),
+            errors,
+        }
+    }
+
+    /// The index of the offending part, starting from 0.
+    pub fn index(&self) -> usize {
+        self.position.0
+    }
+}
+
+#[cfg(feature = "multipart")]
+impl std::fmt::Display for PartIndex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "part {}", self.0)
+    }
+}
+
+impl<P: std::fmt::Display> std::fmt::Display for DocumentError<P> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Failed to deserialize {}:", self.position)?;
+        for e in self.errors.iter() {
+            writeln!(f, "- {}", e)?;
+        }
+        Ok(())
+    }
+}
+
+impl<P: std::fmt::Debug + std::fmt::Display> std::error::Error for DocumentError<P> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.errors)
+    }
+}
+
+/// Deserialize a document of a batch, honoring [`Config::fail_fast`].
+///
+/// Like [`Json`](super::Json), it fails if anything but whitespace follows the document.
+pub(crate) fn deserialize_document<'a, T>(
+    bytes: &'a [u8],
+    config: &Config,
+) -> Result<T, DeserializationErrors>
+where
+    T: EDeserialize<'a>,
+{
+    if config.settings.fail_fast {
+        eserde::json::from_slice_fail_fast(bytes)
+    } else {
+        eserde::json::from_slice(bytes)
+    }
+}
@@ -0,0 +1,122 @@
+use axum_core::extract::{FromRequest, Request};
+use eserde::EDeserialize;
+use serde::de::DeserializeOwned;
+
+use super::{Json, JsonError, JsonRejection};
+
+/// JSON extractor falling back to `T::default()` if the request body doesn't
+/// deserialize into a `T`, e.g. for resilient ingestion endpoints.
+///
+/// The errors that would have been reported by [`Json<T>`] (including syntax errors)
+/// are handed over to the handler instead, to be logged or reported out of band.
+/// Requests are still rejected, with the same [`JsonRejection`]s as [`Json<T>`], if
+/// the problem lies outside of the content of the body: a missing `Content-Type`,
+/// a body beyond the configured limits, data trailing the JSON document, etc.
+///
+/// ⚠️ Since parsing JSON requires consuming the request body, the `BestEffort`
+/// extractor must be *last* if there are multiple extractors in a handler.
+/// See ["the order of extractors"][order-of-extractors]
+///
+/// [order-of-extractors]: https://docs.rs/axum/latest/axum/extract/index.html#the-order-of-extractors
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{routing::post, Router};
+/// use eserde_axum::json::BestEffort;
+///
+/// #[derive(Default, eserde::Deserialize)]
+/// struct Reading {
+///     sensor: String,
+///     celsius: f64,
+/// }
+///
+/// async fn ingest(BestEffort(reading, errors): BestEffort<Reading>) {
+///     if let Some(errors) = errors {
+///         tracing::warn!(%errors, "storing a blank reading");
+///     }
+///     # let _ = reading;
+/// }
+///
+/// let app = Router::new().route("/readings", post(ingest));
+/// # let _: Router = app;
+/// ```
+#[derive(Debug, Default)]
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+#[must_use]
+pub struct BestEffort<T>(pub T, pub Option<JsonError>);
+
+impl<T, S> FromRequest<S> for BestEffort<T>
+where
+    T: DeserializeOwned + Default,
+    T: for<'de> EDeserialize<'de>,
+    S: Send + Sync,
+{
+    type Rejection = JsonRejection;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        match Json::<T>::from_request(req, state).await {
+            Ok(Json(value)) => Ok(Self(value, None)),
+            Err(JsonRejection::JsonError(errors)) => Ok(Self(T::default(), Some(errors))),
+            Err(rejection) => Err(rejection),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils;
+
+    #[derive(Debug, Default, PartialEq, eserde::Deserialize)]
+    struct Reading {
+        sensor: String,
+        celsius: f64,
+    }
+
+    #[tokio::test]
+    async fn test_valid_payload() {
+        let request = test_utils::json_request(r#"{"sensor": "s-1", "celsius": 21.5}"#);
+        let BestEffort(reading, errors) = BestEffort::<Reading>::from_request(request.body(), &())
+            .await
+            .unwrap();
+        assert_eq!(reading.sensor, "s-1");
+        assert!(errors.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_malformed_payload() {
+        let request = test_utils::json_request(r#"{"sensor": 1, "celsius": "hot"}"#);
+        let BestEffort(reading, errors) = BestEffort::<Reading>::from_request(request.body(), &())
+            .await
+            .unwrap();
+        assert_eq!(reading, Reading::default());
+        let errors = errors.unwrap();
+        let paths: Vec<_> = errors
+            .errors()
+            .iter()
+            .map(|error| error.path().unwrap().to_string())
+            .collect();
+        assert_eq!(paths, ["sensor", "celsius"]);
+
+        // Not even JSON.
+        let request = test_utils::json_request(r#"{"sensor": "#);
+        let BestEffort(reading, errors) = BestEffort::<Reading>::from_request(request.body(), &())
+            .await
+            .unwrap();
+        assert_eq!(reading, Reading::default());
+        assert!(errors.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_still_rejects_bad_requests() {
+        let request = test_utils::json_request(r#"{"sensor": "s-1", "celsius": 21.5}"#)
+            .extension(crate::Config::new().max_body_size(8));
+        let rejection = BestEffort::<Reading>::from_request(request.body(), &())
+            .await
+            .unwrap_err();
+        let (status, body) = test_utils::problem(rejection).await;
+        assert_eq!(status, 413);
+        assert_eq!(body["type"], "content_too_large");
+    }
+}
@@ -0,0 +1,153 @@
+use axum_core::extract::{FromRequest, Request};
+use bytes::Bytes;
+use eserde::EDeserialize;
+
+use super::json_::{check_document, check_json_headers, deserialize};
+use super::JsonRejection;
+use crate::config::Config;
+
+/// JSON extractor for zero-copy DTOs, borrowing their fields from the request body.
+///
+/// [`Json<T>`](super::Json) requires `T` to own its data, since the request body is
+/// dropped as soon as the value has been deserialized.
+/// `BorrowedJson`, instead, holds on to the buffered body: call [`BorrowedJson::parse`]
+/// to deserialize a `T` borrowing from it, e.g. a `#[derive(eserde::Deserialize)]`
+/// struct with `&str` or `&[u8]` fields.
+///
+/// The request will be rejected (and a [`JsonRejection`] will be returned) if:
+///
+/// - The request doesn't have a `Content-Type: application/json` (or similar) header.
+/// - The body exceeds the limits set via the [`Config`], e.g. [`Config::max_object_members`].
+/// - Buffering the request body fails.
+///
+/// [`BorrowedJson::parse`] then fails with the same [`JsonRejection`]s as [`Json<T>`](super::Json)
+/// if the body isn't well-formed JSON or doesn't match `T`.
+///
+/// # Lifetimes
+///
+/// The parsed value borrows from the extractor: it can't outlive it, nor be moved
+/// into a `'static` task. Keep the extractor alive for as long as the value is in use.
+///
+/// JSON strings can only be borrowed if they contain no escape sequences (e.g. `\n`),
+/// otherwise there's no contiguous slice of the body to point at and deserializing a
+/// `&str` field fails with an `invalid_type` error.
+/// Use `Cow<'a, str>`, marked with `#[serde(borrow)]`, for fields that may be escaped:
+/// they borrow when possible and allocate otherwise.
+///
+/// ⚠️ Since buffering requires consuming the request body, the `BorrowedJson` extractor
+/// must be *last* if there are multiple extractors in a handler.
+/// See ["the order of extractors"][order-of-extractors]
+///
+/// [order-of-extractors]: https://docs.rs/axum/latest/axum/extract/index.html#the-order-of-extractors
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{routing::post, Router};
+/// use eserde_axum::json::{BorrowedJson, JsonRejection};
+///
+/// #[derive(eserde::Deserialize)]
+/// struct Search<'a> {
+///     query: &'a str,
+///     limit: u32,
+/// }
+///
+/// async fn search(body: BorrowedJson) -> Result<String, JsonRejection> {
+///     let search: Search<'_> = body.parse()?;
+///     Ok(format!("Top {} results for {}", search.limit, search.query))
+/// }
+///
+/// let app = Router::new().route("/search", post(search));
+/// # let _: Router = app;
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+#[must_use]
+pub struct BorrowedJson {
+    bytes: Bytes,
+    config: Config,
+}
+
+impl<S> FromRequest<S> for BorrowedJson
+where
+    S: Send + Sync,
+{
+    type Rejection = JsonRejection;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let config = Config::from_request(&req);
+        check_json_headers(req.headers(), &config)?;
+        let (bytes, _permit) =
+            crate::body::read_body::<_, Self::Rejection>(req, state, &config).await?;
+        check_document(&bytes, &config)?;
+        Ok(Self { bytes, config })
+    }
+}
+
+impl BorrowedJson {
+    /// Deserialize a `T` out of the request body, borrowing from it where possible.
+    ///
+    /// It can be invoked several times, e.g. to try different DTOs.
+    pub fn parse<'a, T>(&'a self) -> Result<T, JsonRejection>
+    where
+        T: EDeserialize<'a>,
+    {
+        deserialize(&self.bytes, &self.config)
+    }
+
+    /// The buffered request body.
+    pub fn bytes(&self) -> &Bytes {
+        &self.bytes
+    }
+
+    /// Take ownership of the buffered request body.
+    pub fn into_bytes(self) -> Bytes {
+        self.bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils;
+
+    #[derive(Debug, eserde::Deserialize)]
+    struct Search<'a> {
+        query: &'a str,
+        limit: u32,
+    }
+
+    #[tokio::test]
+    async fn test_borrowed_field() {
+        let request = test_utils::json_request(r#"{"query": "rust", "limit": 10}"#);
+        let body = BorrowedJson::from_request(request.body(), &())
+            .await
+            .unwrap();
+
+        let search: Search<'_> = body.parse().unwrap();
+        assert_eq!(search.query, "rust");
+        assert_eq!(search.limit, 10);
+        // The field points into the buffered body, rather than into a copy of it.
+        assert!(body.bytes().as_ptr_range().contains(&search.query.as_ptr()));
+    }
+
+    #[tokio::test]
+    async fn test_invalid_body() {
+        let request = test_utils::json_request(r#"{"query": 1, "limit": "10"}"#);
+        let body = BorrowedJson::from_request(request.body(), &())
+            .await
+            .unwrap();
+        let rejection = body.parse::<Search<'_>>().unwrap_err();
+
+        let (status, body) = test_utils::problem(rejection).await;
+        assert_eq!(status, 400);
+        assert_eq!(body["type"], "invalid_request");
+        let pointers: Vec<_> = body["errors"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|error| error["pointer"].as_str().unwrap())
+            .collect();
+        assert_eq!(pointers, ["/query", "/limit"]);
+    }
+}
@@ -0,0 +1,502 @@
+//! Match the variants of enums regardless of their case,
+//! as specified via [`Config::case_insensitive_variants`](crate::Config::case_insensitive_variants).
+//!
+//! The input is deserialized through a [`CaseInsensitive`] deserializer, which wraps
+//! every nested deserializer, visitor and access in turn—like `serde_path_to_error` does—to
+//! spot the enums along the way. Variant names that don't match exactly are swapped for
+//! the declared variant with the same name, ignoring ASCII case, before the enum sees them.
+//! Names without a match are left untouched: the enum rejects them as usual, with an
+//! `unknown variant` error listing the expected ones.
+//!
+//! Internally tagged enums are out of reach: `serde` buffers them via `deserialize_any`
+//! and reads their tag as a plain string, never asking for an enum.
+use std::fmt;
+
+use eserde::{DeserializationErrors, EDeserialize};
+use serde::de::{self, DeserializeSeed, IntoDeserializer};
+
+/// Deserialize a `T` from JSON bytes, matching its variants case-insensitively.
+///
+/// Unlike [`eserde::json::from_slice`], it goes through [`eserde::from_deserializer`]
+/// (or [`eserde::from_deserializer_fail_fast`], if `fail_fast` is set).
+pub(super) fn from_slice<'a, T>(
+    bytes: &'a [u8],
+    fail_fast: bool,
+) -> Result<T, DeserializationErrors>
+where
+    T: EDeserialize<'a>,
+{
+    // `eserde` creates a fresh deserializer for each of its (at most two) passes.
+    let mut passes = [
+        serde_json::Deserializer::from_slice(bytes),
+        serde_json::Deserializer::from_slice(bytes),
+    ];
+    let value = if fail_fast {
+        eserde::from_deserializer_fail_fast(CaseInsensitive(&mut passes[0]))?
+    } else {
+        let mut remaining = passes.iter_mut();
+        eserde::from_deserializer(|| {
+            CaseInsensitive(
+                remaining
+                    .next()
+                    .expect("`eserde` deserializes at most twice"),
+            )
+        })?
+    };
+    match passes[0].end() {
+        Ok(()) => Ok(value),
+        // There's no error to report against the value itself:
+        // the caller locates the trailing data on its own.
+        Err(_) => Err(DeserializationErrors::from(Vec::new())),
+    }
+}
+
+/// Returns the declared variant matching `name`, ignoring ASCII case,
+/// if `name` doesn't match any of them exactly.
+fn canonical(variants: &'static [&'static str], name: &[u8]) -> Option<&'static str> {
+    if variants.iter().any(|variant| variant.as_bytes() == name) {
+        return None;
+    }
+    variants
+        .iter()
+        .find(|variant| variant.as_bytes().eq_ignore_ascii_case(name))
+        .copied()
+}
+
+/// A deserializer matching the variants of the enums it meets case-insensitively.
+pub(super) struct CaseInsensitive<D>(pub(super) D);
+
+/// A visitor whose nested deserializers match variants case-insensitively.
+///
+/// `variants` is empty unless it visits an enum requested via `deserialize_enum`.
+struct Visitor<V> {
+    inner: V,
+    variants: &'static [&'static str],
+}
+
+impl<V> Visitor<V> {
+    fn new(inner: V) -> Self {
+        Self {
+            inner,
+            variants: &[],
+        }
+    }
+}
+
+/// A seed deserializing its value via [`CaseInsensitive`].
+struct Seed<S>(S);
+
+/// A sequence, map or variant access handing out [`CaseInsensitive`] deserializers.
+struct Access<A>(A);
+
+/// An enum access whose variant names are matched case-insensitively against `variants`.
+struct Enum<A> {
+    inner: A,
+    variants: &'static [&'static str],
+}
+
+/// A seed for the name of a variant, swapping it for its canonical spelling.
+struct VariantName<S> {
+    seed: S,
+    variants: &'static [&'static str],
+}
+
+macro_rules! forward_deserialize {
+    ($($method:ident($($arg:ident: $ty:ty),*)),* $(,)?) => {
+        $(
+            fn $method<V>(self, $($arg: $ty,)* visitor: V) -> Result<V::Value, Self::Error>
+            where
+                V: de::Visitor<'de>,
+            {
+                self.0.$method($($arg,)* Visitor::new(visitor))
+            }
+        )*
+    };
+}
+
+impl<'de, D> de::Deserializer<'de> for CaseInsensitive<D>
+where
+    D: de::Deserializer<'de>,
+{
+    type Error = D::Error;
+
+    forward_deserialize! {
+        deserialize_any(),
+        deserialize_bool(),
+        deserialize_i8(),
+        deserialize_i16(),
+        deserialize_i32(),
+        deserialize_i64(),
+        deserialize_i128(),
+        deserialize_u8(),
+        deserialize_u16(),
+        deserialize_u32(),
+        deserialize_u64(),
+        deserialize_u128(),
+        deserialize_f32(),
+        deserialize_f64(),
+        deserialize_char(),
+        deserialize_str(),
+        deserialize_string(),
+        deserialize_bytes(),
+        deserialize_byte_buf(),
+        deserialize_option(),
+        deserialize_unit(),
+        deserialize_unit_struct(name: &'static str),
+        deserialize_newtype_struct(name: &'static str),
+        deserialize_seq(),
+        deserialize_tuple(len: usize),
+        deserialize_tuple_struct(name: &'static str, len: usize),
+        deserialize_map(),
+        deserialize_struct(name: &'static str, fields: &'static [&'static str]),
+        deserialize_identifier(),
+        deserialize_ignored_any(),
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.0.deserialize_enum(
+            name,
+            variants,
+            Visitor {
+                inner: visitor,
+                variants,
+            },
+        )
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.0.is_human_readable()
+    }
+}
+
+macro_rules! forward_visit {
+    ($($method:ident($ty:ty)),* $(,)?) => {
+        $(
+            fn $method<E>(self, v: $ty) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                self.inner.$method(v)
+            }
+        )*
+    };
+}
+
+impl<'de, V> de::Visitor<'de> for Visitor<V>
+where
+    V: de::Visitor<'de>,
+{
+    type Value = V::Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.inner.expecting(formatter)
+    }
+
+    forward_visit! {
+        visit_bool(bool),
+        visit_i8(i8),
+        visit_i16(i16),
+        visit_i32(i32),
+        visit_i64(i64),
+        visit_i128(i128),
+        visit_u8(u8),
+        visit_u16(u16),
+        visit_u32(u32),
+        visit_u64(u64),
+        visit_u128(u128),
+        visit_f32(f32),
+        visit_f64(f64),
+        visit_char(char),
+        visit_str(&str),
+        visit_borrowed_str(&'de str),
+        visit_string(String),
+        visit_bytes(&[u8]),
+        visit_borrowed_bytes(&'de [u8]),
+        visit_byte_buf(Vec<u8>),
+    }
+
+    fn visit_none<E>(self) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        self.inner.visit_none()
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        self.inner.visit_some(CaseInsensitive(deserializer))
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        self.inner.visit_unit()
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        self.inner
+            .visit_newtype_struct(CaseInsensitive(deserializer))
+    }
+
+    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::SeqAccess<'de>,
+    {
+        self.inner.visit_seq(Access(seq))
+    }
+
+    fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::MapAccess<'de>,
+    {
+        self.inner.visit_map(Access(map))
+    }
+
+    fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::EnumAccess<'de>,
+    {
+        self.inner.visit_enum(Enum {
+            inner: data,
+            variants: self.variants,
+        })
+    }
+}
+
+impl<'de, S> DeserializeSeed<'de> for Seed<S>
+where
+    S: DeserializeSeed<'de>,
+{
+    type Value = S::Value;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        self.0.deserialize(CaseInsensitive(deserializer))
+    }
+}
+
+impl<'de, A> de::SeqAccess<'de> for Access<A>
+where
+    A: de::SeqAccess<'de>,
+{
+    type Error = A::Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        self.0.next_element_seed(Seed(seed))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.0.size_hint()
+    }
+}
+
+impl<'de, A> de::MapAccess<'de> for Access<A>
+where
+    A: de::MapAccess<'de>,
+{
+    type Error = A::Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        self.0.next_key_seed(Seed(seed))
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        self.0.next_value_seed(Seed(seed))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.0.size_hint()
+    }
+}
+
+impl<'de, A> de::VariantAccess<'de> for Access<A>
+where
+    A: de::VariantAccess<'de>,
+{
+    type Error = A::Error;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        self.0.unit_variant()
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, Self::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        self.0.newtype_variant_seed(Seed(seed))
+    }
+
+    fn tuple_variant<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.0.tuple_variant(len, Visitor::new(visitor))
+    }
+
+    fn struct_variant<V>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.0.struct_variant(fields, Visitor::new(visitor))
+    }
+}
+
+impl<'de, A> de::EnumAccess<'de> for Enum<A>
+where
+    A: de::EnumAccess<'de>,
+{
+    type Error = A::Error;
+    type Variant = Access<A::Variant>;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant), Self::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let (value, variant) = self.inner.variant_seed(VariantName {
+            seed,
+            variants: self.variants,
+        })?;
+        Ok((value, Access(variant)))
+    }
+}
+
+impl<'de, S> DeserializeSeed<'de> for VariantName<S>
+where
+    S: DeserializeSeed<'de>,
+{
+    type Value = S::Value;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_identifier(self)
+    }
+}
+
+impl<'de, S> de::Visitor<'de> for VariantName<S>
+where
+    S: DeserializeSeed<'de>,
+{
+    type Value = S::Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("variant identifier")
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        self.seed.deserialize(v.into_deserializer())
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        match canonical(self.variants, v.as_bytes()) {
+            Some(variant) => self.seed.deserialize(variant.into_deserializer()),
+            None => self.seed.deserialize(v.into_deserializer()),
+        }
+    }
+
+    fn visit_borrowed_str<E>(self, v: &'de str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        match canonical(self.variants, v.as_bytes()) {
+            Some(variant) => self.seed.deserialize(variant.into_deserializer()),
+            None => self
+                .seed
+                .deserialize(de::value::BorrowedStrDeserializer::new(v)),
+        }
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        match canonical(self.variants, v) {
+            Some(variant) => self.seed.deserialize(variant.into_deserializer()),
+            None => self.seed.deserialize(de::value::BytesDeserializer::new(v)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, eserde::Deserialize)]
+    #[serde(rename_all = "snake_case")]
+    enum Status {
+        Active,
+        OnHold { until: u32 },
+    }
+
+    #[derive(Debug, PartialEq, eserde::Deserialize)]
+    struct Account {
+        statuses: Vec<Status>,
+        previous: Option<Status>,
+    }
+
+    #[test]
+    fn test_nested_variants() {
+        let account: Account = from_slice(
+            br#"{"statuses": ["Active", {"ON_HOLD": {"until": 3}}], "previous": "active"}"#,
+            false,
+        )
+        .unwrap();
+        assert_eq!(
+            account,
+            Account {
+                statuses: vec![Status::Active, Status::OnHold { until: 3 }],
+                previous: Some(Status::Active),
+            }
+        );
+    }
+
+    #[test]
+    fn test_exact_match_first() {
+        #[derive(Debug, PartialEq, eserde::Deserialize)]
+        enum Grade {
+            #[serde(rename = "a")]
+            Lower,
+            #[serde(rename = "A")]
+            Upper,
+        }
+
+        assert_eq!(from_slice::<Grade>(br#""A""#, false).unwrap(), Grade::Upper);
+        assert_eq!(from_slice::<Grade>(br#""a""#, false).unwrap(), Grade::Lower);
+    }
+}
@@ -0,0 +1,201 @@
+use std::future::poll_fn;
+use std::ops::{Deref, DerefMut};
+use std::pin::Pin;
+
+use axum_core::extract::{FromRequest, Request};
+use eserde::EDeserialize;
+use futures_core::Stream;
+use serde::de::DeserializeOwned;
+use tokio::sync::mpsc;
+
+use super::{JsonRejection, JsonStream, JsonStreamError};
+use crate::config::Config;
+
+/// JSON extractor for request bodies holding a (potentially huge) top-level array,
+/// delivering its elements through a bounded [`mpsc::Receiver`].
+///
+/// The body is parsed by a dedicated task, spawned on the Tokio runtime, so the
+/// handler can process the elements concurrently with the parsing. The task waits
+/// whenever the channel is full: the body isn't read any further until the handler
+/// catches up. It stops reading altogether if the receiver is dropped.
+/// The capacity of the channel is set via [`Config::channel_capacity`].
+///
+/// The elements are split and deserialized like they would be by a [`JsonStream`],
+/// with the same errors: check it out for more details. The extraction counts against
+/// [`Config::max_concurrent_extractions`] until the task ends.
+///
+/// ⚠️ Since the elements are read from the request body, the `JsonChannel` extractor must be
+/// *last* if there are multiple extractors in a handler.
+/// See ["the order of extractors"][order-of-extractors]
+///
+/// [order-of-extractors]: https://docs.rs/axum/latest/axum/extract/index.html#the-order-of-extractors
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{routing::post, Router};
+/// use eserde_axum::JsonChannel;
+///
+/// #[derive(eserde::Deserialize)]
+/// struct Reading {
+///     sensor: String,
+///     value: f64,
+/// }
+///
+/// async fn ingest(JsonChannel(mut readings): JsonChannel<Reading>) -> String {
+///     let mut stored = 0;
+///     while let Some(reading) = readings.recv().await {
+///         match reading {
+///             Ok(reading) => { /* store the reading */ stored += 1 }
+///             Err(error) => return format!("Ingestion aborted after {stored} readings: {error}"),
+///         }
+///     }
+///     format!("Stored {stored} readings")
+/// }
+///
+/// let app = Router::new().route("/readings", post(ingest));
+/// # let _: Router = app;
+/// ```
+#[derive(Debug)]
+#[cfg_attr(docsrs, doc(cfg(feature = "channel")))]
+#[must_use]
+pub struct JsonChannel<T>(pub mpsc::Receiver<Result<T, JsonStreamError>>);
+
+impl<T, S> FromRequest<S> for JsonChannel<T>
+where
+    T: DeserializeOwned + Send + 'static,
+    T: for<'de> EDeserialize<'de>,
+    S: Send + Sync,
+{
+    type Rejection = JsonRejection;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let capacity = Config::from_request(&req).channel_limit();
+        let stream = JsonStream::from_request(req, state).await?;
+        Ok(Self(spawn(stream, capacity)))
+    }
+}
+
+/// Drive `stream` on a dedicated task, sending its items through a channel
+/// with the given `capacity`.
+fn spawn<T>(
+    mut stream: JsonStream<T>,
+    capacity: usize,
+) -> mpsc::Receiver<Result<T, JsonStreamError>>
+where
+    T: DeserializeOwned + Send + 'static,
+    T: for<'de> EDeserialize<'de>,
+{
+    let (sender, receiver) = mpsc::channel(capacity);
+    tokio::spawn(async move {
+        while let Some(item) = poll_fn(|cx| Pin::new(&mut stream).poll_next(cx)).await {
+            // Nobody is listening anymore: there's no point in reading the rest of the body.
+            if sender.send(item).await.is_err() {
+                break;
+            }
+        }
+        // Release the extraction permit before closing the channel.
+        drop(stream);
+    });
+    receiver
+}
+
+impl<T> Deref for JsonChannel<T> {
+    type Target = mpsc::Receiver<Result<T, JsonStreamError>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for JsonChannel<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum_core::body::Body;
+    use std::sync::atomic::Ordering;
+
+    /// Let the parsing task run until it can't make progress anymore.
+    async fn settle() {
+        for _ in 0..16 {
+            tokio::task::yield_now().await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_elements_arrive_incrementally() {
+        let request = Request::builder()
+            .method("POST")
+            .uri("/")
+            .header(http::header::CONTENT_TYPE, "application/json")
+            .body(Body::from(r#"[1, "two", 3]"#))
+            .unwrap();
+        let JsonChannel(mut receiver) = JsonChannel::<u32>::from_request(request, &())
+            .await
+            .unwrap();
+        assert_eq!(receiver.recv().await.unwrap().unwrap(), 1);
+        assert!(matches!(
+            receiver.recv().await,
+            Some(Err(JsonStreamError::Element(error))) if error.index() == 1
+        ));
+        assert_eq!(receiver.recv().await.unwrap().unwrap(), 3);
+        assert!(receiver.recv().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_backpressure() {
+        let (body, pulled) =
+            crate::test_utils::chunked_body(&["[", "1", ",2", ",3", ",4", ",5", "]"]);
+        let mut receiver = spawn(JsonStream::<u32>::new(body, None), 1);
+
+        // The first element fills the channel, the second one waits to be sent:
+        // the body isn't read any further, no matter how long we wait.
+        settle().await;
+        let paused_at = pulled.load(Ordering::SeqCst);
+        assert_eq!(paused_at, 4);
+        settle().await;
+        assert_eq!(pulled.load(Ordering::SeqCst), paused_at);
+
+        // Each element taken out of the channel lets one more in.
+        for (expected, chunks) in [(1, 5), (2, 6), (3, 7)] {
+            assert_eq!(receiver.recv().await.unwrap().unwrap(), expected);
+            settle().await;
+            assert_eq!(pulled.load(Ordering::SeqCst), chunks);
+        }
+        assert_eq!(receiver.recv().await.unwrap().unwrap(), 4);
+        assert_eq!(receiver.recv().await.unwrap().unwrap(), 5);
+        assert!(receiver.recv().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_task_holds_an_extraction_permit() {
+        let config = Config::new().max_concurrent_extractions(1, std::time::Duration::from_secs(1));
+        let request = || {
+            Request::builder()
+                .method("POST")
+                .uri("/")
+                .header(http::header::CONTENT_TYPE, "application/json")
+                .extension(config.clone())
+                .body(Body::from("[1, 2]"))
+                .unwrap()
+        };
+        let JsonChannel(mut receiver) = JsonChannel::<u32>::from_request(request(), &())
+            .await
+            .unwrap();
+        let rejection = JsonChannel::<u32>::from_request(request(), &())
+            .await
+            .unwrap_err();
+        assert!(matches!(rejection, JsonRejection::Overloaded(_)));
+
+        // The slot is released once the task has gone through the whole body.
+        while receiver.recv().await.is_some() {}
+        assert!(JsonChannel::<u32>::from_request(request(), &())
+            .await
+            .is_ok());
+    }
+}
@@ -0,0 +1,133 @@
+use axum_core::response::{IntoResponse, Response};
+use http::header::LOCATION;
+use http::{HeaderValue, StatusCode};
+use serde::Serialize;
+
+use super::Json;
+use crate::prefer::Prefer;
+
+/// A `201 Created` response, carrying the newly created resource as JSON
+/// and its URI in the `Location` header.
+///
+/// The body is serialized exactly like [`Json`] does, `Content-Type` included.
+/// If serialization fails, the client gets a 500 response instead.
+/// Use [`Created::prefer`] to leave the body out for clients that asked for
+/// `Prefer: return=minimal`.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{http::StatusCode, routing::post, Router};
+/// use eserde_axum::{Created, Json};
+///
+/// #[derive(eserde::Deserialize)]
+/// struct CreateUser {
+///     email: String,
+/// }
+///
+/// #[derive(serde::Serialize)]
+/// struct User {
+///     id: u64,
+///     email: String,
+/// }
+///
+/// async fn create_user(Json(payload): Json<CreateUser>) -> Result<Created<User>, StatusCode> {
+///     let user = User { id: 42, email: payload.email };
+///     Created::new(format!("/users/{}", user.id), user)
+///         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+/// }
+///
+/// let app = Router::new().route("/users", post(create_user));
+/// # let _: Router = app;
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+#[must_use = "needs to be returned from a handler or otherwise turned into a Response to be useful"]
+pub struct Created<T> {
+    location: HeaderValue,
+    body: T,
+    prefer: Prefer,
+}
+
+impl<T> Created<T> {
+    /// Create a new `Created` response for the resource at `location`.
+    ///
+    /// Fails if `location` can't be converted into a valid header value.
+    pub fn new<L>(location: L, body: T) -> Result<Self, L::Error>
+    where
+        L: TryInto<HeaderValue>,
+    {
+        Ok(Self {
+            location: location.try_into()?,
+            body,
+            prefer: Prefer::default(),
+        })
+    }
+
+    /// Honor the preferences expressed by the client via the `Prefer` header.
+    ///
+    /// See [`Prefer::apply`] for more details.
+    pub fn prefer(self, prefer: Prefer) -> Self {
+        Self { prefer, ..self }
+    }
+
+    /// The URI of the created resource.
+    pub fn location(&self) -> &HeaderValue {
+        &self.location
+    }
+}
+
+impl<T> IntoResponse for Created<T>
+where
+    T: Serialize,
+{
+    fn into_response(self) -> Response {
+        let mut response = Json(self.body).into_response();
+        // Don't mask serialization failures.
+        if response.status().is_success() {
+            *response.status_mut() = StatusCode::CREATED;
+            response.headers_mut().insert(LOCATION, self.location);
+        }
+        self.prefer.apply(response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::header::CONTENT_TYPE;
+
+    #[derive(Serialize)]
+    struct User {
+        id: u64,
+    }
+
+    #[tokio::test]
+    async fn test_created() {
+        let response = Created::new("/users/42", User { id: 42 })
+            .unwrap()
+            .into_response();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        assert_eq!(response.headers()[LOCATION], "/users/42");
+        assert_eq!(response.headers()[CONTENT_TYPE], "application/json");
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(&body[..], br#"{"id":42}"#);
+    }
+
+    #[test]
+    fn test_serialization_failure() {
+        let mut map = std::collections::HashMap::new();
+        map.insert((1, 2), "non-string keys can't be serialized to JSON");
+        let response = Created::new("/maps/1", map).unwrap().into_response();
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert!(!response.headers().contains_key(LOCATION));
+    }
+
+    #[test]
+    fn test_invalid_location() {
+        assert!(Created::new("/users/\n42", User { id: 42 }).is_err());
+    }
+}
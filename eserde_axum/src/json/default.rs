@@ -0,0 +1,94 @@
+/// Materialize a missing optional field with the default value of its type,
+/// rather than leaving it to `None`.
+///
+/// Useful for nested objects whose fields all have defaults, which the handler
+/// would rather not tell apart from an empty object.
+/// It's meant to be used with `#[serde(default = "...")]`, on the fields that opt in:
+///
+/// ```rust
+/// use eserde_axum::json::default_some;
+///
+/// #[derive(Default, eserde::Deserialize)]
+/// struct Notifications {
+///     #[serde(default)]
+///     email: bool,
+///     #[serde(default)]
+///     digest_hour: u8,
+/// }
+///
+/// #[derive(eserde::Deserialize)]
+/// struct Preferences {
+///     // Missing notification settings are the default ones...
+///     #[serde(default = "default_some")]
+///     notifications: Option<Notifications>,
+///     // ...while a missing theme stays `None`.
+///     #[serde(default)]
+///     theme: Option<String>,
+/// }
+/// ```
+///
+/// An explicit `null` still deserializes to `None`.
+pub fn default_some<T: Default>() -> Option<T> {
+    Some(T::default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Json;
+
+    #[derive(Debug, Default, PartialEq, eserde::Deserialize)]
+    struct Notifications {
+        #[serde(default)]
+        email: bool,
+        #[serde(default = "default_digest_hour")]
+        digest_hour: u8,
+    }
+
+    fn default_digest_hour() -> u8 {
+        8
+    }
+
+    #[derive(Debug, eserde::Deserialize)]
+    struct Preferences {
+        #[serde(default = "default_some")]
+        notifications: Option<Notifications>,
+        #[serde(default)]
+        legacy_notifications: Option<Notifications>,
+    }
+
+    #[test]
+    fn test_missing_field_is_materialized() {
+        let Json(preferences) = Json::<Preferences>::from_bytes(b"{}").unwrap();
+        assert_eq!(preferences.notifications, Some(Notifications::default()));
+        // Fields without the attribute are unaffected.
+        assert_eq!(preferences.legacy_notifications, None);
+    }
+
+    #[test]
+    fn test_present_values() {
+        let Json(preferences) =
+            Json::<Preferences>::from_bytes(br#"{"notifications": {"email": true}}"#).unwrap();
+        assert_eq!(
+            preferences.notifications,
+            Some(Notifications {
+                email: true,
+                digest_hour: 8
+            })
+        );
+
+        let Json(preferences) =
+            Json::<Preferences>::from_bytes(br#"{"notifications": null}"#).unwrap();
+        assert_eq!(preferences.notifications, None);
+    }
+
+    #[test]
+    fn test_invalid_values_are_reported() {
+        let Err(crate::JsonRejection::JsonError(error)) =
+            Json::<Preferences>::from_bytes(br#"{"notifications": {"email": "yes"}}"#)
+        else {
+            panic!("Expected a deserialization error");
+        };
+        assert_eq!(error.errors.len(), 1);
+    }
+}
@@ -0,0 +1,193 @@
+use std::fmt;
+use std::marker::PhantomData;
+
+use serde::de::value::{
+    BoolDeserializer, BorrowedBytesDeserializer, BorrowedStrDeserializer, BytesDeserializer,
+    EnumAccessDeserializer, F64Deserializer, I128Deserializer, I64Deserializer,
+    MapAccessDeserializer, SeqAccessDeserializer, StrDeserializer, StringDeserializer,
+    U128Deserializer, U64Deserializer,
+};
+use serde::de::{Deserialize, Deserializer, EnumAccess, Error, MapAccess, SeqAccess, Visitor};
+
+/// Deserialize an optional field, treating empty strings as if the field was missing.
+///
+/// Useful when clients send `""` for the fields that were left blank.
+/// It's meant to be used with `#[serde(default, deserialize_with = "...")]`,
+/// so that the field can also be omitted entirely:
+///
+/// ```rust
+/// use eserde_axum::json::empty_as_none;
+///
+/// #[derive(eserde::Deserialize)]
+/// struct Profile {
+///     // `""` is a valid name...
+///     name: String,
+///     // ...but a blank nickname means there is no nickname.
+///     #[serde(default, deserialize_with = "empty_as_none")]
+///     nickname: Option<String>,
+///     #[serde(default, deserialize_with = "empty_as_none")]
+///     age: Option<u8>,
+/// }
+/// ```
+///
+/// Non-empty values are deserialized into `T` as usual, and so is `null`.
+/// Fields without the attribute are unaffected.
+pub fn empty_as_none<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    deserializer.deserialize_any(EmptyAsNone(PhantomData))
+}
+
+/// Forwards every value to `T`, except for empty strings.
+struct EmptyAsNone<T>(PhantomData<T>);
+
+impl<'de, T> Visitor<'de> for EmptyAsNone<T>
+where
+    T: Deserialize<'de>,
+{
+    type Value = Option<T>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an optional value")
+    }
+
+    fn visit_none<E: Error>(self) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    fn visit_unit<E: Error>(self) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(self)
+    }
+
+    fn visit_bool<E: Error>(self, v: bool) -> Result<Self::Value, E> {
+        T::deserialize(BoolDeserializer::new(v)).map(Some)
+    }
+
+    fn visit_i64<E: Error>(self, v: i64) -> Result<Self::Value, E> {
+        T::deserialize(I64Deserializer::new(v)).map(Some)
+    }
+
+    fn visit_i128<E: Error>(self, v: i128) -> Result<Self::Value, E> {
+        T::deserialize(I128Deserializer::new(v)).map(Some)
+    }
+
+    fn visit_u64<E: Error>(self, v: u64) -> Result<Self::Value, E> {
+        T::deserialize(U64Deserializer::new(v)).map(Some)
+    }
+
+    fn visit_u128<E: Error>(self, v: u128) -> Result<Self::Value, E> {
+        T::deserialize(U128Deserializer::new(v)).map(Some)
+    }
+
+    fn visit_f64<E: Error>(self, v: f64) -> Result<Self::Value, E> {
+        T::deserialize(F64Deserializer::new(v)).map(Some)
+    }
+
+    fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E> {
+        if v.is_empty() {
+            return Ok(None);
+        }
+        T::deserialize(StrDeserializer::new(v)).map(Some)
+    }
+
+    fn visit_borrowed_str<E: Error>(self, v: &'de str) -> Result<Self::Value, E> {
+        if v.is_empty() {
+            return Ok(None);
+        }
+        T::deserialize(BorrowedStrDeserializer::new(v)).map(Some)
+    }
+
+    fn visit_string<E: Error>(self, v: String) -> Result<Self::Value, E> {
+        if v.is_empty() {
+            return Ok(None);
+        }
+        T::deserialize(StringDeserializer::new(v)).map(Some)
+    }
+
+    fn visit_bytes<E: Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        T::deserialize(BytesDeserializer::new(v)).map(Some)
+    }
+
+    fn visit_borrowed_bytes<E: Error>(self, v: &'de [u8]) -> Result<Self::Value, E> {
+        T::deserialize(BorrowedBytesDeserializer::new(v)).map(Some)
+    }
+
+    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        T::deserialize(SeqAccessDeserializer::new(seq)).map(Some)
+    }
+
+    fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        T::deserialize(MapAccessDeserializer::new(map)).map(Some)
+    }
+
+    fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+    where
+        A: EnumAccess<'de>,
+    {
+        T::deserialize(EnumAccessDeserializer::new(data)).map(Some)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Json;
+
+    #[derive(Debug, eserde::Deserialize)]
+    struct Profile {
+        name: String,
+        #[serde(default, deserialize_with = "empty_as_none")]
+        nickname: Option<String>,
+        #[serde(default, deserialize_with = "empty_as_none")]
+        age: Option<u8>,
+    }
+
+    #[test]
+    fn test_empty_string_is_none() {
+        let Json(profile) =
+            Json::<Profile>::from_bytes(br#"{"name": "", "nickname": "", "age": ""}"#).unwrap();
+        assert_eq!(profile.name, "");
+        assert_eq!(profile.nickname, None);
+        assert_eq!(profile.age, None);
+    }
+
+    #[test]
+    fn test_non_empty_values() {
+        let Json(profile) =
+            Json::<Profile>::from_bytes(br#"{"name": "Robert", "nickname": "Bob", "age": 42}"#)
+                .unwrap();
+        assert_eq!(profile.nickname.as_deref(), Some("Bob"));
+        assert_eq!(profile.age, Some(42));
+
+        let Json(profile) =
+            Json::<Profile>::from_bytes(br#"{"name": "Robert", "nickname": null}"#).unwrap();
+        assert_eq!(profile.nickname, None);
+        assert_eq!(profile.age, None);
+    }
+
+    #[test]
+    fn test_invalid_values_are_reported() {
+        let Err(crate::JsonRejection::JsonError(error)) =
+            Json::<Profile>::from_bytes(br#"{"name": "Robert", "age": "old"}"#)
+        else {
+            panic!("Expected a deserialization error");
+        };
+        let error = error.errors().iter().next().unwrap();
+        assert_eq!(error.path().unwrap().to_string(), "age");
+    }
+}
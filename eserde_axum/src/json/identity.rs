@@ -0,0 +1,231 @@
+use std::marker::PhantomData;
+use std::ops::Deref;
+use std::ops::DerefMut;
+
+use crate::config::Config;
+
+use super::json_::check_json_headers;
+use super::*;
+use axum_core::extract::{FromRequest, Request};
+use eserde::EDeserialize;
+use serde::de::DeserializeOwned;
+
+/// The identity of the problems reported by an [`IdentifiedJson`] extractor.
+///
+/// Implement it on a marker type to give the `invalid_request` problems of a route
+/// their own `type`, `title` and, optionally, `detail`.
+pub trait ProblemIdentity {
+    /// The `type` of the problem, e.g. `create_user_invalid`.
+    const TYPE: &'static str;
+    /// The `title` of the problem.
+    const TITLE: &'static str;
+    /// The `detail` of the problem.
+    ///
+    /// The default one is kept if `None`.
+    const DETAIL: Option<&'static str> = None;
+}
+
+/// The `type`, `title` and `detail` of a [`ProblemIdentity`],
+/// carried around by the [`Config`] of the request.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Identity {
+    pub(crate) type_: &'static str,
+    pub(crate) title: &'static str,
+    pub(crate) detail: Option<&'static str>,
+}
+
+impl Identity {
+    fn of<P: ProblemIdentity>() -> Self {
+        Self {
+            type_: P::TYPE,
+            title: P::TITLE,
+            detail: P::DETAIL,
+        }
+    }
+}
+
+/// JSON extractor whose `invalid_request` problems take their `type` and `title`
+/// from the marker type `P`.
+///
+/// It behaves like [`Json`], but problems that would have been of type `invalid_request`
+/// use the identity specified by `P` instead, so that each endpoint can report a distinct
+/// problem type.
+/// The other problems (e.g. `content_too_large`) are unaffected, and so are the
+/// validation errors listed by the problem.
+/// [`Config::about_blank`](crate::Config::about_blank) still takes precedence.
+///
+/// See [`JsonRejection`] for the ways the extractor can fail.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{routing::{post, put}, Router};
+/// use eserde_axum::json::{IdentifiedJson, ProblemIdentity};
+///
+/// #[derive(eserde::Deserialize)]
+/// struct User {
+///     email: String,
+/// }
+///
+/// struct CreateUserInvalid;
+///
+/// impl ProblemIdentity for CreateUserInvalid {
+///     const TYPE: &'static str = "create_user_invalid";
+///     const TITLE: &'static str = "The user can't be created";
+/// }
+///
+/// struct UpdateUserInvalid;
+///
+/// impl ProblemIdentity for UpdateUserInvalid {
+///     const TYPE: &'static str = "update_user_invalid";
+///     const TITLE: &'static str = "The user can't be updated";
+/// }
+///
+/// async fn create_user(user: IdentifiedJson<User, CreateUserInvalid>) {
+///     let User { email } = user.into_inner();
+/// }
+///
+/// async fn update_user(user: IdentifiedJson<User, UpdateUserInvalid>) {
+///     let email = &user.email;
+/// }
+///
+/// let app = Router::new()
+///     .route("/users", post(create_user))
+///     .route("/users/me", put(update_user));
+/// # let _: Router = app;
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+#[must_use]
+pub struct IdentifiedJson<T, P>(pub T, PhantomData<P>);
+
+impl<T, P, S> FromRequest<S> for IdentifiedJson<T, P>
+where
+    T: DeserializeOwned,
+    T: for<'de> EDeserialize<'de>,
+    P: ProblemIdentity,
+    S: Send + Sync,
+{
+    type Rejection = JsonRejection;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let mut config = Config::from_request(&req);
+        config.settings_mut().identity = Some(Identity::of::<P>());
+        check_json_headers(req.headers(), &config)?;
+        let (bytes, _permit) =
+            crate::body::read_body::<_, Self::Rejection>(req, state, &config).await?;
+        let Json(value) = Json::from_bytes_with_config(&bytes, &config)?;
+        Ok(Self::new(value))
+    }
+}
+
+impl<T, P> IdentifiedJson<T, P> {
+    /// Wrap a value, e.g. to build the extractor by hand in tests.
+    pub fn new(value: T) -> Self {
+        Self(value, PhantomData)
+    }
+
+    /// Consume the extractor, returning the deserialized value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+// Implemented by hand, since marker types don't need to implement these traits.
+impl<T: std::fmt::Debug, P> std::fmt::Debug for IdentifiedJson<T, P> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("IdentifiedJson").field(&self.0).finish()
+    }
+}
+
+impl<T: Clone, P> Clone for IdentifiedJson<T, P> {
+    fn clone(&self) -> Self {
+        Self::new(self.0.clone())
+    }
+}
+
+impl<T: Copy, P> Copy for IdentifiedJson<T, P> {}
+
+impl<T: Default, P> Default for IdentifiedJson<T, P> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T, P> Deref for IdentifiedJson<T, P> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T, P> DerefMut for IdentifiedJson<T, P> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils;
+
+    #[derive(Debug, eserde::Deserialize)]
+    #[allow(dead_code)]
+    struct User {
+        email: String,
+    }
+
+    struct CreateUserInvalid;
+
+    impl ProblemIdentity for CreateUserInvalid {
+        const TYPE: &'static str = "create_user_invalid";
+        const TITLE: &'static str = "The user can't be created";
+    }
+
+    struct UpdateUserInvalid;
+
+    impl ProblemIdentity for UpdateUserInvalid {
+        const TYPE: &'static str = "update_user_invalid";
+        const TITLE: &'static str = "The user can't be updated";
+        const DETAIL: Option<&'static str> = Some("Check the fields listed in `errors`");
+    }
+
+    #[tokio::test]
+    async fn test_distinct_identities() {
+        let request = test_utils::json_request(r#"{"email": 42}"#);
+        let rejection =
+            IdentifiedJson::<User, CreateUserInvalid>::from_request(request.body(), &())
+                .await
+                .unwrap_err();
+        let (status, body) = test_utils::problem(rejection).await;
+        assert_eq!(status, 400);
+        assert_eq!(body["type"], "create_user_invalid");
+        assert_eq!(body["title"], "The user can't be created");
+        assert_eq!(
+            body["detail"],
+            "The request is either malformed or doesn't match the expected schema"
+        );
+        assert_eq!(body["errors"][0]["pointer"], "/email");
+
+        let request = test_utils::json_request(r#"{"email": 42}"#);
+        let rejection =
+            IdentifiedJson::<User, UpdateUserInvalid>::from_request(request.body(), &())
+                .await
+                .unwrap_err();
+        let (_, body) = test_utils::problem(rejection).await;
+        assert_eq!(body["type"], "update_user_invalid");
+        assert_eq!(body["title"], "The user can't be updated");
+        assert_eq!(body["detail"], "Check the fields listed in `errors`");
+    }
+
+    #[tokio::test]
+    async fn test_valid_payload() {
+        let request = test_utils::json_request(r#"{"email": "alice@example.com"}"#);
+        let user = IdentifiedJson::<User, CreateUserInvalid>::from_request(request.body(), &())
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(user.email, "alice@example.com");
+    }
+}
@@ -1,16 +1,21 @@
 use std::ops::Deref;
 use std::ops::DerefMut;
 
+use crate::config::Config;
 use crate::details::INTERNAL_SERVER_ERROR;
 
+use super::limits;
 use super::*;
 use axum_core::extract::FromRequest;
 use axum_core::extract::Request;
 use axum_core::response::{IntoResponse, Response};
-use bytes::{BufMut, Bytes, BytesMut};
-use eserde::EDeserialize;
+use bytes::{BufMut, BytesMut};
+use eserde::{DeserializationErrors, EDeserialize};
 use http::header::{self, HeaderMap, HeaderValue};
-use serde::{de::DeserializeOwned, Serialize};
+use serde::{
+    de::{DeserializeOwned, IgnoredAny},
+    Serialize,
+};
 
 /// JSON Extractor / Response.
 ///
@@ -21,6 +26,21 @@ use serde::{de::DeserializeOwned, Serialize};
 /// - The request doesn't have a `Content-Type: application/json` (or similar) header.
 /// - The body doesn't contain syntactically valid JSON or it couldn't be deserialized into the target type.
 /// - Buffering the request body fails.
+/// - The body exceeds one of the structural limits specified via [`Config`](crate::Config).
+/// - The body starts with a byte order mark or is padded with too much whitespace,
+///   in [strict mode](crate::Config::strict_json).
+/// - The content type has repeated or unknown parameters, or a charset other than UTF-8,
+///   in [strict mode](crate::Config::strict_json).
+/// - The top-level value of the body doesn't have one of the
+///   [accepted types](crate::Config::json_root_types).
+/// - Deserialization panics, if the `catch-unwind` feature is enabled.
+///
+/// Outside of strict mode, the parameters of the content type, e.g. the `profile` sent
+/// by JSON-LD clients, are ignored. Use [`Profile`](super::Profile) to get hold of the latter.
+///
+/// [`Config::case_insensitive_variants`](crate::Config::case_insensitive_variants) only applies
+/// to externally tagged enums, i.e. the default representation: the tag of an internally tagged
+/// enum, e.g. `{"type": "foo"}` for a variant named `Foo`, must still match exactly.
 ///
 /// ⚠️ Since parsing JSON requires consuming the request body, the `Json` extractor must be
 /// *last* if there are multiple extractors in a handler.
@@ -28,6 +48,10 @@ use serde::{de::DeserializeOwned, Serialize};
 ///
 /// [order-of-extractors]: https://docs.rs/axum/latest/axum/extract/index.html#the-order-of-extractors
 ///
+/// If the body isn't syntactically valid JSON, the problem locates the syntax error
+/// via the `line`, `column` (both starting at 1) and `offset` (in bytes, starting at 0)
+/// extension members.
+///
 /// See [`JsonRejection`] for more details.
 ///
 /// # Extractor example
@@ -50,6 +74,62 @@ use serde::{de::DeserializeOwned, Serialize};
 /// # let _: Router = app;
 /// ```
 ///
+/// # Handling rejections
+///
+/// The rejection type is [`JsonRejection`], which is also available at the root of the crate.
+/// If you need to inspect or customize the rejection, take a `Result<Json<T>, JsonRejection>`
+/// as argument. The same type can be used as the error of your handler's return type:
+///
+/// ```rust,no_run
+/// use axum::{routing::post, Router};
+/// use eserde_axum::{Json, JsonRejection};
+///
+/// #[derive(eserde::Deserialize)]
+/// struct CreateUser {
+///     email: String,
+///     password: String,
+/// }
+///
+/// #[axum::debug_handler]
+/// async fn create_user(
+///     payload: Result<Json<CreateUser>, JsonRejection>,
+/// ) -> Result<Json<String>, JsonRejection> {
+///     let Json(payload) = payload?;
+///     Ok(Json(payload.email))
+/// }
+///
+/// let app = Router::new().route("/users", post(create_user));
+/// # let _: Router = app;
+/// ```
+///
+/// # Shared ownership
+///
+/// If the payload must be shared across multiple tasks, you can extract
+/// it directly into an [`Arc`](std::sync::Arc) to avoid cloning it downstream.
+/// The inner type is deserialized as usual and then wrapped—errors are reported
+/// exactly as they would be for `Json<T>`.
+///
+/// ```rust,no_run
+/// use std::sync::Arc;
+/// use axum::{routing::post, Router};
+/// use eserde_axum::Json;
+///
+/// #[derive(eserde::Deserialize)]
+/// struct CreateUser {
+///     email: String,
+///     password: String,
+/// }
+///
+/// async fn create_user(Json(payload): Json<Arc<CreateUser>>) {
+///     // Cheap to clone, no matter how large `CreateUser` is.
+///     let for_background_task = Arc::clone(&payload);
+///     # let _ = for_background_task;
+/// }
+///
+/// let app = Router::new().route("/users", post(create_user));
+/// # let _: Router = app;
+/// ```
+///
 /// When used as a response, it can serialize any type that implements [`serde::Serialize`] to
 /// `JSON`, and will automatically set `Content-Type: application/json` header.
 ///
@@ -102,27 +182,51 @@ where
     type Rejection = JsonRejection;
 
     async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
-        check_json_content_type(req.headers())?;
-        let bytes = Bytes::from_request(req, state).await?;
-        Self::from_bytes(&bytes)
+        let config = Config::from_request(&req);
+        check_json_headers(req.headers(), &config)?;
+        let (bytes, _permit) =
+            crate::body::read_body::<_, Self::Rejection>(req, state, &config).await?;
+        Self::from_bytes_with_config(&bytes, &config)
     }
 }
 
 /// Check that the `Content-Type` header is set to `application/json`, or another
 /// `application/*+json` MIME type.
+/// Parameters (e.g. `charset` or `profile`) don't matter, unless in [strict mode](Config::strict_json).
+///
+/// The depth advertised by the [trusted header](Config::trust_depth_header), if any,
+/// is checked too.
 ///
 /// Return an error otherwise.
-fn check_json_content_type(headers: &HeaderMap) -> Result<(), JsonRejection> {
+pub(crate) fn check_json_headers(
+    headers: &HeaderMap,
+    config: &Config,
+) -> Result<(), JsonRejection> {
+    let missing = || {
+        MissingJsonContentType {
+            config: config.clone(),
+        }
+        .into()
+    };
+    let count = headers.get_all(http::header::CONTENT_TYPE).iter().count();
+    if count > 1 {
+        return Err(AmbiguousJsonContentType {
+            count,
+            config: config.clone(),
+        }
+        .into());
+    }
     let Some(content_type) = headers.get(http::header::CONTENT_TYPE) else {
-        return Err(MissingJsonContentType.into());
+        return Err(missing());
     };
     let Ok(content_type) = content_type.to_str() else {
-        return Err(MissingJsonContentType.into());
+        return Err(missing());
     };
 
     let Ok(mime) = content_type.parse::<mime::Mime>() else {
         return Err(JsonContentTypeMismatch {
             actual: content_type.to_string(),
+            config: config.clone(),
         }
         .into());
     };
@@ -132,12 +236,146 @@ fn check_json_content_type(headers: &HeaderMap) -> Result<(), JsonRejection> {
     if !is_json_content_type {
         return Err(JsonContentTypeMismatch {
             actual: content_type.to_string(),
+            config: config.clone(),
         }
         .into());
     }
+    if config.settings.strict_json {
+        check_content_type_params(&mime, content_type, config)?;
+    }
+    check_advertised_depth(headers, config)
+}
+
+/// Reject the request if the [trusted header](Config::trust_depth_header) advertises
+/// a body nested deeper than [`Config::max_depth`].
+///
+/// Missing or malformed values are ignored: the body is checked once buffered anyway.
+fn check_advertised_depth(headers: &HeaderMap, config: &Config) -> Result<(), JsonRejection> {
+    let (Some(name), Some(max)) = (&config.settings.depth_header, config.settings.max_depth) else {
+        return Ok(());
+    };
+    let depth = headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<usize>().ok());
+    match depth {
+        Some(depth) if depth > max.get() => Err(TooDeep {
+            location: DepthLocation::Header {
+                name: name.clone(),
+                depth,
+            },
+            max: max.get(),
+            config: config.clone(),
+        }
+        .into()),
+        _ => Ok(()),
+    }
+}
+
+/// The parameters accepted for JSON content types in [strict mode](Config::strict_json).
+const JSON_PARAMS: [&str; 2] = ["charset", "profile"];
+
+/// Check that each parameter of the content type is known and appears only once,
+/// and that the charset, if specified, is UTF-8.
+fn check_content_type_params(
+    mime: &mime::Mime,
+    content_type: &str,
+    config: &Config,
+) -> Result<(), JsonRejection> {
+    let reject = |reason: String| {
+        Err(InvalidContentType {
+            reason,
+            actual: content_type.to_string(),
+            config: config.clone(),
+        }
+        .into())
+    };
+    let mut seen = Vec::new();
+    for (name, value) in mime.params() {
+        let name = name.as_str();
+        if !JSON_PARAMS.contains(&name) {
+            return reject(format!(
+                "Unexpected `{name}` parameter in the `Content-Type` header"
+            ));
+        }
+        if seen.contains(&name) {
+            return reject(format!(
+                "The `{name}` parameter of the `Content-Type` header is repeated"
+            ));
+        }
+        seen.push(name);
+        if name == "charset" && !value.as_str().eq_ignore_ascii_case("utf-8") {
+            return reject(format!(
+                "Unsupported charset `{value}`: JSON must be UTF-8 encoded"
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Check that the JSON document honors the constraints specified in the [`Config`],
+/// before it gets deserialized.
+pub(crate) fn check_document(bytes: &[u8], config: &Config) -> Result<(), JsonRejection> {
+    if config.settings.strict_json {
+        check_padding(bytes, config)?;
+    }
+    if let Some(expected) = &config.settings.json_root_types {
+        check_root_type(bytes, expected, config)?;
+    }
+    if config.has_json_limits() {
+        limits::check(bytes, config)
+            .map_err(|violation| JsonRejection::from_violation(violation, config.clone()))?;
+    }
     Ok(())
 }
 
+/// The maximum amount of whitespace allowed before or after
+/// the JSON document in [strict mode](Config::strict_json).
+const MAX_PADDING: usize = 1024;
+
+/// Check that the body doesn't start with a byte order mark and that
+/// the JSON document isn't padded with too much whitespace.
+fn check_padding(bytes: &[u8], config: &Config) -> Result<(), JsonRejection> {
+    if bytes.starts_with(b"\xEF\xBB\xBF") {
+        return Err(InvalidEncoding {
+            config: config.clone(),
+        }
+        .into());
+    }
+    let is_whitespace = |b: &&u8| matches!(b, b' ' | b'\t' | b'\n' | b'\r');
+    let leading = bytes.iter().take_while(is_whitespace).count();
+    let trailing = bytes[leading..]
+        .iter()
+        .rev()
+        .take_while(is_whitespace)
+        .count();
+    if leading > MAX_PADDING || trailing > MAX_PADDING {
+        return Err(ExcessiveWhitespace {
+            max: MAX_PADDING,
+            config: config.clone(),
+        }
+        .into());
+    }
+    Ok(())
+}
+
+/// Check that the top-level value of the JSON document has one of the expected types.
+fn check_root_type(
+    bytes: &[u8],
+    expected: &[JsonType],
+    config: &Config,
+) -> Result<(), JsonRejection> {
+    match JsonType::of_root(bytes) {
+        Some(actual) if !expected.contains(&actual) => Err(InvalidRootType {
+            expected: expected.to_vec(),
+            actual,
+            config: config.clone(),
+        }
+        .into()),
+        _ => Ok(()),
+    }
+}
+
 impl<T> Deref for Json<T> {
     type Target = T;
 
@@ -167,13 +405,88 @@ where
     /// but special cases may require first extracting a `Request` into `Bytes` then optionally
     /// constructing a `Json<T>`.
     pub fn from_bytes(bytes: &[u8]) -> Result<Self, JsonRejection> {
-        match eserde::json::from_slice(bytes) {
-            Ok(value) => Ok(Json(value)),
-            Err(errors) => Err(JsonError::new(errors).into()),
-        }
+        Self::from_bytes_with_config(bytes, &Config::default())
+    }
+
+    /// Like [`from_bytes`](Self::from_bytes), but honoring the settings
+    /// specified in the [`Config`].
+    pub(crate) fn from_bytes_with_config(
+        bytes: &[u8],
+        config: &Config,
+    ) -> Result<Self, JsonRejection> {
+        check_document(bytes, config)?;
+        Self::deserialize(bytes, config)
+    }
+
+    /// Deserialize the JSON document, without any further check.
+    pub(super) fn deserialize(bytes: &[u8], config: &Config) -> Result<Self, JsonRejection> {
+        deserialize(bytes, config).map(Json)
     }
 }
 
+/// Deserialize a `T`, possibly borrowing from the JSON document, without any further check.
+pub(super) fn deserialize<'a, T>(bytes: &'a [u8], config: &Config) -> Result<T, JsonRejection>
+where
+    T: EDeserialize<'a>,
+{
+    deserialize_via(bytes, config, || {
+        if config.settings.case_insensitive_variants {
+            super::case_insensitive::from_slice(bytes, config.settings.fail_fast)
+        } else if config.settings.fail_fast {
+            eserde::json::from_slice_fail_fast(bytes)
+        } else {
+            eserde::json::from_slice(bytes)
+        }
+    })
+}
+
+/// Deserialize a `T` out of the JSON document via `from_slice`, turning its errors
+/// into a rejection.
+pub(super) fn deserialize_via<T>(
+    bytes: &[u8],
+    config: &Config,
+    from_slice: impl FnOnce() -> Result<T, DeserializationErrors>,
+) -> Result<T, JsonRejection> {
+    #[cfg(feature = "catch-unwind")]
+    let result = crate::unwind::catch_unwind(config, from_slice)?;
+    #[cfg(not(feature = "catch-unwind"))]
+    let result = from_slice();
+    result.map_err(|errors| {
+        // Trailing data makes the deserializer fail with a single error, at the root.
+        // Locating it takes another pass, so it's only worth it for errors of that shape.
+        let offset = match errors.iter().next() {
+            Some(error)
+                if errors.len() > 1 || error.path().is_some_and(|path| !path.is_empty()) =>
+            {
+                None
+            }
+            _ => trailing_data(bytes),
+        };
+        match offset {
+            Some(offset) => TrailingData {
+                offset,
+                config: config.clone(),
+            }
+            .into(),
+            None => JsonError::new(errors, config.clone(), bytes).into(),
+        }
+    })
+}
+
+/// The offset of the first byte that isn't whitespace after the top-level JSON value,
+/// if the value is well-formed but followed by something else.
+///
+/// Malformed values are left to the deserializer, which reports them in more detail.
+fn trailing_data(bytes: &[u8]) -> Option<usize> {
+    let mut values = serde_json::Deserializer::from_slice(bytes).into_iter::<IgnoredAny>();
+    values.next()?.ok()?;
+    let end = values.byte_offset();
+    bytes[end..]
+        .iter()
+        .position(|byte| !matches!(byte, b' ' | b'\t' | b'\n' | b'\r'))
+        .map(|position| end + position)
+}
+
 impl<T> IntoResponse for Json<T>
 where
     T: Serialize,
@@ -195,3 +508,1109 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::test_utils;
+    use http::StatusCode;
+
+    #[derive(Debug, PartialEq, eserde::Deserialize)]
+    struct User {
+        name: String,
+        age: u8,
+    }
+
+    #[test]
+    fn test_arc() {
+        let Json(user) = Json::<Arc<User>>::from_bytes(br#"{"name": "Alice", "age": 30}"#).unwrap();
+        assert_eq!(
+            *user,
+            User {
+                name: "Alice".into(),
+                age: 30
+            }
+        );
+
+        let Err(JsonRejection::JsonError(error)) =
+            Json::<Arc<User>>::from_bytes(br#"{"name": "Alice", "age": "30"}"#)
+        else {
+            panic!("Expected a deserialization error");
+        };
+        assert_eq!(error.errors.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_too_many_fields() {
+        let request = test_utils::json_request(r#"{"a": {"b": 1, "c": 2, "d": 3}, "e": 4}"#)
+            .extension(Config::new().max_object_members(2));
+        let rejection = Json::<serde_json::Value>::from_request(request.body(), &())
+            .await
+            .unwrap_err();
+        assert!(matches!(rejection, JsonRejection::TooManyFields(_)));
+
+        let (status, body) = test_utils::problem(rejection).await;
+        assert_eq!(status, 400);
+        assert_eq!(body["type"], "too_many_fields");
+        assert_eq!(body["errors"][0]["code"], "too_many_fields");
+        assert_eq!(body["errors"][0]["pointer"], "/a");
+    }
+
+    #[tokio::test]
+    async fn test_string_too_long() {
+        let huge = "a".repeat(100_000);
+        let body = format!(r#"{{"name": "Alice", "tags": ["ok", "{huge}"]}}"#);
+        let config = Config::new().max_string_length(64);
+        let rejection = Json::<serde_json::Value>::from_bytes_with_config(body.as_bytes(), &config)
+            .unwrap_err();
+        let JsonRejection::StringTooLong(inner) = &rejection else {
+            panic!("Expected a `StringTooLong` rejection, got {rejection:?}");
+        };
+        // Only the location of the offending string is kept around.
+        assert!(!format!("{inner:?}").contains(&huge[..65]));
+
+        let (status, body) = test_utils::problem(rejection).await;
+        assert_eq!(status, 400);
+        assert_eq!(body["type"], "string_too_long");
+        assert_eq!(body["errors"][0]["code"], "string_too_long");
+        assert_eq!(body["errors"][0]["pointer"], "/tags/1");
+    }
+
+    #[tokio::test]
+    async fn test_too_deep() {
+        let config = Config::new().max_depth(2);
+        assert!(Json::<serde_json::Value>::from_bytes_with_config(
+            br#"{"a": [1], "b": {}}"#,
+            &config
+        )
+        .is_ok());
+        let rejection = Json::<serde_json::Value>::from_bytes_with_config(
+            br#"{"a": [1], "b": {"c": [2]}}"#,
+            &config,
+        )
+        .unwrap_err();
+        assert!(matches!(rejection, JsonRejection::TooDeep(_)));
+
+        let (status, body) = test_utils::problem(rejection).await;
+        assert_eq!(status, 400);
+        assert_eq!(body["type"], "too_deep");
+        assert_eq!(body["errors"][0]["code"], "too_deep");
+        assert_eq!(body["errors"][0]["pointer"], "/b/c");
+    }
+
+    #[tokio::test]
+    async fn test_advertised_depth() {
+        let config = Config::new()
+            .max_depth(8)
+            .trust_depth_header(http::HeaderName::from_static("x-json-depth"));
+
+        // The rejection happens before the body is even read.
+        let request = test_utils::json_request(r#"{"name": "Alice", "age": 30}"#)
+            .header("x-json-depth", "40")
+            .extension(config.clone());
+        let rejection = Json::<User>::from_request(request.untouchable(), &())
+            .await
+            .unwrap_err();
+        assert!(matches!(rejection, JsonRejection::TooDeep(_)));
+        let (status, body) = test_utils::problem(rejection).await;
+        assert_eq!(status, 400);
+        assert_eq!(body["type"], "too_deep");
+        assert_eq!(body["errors"][0]["source"], "header");
+        assert_eq!(body["errors"][0]["name"], "x-json-depth");
+
+        // Within the limit, the body goes through as usual.
+        let request = test_utils::json_request(r#"{"name": "Alice", "age": 30}"#)
+            .header("x-json-depth", "1")
+            .extension(config.clone());
+        assert!(Json::<User>::from_request(request.body(), &())
+            .await
+            .is_ok());
+
+        // Likewise for a size advertised beyond the limit by the `Content-Length` header.
+        let request = test_utils::json_request(r#"{"name": "Alice", "age": 30}"#)
+            .header("x-json-depth", "1")
+            .header("content-length", "5000")
+            .extension(config.clone().max_body_size(1024));
+        let rejection = Json::<User>::from_request(request.untouchable(), &())
+            .await
+            .unwrap_err();
+        assert!(matches!(rejection, JsonRejection::ContentTooLarge(_)));
+
+        // The header is ignored unless trusted.
+        let request = test_utils::json_request(r#"{"name": "Alice", "age": 30}"#)
+            .header("x-json-depth", "40")
+            .extension(Config::new().max_depth(8));
+        assert!(Json::<User>::from_request(request.body(), &())
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_strict_byte_order_mark() {
+        let body = "\u{FEFF}{\"name\": \"Alice\", \"age\": 30}";
+        let request = test_utils::json_request(body).extension(Config::new().strict_json(true));
+        let rejection = Json::<User>::from_request(request.body(), &())
+            .await
+            .unwrap_err();
+        assert!(matches!(rejection, JsonRejection::InvalidEncoding(_)));
+
+        let (status, body) = test_utils::problem(rejection).await;
+        assert_eq!(status, 400);
+        assert_eq!(body["errors"][0]["code"], "invalid_encoding");
+    }
+
+    #[tokio::test]
+    async fn test_strict_padding() {
+        let padded = format!(
+            "{}{{\"name\": \"Alice\", \"age\": 30}}{}",
+            " ".repeat(10),
+            "\n".repeat(MAX_PADDING + 1)
+        );
+
+        // Lenient by default.
+        let request = test_utils::json_request(padded.clone());
+        assert!(Json::<User>::from_request(request.body(), &())
+            .await
+            .is_ok());
+
+        let request = test_utils::json_request(padded).extension(Config::new().strict_json(true));
+        let rejection = Json::<User>::from_request(request.body(), &())
+            .await
+            .unwrap_err();
+        assert!(matches!(rejection, JsonRejection::ExcessiveWhitespace(_)));
+
+        let (status, body) = test_utils::problem(rejection).await;
+        assert_eq!(status, 400);
+        assert_eq!(body["errors"][0]["code"], "excessive_whitespace");
+    }
+
+    #[tokio::test]
+    async fn test_strict_content_type_params() {
+        let request = |content_type: &'static str, config: Config| {
+            http::Request::builder()
+                .method("POST")
+                .uri("/")
+                .header(http::header::CONTENT_TYPE, content_type)
+                .extension(config)
+                .body(axum_core::body::Body::from(
+                    r#"{"name": "Alice", "age": 30}"#,
+                ))
+                .unwrap()
+        };
+        let duplicated = "application/json; charset=utf-8; charset=utf-8";
+
+        // Lenient by default.
+        assert!(
+            Json::<User>::from_request(request(duplicated, Config::new()), &())
+                .await
+                .is_ok()
+        );
+
+        let strict = Config::new().strict_json(true);
+        let rejection = Json::<User>::from_request(request(duplicated, strict.clone()), &())
+            .await
+            .unwrap_err();
+        assert!(matches!(rejection, JsonRejection::InvalidContentType(_)));
+
+        let (status, body) = test_utils::problem(rejection).await;
+        assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        assert_eq!(body["type"], "invalid_content_type");
+        assert_eq!(body["received"], duplicated);
+        assert_eq!(body["errors"][0]["source"], "header");
+        assert_eq!(body["errors"][0]["code"], "invalid_content_type");
+        assert_eq!(
+            body["errors"][0]["detail"],
+            "The `charset` parameter of the `Content-Type` header is repeated"
+        );
+
+        for content_type in [
+            "application/json; version=2",
+            "application/json; charset=latin1",
+        ] {
+            let rejection = Json::<User>::from_request(request(content_type, strict.clone()), &())
+                .await
+                .unwrap_err();
+            assert!(matches!(rejection, JsonRejection::InvalidContentType(_)));
+        }
+        let accepted = "application/ld+json; charset=UTF-8; profile=\"https://example.com\"";
+        assert!(Json::<User>::from_request(request(accepted, strict), &())
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_fields_within_limit() {
+        let request = test_utils::json_request(r#"{"a": {"b": 1, "c": 2}, "e": 4}"#)
+            .extension(Config::new().max_object_members(2));
+        let Json(value) = Json::<serde_json::Value>::from_request(request.body(), &())
+            .await
+            .unwrap();
+        assert_eq!(value["a"]["c"], 2);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_root_type() {
+        let request = test_utils::json_request(r#"[{"name": "Alice", "age": 30}]"#)
+            .extension(Config::new().json_root_types([JsonType::Object]));
+        let rejection = Json::<serde_json::Value>::from_request(request.body(), &())
+            .await
+            .unwrap_err();
+        assert!(matches!(rejection, JsonRejection::InvalidRootType(_)));
+
+        let (status, body) = test_utils::problem(rejection).await;
+        assert_eq!(status, 400);
+        assert_eq!(body["type"], "invalid_root_type");
+        assert_eq!(body["detail"], "The request body must be an object");
+        assert_eq!(body["errors"][0]["code"], "invalid_root_type");
+        assert_eq!(body["errors"][0]["pointer"], "");
+        assert_eq!(
+            body["errors"][0]["detail"],
+            "Expected an object at the top level of the request body, but found an array"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_valid_root_type() {
+        let request = test_utils::json_request(r#"{"name": "Alice", "age": 30}"#)
+            .extension(Config::new().json_root_types([JsonType::Object]));
+        let Json(user) = Json::<User>::from_request(request.body(), &())
+            .await
+            .unwrap();
+        assert_eq!(user.name, "Alice");
+    }
+
+    #[tokio::test]
+    async fn test_structured_suffix() {
+        let request = |content_type: &str| {
+            Request::builder()
+                .method("POST")
+                .uri("/")
+                .header(header::CONTENT_TYPE, content_type)
+                .body(axum_core::body::Body::from(
+                    r#"{"name": "Alice", "age": 30}"#,
+                ))
+                .unwrap()
+        };
+        let Json(user) = Json::<User>::from_request(request("application/vnd.acme+json"), &())
+            .await
+            .unwrap();
+        assert_eq!(user.name, "Alice");
+
+        let rejection = Json::<User>::from_request(request("application/vnd.acme+xml"), &())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            rejection,
+            JsonRejection::JsonContentTypeMismatch(_)
+        ));
+
+        let (status, body) = test_utils::problem(rejection).await;
+        assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        assert_eq!(body["type"], "unsupported_media_type");
+        assert_eq!(body["errors"][0]["code"], "content_type_mismatch");
+        assert_eq!(body["errors"][0]["name"], "content-type");
+    }
+
+    #[tokio::test]
+    async fn test_content_type_mismatch_members() {
+        let request = http::Request::builder()
+            .method("POST")
+            .uri("/")
+            .header(header::CONTENT_TYPE, "text/plain")
+            .body(axum_core::body::Body::from(
+                r#"{"name": "Alice", "age": 30}"#,
+            ))
+            .unwrap();
+        let rejection = Json::<User>::from_request(request, &()).await.unwrap_err();
+        let JsonRejection::JsonContentTypeMismatch(mismatch) = &rejection else {
+            panic!("Expected a content type mismatch, got {rejection:?}");
+        };
+        assert_eq!(mismatch.received(), "text/plain");
+
+        let (status, body) = test_utils::problem(rejection).await;
+        assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        assert_eq!(
+            body["expected"],
+            serde_json::json!(["application/json", "application/*+json"])
+        );
+        assert_eq!(body["received"], "text/plain");
+    }
+
+    #[tokio::test]
+    async fn test_ambiguous_content_type() {
+        let request = test_utils::json_request(r#"{"name": "Alice", "age": 30}"#)
+            .header("content-type", "text/plain");
+        let rejection = Json::<User>::from_request(request.body(), &())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            rejection,
+            JsonRejection::AmbiguousJsonContentType(_)
+        ));
+
+        let (status, body) = test_utils::problem(rejection).await;
+        assert_eq!(status, 400);
+        assert_eq!(body["type"], "ambiguous_content_type");
+        assert_eq!(body["errors"][0]["source"], "header");
+        assert_eq!(body["errors"][0]["name"], "content-type");
+        assert_eq!(
+            body["errors"][0]["detail"],
+            "Expected a single `Content-Type` header, but found 2"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_about_blank() {
+        let request = test_utils::json_request(r#"{"name": "Alice", "age": "30"}"#)
+            .extension(Config::new().about_blank(true));
+        let rejection = Json::<User>::from_request(request.body(), &())
+            .await
+            .unwrap_err();
+
+        let (status, body) = test_utils::problem(rejection).await;
+        assert_eq!(status, 400);
+        assert_eq!(body["type"], "about:blank");
+        assert_eq!(
+            body["title"],
+            StatusCode::BAD_REQUEST.canonical_reason().unwrap()
+        );
+        assert_eq!(body["errors"][0]["pointer"], "/age");
+    }
+
+    #[tokio::test]
+    async fn test_dedicated_type_by_default() {
+        let request = test_utils::json_request(r#"{"name": "Alice", "age": "30"}"#);
+        let rejection = Json::<User>::from_request(request.body(), &())
+            .await
+            .unwrap_err();
+
+        let (_, body) = test_utils::problem(rejection).await;
+        assert_eq!(body["type"], "invalid_request");
+        assert_eq!(body["title"], "The request is invalid");
+    }
+
+    #[tokio::test]
+    async fn test_malformed_request() {
+        let request = test_utils::json_request(r#"{"name": "Alice", "age": "#)
+            .extension(Config::new().unprocessable_entity(true));
+        let rejection = Json::<User>::from_request(request.body(), &())
+            .await
+            .unwrap_err();
+
+        let (status, body) = test_utils::problem(rejection).await;
+        assert_eq!(status, 400);
+        assert_eq!(body["type"], "malformed_request");
+        assert_eq!(body["status"], 400);
+        assert_eq!(body["errors"][0]["pointer"], "/age");
+    }
+
+    #[tokio::test]
+    async fn test_syntax_error_position() {
+        // The closing brace is missing.
+        let request = test_utils::json_request("{\"name\": \"Alice\",\n \"age\": 30");
+        let rejection = Json::<User>::from_request(request.body(), &())
+            .await
+            .unwrap_err();
+
+        let (status, body) = test_utils::problem(rejection).await;
+        assert_eq!(status, 400);
+        assert_eq!(body["line"], 2);
+        assert_eq!(body["column"], 10);
+        assert_eq!(body["offset"], 27);
+
+        // ...even past the errors that could be recovered from.
+        let request = test_utils::json_request(r#"{"age": "30", "name": "#);
+        let rejection = Json::<User>::from_request(request.body(), &())
+            .await
+            .unwrap_err();
+        let (_, body) = test_utils::problem(rejection).await;
+        assert_eq!(body["offset"], 21);
+
+        // Well-formed bodies that don't match the schema have no position.
+        let request = test_utils::json_request(r#"{"name": "Alice", "age": "30"}"#);
+        let rejection = Json::<User>::from_request(request.body(), &())
+            .await
+            .unwrap_err();
+        let (_, body) = test_utils::problem(rejection).await;
+        assert!(body.get("line").is_none());
+        assert!(body.get("offset").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_unprocessable_entity() {
+        let request = test_utils::json_request(r#"{"name": "Alice", "age": "30"}"#)
+            .extension(Config::new().unprocessable_entity(true));
+        let rejection = Json::<User>::from_request(request.body(), &())
+            .await
+            .unwrap_err();
+
+        let (status, body) = test_utils::problem(rejection).await;
+        assert_eq!(status, 422);
+        assert_eq!(body["type"], "unprocessable_entity");
+        assert_eq!(body["status"], 422);
+        assert_eq!(body["errors"][0]["code"], "invalid_type");
+        assert_eq!(body["errors"][0]["pointer"], "/age");
+    }
+
+    #[tokio::test]
+    async fn test_status_as_string() {
+        let request = test_utils::json_request(r#"{"name": "Alice", "age": "30"}"#)
+            .extension(Config::new().status_as_string(true));
+        let rejection = Json::<User>::from_request(request.body(), &())
+            .await
+            .unwrap_err();
+        let (status, body) = test_utils::problem(rejection).await;
+        assert_eq!(status, 400);
+        assert_eq!(body["status"], "400");
+
+        let request = test_utils::json_request(r#"{"name": "Alice", "age": "30"}"#);
+        let rejection = Json::<User>::from_request(request.body(), &())
+            .await
+            .unwrap_err();
+        let (_, body) = test_utils::problem(rejection).await;
+        assert_eq!(body["status"], 400);
+    }
+
+    #[tokio::test]
+    async fn test_omit_errors() {
+        let request = test_utils::json_request(r#"{"name": "Alice", "age": "30"}"#)
+            .extension(Config::new().omit_errors(true));
+        let rejection = Json::<User>::from_request(request.body(), &())
+            .await
+            .unwrap_err();
+
+        let (status, body) = test_utils::problem(rejection).await;
+        assert_eq!(status, 400);
+        assert!(body.get("errors").is_none());
+        let mut members: Vec<_> = body.as_object().unwrap().keys().collect();
+        members.sort();
+        assert_eq!(members, ["detail", "status", "title", "type"]);
+    }
+
+    #[tokio::test]
+    async fn test_list_missing_fields() {
+        let request =
+            test_utils::json_request("{}").extension(Config::new().list_missing_fields(true));
+        let rejection = Json::<User>::from_request(request.body(), &())
+            .await
+            .unwrap_err();
+
+        let (status, body) = test_utils::problem(rejection).await;
+        assert_eq!(status, 400);
+        assert_eq!(body["missing"], serde_json::json!(["/name", "/age"]));
+        assert_eq!(body["errors"].as_array().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_list_missing_fields_with_codes() {
+        #[derive(Debug, eserde::Deserialize)]
+        #[allow(dead_code)]
+        struct Signup {
+            #[eserde(code = "EMAIL_REQUIRED")]
+            email: String,
+            profile: Profile,
+        }
+
+        #[derive(Debug, eserde::Deserialize)]
+        #[allow(dead_code)]
+        struct Profile {
+            #[eserde(code = "NAME_REQUIRED")]
+            name: String,
+        }
+
+        let request = test_utils::json_request(r#"{"profile": {}}"#)
+            .extension(Config::new().list_missing_fields(true));
+        let rejection = Json::<Signup>::from_request(request.body(), &())
+            .await
+            .unwrap_err();
+
+        let (_, body) = test_utils::problem(rejection).await;
+        assert_eq!(
+            body["missing"],
+            serde_json::json!(["/profile/name", "/email"])
+        );
+        let codes: Vec<_> = body["errors"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|error| error["code"].as_str().unwrap())
+            .collect();
+        assert_eq!(codes, ["NAME_REQUIRED", "EMAIL_REQUIRED"]);
+    }
+
+    #[tokio::test]
+    async fn test_coalesce_invalid_types() {
+        /// An untagged enum, with a hand-written implementation since
+        /// `eserde` can't derive one: it reports a failure for each variant.
+        #[derive(Debug)]
+        #[allow(dead_code)]
+        enum Id {
+            Number(u64),
+            Flag(bool),
+        }
+
+        impl<'de> serde::Deserialize<'de> for Id {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                let value = serde_json::Value::deserialize(deserializer)?;
+                u64::deserialize(&value)
+                    .map(Id::Number)
+                    .or_else(|_| bool::deserialize(&value).map(Id::Flag))
+                    .map_err(serde::de::Error::custom)
+            }
+        }
+
+        impl<'de> EDeserialize<'de> for Id {
+            fn deserialize_for_errors<D>(deserializer: D) -> Result<(), ()>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                use eserde::reporter::ErrorReporter;
+                use serde::Deserialize;
+
+                let value =
+                    serde_json::Value::deserialize(deserializer).map_err(ErrorReporter::report)?;
+                let number = u64::deserialize(&value).map(drop);
+                let flag = bool::deserialize(&value).map(drop);
+                match (number, flag) {
+                    (Err(number), Err(flag)) => {
+                        ErrorReporter::report(number);
+                        ErrorReporter::report(flag);
+                        Err(())
+                    }
+                    _ => Ok(()),
+                }
+            }
+        }
+
+        #[derive(Debug, eserde::Deserialize)]
+        #[allow(dead_code)]
+        struct Lookup {
+            id: Id,
+        }
+
+        let body = r#"{"id": "x"}"#;
+        let problem = |config: Config| async move {
+            let request = test_utils::json_request(body).extension(config);
+            let rejection = Json::<Lookup>::from_request(request.body(), &())
+                .await
+                .unwrap_err();
+            test_utils::problem(rejection).await.1
+        };
+
+        let body = problem(Config::new()).await;
+        assert_eq!(body["errors"].as_array().unwrap().len(), 2);
+
+        let body = problem(Config::new().coalesce_invalid_types(true)).await;
+        assert_eq!(
+            body["errors"],
+            serde_json::json!([{
+                "detail": "invalid type: string \"x\", expected one of: u64, a boolean",
+                "code": "invalid_type",
+                "source": "body",
+                "pointer": "/id"
+            }])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fail_fast() {
+        let body = r#"{"name": 1, "age": "x"}"#;
+
+        let request = test_utils::json_request(body);
+        let rejection = Json::<User>::from_request(request.body(), &())
+            .await
+            .unwrap_err();
+        let (_, problem) = test_utils::problem(rejection).await;
+        assert_eq!(problem["errors"].as_array().unwrap().len(), 2);
+
+        let request = test_utils::json_request(body).extension(Config::new().fail_fast(true));
+        let rejection = Json::<User>::from_request(request.body(), &())
+            .await
+            .unwrap_err();
+        let (status, problem) = test_utils::problem(rejection).await;
+        assert_eq!(status, 400);
+        assert_eq!(problem["type"], "invalid_request");
+        let errors = problem["errors"].as_array().unwrap();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0]["pointer"], "/name");
+        assert_eq!(errors[0]["code"], "invalid_type");
+    }
+
+    #[derive(Debug, eserde::Deserialize)]
+    #[serde(rename_all = "snake_case")]
+    enum Status {
+        Active,
+        Suspended,
+    }
+
+    #[derive(Debug, eserde::Deserialize)]
+    #[allow(dead_code)]
+    struct Subscription {
+        status: Status,
+        seats: u32,
+    }
+
+    #[tokio::test]
+    async fn test_case_insensitive_variants() {
+        let body = r#"{"status": "Active", "seats": 3}"#;
+        let request = test_utils::json_request(body);
+        assert!(Json::<Subscription>::from_request(request.body(), &())
+            .await
+            .is_err());
+
+        let request =
+            test_utils::json_request(body).extension(Config::new().case_insensitive_variants(true));
+        let Json(subscription) = Json::<Subscription>::from_request(request.body(), &())
+            .await
+            .unwrap();
+        assert!(matches!(subscription.status, Status::Active));
+    }
+
+    #[tokio::test]
+    async fn test_case_insensitive_variants_fail_fast() {
+        let config = Config::new()
+            .case_insensitive_variants(true)
+            .fail_fast(true);
+        let request = test_utils::json_request(r#"{"status": "Active", "seats": 3}"#)
+            .extension(config.clone());
+        let Json(subscription) = Json::<Subscription>::from_request(request.body(), &())
+            .await
+            .unwrap();
+        assert!(matches!(subscription.status, Status::Active));
+
+        let request =
+            test_utils::json_request(r#"{"status": "Pending", "seats": "x"}"#).extension(config);
+        let rejection = Json::<Subscription>::from_request(request.body(), &())
+            .await
+            .unwrap_err();
+        let (_, problem) = test_utils::problem(rejection).await;
+        let errors = problem["errors"].as_array().unwrap();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0]["pointer"], "/status");
+    }
+
+    #[tokio::test]
+    async fn test_case_insensitive_variants_internally_tagged() {
+        #[derive(Debug, eserde::Deserialize)]
+        #[serde(tag = "type")]
+        #[allow(dead_code)]
+        enum Event {
+            Created { id: u32 },
+        }
+
+        let config = Config::new().case_insensitive_variants(true);
+        let request =
+            test_utils::json_request(r#"{"type": "created", "id": 1}"#).extension(config.clone());
+        assert!(Json::<Event>::from_request(request.body(), &())
+            .await
+            .is_err());
+        let request = test_utils::json_request(r#"{"type": "Created", "id": 1}"#).extension(config);
+        assert!(Json::<Event>::from_request(request.body(), &())
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_case_insensitive_unknown_variant() {
+        let request = test_utils::json_request(r#"{"status": "Pending", "seats": "x"}"#)
+            .extension(Config::new().case_insensitive_variants(true));
+        let rejection = Json::<Subscription>::from_request(request.body(), &())
+            .await
+            .unwrap_err();
+
+        let (status, problem) = test_utils::problem(rejection).await;
+        assert_eq!(status, 400);
+        let errors = problem["errors"].as_array().unwrap();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0]["pointer"], "/status");
+        assert_eq!(errors[0]["code"], "unknown_variant");
+        let detail = errors[0]["detail"].as_str().unwrap();
+        assert!(
+            detail.contains("expected `active` or `suspended`"),
+            "{detail}"
+        );
+        assert_eq!(errors[1]["pointer"], "/seats");
+    }
+
+    #[derive(Debug, eserde::Deserialize)]
+    #[serde(tag = "type", content = "content", rename_all = "snake_case")]
+    #[allow(dead_code)]
+    enum Shape {
+        Circle { radius: u32 },
+        Square(u32),
+    }
+
+    #[derive(Debug, eserde::Deserialize)]
+    #[allow(dead_code)]
+    struct Drawing {
+        shape: Shape,
+        name: String,
+    }
+
+    async fn drawing_errors(body: &'static str) -> Vec<(String, String)> {
+        let request = test_utils::json_request(body);
+        let rejection = Json::<Drawing>::from_request(request.body(), &())
+            .await
+            .unwrap_err();
+        let (_, problem) = test_utils::problem(rejection).await;
+        problem["errors"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|error| {
+                (
+                    error["pointer"].as_str().unwrap().to_owned(),
+                    error["code"].as_str().unwrap().to_owned(),
+                )
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_adjacently_tagged_bad_content() {
+        let errors = drawing_errors(
+            r#"{"shape": {"type": "circle", "content": {"radius": "x"}}, "name": "Sun"}"#,
+        )
+        .await;
+        assert_eq!(
+            errors,
+            [(
+                "/shape/content/radius".to_owned(),
+                "invalid_type".to_owned()
+            )]
+        );
+
+        let errors =
+            drawing_errors(r#"{"shape": {"type": "square", "content": -1}, "name": "Box"}"#).await;
+        assert_eq!(
+            errors,
+            [("/shape/content".to_owned(), "out_of_range".to_owned())]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_adjacently_tagged_unknown_tag() {
+        let errors =
+            drawing_errors(r#"{"shape": {"type": "hexagon", "content": 6}, "name": 1}"#).await;
+        assert_eq!(
+            errors,
+            [
+                ("/shape/type".to_owned(), "unknown_variant".to_owned()),
+                ("/name".to_owned(), "invalid_type".to_owned()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_field() {
+        #[derive(Debug, eserde::Deserialize)]
+        #[allow(dead_code)]
+        struct Order {
+            customer: User,
+            quantity: u32,
+        }
+
+        let request = test_utils::json_request(
+            r#"{"customer": {"name": "Alice", "age": 30, "name": "Bob"}, "quantity": -1}"#,
+        );
+        let rejection = Json::<Order>::from_request(request.body(), &())
+            .await
+            .unwrap_err();
+
+        let (status, body) = test_utils::problem(rejection).await;
+        assert_eq!(status, 400);
+        let errors = body["errors"].as_array().unwrap();
+        assert_eq!(errors[0]["code"], "duplicate_field");
+        assert_eq!(errors[0]["pointer"], "/customer/name");
+        // The rest of the body is still checked.
+        assert_eq!(errors[1]["pointer"], "/quantity");
+        assert_eq!(errors.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_nested_collection_pointers() {
+        #[derive(Debug, eserde::Deserialize)]
+        #[allow(dead_code)]
+        struct Order {
+            items: Vec<Item>,
+        }
+
+        #[derive(Debug, eserde::Deserialize)]
+        #[allow(dead_code)]
+        struct Item {
+            tags: Vec<u32>,
+            #[serde(flatten)]
+            details: Details,
+        }
+
+        #[derive(Debug, eserde::Deserialize)]
+        #[allow(dead_code)]
+        struct Details {
+            #[serde(default)]
+            sizes: Vec<Vec<u8>>,
+        }
+
+        let request = test_utils::json_request(
+            r#"{"items": [{"tags": []}, {"tags": [1]}, {"tags": ["x", 2], "sizes": [[1], [2, -3]]}]}"#,
+        );
+        let rejection = Json::<Order>::from_request(request.body(), &())
+            .await
+            .unwrap_err();
+
+        let (_, body) = test_utils::problem(rejection).await;
+        let pointers: Vec<_> = body["errors"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|error| error["pointer"].as_str().unwrap())
+            .collect();
+        assert_eq!(pointers, ["/items/2/tags/0", "/items/2/sizes/1/1"]);
+    }
+
+    #[tokio::test]
+    async fn test_errors_by_pointer() {
+        let request = test_utils::json_request(r#"{"name": 5, "age": "30"}"#)
+            .extension(Config::new().errors_by_pointer(true));
+        let rejection = Json::<User>::from_request(request.body(), &())
+            .await
+            .unwrap_err();
+
+        let (_, body) = test_utils::problem(rejection).await;
+        let errors = body["errors"].as_object().unwrap();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(
+            errors["/age"],
+            serde_json::json!(["invalid type: string \"30\", expected u8 at line 1 column 23"])
+        );
+        assert!(errors.contains_key("/name"));
+
+        // Problems at the same location are merged.
+        let request =
+            test_utils::json_request("{}").extension(Config::new().errors_by_pointer(true));
+        let rejection = Json::<User>::from_request(request.body(), &())
+            .await
+            .unwrap_err();
+        let (_, body) = test_utils::problem(rejection).await;
+        assert_eq!(
+            body["errors"],
+            serde_json::json!({"": ["missing field `name`", "missing field `age`"]})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_documentation_links() {
+        let request = test_utils::json_request(r#"{"age": "30"}"#).extension(
+            Config::new().documentation("invalid_type", "https://docs.example.com/errors/{code}"),
+        );
+        let rejection = Json::<User>::from_request(request.body(), &())
+            .await
+            .unwrap_err();
+
+        let (_, body) = test_utils::problem(rejection).await;
+        let errors = body["errors"].as_array().unwrap();
+        let invalid_type = errors.iter().find(|e| e["code"] == "invalid_type").unwrap();
+        assert_eq!(
+            invalid_type["documentation"],
+            "https://docs.example.com/errors/invalid_type"
+        );
+        let missing_field = errors
+            .iter()
+            .find(|e| e["code"] == "missing_field")
+            .unwrap();
+        assert!(missing_field.get("documentation").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_debug_raw_body() {
+        let body = r#"{"name": 42, "age": "thirty"}"#;
+
+        let request = test_utils::json_request(body);
+        let rejection = Json::<User>::from_request(request.body(), &())
+            .await
+            .unwrap_err();
+        let (_, problem) = test_utils::problem(rejection).await;
+        assert!(problem.get("_debug_raw").is_none());
+
+        let request = test_utils::json_request(body).extension(Config::new().debug_raw_body(12));
+        let rejection = Json::<User>::from_request(request.body(), &())
+            .await
+            .unwrap_err();
+        let (_, problem) = test_utils::problem(rejection).await;
+        if cfg!(debug_assertions) {
+            assert_eq!(problem["_debug_raw"], r#"{"name": 42,…"#);
+        } else {
+            assert!(problem.get("_debug_raw").is_none());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_code_override() {
+        #[derive(Debug, eserde::Deserialize)]
+        #[allow(dead_code)]
+        struct Signup {
+            name: String,
+            #[eserde(code = "AGE_INVALID")]
+            age: u8,
+        }
+
+        let codes = |body: &'static str| async move {
+            let request = test_utils::json_request(body);
+            let rejection = Json::<Signup>::from_request(request.body(), &())
+                .await
+                .unwrap_err();
+            let (_, problem) = test_utils::problem(rejection).await;
+            problem["errors"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|error| error["code"].as_str().unwrap().to_owned())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            codes(r#"{"name": 1, "age": "x"}"#).await,
+            ["invalid_type", "AGE_INVALID"]
+        );
+        assert_eq!(
+            codes(r#"{"name": "Ada", "age": 300}"#).await,
+            ["AGE_INVALID"]
+        );
+        assert_eq!(codes(r#"{"name": "Ada"}"#).await, ["AGE_INVALID"]);
+    }
+
+    #[tokio::test]
+    async fn test_log_request_target() {
+        use std::sync::Mutex;
+        use tracing_subscriber::layer::{Context, SubscriberExt};
+
+        /// Collects the fields of the rejection events.
+        #[derive(Clone, Default)]
+        struct Fields(Arc<Mutex<Vec<(String, String)>>>);
+
+        impl tracing::field::Visit for Fields {
+            fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+                self.record_str(field, &format!("{value:?}"));
+            }
+
+            fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+                let mut fields = self.0.lock().unwrap();
+                fields.push((field.name().to_owned(), value.to_owned()));
+            }
+        }
+
+        impl<S: tracing::Subscriber> tracing_subscriber::Layer<S> for Fields {
+            fn on_event(&self, event: &tracing::Event<'_>, _: Context<'_, S>) {
+                if event.metadata().target() == "eserde_axum::json::rejection" {
+                    event.record(&mut self.clone());
+                }
+            }
+        }
+
+        let fields = Fields::default();
+        let _guard =
+            tracing::subscriber::set_default(tracing_subscriber::registry().with(fields.clone()));
+
+        let collected = &fields.0;
+        let reject = |config: Config| async move {
+            let request = http::Request::builder()
+                .method("PATCH")
+                .uri("/users/42?verbose=true")
+                .header(header::CONTENT_TYPE, "application/json")
+                .extension(config)
+                .body(axum_core::body::Body::from(r#"{"name": 1}"#))
+                .unwrap();
+            let rejection = Json::<User>::from_request(request, &()).await.unwrap_err();
+            let _ = rejection.into_response();
+            std::mem::take(&mut *collected.lock().unwrap())
+        };
+        let field = |fields: &[(String, String)], name: &str| {
+            fields
+                .iter()
+                .find(|(field, _)| field == name)
+                .map(|(_, value)| value.clone())
+        };
+
+        let logged = reject(Config::new()).await;
+        assert_eq!(field(&logged, "status").as_deref(), Some("400"));
+        assert_eq!(field(&logged, "http.method"), None);
+
+        let logged = reject(Config::new().log_request_target(true)).await;
+        assert_eq!(field(&logged, "http.method").as_deref(), Some("PATCH"));
+        assert_eq!(field(&logged, "http.target").as_deref(), Some("/users/42"));
+    }
+
+    #[tokio::test]
+    async fn test_trailing_data() {
+        for config in [
+            Config::new(),
+            Config::new().fail_fast(true),
+            Config::new().case_insensitive_variants(true),
+            Config::new()
+                .case_insensitive_variants(true)
+                .fail_fast(true),
+        ] {
+            let request = test_utils::json_request(r#"{"a":1} garbage"#).extension(config);
+            let rejection = Json::<serde_json::Value>::from_request(request.body(), &())
+                .await
+                .unwrap_err();
+            let JsonRejection::TrailingData(error) = &rejection else {
+                panic!("Expected a trailing data error, got {rejection:?}");
+            };
+            assert_eq!(error.offset(), 8);
+        }
+
+        let request = test_utils::json_request(r#"{"a":1} garbage"#);
+        let rejection = Json::<serde_json::Value>::from_request(request.body(), &())
+            .await
+            .unwrap_err();
+
+        let (status, body) = test_utils::problem(rejection).await;
+        assert_eq!(status, 400);
+        assert_eq!(body["type"], "trailing_data");
+        assert_eq!(body["offset"], 8);
+    }
+
+    #[derive(Debug, eserde::Deserialize)]
+    #[allow(dead_code)]
+    struct Location {
+        coordinates: [f64; 3],
+    }
+
+    #[tokio::test]
+    async fn test_array_length() {
+        let request = test_utils::json_request(r#"{"coordinates": [1.0, 2.0]}"#);
+        let rejection = Json::<Location>::from_request(request.body(), &())
+            .await
+            .unwrap_err();
+
+        let (status, body) = test_utils::problem(rejection).await;
+        assert_eq!(status, 400);
+        let error = &body["errors"][0];
+        assert_eq!(error["code"], "invalid_length");
+        assert_eq!(error["pointer"], "/coordinates");
+        assert_eq!(
+            error["length"],
+            serde_json::json!({"expected": 3, "actual": 2})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_array_element() {
+        let request = test_utils::json_request(r#"{"coordinates": [1.0, "north", 3.0]}"#);
+        let rejection = Json::<Location>::from_request(request.body(), &())
+            .await
+            .unwrap_err();
+
+        let (status, body) = test_utils::problem(rejection).await;
+        assert_eq!(status, 400);
+        let errors = body["errors"].as_array().unwrap();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0]["code"], "invalid_type");
+        assert_eq!(errors[0]["pointer"], "/coordinates/1");
+    }
+}
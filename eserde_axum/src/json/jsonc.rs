@@ -0,0 +1,278 @@
+use std::ops::Deref;
+use std::ops::DerefMut;
+
+use crate::config::Config;
+
+use super::json_::check_json_headers;
+use super::*;
+use axum_core::extract::FromRequest;
+use axum_core::extract::Request;
+use eserde::EDeserialize;
+use serde::de::DeserializeOwned;
+
+/// JSON with comments (JSONC) extractor.
+///
+/// It behaves like [`Json`], but the request body may contain
+/// `// line` and `/* block */` comments, as well as trailing commas in
+/// arrays and objects. They are stripped before the body is deserialized.
+///
+/// Comments and trailing commas are replaced with whitespace rather than removed,
+/// so the line and column reported for syntax errors match the original document.
+/// Type errors are accumulated and reported exactly as they would be for [`Json`].
+///
+/// The request must have a `Content-Type: application/json` (or similar) header.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{routing::put, Router};
+/// use eserde_axum::Jsonc;
+///
+/// #[derive(eserde::Deserialize)]
+/// struct Settings {
+///     theme: String,
+///     font_size: u8,
+/// }
+///
+/// async fn upload_settings(Jsonc(settings): Jsonc<Settings>) {
+///     // settings is a `Settings`
+/// }
+///
+/// let app = Router::new().route("/settings", put(upload_settings));
+/// # let _: Router = app;
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(docsrs, doc(cfg(feature = "jsonc")))]
+#[must_use]
+pub struct Jsonc<T>(pub T);
+
+impl<T, S> FromRequest<S> for Jsonc<T>
+where
+    T: DeserializeOwned,
+    T: for<'de> EDeserialize<'de>,
+    S: Send + Sync,
+{
+    type Rejection = JsonRejection;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let config = Config::from_request(&req);
+        check_json_headers(req.headers(), &config)?;
+        let (bytes, _permit) =
+            crate::body::read_body::<_, Self::Rejection>(req, state, &config).await?;
+        Self::from_bytes_with_config(&bytes, &config)
+    }
+}
+
+impl<T> Jsonc<T>
+where
+    T: DeserializeOwned,
+    T: for<'de> EDeserialize<'de>,
+{
+    /// Construct a `Jsonc<T>` from a byte slice. Most users should prefer to use the `FromRequest` impl
+    /// but special cases may require first extracting a `Request` into `Bytes` then optionally
+    /// constructing a `Jsonc<T>`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, JsonRejection> {
+        Self::from_bytes_with_config(bytes, &Config::default())
+    }
+
+    /// Like [`from_bytes`](Self::from_bytes), but honoring the settings
+    /// specified in the [`Config`].
+    fn from_bytes_with_config(bytes: &[u8], config: &Config) -> Result<Self, JsonRejection> {
+        let Json(value) = Json::from_bytes_with_config(&strip(bytes), config)?;
+        Ok(Jsonc(value))
+    }
+}
+
+/// Blank out comments and trailing commas, turning a JSONC document into plain JSON.
+///
+/// Every stripped byte is replaced with a space (line breaks are kept as they are),
+/// so the output has the same length and the same line structure as the input.
+fn strip(input: &[u8]) -> Vec<u8> {
+    let mut output = input.to_vec();
+    blank_comments(&mut output);
+    blank_trailing_commas(&mut output);
+    output
+}
+
+/// Replace `//` and `/* */` comments with whitespace.
+///
+/// Unterminated block comments are left untouched, for the parser to report them.
+fn blank_comments(bytes: &mut [u8]) {
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'"' => i = skip_string(bytes, i),
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                let end = bytes[i..]
+                    .iter()
+                    .position(|&b| b == b'\n')
+                    .map_or(bytes.len(), |offset| i + offset);
+                blank(&mut bytes[i..end]);
+                i = end;
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                let Some(offset) = bytes[i + 2..].windows(2).position(|w| w == b"*/") else {
+                    return;
+                };
+                let end = i + 2 + offset + 2;
+                blank(&mut bytes[i..end]);
+                i = end;
+            }
+            _ => i += 1,
+        }
+    }
+}
+
+/// Replace commas that are directly followed by the end of an array or an object
+/// with whitespace.
+///
+/// Comments must have been blanked out already.
+fn blank_trailing_commas(bytes: &mut [u8]) {
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'"' => i = skip_string(bytes, i),
+            b',' => {
+                let next = bytes[i + 1..]
+                    .iter()
+                    .find(|b| !matches!(b, b' ' | b'\t' | b'\n' | b'\r'));
+                if matches!(next, Some(b'}' | b']')) {
+                    bytes[i] = b' ';
+                }
+                i += 1;
+            }
+            _ => i += 1,
+        }
+    }
+}
+
+/// Given the position of the opening quote of a string,
+/// return the position right after its closing quote.
+fn skip_string(bytes: &[u8], start: usize) -> usize {
+    let mut i = start + 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'"' => return i + 1,
+            _ => i += 1,
+        }
+    }
+    bytes.len()
+}
+
+fn blank(bytes: &mut [u8]) {
+    for b in bytes.iter_mut().filter(|b| !matches!(b, b'\n' | b'\r')) {
+        *b = b' ';
+    }
+}
+
+impl<T> Deref for Jsonc<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Jsonc<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T> From<T> for Jsonc<T> {
+    fn from(inner: T) -> Self {
+        Self(inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils;
+
+    #[derive(Debug, PartialEq, eserde::Deserialize)]
+    struct Settings {
+        theme: String,
+        font_size: u8,
+        plugins: Vec<String>,
+    }
+
+    const COMMENTED: &str = r#"{
+    // The colour scheme of the editor.
+    "theme": "dark // not a comment",
+    /* Pick a size
+       that suits your screen. */
+    "font_size": 14,
+    "plugins": [
+        "git",
+        "lsp", // The language server.
+    ],
+}"#;
+
+    #[test]
+    fn test_strip_preserves_offsets() {
+        let stripped = strip(COMMENTED.as_bytes());
+        assert_eq!(stripped.len(), COMMENTED.len());
+        assert_eq!(
+            stripped.iter().filter(|&&b| b == b'\n').count(),
+            COMMENTED.matches('\n').count()
+        );
+        serde_json::from_slice::<serde_json::Value>(&stripped).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_commented_document() {
+        let request = test_utils::json_request(COMMENTED);
+        let Jsonc(settings) = Jsonc::<Settings>::from_request(request.body(), &())
+            .await
+            .unwrap();
+        assert_eq!(
+            settings,
+            Settings {
+                theme: "dark // not a comment".into(),
+                font_size: 14,
+                plugins: vec!["git".into(), "lsp".into()],
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_type_error_after_comment() {
+        let request = test_utils::json_request(
+            r#"{
+    /* The colour scheme of the editor. */
+    "theme": 1,
+    // In pixels.
+    "font_size": "large",
+    "plugins": [],
+}"#,
+        );
+        let rejection = Jsonc::<Settings>::from_request(request.body(), &())
+            .await
+            .unwrap_err();
+        assert!(matches!(rejection, JsonRejection::JsonError(_)));
+
+        let (status, body) = test_utils::problem(rejection).await;
+        assert_eq!(status, 400);
+        assert_eq!(body["type"], "invalid_request");
+        let pointers: Vec<_> = body["errors"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|error| error["pointer"].as_str().unwrap())
+            .collect();
+        assert_eq!(pointers, ["/theme", "/font_size"]);
+    }
+
+    #[test]
+    fn test_syntax_error_position() {
+        let Err(JsonRejection::JsonError(error)) = Jsonc::<Settings>::from_bytes(
+            b"{\n  /* A comment */ \"theme\": \"dark\"\n  // Missing comma\n  \"font_size\": 14\n}",
+        ) else {
+            panic!("Expected a deserialization error");
+        };
+        let message = error.errors.iter().next().unwrap().message().to_owned();
+        assert!(message.ends_with("line 4 column 3"), "{message}");
+    }
+}
@@ -0,0 +1,206 @@
+//! Structural limits enforced on JSON documents before they are deserialized.
+use std::fmt;
+
+use serde::de::{DeserializeSeed, Deserializer, Error, MapAccess, SeqAccess, Visitor};
+
+use crate::config::Config;
+use crate::details::{escape_token, pointer};
+
+/// A structural limit that the request body doesn't honor.
+pub(crate) enum Violation {
+    /// An object has more members than allowed.
+    TooManyMembers {
+        /// The JSON pointer to the offending object.
+        pointer: String,
+        max: usize,
+    },
+    /// A string is longer than allowed.
+    StringTooLong {
+        /// The JSON pointer to the offending string.
+        pointer: String,
+        max: usize,
+    },
+    /// Objects and arrays are nested deeper than allowed.
+    TooDeep {
+        /// The JSON pointer to the first container beyond the limit.
+        pointer: String,
+        max: usize,
+    },
+}
+
+/// Walk the JSON document to check that it honors the limits specified in the configuration.
+///
+/// This is an extra pass over the document, on top of the one deserializing it.
+/// Values aren't built along the way, but object keys are copied to locate violations.
+/// Syntax errors are ignored: they'll be reported when deserializing
+/// into the target type.
+pub(crate) fn check(bytes: &[u8], config: &Config) -> Result<(), Violation> {
+    let mut scan = Scan {
+        config,
+        path: Vec::new(),
+        depth: 0,
+        violation: None,
+    };
+    let mut de = serde_json::Deserializer::from_slice(bytes);
+    let _ = (&mut scan).deserialize(&mut de);
+    match scan.violation {
+        Some(violation) => Err(violation),
+        None => Ok(()),
+    }
+}
+
+struct Scan<'a> {
+    config: &'a Config,
+    /// The path to the value that's currently being visited.
+    path: Vec<String>,
+    /// The number of objects and arrays enclosing the value that's currently being visited.
+    depth: usize,
+    violation: Option<Violation>,
+}
+
+impl Scan<'_> {
+    /// The JSON pointer to the value that's currently being visited.
+    fn pointer(&self) -> String {
+        pointer(self.path.iter().map(|token| escape_token(token)))
+    }
+
+    fn fail<E: Error>(&mut self, violation: Violation) -> E {
+        self.violation = Some(violation);
+        E::custom("the request body violates the configured limits")
+    }
+
+    /// Step into an object or an array, checking the depth limit.
+    fn enter<E: Error>(&mut self) -> Result<(), E> {
+        self.depth += 1;
+        match self.config.settings.max_depth {
+            Some(max) if self.depth > max.get() => Err(self.fail(Violation::TooDeep {
+                pointer: self.pointer(),
+                max: max.get(),
+            })),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for &mut Scan<'_> {
+    type Value = ();
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for &mut Scan<'_> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("any JSON value")
+    }
+
+    fn visit_bool<E>(self, _v: bool) -> Result<Self::Value, E> {
+        Ok(())
+    }
+
+    fn visit_i64<E>(self, _v: i64) -> Result<Self::Value, E> {
+        Ok(())
+    }
+
+    fn visit_u64<E>(self, _v: u64) -> Result<Self::Value, E> {
+        Ok(())
+    }
+
+    fn visit_f64<E>(self, _v: f64) -> Result<Self::Value, E> {
+        Ok(())
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        if let Some(max) = self.config.settings.max_string_length {
+            // Counting stops as soon as the limit is exceeded.
+            if v.chars().nth(max.get()).is_some() {
+                return Err(self.fail(Violation::StringTooLong {
+                    pointer: self.pointer(),
+                    max: max.get(),
+                }));
+            }
+        }
+        Ok(())
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E> {
+        Ok(())
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        self.enter()?;
+        let mut index = 0usize;
+        loop {
+            self.path.push(index.to_string());
+            let element = seq.next_element_seed(&mut *self)?;
+            self.path.pop();
+            if element.is_none() {
+                self.depth -= 1;
+                return Ok(());
+            }
+            index += 1;
+        }
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        self.enter()?;
+        let mut n_members = 0usize;
+        while let Some(key) = map.next_key::<Key>()? {
+            n_members += 1;
+            if let Some(max) = self.config.settings.max_object_members {
+                if n_members > max.get() {
+                    return Err(self.fail(Violation::TooManyMembers {
+                        pointer: self.pointer(),
+                        max: max.get(),
+                    }));
+                }
+            }
+            self.path.push(key.0);
+            map.next_value_seed(&mut *self)?;
+            self.path.pop();
+        }
+        self.depth -= 1;
+        Ok(())
+    }
+}
+
+/// An object key, as a string.
+struct Key(String);
+
+impl<'de> serde::Deserialize<'de> for Key {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct KeyVisitor;
+
+        impl Visitor<'_> for KeyVisitor {
+            type Value = Key;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("an object key")
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E> {
+                Ok(Key(v.to_owned()))
+            }
+        }
+
+        deserializer.deserialize_str(KeyVisitor)
+    }
+}
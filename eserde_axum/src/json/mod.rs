@@ -1,7 +1,67 @@
 //! Supporting types for the [`Json`] extractor.
+#[cfg(any(feature = "ndjson", feature = "multipart"))]
+pub(crate) mod batch;
+mod best_effort;
+mod borrowed;
+mod case_insensitive;
+#[cfg(feature = "channel")]
+mod channel;
+mod created;
+mod default;
+mod empty;
+pub(crate) mod identity;
 mod json_;
+#[cfg(feature = "jsonc")]
+mod jsonc;
+mod limits;
+mod profile;
 mod rejections;
+mod root;
+#[cfg(feature = "jsonschema")]
+mod schema;
+mod seeded;
+#[cfg(feature = "stream")]
+mod stream;
+mod then_bytes;
+mod validate;
+mod with_value;
 
+#[cfg(any(feature = "ndjson", feature = "multipart"))]
+pub use batch::DocumentError;
+#[cfg(feature = "ndjson")]
+pub use batch::LineNumber;
+#[cfg(feature = "multipart")]
+pub use batch::PartIndex;
+pub use best_effort::BestEffort;
+pub use borrowed::BorrowedJson;
+#[cfg(feature = "channel")]
+#[doc(hidden)]
+pub use channel::JsonChannel;
+#[doc(hidden)]
+pub use created::Created;
+pub use default::default_some;
+pub use empty::empty_as_none;
+pub use identity::{IdentifiedJson, ProblemIdentity};
+#[cfg(feature = "combined")]
+pub(crate) use json_::check_document;
+#[cfg(any(feature = "combined", feature = "stream"))]
+pub(crate) use json_::check_json_headers;
 #[doc(hidden)]
 pub use json_::Json;
+#[cfg(feature = "jsonc")]
+#[doc(hidden)]
+pub use jsonc::Jsonc;
+pub use profile::Profile;
 pub use rejections::*;
+pub use root::JsonType;
+#[cfg(feature = "jsonschema")]
+pub use schema::{SchemaJson, WithSchema};
+pub use seeded::{Seeded, SeededJson};
+#[cfg(feature = "stream")]
+#[doc(hidden)]
+pub use stream::JsonStream;
+#[cfg(feature = "stream")]
+pub use stream::{ElementError, JsonStreamError};
+pub use then_bytes::JsonThenBytes;
+pub use validate::ValidateOnly;
+pub use with_value::JsonWithValue;
@@ -0,0 +1,118 @@
+use std::convert::Infallible;
+
+use axum_core::extract::FromRequestParts;
+use http::request::Parts;
+use http::HeaderMap;
+
+/// Extractor for the `profile` parameter of a JSON `Content-Type`,
+/// e.g. `application/json; profile="https://www.w3.org/ns/json-ld#compacted"`.
+///
+/// [`Json`](super::Json) accepts such requests as regular JSON requests, ignoring the
+/// parameter: add this extractor to the handler (before `Json`, which consumes the
+/// request) to find out which profile the client followed.
+///
+/// Extraction never fails: it yields `None` if the request has no (or more than one)
+/// `Content-Type` header, if the header can't be parsed or if it has no `profile` parameter.
+/// The content type itself isn't checked, that's up to the body extractor.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{routing::post, Router};
+/// use eserde_axum::json::Profile;
+/// use eserde_axum::Json;
+///
+/// #[derive(eserde::Deserialize)]
+/// struct Person {
+///     name: String,
+/// }
+///
+/// async fn create_person(Profile(profile): Profile, Json(person): Json<Person>) {
+///     if let Some(profile) = profile {
+///         tracing::info!(%profile, "The client sent a JSON-LD document");
+///     }
+/// }
+///
+/// let app = Router::new().route("/people", post(create_person));
+/// # let _: Router = app;
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+pub struct Profile(pub Option<String>);
+
+impl Profile {
+    /// Parse the `profile` parameter of the `Content-Type` header, if any.
+    pub(crate) fn from_headers(headers: &HeaderMap) -> Self {
+        let mut content_types = headers.get_all(http::header::CONTENT_TYPE).iter();
+        let (Some(content_type), None) = (content_types.next(), content_types.next()) else {
+            return Self(None);
+        };
+        let profile = content_type
+            .to_str()
+            .ok()
+            .and_then(|content_type| content_type.parse::<mime::Mime>().ok())
+            .and_then(|mime| Some(mime.get_param("profile")?.as_str().to_owned()));
+        Self(profile)
+    }
+}
+
+impl<S> FromRequestParts<S> for Profile
+where
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(Self::from_headers(&parts.headers))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_utils, Json};
+    use axum_core::extract::FromRequest;
+
+    const JSON_LD: &str =
+        r#"application/ld+json; profile="https://www.w3.org/ns/json-ld#compacted""#;
+
+    #[derive(Debug, eserde::Deserialize)]
+    struct Person {
+        name: String,
+    }
+
+    #[tokio::test]
+    async fn test_profile_is_accepted() {
+        for content_type in [
+            r#"application/json; profile="https://example.com/person""#,
+            JSON_LD,
+        ] {
+            let request = http::Request::builder()
+                .method("POST")
+                .uri("/")
+                .header(http::header::CONTENT_TYPE, content_type)
+                .body(axum_core::body::Body::from(r#"{"name": "Alice"}"#))
+                .unwrap();
+            let (mut parts, body) = request.into_parts();
+            let Profile(profile) = Profile::from_request_parts(&mut parts, &()).await.unwrap();
+            assert!(profile.is_some_and(|profile| profile.starts_with("https://")));
+
+            let request = http::Request::from_parts(parts, body);
+            let Json(person) = Json::<Person>::from_request(request, &()).await.unwrap();
+            assert_eq!(person.name, "Alice");
+        }
+    }
+
+    #[test]
+    fn test_profile_value() {
+        let mut headers = HeaderMap::new();
+        headers.insert(http::header::CONTENT_TYPE, JSON_LD.parse().unwrap());
+        assert_eq!(
+            Profile::from_headers(&headers),
+            Profile(Some("https://www.w3.org/ns/json-ld#compacted".into()))
+        );
+
+        let request = test_utils::json_request("{}").body();
+        assert_eq!(Profile::from_headers(request.headers()), Profile(None));
+    }
+}
@@ -1,10 +1,14 @@
-use axum_core::extract::rejection::{BytesRejection, FailedToBufferBody};
-use eserde::DeserializationErrors;
-use http::header::CONTENT_TYPE;
+use axum_core::extract::rejection::BytesRejection;
+use eserde::{DeserializationError, DeserializationErrors};
+use http::{header::CONTENT_TYPE, StatusCode};
 
+use super::limits::Violation;
+use super::JsonType;
+
+use crate::config::Config;
 use crate::details::{
-    InvalidRequest, ProblemDetails, Source, ValidationError, ValidationErrors,
-    INTERNAL_SERVER_ERROR,
+    bytes_rejection_response, InvalidRequest, ProblemDetails, Source, SyntaxError,
+    UnsupportedMediaType, ValidationError, ValidationErrors,
 };
 
 #[doc(hidden)]
@@ -23,6 +27,24 @@ macro_rules! __log_rejection {
             );
         }
     };
+    (
+        rejection_type = $ty:ident,
+        status = $status:expr,
+        config = $config:expr,
+    ) => {
+        {
+            let (method, target) = $config.request_target();
+            tracing::event!(
+                target: "eserde_axum::json::rejection",
+                tracing::Level::TRACE,
+                status = $status.as_u16(),
+                rejection_type = ::std::any::type_name::<$ty>(),
+                http.method = method,
+                http.target = target,
+                "rejecting request",
+            );
+        }
+    };
 }
 
 #[derive(Debug)]
@@ -31,37 +53,78 @@ macro_rules! __log_rejection {
 ///
 /// This rejection is used if the request body couldn't be deserialized
 /// into the target type.
-pub struct JsonError(pub(crate) DeserializationErrors);
+pub struct JsonError {
+    pub(crate) errors: DeserializationErrors,
+    pub(crate) config: Config,
+    pub(crate) debug_raw: Option<String>,
+    pub(crate) pointer_prefix: String,
+    /// Where the body stopped being syntactically valid JSON, if it isn't.
+    pub(crate) syntax_error: Option<Box<SyntaxError>>,
+}
 
 impl JsonError {
-    pub(crate) fn new(err: DeserializationErrors) -> Self {
-        Self(err)
+    pub(crate) fn new(errors: DeserializationErrors, config: Config, body: &[u8]) -> Self {
+        // Locating the syntax error takes another pass, only worth it if there's one.
+        // Case-insensitive variants go through a deserializer that can't tell.
+        let malformed = config.settings.case_insensitive_variants
+            || errors.iter().any(DeserializationError::is_syntax);
+        let syntax_error = malformed
+            .then(|| serde_json::from_slice::<serde::de::IgnoredAny>(body).err())
+            .flatten()
+            .map(|error| Box::new(SyntaxError::new(&error, body)));
+        Self {
+            debug_raw: config.debug_raw(body),
+            errors,
+            config,
+            pointer_prefix: String::new(),
+            syntax_error,
+        }
     }
-}
 
-impl axum_core::response::IntoResponse for JsonError {
-    fn into_response(self) -> axum_core::response::Response {
+    /// The errors encountered while deserializing the request body.
+    pub fn errors(&self) -> &DeserializationErrors {
+        &self.errors
+    }
+
+    /// Prepend `prefix` to the JSON pointers of the reported errors.
+    ///
+    /// Useful when the request body is embedded in a larger document, e.g. by a composite
+    /// extractor: with a `/payload` prefix, an error at `/email` is reported at `/payload/email`.
+    /// Prefixes accumulate, the last one being the outermost.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `prefix` isn't a JSON pointer, i.e. if it's neither empty
+    /// nor starting with a `/`.
+    pub fn pointer_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.pointer_prefix.insert_str(0, &checked_prefix(prefix));
+        self
+    }
+
+    pub(crate) fn validation_errors(&self) -> ValidationErrors {
         let errors = self
-            .0
+            .errors
             .iter()
-            .map(|e| {
-                let pointer = e.path().map(|path| {
-                    path.iter().fold(String::new(), |mut acc, part| {
-                        acc.push('/');
-                        acc.push_str(&part.to_string());
-                        acc
-                    })
-                });
-                ValidationError {
-                    detail: e.message().into(),
-                    source: Source::Body { pointer },
-                }
-            })
+            .map(|error| ValidationError::body(error).pointer_prefix(&self.pointer_prefix))
             .collect();
-        let response = InvalidRequest::new(ValidationErrors { errors });
+        ValidationErrors {
+            errors,
+            by_pointer: false,
+        }
+    }
+}
+
+impl axum_core::response::IntoResponse for JsonError {
+    fn into_response(self) -> axum_core::response::Response {
+        let response = InvalidRequest::new(self.validation_errors())
+            .debug_raw(self.debug_raw)
+            .split_by_syntax(self.syntax_error.is_some(), &self.config)
+            .syntax_error(self.syntax_error.map(|error| *error))
+            .configure(&self.config);
         __log_rejection!(
             rejection_type = JsonError,
-            status = InvalidRequest::status(),
+            status = response.status_code(),
+            config = self.config,
         );
         response.into_response()
     }
@@ -70,7 +133,7 @@ impl axum_core::response::IntoResponse for JsonError {
 impl std::fmt::Display for JsonError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str("Failed to deserialize the request JSON body into the target schema:\n")?;
-        for e in self.0.iter() {
+        for e in self.errors.iter() {
             writeln!(f, "- {}", e)?;
         }
         Ok(())
@@ -79,28 +142,33 @@ impl std::fmt::Display for JsonError {
 
 impl std::error::Error for JsonError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
-        Some(&self.0)
+        Some(&self.errors)
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 #[non_exhaustive]
 #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
 /// Rejection type for [`Json`](super::Json) used if the `Content-Type`
 /// header is missing.
-pub struct MissingJsonContentType;
+pub struct MissingJsonContentType {
+    pub(crate) config: Config,
+}
 
 impl axum_core::response::IntoResponse for MissingJsonContentType {
     fn into_response(self) -> axum_core::response::Response {
-        let error = ValidationError {
-                    detail: "Expected request with `Content-Type: application/json`, but no `Content-Type` header was found".into(),
-                    source: Source::Header {
-                        name: CONTENT_TYPE.as_str().into(),
-                    },
-                };
+        let error = ValidationError::new(
+            Source::Header {
+                name: CONTENT_TYPE.as_str().into(),
+            },
+            "missing_content_type",
+            "Expected request with `Content-Type: application/json`, but no `Content-Type` header was found",
+        );
         let response = InvalidRequest::new(ValidationErrors {
             errors: vec![error],
-        });
+            by_pointer: false,
+        })
+        .configure(&self.config);
         __log_rejection!(
             rejection_type = MissingJsonContentType,
             status = InvalidRequest::status(),
@@ -115,54 +183,666 @@ impl std::fmt::Display for MissingJsonContentType {
 }
 impl std::error::Error for MissingJsonContentType {}
 
-impl Default for MissingJsonContentType {
-    fn default() -> Self {
-        Self
-    }
-}
-
 #[derive(Debug)]
 #[non_exhaustive]
 #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
 /// Rejection type for [`Json`](super::Json) used if the `Content-Type`
 /// header has an incorrect value.
+///
+/// It results in a `415 Unsupported Media Type` problem, listing the accepted
+/// media types in the `expected` member and the header value in the `received` one.
 pub struct JsonContentTypeMismatch {
     pub(crate) actual: String,
+    pub(crate) config: Config,
+}
+
+/// The media types accepted by [`Json`](super::Json), as listed in a [`JsonContentTypeMismatch`] problem.
+const EXPECTED_CONTENT_TYPES: &[&str] = &["application/json", "application/*+json"];
+
+impl JsonContentTypeMismatch {
+    /// The media types accepted by the extractor.
+    pub fn expected(&self) -> &'static [&'static str] {
+        EXPECTED_CONTENT_TYPES
+    }
+
+    /// The value of the `Content-Type` header of the request.
+    pub fn received(&self) -> &str {
+        &self.actual
+    }
 }
 
 impl axum_core::response::IntoResponse for JsonContentTypeMismatch {
     fn into_response(self) -> axum_core::response::Response {
-        let error = ValidationError {
-            detail: format!(
-                "Expected request with `Content-Type: application/json` or `application/*+json`, but found `{}`",
-                self.actual
+        let message = self.to_string();
+        let response = UnsupportedMediaType::mismatch(
+            EXPECTED_CONTENT_TYPES,
+            self.actual,
+            message,
+            "The request body must be JSON",
+        )
+        .configure(&self.config);
+        __log_rejection!(
+            rejection_type = JsonContentTypeMismatch,
+            status = UnsupportedMediaType::status(),
+        );
+        response.into_response()
+    }
+}
+
+impl std::fmt::Display for JsonContentTypeMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Expected request with `Content-Type: application/json` or `application/*+json`, but found `{}`",
+            self.actual
+        )
+    }
+}
+
+impl std::error::Error for JsonContentTypeMismatch {}
+
+#[derive(Debug)]
+#[non_exhaustive]
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+/// Rejection type for [`Json`](super::Json) used in [strict mode](crate::Config::strict_json)
+/// if the parameters of the `Content-Type` header are unexpected: repeated, unknown or
+/// announcing a charset other than UTF-8.
+///
+/// It results in a `415 Unsupported Media Type` problem, listing the accepted
+/// media types in the `expected` member and the header value in the `received` one.
+pub struct InvalidContentType {
+    pub(crate) reason: String,
+    pub(crate) actual: String,
+    pub(crate) config: Config,
+}
+
+impl InvalidContentType {
+    /// The media types accepted by the extractor.
+    pub fn expected(&self) -> &'static [&'static str] {
+        EXPECTED_CONTENT_TYPES
+    }
+
+    /// The value of the `Content-Type` header of the request.
+    pub fn received(&self) -> &str {
+        &self.actual
+    }
+}
+
+impl axum_core::response::IntoResponse for InvalidContentType {
+    fn into_response(self) -> axum_core::response::Response {
+        let response = UnsupportedMediaType::new(
+            "invalid_content_type",
+            self.reason,
+            "The `Content-Type` header only accepts a `charset` (set to `utf-8`) and a `profile`, once each",
+            EXPECTED_CONTENT_TYPES,
+            self.actual,
+        )
+        .typed(
+            "invalid_content_type",
+            "The content type of the request has unexpected parameters",
+        )
+        .configure(&self.config);
+        __log_rejection!(
+            rejection_type = InvalidContentType,
+            status = UnsupportedMediaType::status(),
+        );
+        response.into_response()
+    }
+}
+
+impl std::fmt::Display for InvalidContentType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.reason)
+    }
+}
+
+impl std::error::Error for InvalidContentType {}
+
+#[derive(Debug)]
+#[non_exhaustive]
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+/// Rejection type for [`Json`](super::Json) used if an object in the request body
+/// has more members than allowed by [`Config::max_object_members`](crate::Config::max_object_members).
+pub struct TooManyFields {
+    pub(crate) pointer: String,
+    pub(crate) max: usize,
+    pub(crate) config: Config,
+}
+
+impl TooManyFields {
+    /// Prepend `prefix` to the JSON pointer of the offending object.
+    ///
+    /// See [`JsonError::pointer_prefix`] for more details.
+    pub fn pointer_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.pointer.insert_str(0, &checked_prefix(prefix));
+        self
+    }
+}
+
+impl axum_core::response::IntoResponse for TooManyFields {
+    fn into_response(self) -> axum_core::response::Response {
+        let error = ValidationError::new(
+            Source::Body {
+                pointer: Some(self.pointer),
+                key: false,
+            },
+            "too_many_fields",
+            format!("This object has more than {} fields", self.max),
+        );
+        let response = ProblemDetails {
+            extensions: Some(
+                ValidationErrors {
+                    errors: vec![error],
+                    by_pointer: false,
+                }
+                .configure(&self.config),
             ),
-            source: Source::Header {
-                name: CONTENT_TYPE.as_str().into(),
+            ..ProblemDetails::new(
+                "too_many_fields",
+                StatusCode::BAD_REQUEST,
+                "The request body contains an object with too many fields",
+                format!(
+                    "Objects in the request body can't have more than {} fields",
+                    self.max
+                ),
+            )
+        }
+        .configure(&self.config);
+        __log_rejection!(
+            rejection_type = TooManyFields,
+            status = StatusCode::BAD_REQUEST,
+        );
+        response.into_response()
+    }
+}
+
+impl std::fmt::Display for TooManyFields {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "The object at `{}` in the request body has more than {} fields",
+            self.pointer, self.max
+        )
+    }
+}
+
+impl std::error::Error for TooManyFields {}
+
+#[derive(Debug)]
+#[non_exhaustive]
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+/// Rejection type for [`Json`](super::Json) used if a string in the request body
+/// is longer than allowed by [`Config::max_string_length`](crate::Config::max_string_length).
+pub struct StringTooLong {
+    pub(crate) pointer: String,
+    pub(crate) max: usize,
+    pub(crate) config: Config,
+}
+
+impl StringTooLong {
+    /// Prepend `prefix` to the JSON pointer of the offending string.
+    ///
+    /// See [`JsonError::pointer_prefix`] for more details.
+    pub fn pointer_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.pointer.insert_str(0, &checked_prefix(prefix));
+        self
+    }
+}
+
+impl axum_core::response::IntoResponse for StringTooLong {
+    fn into_response(self) -> axum_core::response::Response {
+        let error = ValidationError::new(
+            Source::Body {
+                pointer: Some(self.pointer),
+                key: false,
             },
+            "string_too_long",
+            format!("This string is longer than {} characters", self.max),
+        );
+        let response = ProblemDetails {
+            extensions: Some(
+                ValidationErrors {
+                    errors: vec![error],
+                    by_pointer: false,
+                }
+                .configure(&self.config),
+            ),
+            ..ProblemDetails::new(
+                "string_too_long",
+                StatusCode::BAD_REQUEST,
+                "The request body contains a string that is too long",
+                format!(
+                    "Strings in the request body can't be longer than {} characters",
+                    self.max
+                ),
+            )
+        }
+        .configure(&self.config);
+        __log_rejection!(
+            rejection_type = StringTooLong,
+            status = StatusCode::BAD_REQUEST,
+        );
+        response.into_response()
+    }
+}
+
+impl std::fmt::Display for StringTooLong {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "The string at `{}` in the request body is longer than {} characters",
+            self.pointer, self.max
+        )
+    }
+}
+
+impl std::error::Error for StringTooLong {}
+
+#[derive(Debug)]
+#[non_exhaustive]
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+/// Rejection type for [`Json`](super::Json) used if the request body nests objects
+/// and arrays deeper than allowed by [`Config::max_depth`](crate::Config::max_depth).
+///
+/// It's also used, before the body is read, if a
+/// [trusted header](crate::Config::trust_depth_header) advertises a deeper body.
+pub struct TooDeep {
+    pub(crate) location: DepthLocation,
+    pub(crate) max: usize,
+    pub(crate) config: Config,
+}
+
+/// Where the depth of the request body was found to exceed the limit.
+#[derive(Debug)]
+pub(crate) enum DepthLocation {
+    /// In the body itself, at the given JSON pointer.
+    Body { pointer: String },
+    /// In the header advertising the depth of the body.
+    Header {
+        name: http::HeaderName,
+        depth: usize,
+    },
+}
+
+impl TooDeep {
+    /// Prepend `prefix` to the JSON pointer of the offending value, if the depth
+    /// was measured on the body itself.
+    ///
+    /// See [`JsonError::pointer_prefix`] for more details.
+    pub fn pointer_prefix(mut self, prefix: impl Into<String>) -> Self {
+        if let DepthLocation::Body { pointer } = &mut self.location {
+            pointer.insert_str(0, &checked_prefix(prefix));
+        }
+        self
+    }
+}
+
+impl axum_core::response::IntoResponse for TooDeep {
+    fn into_response(self) -> axum_core::response::Response {
+        let (detail, source) = match self.location {
+            DepthLocation::Body { pointer } => (
+                format!("This value is nested more than {} levels deep", self.max),
+                Source::Body {
+                    pointer: Some(pointer),
+                    key: false,
+                },
+            ),
+            DepthLocation::Header { name, depth } => (
+                format!(
+                    "The request body is advertised to be nested {depth} levels deep, more than {}",
+                    self.max
+                ),
+                Source::Header {
+                    name: name.as_str().to_owned().into(),
+                },
+            ),
         };
+        let error = ValidationError::new(source, "too_deep", detail);
+        let response = ProblemDetails {
+            extensions: Some(
+                ValidationErrors {
+                    errors: vec![error],
+                    by_pointer: false,
+                }
+                .configure(&self.config),
+            ),
+            ..ProblemDetails::new(
+                "too_deep",
+                StatusCode::BAD_REQUEST,
+                "The request body is nested too deeply",
+                format!(
+                "Objects and arrays in the request body can't be nested more than {} levels deep",
+                self.max
+            ),
+            )
+        }
+        .configure(&self.config);
+        __log_rejection!(rejection_type = TooDeep, status = StatusCode::BAD_REQUEST,);
+        response.into_response()
+    }
+}
+
+impl std::fmt::Display for TooDeep {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.location {
+            DepthLocation::Body { pointer } => write!(
+                f,
+                "The value at `{pointer}` in the request body is nested more than {} levels deep",
+                self.max
+            ),
+            DepthLocation::Header { name, depth } => write!(
+                f,
+                "The `{name}` header advertises a request body nested {depth} levels deep, \
+                but at most {} levels are accepted",
+                self.max
+            ),
+        }
+    }
+}
+
+impl std::error::Error for TooDeep {}
+
+#[derive(Debug)]
+#[non_exhaustive]
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+/// Rejection type for [`Json`](super::Json) used in [strict mode](crate::Config::strict_json)
+/// if the request body starts with a byte order mark.
+pub struct InvalidEncoding {
+    pub(crate) config: Config,
+}
+
+impl axum_core::response::IntoResponse for InvalidEncoding {
+    fn into_response(self) -> axum_core::response::Response {
+        let error = ValidationError::new(
+            Source::Body {
+                pointer: None,
+                key: false,
+            },
+            "invalid_encoding",
+            "The request body must be UTF-8 encoded JSON without a byte order mark",
+        );
         let response = InvalidRequest::new(ValidationErrors {
             errors: vec![error],
-        });
+            by_pointer: false,
+        })
+        .configure(&self.config);
         __log_rejection!(
-            rejection_type = JsonContentTypeMismatch,
+            rejection_type = InvalidEncoding,
             status = InvalidRequest::status(),
         );
         response.into_response()
     }
 }
 
-impl std::fmt::Display for JsonContentTypeMismatch {
+impl std::fmt::Display for InvalidEncoding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "The request body starts with a byte order mark")
+    }
+}
+
+impl std::error::Error for InvalidEncoding {}
+
+#[derive(Debug)]
+#[non_exhaustive]
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+/// Rejection type for [`Json`](super::Json) used in [strict mode](crate::Config::strict_json)
+/// if the JSON document in the request body is padded with too much whitespace.
+pub struct ExcessiveWhitespace {
+    pub(crate) max: usize,
+    pub(crate) config: Config,
+}
+
+impl axum_core::response::IntoResponse for ExcessiveWhitespace {
+    fn into_response(self) -> axum_core::response::Response {
+        let error = ValidationError::new(Source::Body {
+                pointer: None,
+                key: false,
+            }, "excessive_whitespace", format!(
+                "The request body can't have more than {} bytes of whitespace before or after the JSON document",
+                self.max
+            ));
+        let response = InvalidRequest::new(ValidationErrors {
+            errors: vec![error],
+            by_pointer: false,
+        })
+        .configure(&self.config);
+        __log_rejection!(
+            rejection_type = ExcessiveWhitespace,
+            status = InvalidRequest::status(),
+        );
+        response.into_response()
+    }
+}
+
+impl std::fmt::Display for ExcessiveWhitespace {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "Expected request with `Content-Type: application/json` or `application/*+json`, but found `{}`",
+            "The request body has more than {} bytes of whitespace before or after the JSON document",
+            self.max
+        )
+    }
+}
+
+impl std::error::Error for ExcessiveWhitespace {}
+
+#[derive(Debug)]
+#[non_exhaustive]
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+/// Rejection type for [`Json`](super::Json) used if the JSON document in the request
+/// body is followed by something other than whitespace, e.g. `{"a": 1} garbage`.
+///
+/// It is rendered as a `400 Bad Request` problem, of type `trailing_data`, with the
+/// byte offset where the trailing data starts in the `offset` member.
+pub struct TrailingData {
+    pub(crate) offset: usize,
+    pub(crate) config: Config,
+}
+
+impl TrailingData {
+    /// The byte offset, in the request body, where the trailing data starts.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+#[derive(serde::Serialize)]
+struct TrailingDataOffset {
+    offset: usize,
+}
+
+impl axum_core::response::IntoResponse for TrailingData {
+    fn into_response(self) -> axum_core::response::Response {
+        let status = StatusCode::BAD_REQUEST;
+        let problem = ProblemDetails {
+            extensions: Some(TrailingDataOffset {
+                offset: self.offset,
+            }),
+            ..ProblemDetails::new(
+                "trailing_data",
+                status,
+                "The request body has trailing data",
+                self.to_string(),
+            )
+        }
+        .configure(&self.config);
+        __log_rejection!(rejection_type = TrailingData, status = status,);
+        problem.into_response()
+    }
+}
+
+impl std::fmt::Display for TrailingData {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "The JSON document in the request body is followed by unexpected data, starting at byte {}",
+            self.offset
+        )
+    }
+}
+
+impl std::error::Error for TrailingData {}
+
+#[derive(Debug)]
+#[non_exhaustive]
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+/// Rejection type for [`Json`](super::Json) used if the request has
+/// more than one `Content-Type` header, e.g. because of a misconfigured proxy.
+pub struct AmbiguousJsonContentType {
+    pub(crate) count: usize,
+    pub(crate) config: Config,
+}
+
+impl axum_core::response::IntoResponse for AmbiguousJsonContentType {
+    fn into_response(self) -> axum_core::response::Response {
+        let status = StatusCode::BAD_REQUEST;
+        let problem = ProblemDetails::ambiguous_content_type(self.count).configure(&self.config);
+        let problem = ProblemDetails {
+            extensions: problem
+                .extensions
+                .map(|errors| errors.configure(&self.config)),
+            ..problem
+        };
+        __log_rejection!(rejection_type = AmbiguousJsonContentType, status = status,);
+        problem.into_response()
+    }
+}
+
+impl std::fmt::Display for AmbiguousJsonContentType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Expected a single `Content-Type` header, but found {}",
+            self.count
+        )
+    }
+}
+
+impl std::error::Error for AmbiguousJsonContentType {}
+
+#[derive(Debug)]
+#[non_exhaustive]
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+/// Rejection type for [`Json`](super::Json) used if the top-level value of the request body
+/// doesn't have one of the types accepted via [`Config::json_root_types`](crate::Config::json_root_types).
+pub struct InvalidRootType {
+    pub(crate) expected: Vec<JsonType>,
+    pub(crate) actual: JsonType,
+    pub(crate) config: Config,
+}
+
+impl InvalidRootType {
+    /// The accepted types, in a human-readable form, e.g. `an object or an array`.
+    fn expected(&self) -> String {
+        let types: Vec<_> = self.expected.iter().map(ToString::to_string).collect();
+        types.join(" or ")
+    }
+}
+
+impl axum_core::response::IntoResponse for InvalidRootType {
+    fn into_response(self) -> axum_core::response::Response {
+        let error = ValidationError::new(
+            Source::Body {
+                pointer: Some(String::new()),
+                key: false,
+            },
+            "invalid_root_type",
+            self.to_string(),
+        );
+        let response = ProblemDetails {
+            extensions: Some(
+                ValidationErrors {
+                    errors: vec![error],
+                    by_pointer: false,
+                }
+                .configure(&self.config),
+            ),
+            ..ProblemDetails::new(
+                "invalid_root_type",
+                StatusCode::BAD_REQUEST,
+                "The request body has the wrong top-level type",
+                format!("The request body must be {}", self.expected()),
+            )
+        }
+        .configure(&self.config);
+        __log_rejection!(
+            rejection_type = InvalidRootType,
+            status = StatusCode::BAD_REQUEST,
+        );
+        response.into_response()
+    }
+}
+
+impl std::fmt::Display for InvalidRootType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Expected {} at the top level of the request body, but found {}",
+            self.expected(),
             self.actual
         )
     }
 }
 
-impl std::error::Error for JsonContentTypeMismatch {}
+impl std::error::Error for InvalidRootType {}
+
+#[cfg(feature = "jsonschema")]
+#[derive(Debug)]
+#[non_exhaustive]
+#[cfg_attr(docsrs, doc(cfg(feature = "jsonschema")))]
+/// Rejection type for [`SchemaJson`](super::SchemaJson) used if the request body
+/// doesn't conform to the JSON Schema of the target type.
+pub struct SchemaViolation {
+    pub(crate) errors: Vec<ValidationError>,
+    pub(crate) config: Config,
+}
+
+#[cfg(feature = "jsonschema")]
+impl SchemaViolation {
+    /// Prepend `prefix` to the JSON pointers of the schema violations.
+    ///
+    /// See [`JsonError::pointer_prefix`] for more details.
+    pub fn pointer_prefix(mut self, prefix: impl Into<String>) -> Self {
+        let prefix = checked_prefix(prefix);
+        self.errors = self
+            .errors
+            .into_iter()
+            .map(|error| error.pointer_prefix(&prefix))
+            .collect();
+        self
+    }
+}
+
+#[cfg(feature = "jsonschema")]
+impl axum_core::response::IntoResponse for SchemaViolation {
+    fn into_response(self) -> axum_core::response::Response {
+        let response = InvalidRequest::new(ValidationErrors {
+            errors: self.errors,
+            by_pointer: false,
+        })
+        .split_by_syntax(false, &self.config)
+        .configure(&self.config);
+        __log_rejection!(
+            rejection_type = SchemaViolation,
+            status = response.status_code(),
+        );
+        response.into_response()
+    }
+}
+
+#[cfg(feature = "jsonschema")]
+impl std::fmt::Display for SchemaViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "The request body doesn't conform to the schema: {} violation(s)",
+            self.errors.len()
+        )
+    }
+}
+
+#[cfg(feature = "jsonschema")]
+impl std::error::Error for SchemaViolation {}
 
 /// Rejection used for [`Json`](super::Json).
 ///
@@ -180,49 +860,111 @@ pub enum JsonRejection {
     #[allow(missing_docs)]
     MissingJsonContentType(MissingJsonContentType),
     #[allow(missing_docs)]
+    AmbiguousJsonContentType(AmbiguousJsonContentType),
+    #[allow(missing_docs)]
     JsonContentTypeMismatch(JsonContentTypeMismatch),
     #[allow(missing_docs)]
+    InvalidContentType(InvalidContentType),
+    #[allow(missing_docs)]
     BytesRejection(BytesRejection),
+    #[allow(missing_docs)]
+    ContentTooLarge(crate::body::ContentTooLarge),
+    #[allow(missing_docs)]
+    Overloaded(crate::body::Overloaded),
+    #[allow(missing_docs)]
+    InvalidContentLength(crate::body::InvalidContentLength),
+    #[cfg(feature = "timeout")]
+    #[allow(missing_docs)]
+    RequestTimeout(crate::timeout::RequestTimeout),
+    #[allow(missing_docs)]
+    TooManyFields(TooManyFields),
+    #[allow(missing_docs)]
+    TooDeep(TooDeep),
+    #[allow(missing_docs)]
+    StringTooLong(StringTooLong),
+    #[allow(missing_docs)]
+    InvalidEncoding(InvalidEncoding),
+    #[allow(missing_docs)]
+    ExcessiveWhitespace(ExcessiveWhitespace),
+    #[allow(missing_docs)]
+    TrailingData(TrailingData),
+    #[allow(missing_docs)]
+    InvalidRootType(InvalidRootType),
+    #[cfg(feature = "jsonschema")]
+    #[allow(missing_docs)]
+    SchemaViolation(SchemaViolation),
+    #[cfg(feature = "catch-unwind")]
+    #[allow(missing_docs)]
+    DeserializationPanicked(crate::unwind::DeserializationPanicked),
+}
+impl JsonRejection {
+    /// Prepend `prefix` to the JSON pointers reported for the request body.
+    ///
+    /// Rejections that aren't about the content of the body are left untouched.
+    /// See [`JsonError::pointer_prefix`] for more details.
+    pub fn pointer_prefix(self, prefix: impl Into<String>) -> Self {
+        let prefix = checked_prefix(prefix);
+        match self {
+            Self::JsonError(inner) => Self::JsonError(inner.pointer_prefix(prefix)),
+            Self::TooManyFields(inner) => Self::TooManyFields(inner.pointer_prefix(prefix)),
+            Self::TooDeep(inner) => Self::TooDeep(inner.pointer_prefix(prefix)),
+            Self::StringTooLong(inner) => Self::StringTooLong(inner.pointer_prefix(prefix)),
+            #[cfg(feature = "jsonschema")]
+            Self::SchemaViolation(inner) => Self::SchemaViolation(inner.pointer_prefix(prefix)),
+            other => other,
+        }
+    }
+
+    /// The rejection for a request body that violates the configured structural limits.
+    pub(crate) fn from_violation(violation: Violation, config: Config) -> Self {
+        match violation {
+            Violation::TooManyMembers { pointer, max } => TooManyFields {
+                pointer,
+                max,
+                config,
+            }
+            .into(),
+            Violation::StringTooLong { pointer, max } => StringTooLong {
+                pointer,
+                max,
+                config,
+            }
+            .into(),
+            Violation::TooDeep { pointer, max } => TooDeep {
+                location: DepthLocation::Body { pointer },
+                max,
+                config,
+            }
+            .into(),
+        }
+    }
 }
+
 impl axum_core::response::IntoResponse for JsonRejection {
     fn into_response(self) -> axum_core::response::Response {
         match self {
             Self::JsonError(inner) => inner.into_response(),
             Self::MissingJsonContentType(inner) => inner.into_response(),
+            Self::AmbiguousJsonContentType(inner) => inner.into_response(),
             Self::JsonContentTypeMismatch(inner) => inner.into_response(),
-            Self::BytesRejection(inner) => {
-                let mut response = None;
-                #[allow(clippy::single_match)]
-                match inner {
-                    BytesRejection::FailedToBufferBody(failed_to_buffer_body) => {
-                        match failed_to_buffer_body {
-                            FailedToBufferBody::LengthLimitError(length_limit_error) => {
-                                let details: ProblemDetails<()> = ProblemDetails {
-                                    type_: "content_too_large".into(),
-                                    status: length_limit_error.status().as_u16(),
-                                    title: "The content is too large".into(),
-                                    detail: length_limit_error.body_text().into(),
-                                    extensions: None,
-                                };
-                                response = Some(details.into_response());
-                            }
-                            FailedToBufferBody::UnknownBodyError(unknown_body_error) => {
-                                let details: ProblemDetails<()> = ProblemDetails {
-                                    type_: "body_buffering_error".into(),
-                                    status: unknown_body_error.status().as_u16(),
-                                    title: "Failed to buffer the body".into(),
-                                    detail: unknown_body_error.body_text().into(),
-                                    extensions: None,
-                                };
-                                response = Some(details.into_response());
-                            }
-                            _ => {}
-                        }
-                    }
-                    _ => {}
-                }
-                response.unwrap_or_else(|| INTERNAL_SERVER_ERROR.into_response())
-            }
+            Self::InvalidContentType(inner) => inner.into_response(),
+            Self::TooManyFields(inner) => inner.into_response(),
+            Self::TooDeep(inner) => inner.into_response(),
+            Self::StringTooLong(inner) => inner.into_response(),
+            Self::InvalidEncoding(inner) => inner.into_response(),
+            Self::ExcessiveWhitespace(inner) => inner.into_response(),
+            Self::TrailingData(inner) => inner.into_response(),
+            Self::InvalidRootType(inner) => inner.into_response(),
+            #[cfg(feature = "jsonschema")]
+            Self::SchemaViolation(inner) => inner.into_response(),
+            #[cfg(feature = "catch-unwind")]
+            Self::DeserializationPanicked(inner) => inner.into_response(),
+            Self::BytesRejection(inner) => bytes_rejection_response(inner),
+            Self::ContentTooLarge(inner) => inner.into_response(),
+            Self::Overloaded(inner) => inner.into_response(),
+            Self::InvalidContentLength(inner) => inner.into_response(),
+            #[cfg(feature = "timeout")]
+            Self::RequestTimeout(inner) => inner.into_response(),
         }
     }
 }
@@ -237,23 +979,119 @@ impl From<MissingJsonContentType> for JsonRejection {
         Self::MissingJsonContentType(inner)
     }
 }
+impl From<AmbiguousJsonContentType> for JsonRejection {
+    fn from(inner: AmbiguousJsonContentType) -> Self {
+        Self::AmbiguousJsonContentType(inner)
+    }
+}
 impl From<JsonContentTypeMismatch> for JsonRejection {
     fn from(inner: JsonContentTypeMismatch) -> Self {
         Self::JsonContentTypeMismatch(inner)
     }
 }
+impl From<InvalidContentType> for JsonRejection {
+    fn from(inner: InvalidContentType) -> Self {
+        Self::InvalidContentType(inner)
+    }
+}
 impl From<BytesRejection> for JsonRejection {
     fn from(inner: BytesRejection) -> Self {
         Self::BytesRejection(inner)
     }
 }
+impl From<crate::body::ContentTooLarge> for JsonRejection {
+    fn from(inner: crate::body::ContentTooLarge) -> Self {
+        Self::ContentTooLarge(inner)
+    }
+}
+impl From<crate::body::Overloaded> for JsonRejection {
+    fn from(inner: crate::body::Overloaded) -> Self {
+        Self::Overloaded(inner)
+    }
+}
+impl From<crate::body::InvalidContentLength> for JsonRejection {
+    fn from(inner: crate::body::InvalidContentLength) -> Self {
+        Self::InvalidContentLength(inner)
+    }
+}
+#[cfg(feature = "timeout")]
+impl From<crate::timeout::RequestTimeout> for JsonRejection {
+    fn from(inner: crate::timeout::RequestTimeout) -> Self {
+        Self::RequestTimeout(inner)
+    }
+}
+impl From<TooManyFields> for JsonRejection {
+    fn from(inner: TooManyFields) -> Self {
+        Self::TooManyFields(inner)
+    }
+}
+impl From<TooDeep> for JsonRejection {
+    fn from(inner: TooDeep) -> Self {
+        Self::TooDeep(inner)
+    }
+}
+impl From<StringTooLong> for JsonRejection {
+    fn from(inner: StringTooLong) -> Self {
+        Self::StringTooLong(inner)
+    }
+}
+impl From<InvalidEncoding> for JsonRejection {
+    fn from(inner: InvalidEncoding) -> Self {
+        Self::InvalidEncoding(inner)
+    }
+}
+impl From<ExcessiveWhitespace> for JsonRejection {
+    fn from(inner: ExcessiveWhitespace) -> Self {
+        Self::ExcessiveWhitespace(inner)
+    }
+}
+impl From<TrailingData> for JsonRejection {
+    fn from(inner: TrailingData) -> Self {
+        Self::TrailingData(inner)
+    }
+}
+impl From<InvalidRootType> for JsonRejection {
+    fn from(inner: InvalidRootType) -> Self {
+        Self::InvalidRootType(inner)
+    }
+}
+#[cfg(feature = "jsonschema")]
+impl From<SchemaViolation> for JsonRejection {
+    fn from(inner: SchemaViolation) -> Self {
+        Self::SchemaViolation(inner)
+    }
+}
+#[cfg(feature = "catch-unwind")]
+impl From<crate::unwind::DeserializationPanicked> for JsonRejection {
+    fn from(inner: crate::unwind::DeserializationPanicked) -> Self {
+        Self::DeserializationPanicked(inner)
+    }
+}
 impl std::fmt::Display for JsonRejection {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::JsonError(inner) => write!(f, "{inner}"),
             Self::MissingJsonContentType(inner) => write!(f, "{inner}"),
+            Self::AmbiguousJsonContentType(inner) => write!(f, "{inner}"),
             Self::JsonContentTypeMismatch(inner) => write!(f, "{inner}"),
+            Self::InvalidContentType(inner) => write!(f, "{inner}"),
             Self::BytesRejection(inner) => write!(f, "{inner}"),
+            Self::ContentTooLarge(inner) => write!(f, "{inner}"),
+            Self::Overloaded(inner) => write!(f, "{inner}"),
+            Self::InvalidContentLength(inner) => write!(f, "{inner}"),
+            #[cfg(feature = "timeout")]
+            Self::RequestTimeout(inner) => write!(f, "{inner}"),
+            Self::TooManyFields(inner) => write!(f, "{inner}"),
+            Self::TooDeep(inner) => write!(f, "{inner}"),
+            Self::StringTooLong(inner) => write!(f, "{inner}"),
+            Self::InvalidEncoding(inner) => write!(f, "{inner}"),
+            Self::ExcessiveWhitespace(inner) => write!(f, "{inner}"),
+            Self::TrailingData(inner) => write!(f, "{inner}"),
+            Self::InvalidRootType(inner) => write!(f, "{inner}"),
+            #[cfg(feature = "jsonschema")]
+            Self::SchemaViolation(inner) => write!(f, "{inner}"),
+            #[cfg(feature = "catch-unwind")]
+            Self::DeserializationPanicked(inner) => write!(f, "{inner}"),
         }
     }
 }
@@ -262,8 +1100,163 @@ impl std::error::Error for JsonRejection {
         match self {
             Self::JsonError(inner) => inner.source(),
             Self::MissingJsonContentType(inner) => inner.source(),
+            Self::AmbiguousJsonContentType(inner) => inner.source(),
             Self::JsonContentTypeMismatch(inner) => inner.source(),
+            Self::InvalidContentType(inner) => inner.source(),
             Self::BytesRejection(inner) => inner.source(),
+            Self::ContentTooLarge(inner) => inner.source(),
+            Self::Overloaded(inner) => inner.source(),
+            Self::InvalidContentLength(inner) => inner.source(),
+            #[cfg(feature = "timeout")]
+            Self::RequestTimeout(inner) => inner.source(),
+            Self::TooManyFields(inner) => inner.source(),
+            Self::TooDeep(inner) => inner.source(),
+            Self::StringTooLong(inner) => inner.source(),
+            Self::InvalidEncoding(inner) => inner.source(),
+            Self::ExcessiveWhitespace(inner) => inner.source(),
+            Self::TrailingData(inner) => inner.source(),
+            Self::InvalidRootType(inner) => inner.source(),
+            #[cfg(feature = "jsonschema")]
+            Self::SchemaViolation(inner) => inner.source(),
+            #[cfg(feature = "catch-unwind")]
+            Self::DeserializationPanicked(inner) => inner.source(),
         }
     }
 }
+
+/// Make sure `prefix` can be prepended to a JSON pointer, see [`JsonError::pointer_prefix`].
+fn checked_prefix(prefix: impl Into<String>) -> String {
+    let prefix = prefix.into();
+    assert!(
+        prefix.is_empty() || prefix.starts_with('/'),
+        "The pointer prefix must be empty or start with a `/`, got `{prefix}`"
+    );
+    prefix
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Json;
+
+    #[derive(Debug, eserde::Deserialize)]
+    #[allow(dead_code)]
+    struct Person {
+        age: u8,
+    }
+
+    fn errors(body: &[u8]) -> serde_json::Value {
+        let Err(super::JsonRejection::JsonError(error)) = Json::<Person>::from_bytes(body) else {
+            panic!("Expected a deserialization error");
+        };
+        serde_json::to_value(error.validation_errors()).unwrap()
+    }
+
+    #[test]
+    fn test_out_of_range() {
+        let errors = errors(br#"{"age": 300}"#);
+        let error = &errors["errors"][0];
+        assert_eq!(error["code"], "out_of_range");
+        assert_eq!(error["pointer"], "/age");
+        assert_eq!(error["range"], serde_json::json!({ "min": 0, "max": 255 }));
+    }
+
+    #[test]
+    fn test_invalid_type() {
+        let errors = errors(br#"{"age": "x"}"#);
+        let error = &errors["errors"][0];
+        assert_eq!(error["code"], "invalid_type");
+        assert_eq!(error["pointer"], "/age");
+        assert!(error.get("range").is_none());
+    }
+
+    #[derive(Debug, eserde::Deserialize)]
+    #[allow(dead_code)]
+    struct Booking {
+        guest: Person,
+        #[serde(with = "date")]
+        check_in: (u16, u16, u16),
+    }
+
+    mod date {
+        use serde::de::Error;
+
+        pub fn deserialize<'de, D>(deserializer: D) -> Result<(u16, u16, u16), D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            let s: String = serde::Deserialize::deserialize(deserializer)?;
+            let mut parts = s.splitn(3, '-').map(str::parse);
+            match (parts.next(), parts.next(), parts.next()) {
+                (Some(Ok(year)), Some(Ok(month)), Some(Ok(day))) => Ok((year, month, day)),
+                _ => Err(D::Error::custom("expected a YYYY-MM-DD date")),
+            }
+        }
+    }
+
+    #[test]
+    fn test_with_pointer() {
+        let Err(super::JsonRejection::JsonError(error)) =
+            Json::<Booking>::from_bytes(br#"{"guest": {"age": "x"}, "check_in": "tomorrow"}"#)
+        else {
+            panic!("Expected a deserialization error");
+        };
+        let errors = serde_json::to_value(error.validation_errors()).unwrap();
+        let errors = errors["errors"].as_array().unwrap();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0]["pointer"], "/guest/age");
+        assert_eq!(errors[1]["source"], "body");
+        assert_eq!(errors[1]["pointer"], "/check_in");
+        assert_eq!(errors[1]["detail"], "expected a YYYY-MM-DD date");
+    }
+
+    #[tokio::test]
+    async fn test_pointer_prefix() {
+        let rejection = Json::<Booking>::from_bytes(br#"{"guest": {"age": "x"}}"#)
+            .unwrap_err()
+            .pointer_prefix("/booking")
+            .pointer_prefix("/payload");
+
+        let (_, body) = crate::test_utils::problem(rejection).await;
+        let errors = body["errors"].as_array().unwrap();
+        assert_eq!(errors[0]["pointer"], "/payload/booking/guest/age");
+        // Missing fields are reported on the enclosing object, i.e. the root of the body.
+        assert_eq!(errors[1]["code"], "missing_field");
+        assert_eq!(errors[1]["pointer"], "/payload/booking");
+    }
+
+    #[test]
+    #[should_panic(expected = "The pointer prefix must be empty or start with a `/`")]
+    fn test_pointer_prefix_without_slash() {
+        let _ = Json::<Booking>::from_bytes(br#"{"guest": {"age": "x"}}"#)
+            .unwrap_err()
+            .pointer_prefix("booking");
+    }
+
+    #[test]
+    fn test_map_key_error() {
+        #[derive(Debug, eserde::Deserialize)]
+        #[allow(dead_code)]
+        struct Inventory {
+            stock: std::collections::HashMap<u32, u32>,
+        }
+
+        let Err(super::JsonRejection::JsonError(error)) =
+            Json::<Inventory>::from_bytes(br#"{"stock": {"1": 10, "two": 20}}"#)
+        else {
+            panic!("Expected a deserialization error");
+        };
+        let errors = serde_json::to_value(error.validation_errors()).unwrap();
+        let errors = errors["errors"].as_array().unwrap();
+        assert_eq!(errors[0]["pointer"], "/stock");
+        assert_eq!(errors[0]["key"], true);
+        // Errors in values don't carry the `key` member.
+        let Err(super::JsonRejection::JsonError(error)) =
+            Json::<Inventory>::from_bytes(br#"{"stock": {"1": "ten"}}"#)
+        else {
+            panic!("Expected a deserialization error");
+        };
+        let errors = serde_json::to_value(error.validation_errors()).unwrap();
+        assert_eq!(errors["errors"][0]["pointer"], "/stock/1");
+        assert!(errors["errors"][0].get("key").is_none());
+    }
+}
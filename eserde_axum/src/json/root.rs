@@ -0,0 +1,74 @@
+//! Check the type of the top-level value of JSON documents before they are deserialized.
+use std::fmt;
+
+/// The types of JSON values.
+///
+/// Used to restrict the top-level values accepted by the JSON extractors,
+/// see [`Config::json_root_types`](crate::Config::json_root_types).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+pub enum JsonType {
+    #[allow(missing_docs)]
+    Object,
+    #[allow(missing_docs)]
+    Array,
+    #[allow(missing_docs)]
+    String,
+    #[allow(missing_docs)]
+    Number,
+    #[allow(missing_docs)]
+    Boolean,
+    #[allow(missing_docs)]
+    Null,
+}
+
+impl JsonType {
+    /// The type of the top-level value of a JSON document, as
+    /// announced by its first significant byte.
+    ///
+    /// Returns `None` if the document is empty or doesn't start
+    /// with a valid JSON value: the parser will report the problem.
+    pub(crate) fn of_root(bytes: &[u8]) -> Option<Self> {
+        let first = bytes
+            .iter()
+            .find(|b| !matches!(b, b' ' | b'\t' | b'\n' | b'\r'))?;
+        match first {
+            b'{' => Some(Self::Object),
+            b'[' => Some(Self::Array),
+            b'"' => Some(Self::String),
+            b'-' | b'0'..=b'9' => Some(Self::Number),
+            b't' | b'f' => Some(Self::Boolean),
+            b'n' => Some(Self::Null),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for JsonType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Object => "an object",
+            Self::Array => "an array",
+            Self::String => "a string",
+            Self::Number => "a number",
+            Self::Boolean => "a boolean",
+            Self::Null => "null",
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_of_root() {
+        assert_eq!(JsonType::of_root(b" \n{}"), Some(JsonType::Object));
+        assert_eq!(JsonType::of_root(b"[1]"), Some(JsonType::Array));
+        assert_eq!(JsonType::of_root(b"-1.5"), Some(JsonType::Number));
+        assert_eq!(JsonType::of_root(b"false"), Some(JsonType::Boolean));
+        assert_eq!(JsonType::of_root(b"null"), Some(JsonType::Null));
+        assert_eq!(JsonType::of_root(b"   "), None);
+        assert_eq!(JsonType::of_root(b"<xml/>"), None);
+    }
+}
@@ -0,0 +1,220 @@
+use std::ops::Deref;
+use std::ops::DerefMut;
+
+use crate::config::Config;
+use crate::details::{Source, ValidationError};
+
+use super::json_::{check_document, check_json_headers};
+use super::*;
+use axum_core::extract::{FromRequest, Request};
+use eserde::EDeserialize;
+use serde::de::DeserializeOwned;
+
+/// A type whose JSON representation is described by a [JSON Schema](https://json-schema.org/).
+///
+/// See [`SchemaJson`] for more details.
+#[cfg_attr(docsrs, doc(cfg(feature = "jsonschema")))]
+pub trait WithSchema {
+    /// The compiled schema that request bodies must conform to.
+    ///
+    /// Compile it once, e.g. in a [`LazyLock`](std::sync::LazyLock):
+    /// building a validator is far more expensive than running it.
+    fn schema() -> &'static jsonschema::Validator;
+}
+
+/// JSON extractor that validates request bodies against the [JSON Schema](https://json-schema.org/)
+/// of the target type, before deserializing them.
+///
+/// It behaves like [`Json`], but every schema violation is reported—with a `schema_violation`
+/// code, the message of the validator and a pointer to the offending value—before
+/// the body gets deserialized into `T`. The body is only deserialized if it conforms to the schema.
+///
+/// See [`JsonRejection`] for the ways the extractor can fail.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use std::sync::LazyLock;
+///
+/// use axum::{routing::post, Router};
+/// use eserde_axum::json::{SchemaJson, WithSchema};
+///
+/// #[derive(eserde::Deserialize)]
+/// struct Signup {
+///     age: u8,
+/// }
+///
+/// impl WithSchema for Signup {
+///     fn schema() -> &'static jsonschema::Validator {
+///         static SCHEMA: LazyLock<jsonschema::Validator> = LazyLock::new(|| {
+///             let schema = serde_json::json!({
+///                 "type": "object",
+///                 "properties": { "age": { "type": "integer", "minimum": 18 } },
+///                 "required": ["age"]
+///             });
+///             jsonschema::validator_for(&schema).expect("Invalid schema")
+///         });
+///         &SCHEMA
+///     }
+/// }
+///
+/// async fn signup(SchemaJson(payload): SchemaJson<Signup>) {}
+///
+/// let app = Router::new().route("/signup", post(signup));
+/// # let _: Router = app;
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(docsrs, doc(cfg(feature = "jsonschema")))]
+#[must_use]
+pub struct SchemaJson<T>(pub T);
+
+impl<T, S> FromRequest<S> for SchemaJson<T>
+where
+    T: DeserializeOwned,
+    T: for<'de> EDeserialize<'de>,
+    T: WithSchema,
+    S: Send + Sync,
+{
+    type Rejection = JsonRejection;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let config = Config::from_request(&req);
+        check_json_headers(req.headers(), &config)?;
+        let (bytes, _permit) =
+            crate::body::read_body::<_, Self::Rejection>(req, state, &config).await?;
+        Self::from_bytes_with_config(&bytes, &config)
+    }
+}
+
+impl<T> SchemaJson<T>
+where
+    T: DeserializeOwned,
+    T: for<'de> EDeserialize<'de>,
+    T: WithSchema,
+{
+    /// Construct a `SchemaJson<T>` from a byte slice. Most users should prefer to use the `FromRequest` impl
+    /// but special cases may require first extracting a `Request` into `Bytes` then optionally
+    /// constructing a `SchemaJson<T>`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, JsonRejection> {
+        Self::from_bytes_with_config(bytes, &Config::default())
+    }
+
+    /// Like [`from_bytes`](Self::from_bytes), but honoring the settings
+    /// specified in the [`Config`].
+    fn from_bytes_with_config(bytes: &[u8], config: &Config) -> Result<Self, JsonRejection> {
+        check_document(bytes, config)?;
+        // Syntax errors are reported by the deserializer, with their position.
+        if let Ok(value) = serde_json::from_slice::<serde_json::Value>(bytes) {
+            let errors: Vec<_> = T::schema()
+                .iter_errors(&value)
+                .map(|error| {
+                    ValidationError::new(
+                        Source::Body {
+                            pointer: Some(error.instance_path().to_string()),
+                            key: false,
+                        },
+                        "schema_violation",
+                        error.to_string(),
+                    )
+                })
+                .collect();
+            if !errors.is_empty() {
+                return Err(SchemaViolation {
+                    errors,
+                    config: config.clone(),
+                }
+                .into());
+            }
+        }
+        let Json(value) = Json::deserialize(bytes, config)?;
+        Ok(SchemaJson(value))
+    }
+}
+
+impl<T> Deref for SchemaJson<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for SchemaJson<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T> From<T> for SchemaJson<T> {
+    fn from(inner: T) -> Self {
+        Self(inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::LazyLock;
+
+    use super::*;
+    use crate::test_utils;
+
+    #[derive(Debug, eserde::Deserialize)]
+    #[allow(dead_code)]
+    struct Signup {
+        name: String,
+        age: u8,
+    }
+
+    impl WithSchema for Signup {
+        fn schema() -> &'static jsonschema::Validator {
+            static SCHEMA: LazyLock<jsonschema::Validator> = LazyLock::new(|| {
+                let schema = serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "name": { "type": "string" },
+                        "age": { "type": "integer", "minimum": 18 }
+                    },
+                    "required": ["name", "age"]
+                });
+                jsonschema::validator_for(&schema).unwrap()
+            });
+            &SCHEMA
+        }
+    }
+
+    #[tokio::test]
+    async fn test_schema_violation() {
+        let request = test_utils::json_request(r#"{"name": "Alice", "age": 16}"#);
+        let rejection = SchemaJson::<Signup>::from_request(request.body(), &())
+            .await
+            .unwrap_err();
+        assert!(matches!(rejection, JsonRejection::SchemaViolation(_)));
+
+        let (status, body) = test_utils::problem(rejection).await;
+        assert_eq!(status, 400);
+        assert_eq!(body["type"], "invalid_request");
+        let errors = body["errors"].as_array().unwrap();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0]["code"], "schema_violation");
+        assert_eq!(errors[0]["source"], "body");
+        assert_eq!(errors[0]["pointer"], "/age");
+        assert_eq!(errors[0]["detail"], "16 is less than the minimum of 18");
+    }
+
+    #[tokio::test]
+    async fn test_conforming_body() {
+        let request = test_utils::json_request(r#"{"name": "Alice", "age": 30}"#);
+        let SchemaJson(signup) = SchemaJson::<Signup>::from_request(request.body(), &())
+            .await
+            .unwrap();
+        assert_eq!(signup.age, 30);
+    }
+
+    #[test]
+    fn test_syntax_error() {
+        let Err(JsonRejection::JsonError(_)) = SchemaJson::<Signup>::from_bytes(br#"{"age": "#)
+        else {
+            panic!("Expected a deserialization error");
+        };
+    }
+}
@@ -0,0 +1,277 @@
+use std::ops::Deref;
+use std::ops::DerefMut;
+
+use crate::config::Config;
+
+use super::json_::{check_document, check_json_headers, deserialize_via};
+use super::*;
+use axum_core::extract::{FromRef, FromRequest, Request};
+use eserde::EDeserialize;
+use serde::de::{DeserializeOwned, DeserializeSeed};
+
+/// A type whose deserialization depends on some runtime context,
+/// e.g. a tenant-specific mapping.
+///
+/// See [`SeededJson`] for more details.
+pub trait Seeded: Sized {
+    /// The context required to deserialize the type, as a [`DeserializeSeed`].
+    ///
+    /// It's retrieved from the application state by [`SeededJson`].
+    type Seed: for<'de> DeserializeSeed<'de, Value = Self>;
+}
+
+/// JSON extractor for types whose deserialization depends on the application state.
+///
+/// It behaves like [`Json`], but the value is deserialized by the [`Seeded::Seed`] of the
+/// target type, retrieved from the state (via [`FromRef`]).
+/// If the seed fails, its error is reported alongside all the others that don't depend
+/// on it, as found by the `eserde` implementation of the target type:
+/// see [`eserde::json::from_slice_seed`] for more details.
+/// [`Config::fail_fast`] and [`Config::case_insensitive_variants`] don't apply.
+///
+/// The errors of the seed are located by the part of the document it was deserializing:
+/// deserialize the context-dependent fields with seeds of their own, e.g. via
+/// [`MapAccess::next_value_seed`](serde::de::MapAccess::next_value_seed), to point at them.
+///
+/// See [`JsonRejection`] for the ways the extractor can fail.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{extract::FromRef, routing::post, Router};
+/// use eserde_axum::json::{Seeded, SeededJson};
+/// use serde::de::{DeserializeSeed, Deserializer, Error};
+///
+/// #[derive(Clone)]
+/// struct Plans(Vec<String>);
+///
+/// #[derive(Clone, FromRef)]
+/// struct AppState {
+///     plans: Plans,
+/// }
+///
+/// #[derive(eserde::Deserialize)]
+/// struct Subscribe {
+///     plan: String,
+///     seats: u32,
+/// }
+///
+/// impl Seeded for Subscribe {
+///     type Seed = Plans;
+/// }
+///
+/// impl<'de> DeserializeSeed<'de> for Plans {
+///     type Value = Subscribe;
+///
+///     fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Subscribe, D::Error> {
+///         let subscribe: Subscribe = serde::Deserialize::deserialize(deserializer)?;
+///         if !self.0.contains(&subscribe.plan) {
+///             return Err(D::Error::custom("unknown plan"));
+///         }
+///         Ok(subscribe)
+///     }
+/// }
+///
+/// async fn subscribe(SeededJson(payload): SeededJson<Subscribe>) {}
+///
+/// let app = Router::new()
+///     .route("/subscriptions", post(subscribe))
+///     .with_state(AppState {
+///         plans: Plans(vec!["free".into(), "pro".into()]),
+///     });
+/// # let _: Router = app;
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+#[must_use]
+pub struct SeededJson<T>(pub T);
+
+impl<T, S> FromRequest<S> for SeededJson<T>
+where
+    T: DeserializeOwned,
+    T: for<'de> EDeserialize<'de>,
+    T: Seeded,
+    T::Seed: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = JsonRejection;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let config = Config::from_request(&req);
+        check_json_headers(req.headers(), &config)?;
+        let (bytes, _permit) =
+            crate::body::read_body::<_, Self::Rejection>(req, state, &config).await?;
+        let seed = T::Seed::from_ref(state);
+        Self::from_bytes_with_config(&bytes, seed, &config)
+    }
+}
+
+impl<T> SeededJson<T>
+where
+    T: DeserializeOwned,
+    T: for<'de> EDeserialize<'de>,
+    T: Seeded,
+{
+    /// Construct a `SeededJson<T>` from a byte slice and a seed. Most users should prefer to use
+    /// the `FromRequest` impl but special cases may require first extracting a `Request` into
+    /// `Bytes` then optionally constructing a `SeededJson<T>`.
+    pub fn from_bytes(bytes: &[u8], seed: T::Seed) -> Result<Self, JsonRejection> {
+        Self::from_bytes_with_config(bytes, seed, &Config::default())
+    }
+
+    fn from_bytes_with_config(
+        bytes: &[u8],
+        seed: T::Seed,
+        config: &Config,
+    ) -> Result<Self, JsonRejection> {
+        check_document(bytes, config)?;
+        deserialize_via(bytes, config, || eserde::json::from_slice_seed(seed, bytes))
+            .map(SeededJson)
+    }
+}
+
+impl<T> Deref for SeededJson<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for SeededJson<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T> From<T> for SeededJson<T> {
+    fn from(inner: T) -> Self {
+        Self(inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils;
+    use serde::de::{Deserializer, Error, IgnoredAny, MapAccess, Visitor};
+
+    #[derive(Clone)]
+    struct Plans(Vec<&'static str>);
+
+    #[derive(Clone)]
+    struct AppState {
+        plans: Plans,
+    }
+
+    impl FromRef<AppState> for Plans {
+        fn from_ref(state: &AppState) -> Self {
+            state.plans.clone()
+        }
+    }
+
+    #[derive(Debug, eserde::Deserialize)]
+    struct Subscribe {
+        plan: String,
+        seats: u8,
+    }
+
+    impl Seeded for Subscribe {
+        type Seed = Plans;
+    }
+
+    /// Deserialize the plan with a seed of its own, to point at it on failure.
+    impl<'de> DeserializeSeed<'de> for Plans {
+        type Value = Subscribe;
+
+        fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Subscribe, D::Error> {
+            deserializer.deserialize_struct("Subscribe", &["plan", "seats"], self)
+        }
+    }
+
+    impl<'de> Visitor<'de> for Plans {
+        type Value = Subscribe;
+
+        fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            f.write_str("a subscription")
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Subscribe, A::Error> {
+            let (mut plan, mut seats) = (None, None);
+            while let Some(key) = map.next_key::<String>()? {
+                match key.as_str() {
+                    "plan" => plan = Some(map.next_value_seed(KnownPlan(&self.0))?),
+                    "seats" => seats = Some(map.next_value()?),
+                    _ => {
+                        map.next_value::<IgnoredAny>()?;
+                    }
+                }
+            }
+            Ok(Subscribe {
+                plan: plan.ok_or_else(|| A::Error::missing_field("plan"))?,
+                seats: seats.ok_or_else(|| A::Error::missing_field("seats"))?,
+            })
+        }
+    }
+
+    struct KnownPlan<'a>(&'a [&'static str]);
+
+    impl<'de> DeserializeSeed<'de> for KnownPlan<'_> {
+        type Value = String;
+
+        fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<String, D::Error> {
+            let plan: String = serde::Deserialize::deserialize(deserializer)?;
+            if !self.0.contains(&plan.as_str()) {
+                return Err(D::Error::custom(format!("unknown plan `{plan}`")));
+            }
+            Ok(plan)
+        }
+    }
+
+    fn state() -> AppState {
+        AppState {
+            plans: Plans(vec!["free", "pro"]),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_valid_seeded_field() {
+        let request = test_utils::json_request(r#"{"plan": "pro", "seats": 3}"#);
+        let SeededJson(subscribe) = SeededJson::<Subscribe>::from_request(request.body(), &state())
+            .await
+            .unwrap();
+        assert_eq!(subscribe.plan, "pro");
+        assert_eq!(subscribe.seats, 3);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_seeded_field() {
+        let request = test_utils::json_request(r#"{"plan": "enterprise", "seats": "3"}"#);
+        let rejection = SeededJson::<Subscribe>::from_request(request.body(), &state())
+            .await
+            .unwrap_err();
+
+        let (status, body) = test_utils::problem(rejection).await;
+        assert_eq!(status, 400);
+        let errors = body["errors"].as_array().unwrap();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0]["pointer"], "/plan");
+        assert_eq!(
+            errors[0]["detail"],
+            "unknown plan `enterprise` at line 1 column 21"
+        );
+        assert_eq!(errors[1]["pointer"], "/seats");
+    }
+
+    #[test]
+    fn test_context_free_error_is_reported_once() {
+        let plans = Plans(vec!["free"]);
+        let rejection =
+            SeededJson::<Subscribe>::from_bytes(br#"{"plan": "free", "seats": -1}"#, plans)
+                .unwrap_err();
+        let JsonRejection::JsonError(error) = rejection else {
+            panic!("Expected a deserialization error, got {rejection:?}");
+        };
+        assert_eq!(error.errors().len(), 1);
+    }
+}
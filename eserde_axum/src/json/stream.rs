@@ -0,0 +1,632 @@
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use axum_core::body::{Body, BodyDataStream};
+use axum_core::extract::{FromRequest, Request};
+use bytes::{Bytes, BytesMut};
+use eserde::{DeserializationErrors, EDeserialize};
+use futures_core::Stream;
+use serde::de::DeserializeOwned;
+
+use super::{check_json_headers, JsonRejection};
+use crate::body::ExtractionPermit;
+use crate::config::Config;
+
+/// JSON extractor for request bodies holding a (potentially huge) top-level array,
+/// yielding its elements one at a time as a [`Stream`].
+///
+/// The body is read chunk by chunk, as the stream is polled: only the element
+/// being parsed is buffered, so the elements can be processed and dropped
+/// without holding the whole array in memory.
+/// Each element is deserialized independently into some type that implements
+/// [`serde::de::DeserializeOwned`] and [`eserde::EDeserialize`]: an element that
+/// fails to deserialize doesn't abort the others, it's reported as an
+/// [`ElementError`] carrying its index.
+/// The stream ends with a [`JsonStreamError`] if the body isn't a well-formed array
+/// or if it can't be read.
+///
+/// [`Config::max_body_size`] caps the size of each element, rather than the size of
+/// the whole body. The stream counts against [`Config::max_concurrent_extractions`]
+/// until it's dropped. The other JSON settings of the [`Config`] are ignored.
+///
+/// The request will be rejected (and a [`JsonRejection`] will be returned) if the
+/// `Content-Type` header is missing or isn't a JSON content type, like it would be for
+/// [`Json<T>`](crate::Json).
+///
+/// ⚠️ Since the elements are read from the request body, the `JsonStream` extractor must be
+/// *last* if there are multiple extractors in a handler.
+/// See ["the order of extractors"][order-of-extractors]
+///
+/// [order-of-extractors]: https://docs.rs/axum/latest/axum/extract/index.html#the-order-of-extractors
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{routing::post, Router};
+/// use eserde_axum::{json::JsonStreamError, JsonStream};
+/// use futures_core::Stream;
+/// use std::future::poll_fn;
+/// use std::pin::Pin;
+///
+/// #[derive(eserde::Deserialize)]
+/// struct Reading {
+///     sensor: String,
+///     value: f64,
+/// }
+///
+/// async fn upload(mut readings: JsonStream<Reading>) -> String {
+///     let (mut stored, mut failed) = (0, Vec::new());
+///     while let Some(reading) = poll_fn(|cx| Pin::new(&mut readings).poll_next(cx)).await {
+///         match reading {
+///             Ok(reading) => { /* store the reading */ stored += 1 }
+///             Err(JsonStreamError::Element(error)) => failed.push(error.index().to_string()),
+///             Err(error) => return format!("Upload aborted: {error}"),
+///         }
+///     }
+///     format!("Stored {stored} readings, failed: {}", failed.join(", "))
+/// }
+///
+/// let app = Router::new().route("/readings", post(upload));
+/// # let _: Router = app;
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "stream")))]
+#[must_use]
+pub struct JsonStream<T> {
+    body: BodyDataStream,
+    scanner: Scanner,
+    max_element_size: Option<usize>,
+    body_done: bool,
+    finished: bool,
+    /// Held until the stream is dropped.
+    _permit: ExtractionPermit,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> std::fmt::Debug for JsonStream<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JsonStream")
+            .field("index", &self.scanner.index)
+            .field("finished", &self.finished)
+            .finish_non_exhaustive()
+    }
+}
+
+/// An element of a [`JsonStream`] that couldn't be deserialized.
+#[derive(Debug)]
+#[cfg_attr(docsrs, doc(cfg(feature = "stream")))]
+pub struct ElementError {
+    index: usize,
+    errors: DeserializationErrors,
+}
+
+impl ElementError {
+    /// The position of the offending element in the array, starting from 0.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// The errors encountered while deserializing the element.
+    ///
+    /// Their paths are relative to the element.
+    pub fn errors(&self) -> &DeserializationErrors {
+        &self.errors
+    }
+}
+
+impl std::fmt::Display for ElementError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Failed to deserialize element {}:", self.index)?;
+        for e in self.errors.iter() {
+            writeln!(f, "- {}", e)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ElementError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.errors)
+    }
+}
+
+/// The errors yielded by a [`JsonStream`].
+#[derive(Debug)]
+#[non_exhaustive]
+#[cfg_attr(docsrs, doc(cfg(feature = "stream")))]
+pub enum JsonStreamError {
+    /// An element couldn't be deserialized.
+    ///
+    /// The stream goes on with the following elements.
+    Element(ElementError),
+    /// The body isn't a well-formed JSON array. The stream ends.
+    Malformed {
+        /// The position, in bytes from the start of the body, of the unexpected input.
+        offset: u64,
+    },
+    /// An element is larger than [`Config::max_body_size`]. The stream ends.
+    ElementTooLarge {
+        /// The position of the offending element in the array, starting from 0.
+        index: usize,
+        /// The maximum size of an element, in bytes.
+        max: usize,
+    },
+    /// Reading the request body failed. The stream ends.
+    Body(axum_core::Error),
+}
+
+impl std::fmt::Display for JsonStreamError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Element(inner) => write!(f, "{inner}"),
+            Self::Malformed { offset } => write!(
+                f,
+                "Expected the request body to be a JSON array, but found unexpected input at byte {offset}"
+            ),
+            Self::ElementTooLarge { index, max } => write!(
+                f,
+                "Element {index} of the request body exceeds the limit of {max} bytes"
+            ),
+            Self::Body(inner) => write!(f, "Failed to read the request body: {inner}"),
+        }
+    }
+}
+
+impl std::error::Error for JsonStreamError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Element(inner) => Some(inner),
+            Self::Body(inner) => Some(inner),
+            Self::Malformed { .. } | Self::ElementTooLarge { .. } => None,
+        }
+    }
+}
+
+impl<T, S> FromRequest<S> for JsonStream<T>
+where
+    T: DeserializeOwned,
+    T: for<'de> EDeserialize<'de>,
+    S: Send + Sync,
+{
+    type Rejection = JsonRejection;
+
+    async fn from_request(req: Request, _state: &S) -> Result<Self, Self::Rejection> {
+        let config = Config::from_request(&req);
+        check_json_headers(req.headers(), &config)?;
+        let permit = ExtractionPermit::acquire(&config)?;
+        Ok(Self {
+            _permit: permit,
+            ..Self::new(req.into_body(), config.settings.max_body_size)
+        })
+    }
+}
+
+impl<T> JsonStream<T> {
+    pub(super) fn new(body: Body, max_element_size: Option<usize>) -> Self {
+        Self {
+            body: body.into_data_stream(),
+            scanner: Scanner::default(),
+            max_element_size,
+            body_done: false,
+            finished: false,
+            _permit: ExtractionPermit::default(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Stream for JsonStream<T>
+where
+    T: DeserializeOwned,
+    T: for<'de> EDeserialize<'de>,
+{
+    type Item = Result<T, JsonStreamError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            if this.finished {
+                return Poll::Ready(None);
+            }
+            let error = match this.scanner.step(this.body_done, this.max_element_size) {
+                Step::Element(bytes) => {
+                    let index = this.scanner.index;
+                    this.scanner.index += 1;
+                    let element = eserde::json::from_slice(&bytes)
+                        .map_err(|errors| JsonStreamError::Element(ElementError { index, errors }));
+                    return Poll::Ready(Some(element));
+                }
+                Step::End => {
+                    this.finished = true;
+                    continue;
+                }
+                Step::Malformed { offset } => JsonStreamError::Malformed { offset },
+                Step::TooLarge { max } => JsonStreamError::ElementTooLarge {
+                    index: this.scanner.index,
+                    max,
+                },
+                Step::NeedMore => match std::task::ready!(Pin::new(&mut this.body).poll_next(cx)) {
+                    Some(Ok(chunk)) => {
+                        this.scanner.buffer.extend_from_slice(&chunk);
+                        continue;
+                    }
+                    Some(Err(e)) => JsonStreamError::Body(e),
+                    None => {
+                        this.body_done = true;
+                        continue;
+                    }
+                },
+            };
+            this.finished = true;
+            return Poll::Ready(Some(Err(error)));
+        }
+    }
+}
+
+/// Splits a JSON array into its elements, as the bytes come in.
+///
+/// The elements are only delimited here—nesting and strings are tracked, nothing
+/// more: their content is validated when they are deserialized.
+#[derive(Debug, Default)]
+struct Scanner {
+    /// The bytes that haven't been handed over yet.
+    buffer: BytesMut,
+    /// How many bytes at the start of `buffer` have already been scanned.
+    pos: usize,
+    /// How many bytes have been dropped from the start of `buffer`, to report offsets.
+    consumed: u64,
+    state: State,
+    /// The index of the next element.
+    index: usize,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+enum State {
+    /// Before the opening `[`.
+    #[default]
+    Start,
+    /// After `[` (`first`) or after `,`: expecting an element.
+    BeforeElement { first: bool },
+    /// Within an element, which starts at the beginning of the buffer.
+    InElement {
+        depth: usize,
+        in_string: bool,
+        escaped: bool,
+    },
+    /// After an element: expecting `,` or `]`.
+    AfterElement,
+    /// After the closing `]`: only whitespace is allowed.
+    Trailing,
+}
+
+enum Step {
+    Element(Bytes),
+    End,
+    Malformed { offset: u64 },
+    TooLarge { max: usize },
+    NeedMore,
+}
+
+impl Scanner {
+    /// Scan the buffered bytes for the next element.
+    ///
+    /// `eof` is set once the whole body has been buffered.
+    fn step(&mut self, eof: bool, max_element_size: Option<usize>) -> Step {
+        while self.pos < self.buffer.len() {
+            let byte = self.buffer[self.pos];
+            match &mut self.state {
+                State::InElement {
+                    depth,
+                    in_string,
+                    escaped,
+                } => {
+                    let end = if *in_string {
+                        if *escaped {
+                            *escaped = false;
+                        } else if byte == b'\\' {
+                            *escaped = true;
+                        } else if byte == b'"' {
+                            *in_string = false;
+                        }
+                        (!*in_string && *depth == 0).then_some(self.pos + 1)
+                    } else {
+                        match byte {
+                            b'"' => {
+                                *in_string = true;
+                                None
+                            }
+                            b'{' | b'[' => {
+                                *depth += 1;
+                                None
+                            }
+                            b'}' | b']' if *depth > 0 => {
+                                *depth -= 1;
+                                (*depth == 0).then_some(self.pos + 1)
+                            }
+                            // The end of a scalar, left for `AfterElement` to consume.
+                            b'}' | b']' | b',' if *depth == 0 => Some(self.pos),
+                            byte if byte.is_ascii_whitespace() && *depth == 0 => Some(self.pos),
+                            _ => None,
+                        }
+                    };
+                    // The element starts the buffer: its size is the number of bytes scanned,
+                    // its closing byte included.
+                    let scanned = end.unwrap_or(self.pos + 1);
+                    if let Some(max) = max_element_size.filter(|max| scanned > *max) {
+                        return Step::TooLarge { max };
+                    }
+                    if let Some(end) = end {
+                        self.state = State::AfterElement;
+                        return Step::Element(self.take(end));
+                    }
+                    self.pos += 1;
+                }
+                _ if byte.is_ascii_whitespace() => self.pos += 1,
+                State::Start if byte == b'[' => {
+                    self.state = State::BeforeElement { first: true };
+                    self.pos += 1;
+                }
+                State::BeforeElement { first: true } if byte == b']' => {
+                    self.state = State::Trailing;
+                    self.pos += 1;
+                }
+                State::BeforeElement { .. } if byte != b',' && byte != b']' => {
+                    // Drop the separators, so that the element starts the buffer.
+                    self.take(self.pos);
+                    self.state = State::InElement {
+                        depth: 0,
+                        in_string: false,
+                        escaped: false,
+                    };
+                }
+                State::AfterElement if byte == b',' => {
+                    self.state = State::BeforeElement { first: false };
+                    self.pos += 1;
+                }
+                State::AfterElement if byte == b']' => {
+                    self.state = State::Trailing;
+                    self.pos += 1;
+                }
+                _ => return self.malformed(),
+            }
+        }
+        if !matches!(self.state, State::InElement { .. }) {
+            // Only whitespace and punctuation have been scanned, no need to keep it around.
+            self.take(self.pos);
+        }
+        match (eof, self.state) {
+            (false, _) => Step::NeedMore,
+            (true, State::Trailing) => Step::End,
+            (true, _) => self.malformed(),
+        }
+    }
+
+    /// Hand over the first `len` bytes of the buffer, which cover
+    /// everything that has been scanned so far.
+    fn take(&mut self, len: usize) -> Bytes {
+        self.consumed += len as u64;
+        self.pos = 0;
+        self.buffer.split_to(len).freeze()
+    }
+
+    fn malformed(&self) -> Step {
+        Step::Malformed {
+            offset: self.consumed + self.pos as u64,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+    use std::future::poll_fn;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    #[derive(Debug, PartialEq, eserde::Deserialize)]
+    struct Reading {
+        sensor: String,
+        value: u32,
+    }
+
+    fn chunked<T>(chunks: &[&str]) -> (JsonStream<T>, Arc<AtomicUsize>) {
+        let (body, pulled) = crate::test_utils::chunked_body(chunks);
+        (JsonStream::new(body, None), pulled)
+    }
+
+    async fn next<T>(stream: &mut JsonStream<T>) -> Option<Result<T, JsonStreamError>>
+    where
+        T: DeserializeOwned,
+        T: for<'de> EDeserialize<'de>,
+    {
+        poll_fn(|cx| Pin::new(&mut *stream).poll_next(cx)).await
+    }
+
+    async fn collect<T>(stream: &mut JsonStream<T>) -> Vec<Result<T, JsonStreamError>>
+    where
+        T: DeserializeOwned,
+        T: for<'de> EDeserialize<'de>,
+    {
+        let mut items = Vec::new();
+        while let Some(item) = next(stream).await {
+            items.push(item);
+        }
+        items
+    }
+
+    #[tokio::test]
+    async fn test_elements_split_across_chunks() {
+        let (mut stream, _) = chunked::<Reading>(&[
+            " [ {\"sensor\": \"a\", ",
+            "\"value\": 1}, {\"sensor\": \"b,]\\\"\"",
+            ", \"value\": 2}\n,{\"sensor\": 3, \"value\": -1}",
+            "]  ",
+        ]);
+        let items = collect(&mut stream).await;
+        assert_eq!(items.len(), 3);
+        assert_eq!(
+            items[0].as_ref().unwrap(),
+            &Reading {
+                sensor: "a".into(),
+                value: 1
+            }
+        );
+        assert_eq!(items[1].as_ref().unwrap().sensor, "b,]\"");
+        let Err(JsonStreamError::Element(error)) = &items[2] else {
+            panic!("Expected an element error");
+        };
+        assert_eq!(error.index(), 2);
+        assert_eq!(error.errors().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_scalars() {
+        let (mut stream, _) = chunked::<u32>(&["[1,2", "3 , \"x\",4]"]);
+        let items = collect(&mut stream).await;
+        assert_eq!(items.len(), 4);
+        assert_eq!(*items[0].as_ref().unwrap(), 1);
+        assert_eq!(*items[1].as_ref().unwrap(), 23);
+        assert!(matches!(&items[2], Err(JsonStreamError::Element(e)) if e.index() == 2));
+        assert_eq!(*items[3].as_ref().unwrap(), 4);
+
+        let (mut stream, _) = chunked::<u32>(&["[]"]);
+        assert!(collect(&mut stream).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_malformed() {
+        for (body, expected) in [
+            ("", 0),
+            ("{}", 0),
+            ("[1,,2]", 3),
+            ("[1 2]", 3),
+            ("[1,", 3),
+            ("[1] x", 4),
+        ] {
+            let (mut stream, _) = chunked::<u32>(&[body]);
+            let items = collect(&mut stream).await;
+            let Some(Err(JsonStreamError::Malformed { offset })) = items.last() else {
+                panic!("Expected `{body}` to be malformed");
+            };
+            assert_eq!(*offset, expected, "{body}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_element_too_large() {
+        let (stream, _) = chunked::<String>(&["[\"short\", \"much too long\"]"]);
+        let mut stream = JsonStream {
+            max_element_size: Some(8),
+            ..stream
+        };
+        assert_eq!(next(&mut stream).await.unwrap().unwrap(), "short");
+        assert!(matches!(
+            next(&mut stream).await,
+            Some(Err(JsonStreamError::ElementTooLarge { index: 1, max: 8 }))
+        ));
+        assert!(next(&mut stream).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_element_size_boundary() {
+        /// Check that the first element, of exactly `max` bytes, is accepted
+        /// while the second one, one byte longer, is rejected.
+        async fn check<T>(body: &str, max: usize)
+        where
+            T: DeserializeOwned,
+            T: for<'de> EDeserialize<'de>,
+        {
+            let (stream, _) = chunked::<T>(&[body]);
+            let mut stream = JsonStream {
+                max_element_size: Some(max),
+                ..stream
+            };
+            assert!(next(&mut stream).await.unwrap().is_ok(), "{body}");
+            assert!(
+                matches!(
+                    next(&mut stream).await,
+                    Some(Err(JsonStreamError::ElementTooLarge { index: 1, .. }))
+                ),
+                "{body}"
+            );
+        }
+
+        check::<String>(r#"["123456", "1234567"]"#, 8).await;
+        check::<BTreeMap<String, u32>>(r#"[{"a":12}, {"a":123}]"#, 8).await;
+        check::<Vec<u32>>("[[1,2,3], [1,2,34]]", 7).await;
+        check::<u32>("[12345678, 123456789]", 8).await;
+    }
+
+    #[tokio::test]
+    async fn test_memory_stays_bounded() {
+        const ELEMENTS: usize = 10_000;
+        let element = r#"{"sensor": "thermometer", "value": 42}"#;
+        let mut chunks = vec!["["];
+        for i in 0..ELEMENTS {
+            if i > 0 {
+                chunks.push(",");
+            }
+            chunks.push(element);
+        }
+        chunks.push("]");
+        let (mut stream, pulled) = chunked::<Reading>(&chunks);
+
+        let mut count = 0;
+        while let Some(reading) = next(&mut stream).await {
+            assert_eq!(reading.unwrap().value, 42);
+            count += 1;
+            // The body is pulled lazily, one element at a time...
+            assert!(pulled.load(Ordering::SeqCst) <= 2 * count + 1);
+            // ...and only the bytes that haven't been handed over are kept around.
+            assert!(stream.scanner.buffer.len() <= element.len());
+        }
+        assert_eq!(count, ELEMENTS);
+    }
+
+    #[tokio::test]
+    async fn test_content_type_is_checked() {
+        let request = Request::builder()
+            .method("POST")
+            .uri("/")
+            .header(http::header::CONTENT_TYPE, "text/plain")
+            .body(Body::from("[]"))
+            .unwrap();
+        let rejection = JsonStream::<u32>::from_request(request, &())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            rejection,
+            JsonRejection::JsonContentTypeMismatch(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_stream_holds_an_extraction_permit() {
+        let config = Config::new().max_concurrent_extractions(1, std::time::Duration::from_secs(1));
+        let request = || {
+            Request::builder()
+                .method("POST")
+                .uri("/")
+                .header(http::header::CONTENT_TYPE, "application/json")
+                .extension(config.clone())
+                .body(Body::from("[1]"))
+                .unwrap()
+        };
+        let mut stream = JsonStream::<u32>::from_request(request(), &())
+            .await
+            .unwrap();
+        assert_eq!(next(&mut stream).await.unwrap().unwrap(), 1);
+        // Exhausted or not, the stream holds the only slot until it's dropped.
+        assert!(next(&mut stream).await.is_none());
+        let rejection = JsonStream::<u32>::from_request(request(), &())
+            .await
+            .unwrap_err();
+        assert!(matches!(rejection, JsonRejection::Overloaded(_)));
+
+        drop(stream);
+        assert!(JsonStream::<u32>::from_request(request(), &())
+            .await
+            .is_ok());
+    }
+}
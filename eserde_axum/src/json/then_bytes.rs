@@ -0,0 +1,155 @@
+use axum_core::extract::{FromRequest, Request};
+use bytes::Bytes;
+use eserde::EDeserialize;
+use serde::de::DeserializeOwned;
+
+use super::{Json, JsonRejection};
+use crate::config::Config;
+
+/// Extractor for request bodies made of a JSON header, on its own line,
+/// followed by arbitrary bytes—e.g. the metadata of an upload and the file itself.
+///
+/// The body is split at the first newline (`\n`): what comes before is deserialized
+/// into `T`, exactly like [`Json<T>`] would, while what comes after is handed over
+/// as is. The newline itself belongs to neither.
+/// If there is no newline, the whole body is the JSON header and the trailing bytes
+/// are empty.
+/// Since the body is not a JSON document, the `Content-Type` header isn't checked.
+///
+/// The request will be rejected (and a [`JsonRejection`] will be returned) if the
+/// header fails to deserialize or if buffering the request body fails.
+///
+/// ⚠️ Since parsing the header requires consuming the request body, the `JsonThenBytes`
+/// extractor must be *last* if there are multiple extractors in a handler.
+/// See ["the order of extractors"][order-of-extractors]
+///
+/// [order-of-extractors]: https://docs.rs/axum/latest/axum/extract/index.html#the-order-of-extractors
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{routing::post, Router};
+/// use eserde_axum::json::JsonThenBytes;
+///
+/// #[derive(eserde::Deserialize)]
+/// struct Upload {
+///     file_name: String,
+/// }
+///
+/// async fn upload(JsonThenBytes(upload, content): JsonThenBytes<Upload>) -> String {
+///     format!("Received {} bytes for {}", content.len(), upload.file_name)
+/// }
+///
+/// let app = Router::new().route("/uploads", post(upload));
+/// # let _: Router = app;
+/// ```
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+#[must_use]
+pub struct JsonThenBytes<T>(pub T, pub Bytes);
+
+impl<T, S> FromRequest<S> for JsonThenBytes<T>
+where
+    T: DeserializeOwned,
+    T: for<'de> EDeserialize<'de>,
+    S: Send + Sync,
+{
+    type Rejection = JsonRejection;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let config = Config::from_request(&req);
+        let (bytes, _permit) =
+            crate::body::read_body::<_, Self::Rejection>(req, state, &config).await?;
+        Self::from_bytes_with_config(bytes, &config)
+    }
+}
+
+impl<T> JsonThenBytes<T>
+where
+    T: DeserializeOwned,
+    T: for<'de> EDeserialize<'de>,
+{
+    /// Split a buffered body into its JSON header and its trailing bytes.
+    ///
+    /// Most users should prefer to use the `FromRequest` impl.
+    pub fn from_bytes(bytes: Bytes) -> Result<Self, JsonRejection> {
+        Self::from_bytes_with_config(bytes, &Config::default())
+    }
+
+    fn from_bytes_with_config(bytes: Bytes, config: &Config) -> Result<Self, JsonRejection> {
+        let (header, rest) = match bytes.iter().position(|&b| b == b'\n') {
+            Some(newline) => (bytes.slice(..newline), bytes.slice(newline + 1..)),
+            None => (bytes, Bytes::new()),
+        };
+        let Json(value) = Json::from_bytes_with_config(&header, config)?;
+        Ok(Self(value, rest))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils;
+    use axum_core::body::Body;
+
+    #[derive(Debug, PartialEq, eserde::Deserialize)]
+    struct Upload {
+        file_name: String,
+        size: u32,
+    }
+
+    fn request(body: &'static [u8]) -> Request {
+        http::Request::builder()
+            .method("POST")
+            .uri("/")
+            .header(http::header::CONTENT_TYPE, "application/octet-stream")
+            .body(Body::from(body))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_header_then_binary() {
+        let body = b"{\"file_name\": \"logo.png\", \"size\": 6}\n\x89PNG\n\x00";
+        let JsonThenBytes(upload, content) =
+            JsonThenBytes::<Upload>::from_request(request(body), &())
+                .await
+                .unwrap();
+        assert_eq!(
+            upload,
+            Upload {
+                file_name: "logo.png".into(),
+                size: 6
+            }
+        );
+        assert_eq!(&content[..], b"\x89PNG\n\x00");
+    }
+
+    #[test]
+    fn test_no_delimiter() {
+        let JsonThenBytes(upload, content) = JsonThenBytes::<Upload>::from_bytes(
+            Bytes::from_static(br#"{"file_name": "empty.txt", "size": 0}"#),
+        )
+        .unwrap();
+        assert_eq!(upload.file_name, "empty.txt");
+        assert!(content.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_malformed_header() {
+        let rejection =
+            JsonThenBytes::<Upload>::from_request(request(b"{\"file_name\": 1}\n\x00\x01"), &())
+                .await
+                .unwrap_err();
+
+        let (status, body) = test_utils::problem(rejection).await;
+        assert_eq!(status, 400);
+        assert_eq!(body["type"], "invalid_request");
+        let pointers: Vec<_> = body["errors"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|error| error["pointer"].as_str().unwrap())
+            .collect();
+        assert_eq!(pointers, ["/file_name", ""]);
+    }
+}
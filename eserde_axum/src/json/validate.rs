@@ -0,0 +1,102 @@
+use std::marker::PhantomData;
+
+use axum_core::extract::{FromRequest, Request};
+use axum_core::response::{IntoResponse, Response};
+use eserde::EDeserialize;
+use http::StatusCode;
+use serde::de::DeserializeOwned;
+
+use super::{Json, JsonRejection};
+
+/// JSON extractor that only checks whether the request body is a valid `T`,
+/// e.g. for a `/validate` endpoint.
+///
+/// It accepts and rejects exactly the same requests as [`Json<T>`], with the same
+/// [`JsonRejection`]s, but it discards the deserialized value right away:
+/// the handler gets no `T` to hold on to.
+/// Return the extractor itself from the handler to reply with a `204 No Content`.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{routing::post, Router};
+/// use eserde_axum::json::ValidateOnly;
+///
+/// #[derive(eserde::Deserialize)]
+/// struct Order {
+///     sku: String,
+///     quantity: u32,
+/// }
+///
+/// async fn validate_order(valid: ValidateOnly<Order>) -> ValidateOnly<Order> {
+///     valid
+/// }
+///
+/// let app = Router::new().route("/orders/validate", post(validate_order));
+/// # let _: Router = app;
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+#[must_use]
+pub struct ValidateOnly<T>(PhantomData<fn() -> T>);
+
+impl<T, S> FromRequest<S> for ValidateOnly<T>
+where
+    T: DeserializeOwned,
+    T: for<'de> EDeserialize<'de>,
+    S: Send + Sync,
+{
+    type Rejection = JsonRejection;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let Json(_) = Json::<T>::from_request(req, state).await?;
+        Ok(Self(PhantomData))
+    }
+}
+
+impl<T> IntoResponse for ValidateOnly<T> {
+    fn into_response(self) -> Response {
+        StatusCode::NO_CONTENT.into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils;
+
+    #[derive(Debug, eserde::Deserialize)]
+    #[allow(dead_code)]
+    struct Order {
+        sku: String,
+        quantity: u32,
+    }
+
+    #[tokio::test]
+    async fn test_valid_payload() {
+        let request = test_utils::json_request(r#"{"sku": "A-1", "quantity": 3}"#);
+        let valid = ValidateOnly::<Order>::from_request(request.body(), &())
+            .await
+            .unwrap();
+        assert_eq!(valid.into_response().status(), StatusCode::NO_CONTENT);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_payload() {
+        let request = test_utils::json_request(r#"{"sku": 1, "quantity": -3}"#);
+        let rejection = ValidateOnly::<Order>::from_request(request.body(), &())
+            .await
+            .unwrap_err();
+
+        let (status, body) = test_utils::problem(rejection).await;
+        assert_eq!(status, 400);
+        assert_eq!(body["type"], "invalid_request");
+        let pointers: Vec<_> = body["errors"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|error| error["pointer"].as_str().unwrap())
+            .collect();
+        assert_eq!(pointers, ["/sku", "/quantity"]);
+    }
+}
@@ -0,0 +1,131 @@
+use axum_core::extract::{FromRequest, Request};
+use eserde::EDeserialize;
+use serde::de::DeserializeOwned;
+
+use super::json_::{check_document, check_json_headers, deserialize};
+use super::JsonRejection;
+use crate::config::Config;
+
+/// JSON extractor returning both the typed `T` and the generic [`serde_json::Value`]
+/// of the request body—e.g. to audit or diff the raw payload next to the typed one.
+///
+/// The body is deserialized into `T` exactly like [`Json<T>`](super::Json) would,
+/// rejecting the request with the same [`JsonRejection`]s (e.g. an `invalid_request`
+/// problem listing all the errors) if it doesn't match.
+///
+/// # Performance
+///
+/// The buffered body is parsed twice: once into `T`, then, if that succeeds, into the
+/// [`serde_json::Value`].
+/// Deriving `T` from the `Value` would save a pass over the bytes, but it changes what
+/// `T` accepts (e.g. no borrowed or [`RawValue`](serde_json::value::RawValue) fields)
+/// and it loses the line/column positions of the errors.
+///
+/// ⚠️ Since parsing JSON requires consuming the request body, the `JsonWithValue`
+/// extractor must be *last* if there are multiple extractors in a handler.
+/// See ["the order of extractors"][order-of-extractors]
+///
+/// [order-of-extractors]: https://docs.rs/axum/latest/axum/extract/index.html#the-order-of-extractors
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{routing::patch, Router};
+/// use eserde_axum::json::JsonWithValue;
+///
+/// #[derive(eserde::Deserialize)]
+/// struct UpdateUser {
+///     name: String,
+/// }
+///
+/// async fn update_user(JsonWithValue(update, raw): JsonWithValue<UpdateUser>) -> String {
+///     format!("Renaming to {}, audited as {raw}", update.name)
+/// }
+///
+/// let app = Router::new().route("/users/{id}", patch(update_user));
+/// # let _: Router = app;
+/// ```
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+#[must_use]
+pub struct JsonWithValue<T>(pub T, pub serde_json::Value);
+
+impl<T, S> FromRequest<S> for JsonWithValue<T>
+where
+    T: DeserializeOwned,
+    T: for<'de> EDeserialize<'de>,
+    S: Send + Sync,
+{
+    type Rejection = JsonRejection;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let config = Config::from_request(&req);
+        check_json_headers(req.headers(), &config)?;
+        let (bytes, _permit) =
+            crate::body::read_body::<_, Self::Rejection>(req, state, &config).await?;
+        Self::from_bytes_with_config(&bytes, &config)
+    }
+}
+
+impl<T> JsonWithValue<T>
+where
+    T: DeserializeOwned,
+    T: for<'de> EDeserialize<'de>,
+{
+    /// Construct a `JsonWithValue<T>` from a byte slice.
+    ///
+    /// Most users should prefer to use the `FromRequest` impl.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, JsonRejection> {
+        Self::from_bytes_with_config(bytes, &Config::default())
+    }
+
+    fn from_bytes_with_config(bytes: &[u8], config: &Config) -> Result<Self, JsonRejection> {
+        check_document(bytes, config)?;
+        let typed = deserialize(bytes, config)?;
+        let value = deserialize(bytes, config)?;
+        Ok(Self(typed, value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils;
+
+    #[derive(Debug, eserde::Deserialize)]
+    struct User {
+        name: String,
+        age: u8,
+    }
+
+    #[tokio::test]
+    async fn test_typed_and_value() {
+        let request = test_utils::json_request(r#"{"name": "Alice", "age": 30, "extra": [1]}"#);
+        let JsonWithValue(user, value) = JsonWithValue::<User>::from_request(request.body(), &())
+            .await
+            .unwrap();
+        assert_eq!(value["name"], user.name.as_str());
+        assert_eq!(value["age"], user.age);
+        // Unknown fields are kept in the value, even if `T` ignores them.
+        assert_eq!(value["extra"], serde_json::json!([1]));
+    }
+
+    #[tokio::test]
+    async fn test_invalid_request() {
+        let request = test_utils::json_request(r#"{"name": 1, "age": "x"}"#);
+        let rejection = JsonWithValue::<User>::from_request(request.body(), &())
+            .await
+            .unwrap_err();
+
+        let (status, body) = test_utils::problem(rejection).await;
+        assert_eq!(status, 400);
+        assert_eq!(body["type"], "invalid_request");
+        let pointers: Vec<_> = body["errors"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|error| error["pointer"].as_str().unwrap())
+            .collect();
+        assert_eq!(pointers, ["/name", "/age"]);
+    }
+}
@@ -5,7 +5,56 @@
 //! They are designed to be drop-in replacement for their official [`axum`]
 //! counterpart.
 //!
-//! Check out [`Json`] for working with JSON payloads.
+//! Check out [`Json`] for working with JSON payloads (or [`Jsonc`], if they may
+//! contain comments) and [`Form`] for URL encoded forms.
+//! Enable the `query` feature to deserialize query strings, including nested
+//! parameters such as `filter[status]`, see `Query`.
+//! Enable the `combined` feature to deserialize the path parameters, the query string
+//! and the JSON body of a request in one go, see `Combined`.
+//! Enable the `jsonschema` feature to validate JSON payloads against a JSON Schema
+//! too, see `SchemaJson`.
+//! Check out [`Created`] to respond to successful creations with a `Location` header,
+//! and [`Prefer`] to skip the response body for clients that don't need it.
+//! Check out [`NdJson`] for bulk payloads, where each line is deserialized independently.
+//! Enable the `multipart` feature to deserialize each JSON part of a `multipart/mixed` batch,
+//! see `MultipartJsonBatch`.
+//! Enable the `stream` feature to process the elements of huge JSON arrays one at a time,
+//! without buffering the whole body, see `JsonStream`.
+//! Enable the `channel` feature to receive them through a bounded channel instead,
+//! parsing the body concurrently with the handler, see `JsonChannel`.
+//! Enable the `protobuf` feature to decode protobuf bodies, see `Protobuf`.
+//! Check out [`Text`] for bodies holding a single plain text value, parsed via [`FromStr`](std::str::FromStr).
+//! Check out [`Binary`] for binary bodies starting with a fixed-size header.
+//! Check out [`RangeHeader`] to serve partial content.
+//! Check out [`ContentDisposition`] to get the (validated) filename of an upload.
+//! Check out [`ApiVersion`] to validate the API version segment of the request path.
+//! Check out [`AcceptNegotiation`] to pick the response media type out of the `Accept` header.
+//! Enable the `uuid` feature to require a UUID `Idempotency-Key` header, see `IdempotencyKey`.
+//! Check out [`ErrorSink`] to report the failures of your own validation logic
+//! alongside deserialization errors.
+//! Check out [`MultiStatus`] to report the outcome of each item of a batch operation.
+//! Check out [`InvalidResponse`] to validate outgoing data with the same error model.
+//! Check out [`Conflict`] to point clients at the values clashing with existing data,
+//! and [`NotFound`] to tell them which resource doesn't exist.
+//! Check out [`Problem`] to report your own errors, and enable the `thiserror` feature
+//! to derive the conversion of an error enum into problems, see `IntoProblem`.
+//! Enable the `chrono` or `time` features to deserialize RFC 3339 date-times with errors
+//! explaining the expected format, see `datetime`.
+//! Enable the `i18n` feature to translate validation errors via Fluent bundles,
+//! see [`Config::catalog`].
+//! Enable the `timeout` feature to bound the time spent reading request bodies,
+//! see [`Config::body_read_timeout`].
+//! Enable the `catch-unwind` feature to answer with a 500 problem, rather than
+//! dropping the connection, when deserializing a JSON body panics.
+//! Enable the `opentelemetry` feature to include the trace ID of the current span
+//! in `invalid_request` problems, as a `trace_id` member.
+//! Enable the `cbor` feature to answer with `application/problem+cbor` problems to clients
+//! preferring CBOR over JSON in their `Accept` header.
+//! Enable the `layers` feature to turn the router's `405 Method Not Allowed` responses
+//! into problems, see `MethodNotAllowedLayer`, and to install the same [`Config`] for
+//! every route of a router, see `ConfigLayer`.
+//! Enable the `testing` feature to inspect problem responses in unit tests, see `testing::problem`.
+//! Check out [`Unauthorized`] to reject unauthenticated requests with a `WWW-Authenticate` challenge.
 //!
 //! [`axum`]: https://docs.rs/axum
 //! [`eserde`]: https://docs.rs/eserde
@@ -18,6 +67,204 @@ pub mod json;
 
 #[cfg(feature = "json")]
 #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
-pub use json::Json;
+pub use json::{Created, Json, JsonRejection};
+
+#[cfg(feature = "jsonc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "jsonc")))]
+pub use json::Jsonc;
+
+#[cfg(feature = "jsonschema")]
+#[cfg_attr(docsrs, doc(cfg(feature = "jsonschema")))]
+pub use json::SchemaJson;
+
+#[cfg(feature = "stream")]
+#[cfg_attr(docsrs, doc(cfg(feature = "stream")))]
+pub use json::JsonStream;
+
+#[cfg(feature = "channel")]
+#[cfg_attr(docsrs, doc(cfg(feature = "channel")))]
+pub use json::JsonChannel;
+
+#[cfg(feature = "ndjson")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ndjson")))]
+pub mod ndjson;
+
+#[cfg(feature = "ndjson")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ndjson")))]
+pub use ndjson::{NdJson, NdJsonRejection};
+
+#[cfg(feature = "multipart")]
+#[cfg_attr(docsrs, doc(cfg(feature = "multipart")))]
+pub mod multipart;
+
+#[cfg(feature = "multipart")]
+#[cfg_attr(docsrs, doc(cfg(feature = "multipart")))]
+pub use multipart::{MultipartJsonBatch, MultipartJsonBatchRejection};
+
+#[cfg(feature = "protobuf")]
+#[cfg_attr(docsrs, doc(cfg(feature = "protobuf")))]
+pub mod protobuf;
+
+#[cfg(feature = "protobuf")]
+#[cfg_attr(docsrs, doc(cfg(feature = "protobuf")))]
+pub use protobuf::{Protobuf, ProtobufRejection};
+
+#[cfg(feature = "form")]
+#[cfg_attr(docsrs, doc(cfg(feature = "form")))]
+pub mod form;
+
+#[cfg(feature = "form")]
+#[cfg_attr(docsrs, doc(cfg(feature = "form")))]
+pub use form::{Form, FormRejection};
+
+#[cfg(feature = "query")]
+#[cfg_attr(docsrs, doc(cfg(feature = "query")))]
+pub mod query;
+
+#[cfg(feature = "query")]
+#[cfg_attr(docsrs, doc(cfg(feature = "query")))]
+pub use query::{Query, QueryRejection};
+
+#[cfg(feature = "combined")]
+#[cfg_attr(docsrs, doc(cfg(feature = "combined")))]
+pub mod combined;
+
+#[cfg(feature = "combined")]
+#[cfg_attr(docsrs, doc(cfg(feature = "combined")))]
+pub use combined::{Combine, Combined, CombinedRejection};
+
+#[cfg(any(feature = "humantime", feature = "bytesize"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "humantime", feature = "bytesize"))))]
+pub mod humanize;
+
+#[cfg(any(feature = "chrono", feature = "time"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "chrono", feature = "time"))))]
+pub mod datetime;
+
+#[cfg(feature = "anyhow")]
+#[cfg_attr(docsrs, doc(cfg(feature = "anyhow")))]
+pub mod server_error;
+
+#[cfg(feature = "anyhow")]
+#[cfg_attr(docsrs, doc(cfg(feature = "anyhow")))]
+pub use server_error::{ServerError, ServerErrorLayer};
+
+#[cfg(feature = "layers")]
+#[cfg_attr(docsrs, doc(cfg(feature = "layers")))]
+pub mod method;
+
+#[cfg(feature = "layers")]
+#[cfg_attr(docsrs, doc(cfg(feature = "layers")))]
+pub use method::MethodNotAllowedLayer;
+
+pub mod prefer;
+
+pub use prefer::Prefer;
+
+pub mod range;
+
+pub use range::RangeHeader;
+
+pub mod disposition;
+
+pub use disposition::ContentDisposition;
+
+pub mod sink;
+
+pub use sink::{ErrorSink, ErrorSource};
+
+pub mod multi_status;
+
+pub use multi_status::MultiStatus;
+
+pub mod text;
+
+pub mod binary;
+
+pub use binary::Binary;
+
+#[cfg(feature = "timeout")]
+#[cfg_attr(docsrs, doc(cfg(feature = "timeout")))]
+pub mod timeout;
+
+#[cfg(feature = "catch-unwind")]
+#[cfg_attr(docsrs, doc(cfg(feature = "catch-unwind")))]
+pub mod unwind;
+
+pub use text::Text;
+
+pub mod version;
+
+pub use version::ApiVersion;
+
+pub mod accept;
+
+pub use accept::AcceptNegotiation;
+
+#[cfg(feature = "uuid")]
+#[cfg_attr(docsrs, doc(cfg(feature = "uuid")))]
+pub mod idempotency;
+
+#[cfg(feature = "uuid")]
+#[cfg_attr(docsrs, doc(cfg(feature = "uuid")))]
+pub use idempotency::IdempotencyKey;
+
+#[cfg(feature = "i18n")]
+#[cfg_attr(docsrs, doc(cfg(feature = "i18n")))]
+pub mod i18n;
+
+pub mod body;
+
+#[cfg(feature = "testing")]
+#[cfg_attr(docsrs, doc(cfg(feature = "testing")))]
+pub mod testing;
+
+mod config;
+pub use config::{Config, ErrorOrder};
+#[cfg(feature = "layers")]
+#[cfg_attr(docsrs, doc(cfg(feature = "layers")))]
+pub use config::{ConfigLayer, ConfigService};
 
 pub(crate) mod details;
+pub use details::{Conflict, InvalidResponse, NotFound, Problem, TooManyRequests, Unauthorized};
+
+/// Derive the conversion of an error enum into a [`Problem`], and into a response.
+///
+/// Each variant must be annotated with its status and problem type, via
+/// `#[problem(status = 409, type = "conflict")]`.
+/// The `Display` implementation of the variant (e.g. from `thiserror`) becomes
+/// the `detail` of the problem, and the canonical reason of the status its `title`,
+/// unless they are overridden with `detail = "..."` and `title = "..."`.
+///
+/// ```rust
+/// use axum::response::IntoResponse;
+/// use eserde_axum::IntoProblem;
+///
+/// #[derive(Debug, thiserror::Error, IntoProblem)]
+/// enum OrderError {
+///     #[error("Order {0} was already shipped")]
+///     #[problem(status = 409, type = "order_shipped")]
+///     Shipped(u64),
+///     #[error("The payment provider is down")]
+///     #[problem(status = 503, type = "payment_unavailable", detail = "Try again later")]
+///     PaymentUnavailable,
+/// }
+///
+/// let response = OrderError::Shipped(42).into_response();
+/// assert_eq!(response.status(), 409);
+/// ```
+#[cfg(feature = "thiserror")]
+#[cfg_attr(docsrs, doc(cfg(feature = "thiserror")))]
+pub use eserde_axum_derive::IntoProblem;
+
+#[doc(hidden)]
+pub use axum_core as _axum_core;
+#[doc(hidden)]
+pub use http as _http;
+
+// Let the derive macros refer to `::eserde_axum` in our own tests.
+#[cfg(test)]
+extern crate self as eserde_axum;
+
+#[cfg(test)]
+mod test_utils;
@@ -0,0 +1,206 @@
+//! Turn the `405 Method Not Allowed` responses produced by the router into problem responses.
+//!
+//! axum replies with an empty `405` when a route exists, but it doesn't handle the
+//! method of the request. The [`MethodNotAllowedLayer`] replaces the empty body with a
+//! `method_not_allowed` problem listing the methods supported by the route, as an
+//! `allowed_methods` member. The `Allow` header is preserved.
+//!
+//! Wrap the whole router with the layer, rather than using [`Router::layer`]:
+//! axum only sets the `Allow` header once the response has gone through the
+//! layers of the route, so they can't see the allowed methods.
+//!
+//! ```rust,no_run
+//! use axum::{routing::get, Router};
+//! use eserde_axum::MethodNotAllowedLayer;
+//! use tower_layer::Layer;
+//!
+//! async fn handler() {}
+//!
+//! let router = Router::new().route("/", get(handler));
+//! // Serve it via `axum::serve(listener, app.into_make_service())`.
+//! let app = MethodNotAllowedLayer::new().layer(router);
+//! # let _: eserde_axum::method::MethodNotAllowedService<Router> = app;
+//! ```
+//!
+//! [`Router::layer`]: https://docs.rs/axum/latest/axum/struct.Router.html#method.layer
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use axum_core::response::{IntoResponse, Response};
+use http::header::{ALLOW, CONTENT_TYPE};
+use http::{Method, Request, StatusCode};
+
+use crate::config::Config;
+use crate::details::{ProblemDetails, APPLICATION_PROBLEM_JSON};
+
+/// The extension members of a `method_not_allowed` problem.
+#[derive(serde::Serialize)]
+struct MethodNotAllowedMembers {
+    /// The methods supported by the target resource, as listed in the `Allow` header.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    allowed_methods: Vec<String>,
+}
+
+/// The `method_not_allowed` problem replacing the body of `response`, a 405 returned
+/// for a request with the given method.
+fn problem(method: &Method, response: Response, config: &Config) -> Response {
+    let (mut parts, _) = response.into_parts();
+    let allowed_methods = parts
+        .headers
+        .get_all(ALLOW)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .filter(|method| !method.is_empty())
+        .map(ToOwned::to_owned)
+        .collect();
+    let problem = ProblemDetails {
+        extensions: Some(MethodNotAllowedMembers { allowed_methods }),
+        ..ProblemDetails::new(
+            "method_not_allowed",
+            StatusCode::METHOD_NOT_ALLOWED,
+            "The method is not allowed for the target resource",
+            format!("The target resource doesn't support the `{method}` method"),
+        )
+    }
+    .configure(config);
+    tracing::event!(
+        target: "eserde_axum::method::rejection",
+        tracing::Level::TRACE,
+        status = StatusCode::METHOD_NOT_ALLOWED.as_u16(),
+        rejection_type = "method_not_allowed",
+        "rejecting request",
+    );
+    let (problem_parts, body) = problem.into_response().into_parts();
+    // Keep the `Allow` header, along with everything else set by the router.
+    parts.headers.extend(problem_parts.headers);
+    Response::from_parts(parts, body)
+}
+
+/// A [`Layer`](tower_layer::Layer) that converts empty `405 Method Not Allowed`
+/// responses into `method_not_allowed` problems.
+///
+/// Check out the [module documentation](crate::method) for more details.
+/// The problem honors the [`Config`] installed in the request extensions, if any.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MethodNotAllowedLayer;
+
+impl MethodNotAllowedLayer {
+    /// Create a new `MethodNotAllowedLayer`.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl<S> tower_layer::Layer<S> for MethodNotAllowedLayer {
+    type Service = MethodNotAllowedService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        MethodNotAllowedService { inner }
+    }
+}
+
+/// The [`Service`](tower_service::Service) returned by [`MethodNotAllowedLayer`].
+#[derive(Debug, Clone)]
+pub struct MethodNotAllowedService<S> {
+    inner: S,
+}
+
+impl<S, B> tower_service::Service<Request<B>> for MethodNotAllowedService<S>
+where
+    S: tower_service::Service<Request<B>, Response = Response>,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = ResponseFuture<S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        let method = request.method().clone();
+        let config = Config::from_request(&request);
+        ResponseFuture {
+            inner: self.inner.call(request),
+            method,
+            config,
+        }
+    }
+}
+
+pin_project_lite::pin_project! {
+    /// The response future of [`MethodNotAllowedService`].
+    pub struct ResponseFuture<F> {
+        #[pin]
+        inner: F,
+        method: Method,
+        config: Config,
+    }
+}
+
+impl<F, E> Future for ResponseFuture<F>
+where
+    F: Future<Output = Result<Response, E>>,
+{
+    type Output = Result<Response, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let response = std::task::ready!(this.inner.poll(cx))?;
+        // Leave alone the 405s that already carry a problem, e.g. those built by handlers.
+        if response.status() != StatusCode::METHOD_NOT_ALLOWED
+            || response.headers().get(CONTENT_TYPE) == Some(&APPLICATION_PROBLEM_JSON)
+        {
+            return Poll::Ready(Ok(response));
+        }
+        Poll::Ready(Ok(problem(this.method, response, this.config)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{routing::get, Router};
+    use tower::ServiceExt;
+    use tower_layer::Layer;
+
+    use super::*;
+    use crate::test_utils;
+
+    #[tokio::test]
+    async fn test_method_not_allowed() {
+        let app = MethodNotAllowedLayer::new().layer(Router::new().route("/", get(|| async {})));
+        let request = Request::builder()
+            .method("POST")
+            .uri("/")
+            .body(axum::body::Body::empty())
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.headers()[CONTENT_TYPE], "application/problem+json");
+        assert_eq!(response.headers()[ALLOW], "GET,HEAD");
+
+        let (status, body) = test_utils::problem(response).await;
+        assert_eq!(status, StatusCode::METHOD_NOT_ALLOWED);
+        assert_eq!(body["type"], "method_not_allowed");
+        assert_eq!(body["status"], 405);
+        assert_eq!(
+            body["detail"],
+            "The target resource doesn't support the `POST` method"
+        );
+        assert_eq!(body["allowed_methods"], serde_json::json!(["GET", "HEAD"]));
+    }
+
+    #[tokio::test]
+    async fn test_allowed_method() {
+        let app =
+            MethodNotAllowedLayer::new().layer(Router::new().route("/", get(|| async { "Hello" })));
+        let request = Request::builder()
+            .uri("/")
+            .body(axum::body::Body::empty())
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
@@ -0,0 +1,217 @@
+//! Report the outcome of each item of a batch operation.
+//!
+//! See [`MultiStatus`] for more details.
+use axum_core::response::{IntoResponse, Response};
+use http::header::CONTENT_TYPE;
+use http::{HeaderValue, StatusCode};
+
+use crate::config::Fallback;
+use crate::details::{ProblemDetails, INTERNAL_SERVER_ERROR, INTERNAL_SERVER_ERROR_PROBLEM};
+use crate::{Conflict, ErrorSink, NotFound, Unauthorized};
+
+/// A `207 Multi-Status` response, for batch operations that may partially fail.
+///
+/// It lists the outcome of each item of the batch, in order, as an `items` array:
+/// successful items only carry their status code, while failed ones embed the same
+/// problem (e.g. a [`Conflict`] or the `invalid_request` problem of an [`ErrorSink`])
+/// they would have been rejected with on their own.
+///
+/// ```json
+/// {
+///   "items": [
+///     { "status": 201 },
+///     {
+///       "status": 409,
+///       "problem": { "type": "conflict", "status": 409, "title": "...", "detail": "...", "errors": [...] }
+///     }
+///   ]
+/// }
+/// ```
+///
+/// # Example
+///
+/// ```rust
+/// use axum::http::StatusCode;
+/// use axum::response::IntoResponse;
+/// use eserde_axum::{Conflict, MultiStatus};
+///
+/// let response = MultiStatus::new()
+///     .success(StatusCode::CREATED)
+///     .failure(Conflict::new("/email", "The email address is already registered"))
+///     .into_response();
+/// assert_eq!(response.status(), 207);
+/// ```
+#[derive(Debug, Default)]
+#[must_use]
+pub struct MultiStatus {
+    items: Vec<Item>,
+}
+
+#[derive(Debug, serde::Serialize)]
+struct Item {
+    status: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    problem: Option<serde_json::Value>,
+}
+
+impl MultiStatus {
+    /// Create a response without any item.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append an item that succeeded with the given status code, e.g. `201 Created`.
+    pub fn success(mut self, status: StatusCode) -> Self {
+        self.items.push(Item {
+            status: status.as_u16(),
+            problem: None,
+        });
+        self
+    }
+
+    /// Append an item that failed with the given problem.
+    ///
+    /// Configure the problem (e.g. via [`Conflict::configure`]) before adding it,
+    /// if needed.
+    pub fn failure(mut self, problem: impl Into<ItemFailure>) -> Self {
+        let ItemFailure { status, problem } = problem.into();
+        self.items.push(Item {
+            status,
+            problem: Some(problem),
+        });
+        self
+    }
+
+    /// The number of items added so far.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns `true` if no items have been added.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+impl IntoResponse for MultiStatus {
+    fn into_response(self) -> Response {
+        #[derive(serde::Serialize)]
+        struct Body<'a> {
+            items: &'a [Item],
+        }
+
+        match serde_json::to_vec(&Body { items: &self.items }) {
+            Ok(body) => (
+                StatusCode::MULTI_STATUS,
+                [(CONTENT_TYPE, HeaderValue::from_static("application/json"))],
+                body,
+            )
+                .into_response(),
+            Err(_) => INTERNAL_SERVER_ERROR.into_response(),
+        }
+    }
+}
+
+/// A problem reported for a single item of a [`MultiStatus`] response.
+///
+/// Build it out of the problems of this crate, e.g. a [`Conflict`], a [`NotFound`]
+/// or an [`ErrorSink`].
+#[derive(Debug)]
+pub struct ItemFailure {
+    status: u16,
+    problem: serde_json::Value,
+}
+
+impl ItemFailure {
+    fn new<Extension>(problem: ProblemDetails<Extension>) -> Self
+    where
+        Extension: serde::Serialize,
+    {
+        match serde_json::to_value(&problem) {
+            Ok(value) => Self {
+                status: problem.status.code,
+                problem: value,
+            },
+            // Mirror the fallback of a standalone problem response.
+            Err(_) => Self {
+                status: StatusCode::INTERNAL_SERVER_ERROR.as_u16(),
+                problem: problem
+                    .fallback
+                    .as_ref()
+                    .and_then(Fallback::body)
+                    .and_then(|fallback| serde_json::from_slice(&fallback).ok())
+                    .unwrap_or_else(|| {
+                        serde_json::from_slice(INTERNAL_SERVER_ERROR_PROBLEM)
+                            .expect("The fallback problem is valid JSON")
+                    }),
+            },
+        }
+    }
+}
+
+impl From<Conflict> for ItemFailure {
+    fn from(problem: Conflict) -> Self {
+        Self::new(problem.0)
+    }
+}
+
+impl From<NotFound> for ItemFailure {
+    fn from(problem: NotFound) -> Self {
+        Self::new(problem.0)
+    }
+}
+
+impl From<Unauthorized> for ItemFailure {
+    fn from(problem: Unauthorized) -> Self {
+        Self::new(problem.0)
+    }
+}
+
+impl From<ErrorSink> for ItemFailure {
+    fn from(sink: ErrorSink) -> Self {
+        Self::new(sink.into_problem().into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils;
+
+    #[tokio::test]
+    async fn test_partial_failure() {
+        let response = MultiStatus::new()
+            .success(StatusCode::CREATED)
+            .failure(Conflict::new(
+                "/email",
+                "The email address is already registered",
+            ));
+        assert_eq!(response.len(), 2);
+
+        let (status, body) = test_utils::problem(response).await;
+        assert_eq!(status, StatusCode::MULTI_STATUS);
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "items": [
+                    { "status": 201 },
+                    {
+                        "status": 409,
+                        "problem": {
+                            "type": "conflict",
+                            "status": 409,
+                            "title": "The request conflicts with the current state of the resource",
+                            "detail": "The request body contains values that conflict with existing data",
+                            "errors": [{
+                                "detail": "The email address is already registered",
+                                "code": "conflict",
+                                "source": "body",
+                                "pointer": "/email"
+                            }]
+                        }
+                    }
+                ]
+            })
+        );
+    }
+}
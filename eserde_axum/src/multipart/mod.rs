@@ -0,0 +1,7 @@
+//! Supporting types for the [`MultipartJsonBatch`] extractor.
+mod multipart_;
+mod rejections;
+
+#[doc(hidden)]
+pub use multipart_::{MultipartJsonBatch, PartError};
+pub use rejections::*;
@@ -0,0 +1,412 @@
+use std::ops::Deref;
+use std::ops::DerefMut;
+
+use super::*;
+use crate::config::Config;
+use crate::json::batch::deserialize_document;
+use crate::json::{DocumentError, PartIndex};
+use axum_core::extract::FromRequest;
+use axum_core::extract::Request;
+use eserde::EDeserialize;
+use http::header::HeaderMap;
+use serde::de::DeserializeOwned;
+
+/// `multipart/mixed` extractor, for batch payloads where each part is a JSON document.
+///
+/// Each part of the request body is deserialized independently into some type that
+/// implements [`serde::de::DeserializeOwned`] and [`eserde::EDeserialize`].
+/// The headers of the parts are ignored: their content is always parsed as JSON.
+///
+/// The request will be rejected (and a [`MultipartJsonBatchRejection`] will be returned) if:
+///
+/// - The request doesn't have a `Content-Type: multipart/mixed` header, with a `boundary`.
+/// - Buffering the request body fails.
+/// - The request body isn't a well-formed `multipart/mixed` document.
+/// - The request body has too many parts.
+/// - Any of the parts fails to deserialize. The errors of all the parts are reported at
+///   once, each one pointing at the index of its part, e.g. `/1/sku`.
+///
+/// The JSON limits of the [`Config`] (e.g. [`Config::max_depth`]) aren't enforced on
+/// the parts, but their number is capped by [`Config::max_multipart_parts`].
+/// Each part is only reported up to its first error if [`Config::fail_fast`] is enabled.
+///
+/// ⚠️ Since parsing the parts requires consuming the request body, the `MultipartJsonBatch`
+/// extractor must be *last* if there are multiple extractors in a handler.
+/// See ["the order of extractors"][order-of-extractors]
+///
+/// [order-of-extractors]: https://docs.rs/axum/latest/axum/extract/index.html#the-order-of-extractors
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{routing::post, Router};
+/// use eserde_axum::MultipartJsonBatch;
+///
+/// #[derive(eserde::Deserialize)]
+/// struct Product {
+///     sku: String,
+///     stock: u32,
+/// }
+///
+/// async fn bulk_insert(MultipartJsonBatch(products): MultipartJsonBatch<Product>) -> String {
+///     format!("Inserted {} products", products.len())
+/// }
+///
+/// let app = Router::new().route("/products/batch", post(bulk_insert));
+/// # let _: Router = app;
+/// ```
+#[derive(Debug, Default)]
+#[cfg_attr(docsrs, doc(cfg(feature = "multipart")))]
+#[must_use]
+pub struct MultipartJsonBatch<T>(pub Vec<T>);
+
+/// A part of a [`MultipartJsonBatch`] payload that couldn't be deserialized.
+#[cfg_attr(docsrs, doc(cfg(feature = "multipart")))]
+pub type PartError = DocumentError<PartIndex>;
+
+impl<T, S> FromRequest<S> for MultipartJsonBatch<T>
+where
+    T: DeserializeOwned,
+    T: for<'de> EDeserialize<'de>,
+    S: Send + Sync,
+{
+    type Rejection = MultipartJsonBatchRejection;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let config = Config::from_request(&req);
+        let boundary = check_multipart_content_type(req.headers(), &config)?;
+        let (bytes, _permit) =
+            crate::body::read_body::<_, Self::Rejection>(req, state, &config).await?;
+        let max = config.multipart_part_limit();
+        let parts = split_parts(&bytes, &boundary, max).map_err(|error| match error {
+            SplitError::Malformed => MultipartJsonBatchRejection::from(MalformedMultipart {
+                config: config.clone(),
+            }),
+            SplitError::TooManyParts => TooManyParts {
+                max,
+                config: config.clone(),
+            }
+            .into(),
+        })?;
+
+        let mut values = Vec::with_capacity(parts.len());
+        let mut failed = Vec::new();
+        for (index, part) in parts.into_iter().enumerate() {
+            match deserialize_document(part, &config) {
+                Ok(value) => values.push(value),
+                Err(errors) => failed.push(PartError::part_error(index, errors)),
+            }
+        }
+        if failed.is_empty() {
+            Ok(Self(values))
+        } else {
+            Err(MultipartJsonError {
+                parts: failed,
+                config,
+            }
+            .into())
+        }
+    }
+}
+
+/// Split a `multipart/mixed` body into the content of its parts, as specified in
+/// [RFC 2046](https://www.rfc-editor.org/rfc/rfc2046#section-5.1.1).
+///
+/// The preamble and the epilogue are discarded, as well as the headers of each part.
+/// It fails if the body isn't well-formed, e.g. if the closing delimiter is missing,
+/// or as soon as there are more than `max` parts.
+fn split_parts<'a>(
+    body: &'a [u8],
+    boundary: &str,
+    max: usize,
+) -> Result<Vec<&'a [u8]>, SplitError> {
+    let delimiter = format!("\r\n--{boundary}");
+    let delimiter = delimiter.as_bytes();
+    let find = |haystack: &[u8]| {
+        haystack
+            .windows(delimiter.len())
+            .position(|window| window == delimiter)
+    };
+
+    // The first delimiter may be at the very start of the body, without a leading line break.
+    let mut rest = match body.strip_prefix(&delimiter[2..]) {
+        Some(rest) => rest,
+        None => &body[find(body).ok_or(SplitError::Malformed)? + delimiter.len()..],
+    };
+    let mut parts = Vec::new();
+    loop {
+        if rest.starts_with(b"--") {
+            return Ok(parts);
+        }
+        if parts.len() == max {
+            return Err(SplitError::TooManyParts);
+        }
+        // Skip the transport padding, up to the line break ending the delimiter line.
+        let padding = rest
+            .iter()
+            .take_while(|byte| matches!(byte, b' ' | b'\t'))
+            .count();
+        rest = rest[padding..]
+            .strip_prefix(b"\r\n")
+            .ok_or(SplitError::Malformed)?;
+        let end = find(rest).ok_or(SplitError::Malformed)?;
+        let part = &rest[..end];
+        let content = match part.strip_prefix(b"\r\n") {
+            // No headers.
+            Some(content) => content,
+            None => {
+                let headers_end = part
+                    .windows(4)
+                    .position(|window| window == b"\r\n\r\n")
+                    .ok_or(SplitError::Malformed)?;
+                &part[headers_end + 4..]
+            }
+        };
+        parts.push(content);
+        rest = &rest[end + delimiter.len()..];
+    }
+}
+
+/// Why a body couldn't be split into parts.
+enum SplitError {
+    Malformed,
+    TooManyParts,
+}
+
+/// Check that the `Content-Type` header is set to `multipart/mixed`, with a boundary.
+///
+/// Return the boundary, or an error otherwise.
+fn check_multipart_content_type(
+    headers: &HeaderMap,
+    config: &Config,
+) -> Result<String, MultipartJsonBatchRejection> {
+    let missing = || {
+        MissingMultipartContentType {
+            config: config.clone(),
+        }
+        .into()
+    };
+    let count = headers.get_all(http::header::CONTENT_TYPE).iter().count();
+    if count > 1 {
+        return Err(AmbiguousMultipartContentType {
+            count,
+            config: config.clone(),
+        }
+        .into());
+    }
+    let Some(content_type) = headers.get(http::header::CONTENT_TYPE) else {
+        return Err(missing());
+    };
+    let Ok(content_type) = content_type.to_str() else {
+        return Err(missing());
+    };
+
+    let boundary = content_type
+        .parse::<mime::Mime>()
+        .ok()
+        .filter(|mime| mime.essence_str() == "multipart/mixed")
+        .and_then(|mime| mime.get_param(mime::BOUNDARY).map(|b| b.to_string()))
+        .filter(|boundary| !boundary.is_empty());
+    boundary.ok_or_else(|| {
+        MultipartContentTypeMismatch {
+            actual: content_type.to_string(),
+            config: config.clone(),
+        }
+        .into()
+    })
+}
+
+impl<T> Deref for MultipartJsonBatch<T> {
+    type Target = Vec<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for MultipartJsonBatch<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils;
+    use http::header::CONTENT_TYPE;
+
+    #[derive(Debug, PartialEq, eserde::Deserialize)]
+    struct Product {
+        sku: String,
+        stock: u32,
+    }
+
+    fn request(body: &'static str) -> Request {
+        Request::builder()
+            .method("POST")
+            .uri("/")
+            .header(CONTENT_TYPE, "multipart/mixed; boundary=batch")
+            .body(axum_core::body::Body::from(body))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_parts_are_deserialized() {
+        let body = concat!(
+            "This is the preamble.\r\n",
+            "--batch\r\n",
+            "Content-Type: application/json\r\n",
+            "\r\n",
+            r#"{"sku": "A-1", "stock": 10}"#,
+            "\r\n--batch\r\n",
+            "\r\n",
+            r#"{"sku": "A-2", "stock": 7}"#,
+            "\r\n--batch--\r\n",
+            "This is the epilogue.",
+        );
+        let MultipartJsonBatch(products) =
+            MultipartJsonBatch::<Product>::from_request(request(body), &())
+                .await
+                .unwrap();
+        assert_eq!(
+            products,
+            [
+                Product {
+                    sku: "A-1".into(),
+                    stock: 10
+                },
+                Product {
+                    sku: "A-2".into(),
+                    stock: 7
+                }
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_bad_part_is_reported() {
+        let body = concat!(
+            "--batch\r\n",
+            "Content-Type: application/json\r\n",
+            "\r\n",
+            r#"{"sku": "A-1", "stock": 10}"#,
+            "\r\n--batch\r\n",
+            "Content-Type: application/json\r\n",
+            "\r\n",
+            r#"{"sku": 2, "stock": -1}"#,
+            "\r\n--batch\r\n",
+            "Content-Type: application/json\r\n",
+            "\r\n",
+            r#"{"sku": "A-3", "stock": 7}"#,
+            "\r\n--batch--",
+        );
+        let rejection = MultipartJsonBatch::<Product>::from_request(request(body), &())
+            .await
+            .unwrap_err();
+        let MultipartJsonBatchRejection::MultipartJsonError(error) = &rejection else {
+            panic!("Expected a deserialization error, got {rejection:?}");
+        };
+        assert_eq!(error.parts().len(), 1);
+        assert_eq!(error.parts()[0].index(), 1);
+
+        let (status, body) = test_utils::problem(rejection).await;
+        assert_eq!(status, 400);
+        assert_eq!(body["type"], "invalid_request");
+        let errors: Vec<_> = body["errors"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|error| {
+                (
+                    error["source"].as_str().unwrap(),
+                    error["pointer"].as_str().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(errors, [("body", "/1/sku"), ("body", "/1/stock")]);
+    }
+
+    #[tokio::test]
+    async fn test_fail_fast_stops_at_the_first_error_of_a_part() {
+        let body = concat!(
+            "--batch\r\n",
+            "\r\n",
+            r#"{"sku": 2, "stock": -1}"#,
+            "\r\n--batch--",
+        );
+        let mut request = request(body);
+        request
+            .extensions_mut()
+            .insert(Config::new().fail_fast(true));
+        let rejection = MultipartJsonBatch::<Product>::from_request(request, &())
+            .await
+            .unwrap_err();
+        let MultipartJsonBatchRejection::MultipartJsonError(error) = &rejection else {
+            panic!("Expected a deserialization error, got {rejection:?}");
+        };
+        assert_eq!(error.parts()[0].errors().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_too_many_parts() {
+        let body = concat!(
+            "--batch\r\n\r\n1",
+            "\r\n--batch\r\n\r\n2",
+            "\r\n--batch\r\n\r\n3",
+            "\r\n--batch--",
+        );
+        let limited = |max| {
+            let mut request = request(body);
+            request
+                .extensions_mut()
+                .insert(Config::new().max_multipart_parts(max));
+            MultipartJsonBatch::<u32>::from_request(request, &())
+        };
+        let MultipartJsonBatch(numbers) = limited(3).await.unwrap();
+        assert_eq!(numbers, [1, 2, 3]);
+
+        let rejection = limited(2).await.unwrap_err();
+        assert!(matches!(
+            rejection,
+            MultipartJsonBatchRejection::TooManyParts(_)
+        ));
+        let (status, body) = test_utils::problem(rejection).await;
+        assert_eq!(status, 400);
+        assert_eq!(body["errors"][0]["code"], "too_many_parts");
+    }
+
+    #[tokio::test]
+    async fn test_malformed_multipart() {
+        let body = concat!("--batch\r\n", "\r\n", r#"{"sku": "A-1", "stock": 10}"#);
+        let rejection = MultipartJsonBatch::<Product>::from_request(request(body), &())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            rejection,
+            MultipartJsonBatchRejection::MalformedMultipart(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_missing_boundary() {
+        let request = Request::builder()
+            .method("POST")
+            .uri("/")
+            .header(CONTENT_TYPE, "multipart/mixed")
+            .body(axum_core::body::Body::from("{}"))
+            .unwrap();
+        let rejection = MultipartJsonBatch::<Product>::from_request(request, &())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            rejection,
+            MultipartJsonBatchRejection::MultipartContentTypeMismatch(_)
+        ));
+
+        let (status, body) = test_utils::problem(rejection).await;
+        assert_eq!(status, 415);
+        assert_eq!(body["expected"], serde_json::json!(["multipart/mixed"]));
+        assert_eq!(body["received"], "multipart/mixed");
+    }
+}
@@ -0,0 +1,461 @@
+use axum_core::extract::rejection::BytesRejection;
+use http::{header::CONTENT_TYPE, StatusCode};
+
+use crate::config::Config;
+use crate::details::{
+    bytes_rejection_response, InvalidRequest, ProblemDetails, Source, UnsupportedMediaType,
+    ValidationError, ValidationErrors,
+};
+
+use super::PartError;
+
+#[doc(hidden)]
+macro_rules! __log_rejection {
+    (
+        rejection_type = $ty:ident,
+        status = $status:expr,
+    ) => {
+        {
+            tracing::event!(
+                target: "eserde_axum::multipart::rejection",
+                tracing::Level::TRACE,
+                status = $status.as_u16(),
+                rejection_type = ::std::any::type_name::<$ty>(),
+                "rejecting request",
+            );
+        }
+    };
+}
+
+#[derive(Debug, Default)]
+#[non_exhaustive]
+#[cfg_attr(docsrs, doc(cfg(feature = "multipart")))]
+/// Rejection type for [`MultipartJsonBatch`](super::MultipartJsonBatch) used if the `Content-Type`
+/// header is missing.
+pub struct MissingMultipartContentType {
+    pub(crate) config: Config,
+}
+
+impl axum_core::response::IntoResponse for MissingMultipartContentType {
+    fn into_response(self) -> axum_core::response::Response {
+        let error = ValidationError::new(
+            Source::Header {
+                name: CONTENT_TYPE.as_str().into(),
+            },
+            "missing_content_type",
+            "Expected request with `Content-Type: multipart/mixed`, but no `Content-Type` header was found",
+        );
+        let response = InvalidRequest::new(ValidationErrors {
+            errors: vec![error],
+            by_pointer: false,
+        })
+        .configure(&self.config);
+        __log_rejection!(
+            rejection_type = MissingMultipartContentType,
+            status = InvalidRequest::status(),
+        );
+        response.into_response()
+    }
+}
+
+impl std::fmt::Display for MissingMultipartContentType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Expected request with `Content-Type: multipart/mixed`")
+    }
+}
+
+impl std::error::Error for MissingMultipartContentType {}
+
+#[derive(Debug)]
+#[non_exhaustive]
+#[cfg_attr(docsrs, doc(cfg(feature = "multipart")))]
+/// Rejection type for [`MultipartJsonBatch`](super::MultipartJsonBatch) used if the `Content-Type`
+/// header has an incorrect value.
+///
+/// It results in a `415 Unsupported Media Type` problem, listing the accepted
+/// media types in the `expected` member and the header value in the `received` one.
+pub struct MultipartContentTypeMismatch {
+    pub(crate) actual: String,
+    pub(crate) config: Config,
+}
+
+/// The media types accepted by [`MultipartJsonBatch`](super::MultipartJsonBatch), as listed in a [`MultipartContentTypeMismatch`] problem.
+const EXPECTED_CONTENT_TYPES: &[&str] = &["multipart/mixed"];
+
+impl MultipartContentTypeMismatch {
+    /// The media types accepted by the extractor.
+    pub fn expected(&self) -> &'static [&'static str] {
+        EXPECTED_CONTENT_TYPES
+    }
+
+    /// The value of the `Content-Type` header of the request.
+    pub fn received(&self) -> &str {
+        &self.actual
+    }
+}
+
+impl axum_core::response::IntoResponse for MultipartContentTypeMismatch {
+    fn into_response(self) -> axum_core::response::Response {
+        let message = self.to_string();
+        let response = UnsupportedMediaType::mismatch(
+            EXPECTED_CONTENT_TYPES,
+            self.actual,
+            message,
+            "The request body must be a `multipart/mixed` batch of JSON documents",
+        )
+        .configure(&self.config);
+        __log_rejection!(
+            rejection_type = MultipartContentTypeMismatch,
+            status = UnsupportedMediaType::status(),
+        );
+        response.into_response()
+    }
+}
+
+impl std::fmt::Display for MultipartContentTypeMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Expected request with `Content-Type: multipart/mixed` and a `boundary` parameter, but found `{}`",
+            self.actual
+        )
+    }
+}
+
+impl std::error::Error for MultipartContentTypeMismatch {}
+
+#[derive(Debug)]
+#[non_exhaustive]
+#[cfg_attr(docsrs, doc(cfg(feature = "multipart")))]
+/// Rejection type for [`MultipartJsonBatch`](super::MultipartJsonBatch) used if the request has
+/// more than one `Content-Type` header, e.g. because of a misconfigured proxy.
+pub struct AmbiguousMultipartContentType {
+    pub(crate) count: usize,
+    pub(crate) config: Config,
+}
+
+impl axum_core::response::IntoResponse for AmbiguousMultipartContentType {
+    fn into_response(self) -> axum_core::response::Response {
+        let status = StatusCode::BAD_REQUEST;
+        let problem = ProblemDetails::ambiguous_content_type(self.count).configure(&self.config);
+        let problem = ProblemDetails {
+            extensions: problem
+                .extensions
+                .map(|errors| errors.configure(&self.config)),
+            ..problem
+        };
+        __log_rejection!(
+            rejection_type = AmbiguousMultipartContentType,
+            status = status,
+        );
+        problem.into_response()
+    }
+}
+
+impl std::fmt::Display for AmbiguousMultipartContentType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Expected a single `Content-Type` header, but found {}",
+            self.count
+        )
+    }
+}
+
+impl std::error::Error for AmbiguousMultipartContentType {}
+
+#[derive(Debug)]
+#[non_exhaustive]
+#[cfg_attr(docsrs, doc(cfg(feature = "multipart")))]
+/// Rejection type for [`MultipartJsonBatch`](super::MultipartJsonBatch) used if the
+/// request body isn't a well-formed `multipart/mixed` document, e.g. because the
+/// closing boundary is missing.
+pub struct MalformedMultipart {
+    pub(crate) config: Config,
+}
+
+impl axum_core::response::IntoResponse for MalformedMultipart {
+    fn into_response(self) -> axum_core::response::Response {
+        let error = ValidationError::new(
+            Source::Body {
+                pointer: None,
+                key: false,
+            },
+            "malformed_multipart",
+            self.to_string(),
+        );
+        let response = InvalidRequest::new(ValidationErrors {
+            errors: vec![error],
+            by_pointer: false,
+        })
+        .configure(&self.config);
+        __log_rejection!(
+            rejection_type = MalformedMultipart,
+            status = InvalidRequest::status(),
+        );
+        response.into_response()
+    }
+}
+
+impl std::fmt::Display for MalformedMultipart {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "The request body isn't a well-formed `multipart/mixed` document"
+        )
+    }
+}
+
+impl std::error::Error for MalformedMultipart {}
+
+#[derive(Debug)]
+#[non_exhaustive]
+#[cfg_attr(docsrs, doc(cfg(feature = "multipart")))]
+/// Rejection type for [`MultipartJsonBatch`](super::MultipartJsonBatch) used if the
+/// request body has more parts than allowed by
+/// [`Config::max_multipart_parts`](crate::Config::max_multipart_parts).
+pub struct TooManyParts {
+    pub(crate) max: usize,
+    pub(crate) config: Config,
+}
+
+impl axum_core::response::IntoResponse for TooManyParts {
+    fn into_response(self) -> axum_core::response::Response {
+        let error = ValidationError::new(
+            Source::Body {
+                pointer: None,
+                key: false,
+            },
+            "too_many_parts",
+            self.to_string(),
+        );
+        let response = InvalidRequest::new(ValidationErrors {
+            errors: vec![error],
+            by_pointer: false,
+        })
+        .configure(&self.config);
+        __log_rejection!(
+            rejection_type = TooManyParts,
+            status = InvalidRequest::status(),
+        );
+        response.into_response()
+    }
+}
+
+impl std::fmt::Display for TooManyParts {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "The request body has more than {} parts", self.max)
+    }
+}
+
+impl std::error::Error for TooManyParts {}
+
+#[derive(Debug)]
+#[non_exhaustive]
+#[cfg_attr(docsrs, doc(cfg(feature = "multipart")))]
+/// Rejection type for [`MultipartJsonBatch`](super::MultipartJsonBatch) used if
+/// some of the parts couldn't be deserialized into the target type.
+///
+/// The errors of all the parts are reported at once, their JSON pointer starting
+/// with the index of the part they belong to, e.g. `/1/sku` for the `sku` field of
+/// the second part.
+pub struct MultipartJsonError {
+    pub(crate) parts: Vec<PartError>,
+    pub(crate) config: Config,
+}
+
+impl MultipartJsonError {
+    /// The parts that couldn't be deserialized, in order.
+    pub fn parts(&self) -> &[PartError] {
+        &self.parts
+    }
+}
+
+impl axum_core::response::IntoResponse for MultipartJsonError {
+    fn into_response(self) -> axum_core::response::Response {
+        let errors = self
+            .parts
+            .iter()
+            .flat_map(|part| {
+                let prefix = format!("/{}", part.index());
+                part.errors()
+                    .iter()
+                    .map(move |error| ValidationError::body(error).pointer_prefix(&prefix))
+            })
+            .collect();
+        let response = InvalidRequest::new(ValidationErrors {
+            errors,
+            by_pointer: false,
+        })
+        .configure(&self.config);
+        __log_rejection!(
+            rejection_type = MultipartJsonError,
+            status = InvalidRequest::status(),
+        );
+        response.into_response()
+    }
+}
+
+impl std::fmt::Display for MultipartJsonError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(
+            "Failed to deserialize the parts of the request body into the target schema:\n",
+        )?;
+        for part in &self.parts {
+            for e in part.errors().iter() {
+                writeln!(f, "- part {}: {}", part.index(), e)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for MultipartJsonError {}
+
+/// Rejection used for [`MultipartJsonBatch`](super::MultipartJsonBatch).
+///
+/// Contains one variant for each way the [`MultipartJsonBatch`](super::MultipartJsonBatch) extractor
+/// can fail.
+///
+/// All error responses follow the problem details specification,
+/// as outlined in [RFC 9457](https://www.rfc-editor.org/rfc/rfc9457.html).
+#[derive(Debug)]
+#[non_exhaustive]
+#[cfg_attr(docsrs, doc(cfg(feature = "multipart")))]
+pub enum MultipartJsonBatchRejection {
+    #[allow(missing_docs)]
+    MissingMultipartContentType(MissingMultipartContentType),
+    #[allow(missing_docs)]
+    AmbiguousMultipartContentType(AmbiguousMultipartContentType),
+    #[allow(missing_docs)]
+    MultipartContentTypeMismatch(MultipartContentTypeMismatch),
+    #[allow(missing_docs)]
+    MalformedMultipart(MalformedMultipart),
+    #[allow(missing_docs)]
+    TooManyParts(TooManyParts),
+    #[allow(missing_docs)]
+    MultipartJsonError(MultipartJsonError),
+    #[allow(missing_docs)]
+    BytesRejection(BytesRejection),
+    #[allow(missing_docs)]
+    ContentTooLarge(crate::body::ContentTooLarge),
+    #[allow(missing_docs)]
+    Overloaded(crate::body::Overloaded),
+    #[allow(missing_docs)]
+    InvalidContentLength(crate::body::InvalidContentLength),
+    #[cfg(feature = "timeout")]
+    #[allow(missing_docs)]
+    RequestTimeout(crate::timeout::RequestTimeout),
+}
+
+impl axum_core::response::IntoResponse for MultipartJsonBatchRejection {
+    fn into_response(self) -> axum_core::response::Response {
+        match self {
+            Self::MissingMultipartContentType(inner) => inner.into_response(),
+            Self::AmbiguousMultipartContentType(inner) => inner.into_response(),
+            Self::MultipartContentTypeMismatch(inner) => inner.into_response(),
+            Self::MalformedMultipart(inner) => inner.into_response(),
+            Self::TooManyParts(inner) => inner.into_response(),
+            Self::MultipartJsonError(inner) => inner.into_response(),
+            Self::BytesRejection(inner) => bytes_rejection_response(inner),
+            Self::ContentTooLarge(inner) => inner.into_response(),
+            Self::Overloaded(inner) => inner.into_response(),
+            Self::InvalidContentLength(inner) => inner.into_response(),
+            #[cfg(feature = "timeout")]
+            Self::RequestTimeout(inner) => inner.into_response(),
+        }
+    }
+}
+
+impl From<MissingMultipartContentType> for MultipartJsonBatchRejection {
+    fn from(inner: MissingMultipartContentType) -> Self {
+        Self::MissingMultipartContentType(inner)
+    }
+}
+impl From<AmbiguousMultipartContentType> for MultipartJsonBatchRejection {
+    fn from(inner: AmbiguousMultipartContentType) -> Self {
+        Self::AmbiguousMultipartContentType(inner)
+    }
+}
+impl From<MultipartContentTypeMismatch> for MultipartJsonBatchRejection {
+    fn from(inner: MultipartContentTypeMismatch) -> Self {
+        Self::MultipartContentTypeMismatch(inner)
+    }
+}
+impl From<MalformedMultipart> for MultipartJsonBatchRejection {
+    fn from(inner: MalformedMultipart) -> Self {
+        Self::MalformedMultipart(inner)
+    }
+}
+impl From<TooManyParts> for MultipartJsonBatchRejection {
+    fn from(inner: TooManyParts) -> Self {
+        Self::TooManyParts(inner)
+    }
+}
+impl From<MultipartJsonError> for MultipartJsonBatchRejection {
+    fn from(inner: MultipartJsonError) -> Self {
+        Self::MultipartJsonError(inner)
+    }
+}
+impl From<BytesRejection> for MultipartJsonBatchRejection {
+    fn from(inner: BytesRejection) -> Self {
+        Self::BytesRejection(inner)
+    }
+}
+impl From<crate::body::ContentTooLarge> for MultipartJsonBatchRejection {
+    fn from(inner: crate::body::ContentTooLarge) -> Self {
+        Self::ContentTooLarge(inner)
+    }
+}
+impl From<crate::body::Overloaded> for MultipartJsonBatchRejection {
+    fn from(inner: crate::body::Overloaded) -> Self {
+        Self::Overloaded(inner)
+    }
+}
+impl From<crate::body::InvalidContentLength> for MultipartJsonBatchRejection {
+    fn from(inner: crate::body::InvalidContentLength) -> Self {
+        Self::InvalidContentLength(inner)
+    }
+}
+#[cfg(feature = "timeout")]
+impl From<crate::timeout::RequestTimeout> for MultipartJsonBatchRejection {
+    fn from(inner: crate::timeout::RequestTimeout) -> Self {
+        Self::RequestTimeout(inner)
+    }
+}
+impl std::fmt::Display for MultipartJsonBatchRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingMultipartContentType(inner) => write!(f, "{inner}"),
+            Self::AmbiguousMultipartContentType(inner) => write!(f, "{inner}"),
+            Self::MultipartContentTypeMismatch(inner) => write!(f, "{inner}"),
+            Self::MalformedMultipart(inner) => write!(f, "{inner}"),
+            Self::TooManyParts(inner) => write!(f, "{inner}"),
+            Self::MultipartJsonError(inner) => write!(f, "{inner}"),
+            Self::BytesRejection(inner) => write!(f, "{inner}"),
+            Self::ContentTooLarge(inner) => write!(f, "{inner}"),
+            Self::Overloaded(inner) => write!(f, "{inner}"),
+            Self::InvalidContentLength(inner) => write!(f, "{inner}"),
+            #[cfg(feature = "timeout")]
+            Self::RequestTimeout(inner) => write!(f, "{inner}"),
+        }
+    }
+}
+impl std::error::Error for MultipartJsonBatchRejection {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::MissingMultipartContentType(inner) => inner.source(),
+            Self::AmbiguousMultipartContentType(inner) => inner.source(),
+            Self::MultipartContentTypeMismatch(inner) => inner.source(),
+            Self::MalformedMultipart(inner) => inner.source(),
+            Self::TooManyParts(inner) => inner.source(),
+            Self::MultipartJsonError(inner) => inner.source(),
+            Self::BytesRejection(inner) => inner.source(),
+            Self::ContentTooLarge(inner) => inner.source(),
+            Self::Overloaded(inner) => inner.source(),
+            Self::InvalidContentLength(inner) => inner.source(),
+            #[cfg(feature = "timeout")]
+            Self::RequestTimeout(inner) => inner.source(),
+        }
+    }
+}
@@ -0,0 +1,7 @@
+//! Supporting types for the [`NdJson`] extractor.
+mod ndjson_;
+mod rejections;
+
+#[doc(hidden)]
+pub use ndjson_::{LineError, NdJson};
+pub use rejections::*;
@@ -0,0 +1,250 @@
+use std::ops::Deref;
+use std::ops::DerefMut;
+
+use super::*;
+use crate::config::Config;
+use crate::json::batch::deserialize_document;
+use crate::json::{DocumentError, LineNumber};
+use axum_core::extract::FromRequest;
+use axum_core::extract::Request;
+use eserde::EDeserialize;
+use http::header::HeaderMap;
+use serde::de::DeserializeOwned;
+
+/// Newline delimited JSON (NDJSON) extractor, for bulk payloads.
+///
+/// Each line of the request body is deserialized independently into some type that
+/// implements [`serde::de::DeserializeOwned`] and [`eserde::EDeserialize`]:
+/// a line that fails to deserialize doesn't abort the others, it's reported as a
+/// [`LineError`] in the corresponding position of the output.
+/// Empty lines are skipped. With [`Config::fail_fast`], each line is only reported
+/// up to its first error.
+///
+/// The request will be rejected (and a [`NdJsonRejection`] will be returned) if:
+///
+/// - The request doesn't have a `Content-Type: application/x-ndjson` header.
+/// - Buffering the request body fails.
+///
+/// ⚠️ Since parsing the lines requires consuming the request body, the `NdJson` extractor must be
+/// *last* if there are multiple extractors in a handler.
+/// See ["the order of extractors"][order-of-extractors]
+///
+/// [order-of-extractors]: https://docs.rs/axum/latest/axum/extract/index.html#the-order-of-extractors
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{routing::post, Router};
+/// use eserde_axum::NdJson;
+///
+/// #[derive(eserde::Deserialize)]
+/// struct Product {
+///     sku: String,
+///     stock: u32,
+/// }
+///
+/// async fn bulk_insert(NdJson(lines): NdJson<Product>) -> String {
+///     let mut failed = Vec::new();
+///     for line in lines {
+///         match line {
+///             Ok(product) => { /* insert the product */ }
+///             Err(error) => failed.push(error.line().to_string()),
+///         }
+///     }
+///     format!("Failed lines: {}", failed.join(", "))
+/// }
+///
+/// let app = Router::new().route("/products/bulk", post(bulk_insert));
+/// # let _: Router = app;
+/// ```
+#[derive(Debug, Default)]
+#[cfg_attr(docsrs, doc(cfg(feature = "ndjson")))]
+#[must_use]
+pub struct NdJson<T>(pub Vec<Result<T, LineError>>);
+
+/// A line of an [`NdJson`] payload that couldn't be deserialized.
+#[cfg_attr(docsrs, doc(cfg(feature = "ndjson")))]
+pub type LineError = DocumentError<LineNumber>;
+
+impl<T, S> FromRequest<S> for NdJson<T>
+where
+    T: DeserializeOwned,
+    T: for<'de> EDeserialize<'de>,
+    S: Send + Sync,
+{
+    type Rejection = NdJsonRejection;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let config = Config::from_request(&req);
+        check_ndjson_content_type(req.headers(), &config)?;
+        let (bytes, _permit) =
+            crate::body::read_body::<_, Self::Rejection>(req, state, &config).await?;
+        Ok(Self::from_bytes_with_config(&bytes, &config))
+    }
+}
+
+impl<T> NdJson<T>
+where
+    T: DeserializeOwned,
+    T: for<'de> EDeserialize<'de>,
+{
+    /// Construct a `NdJson<T>` from a byte slice. Most users should prefer to use the `FromRequest` impl
+    /// but special cases may require first extracting a `Request` into `Bytes` then optionally
+    /// constructing a `NdJson<T>`.
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        Self::from_bytes_with_config(bytes, &Config::default())
+    }
+
+    /// Like [`from_bytes`](Self::from_bytes), but honoring the settings
+    /// specified in the [`Config`].
+    pub(crate) fn from_bytes_with_config(bytes: &[u8], config: &Config) -> Self {
+        let lines = bytes
+            .split(|&b| b == b'\n')
+            .enumerate()
+            .map(|(i, line)| (i + 1, line.strip_suffix(b"\r").unwrap_or(line)))
+            .filter(|(_, line)| !line.iter().all(u8::is_ascii_whitespace))
+            .map(|(line, bytes)| {
+                deserialize_document(bytes, config)
+                    .map_err(|errors| LineError::line_error(line, errors))
+            })
+            .collect();
+        NdJson(lines)
+    }
+}
+
+/// Check that the `Content-Type` header is set to `application/x-ndjson`.
+///
+/// Return an error otherwise.
+fn check_ndjson_content_type(headers: &HeaderMap, config: &Config) -> Result<(), NdJsonRejection> {
+    let missing = || {
+        MissingNdJsonContentType {
+            config: config.clone(),
+        }
+        .into()
+    };
+    let count = headers.get_all(http::header::CONTENT_TYPE).iter().count();
+    if count > 1 {
+        return Err(AmbiguousNdJsonContentType {
+            count,
+            config: config.clone(),
+        }
+        .into());
+    }
+    let Some(content_type) = headers.get(http::header::CONTENT_TYPE) else {
+        return Err(missing());
+    };
+    let Ok(content_type) = content_type.to_str() else {
+        return Err(missing());
+    };
+
+    let is_ndjson_content_type = content_type
+        .parse::<mime::Mime>()
+        .is_ok_and(|mime| mime.essence_str() == "application/x-ndjson");
+    if !is_ndjson_content_type {
+        return Err(NdJsonContentTypeMismatch {
+            actual: content_type.to_string(),
+            config: config.clone(),
+        }
+        .into());
+    }
+    Ok(())
+}
+
+impl<T> Deref for NdJson<T> {
+    type Target = Vec<Result<T, LineError>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for NdJson<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::header::CONTENT_TYPE;
+
+    #[derive(Debug, PartialEq, eserde::Deserialize)]
+    struct Product {
+        sku: String,
+        stock: u32,
+    }
+
+    fn request(body: &'static str) -> Request {
+        Request::builder()
+            .method("POST")
+            .uri("/")
+            .header(CONTENT_TYPE, "application/x-ndjson")
+            .body(axum_core::body::Body::from(body))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_bad_lines_are_reported() {
+        let body = concat!(
+            r#"{"sku": "A-1", "stock": 10}"#,
+            "\n",
+            r#"{"sku": "A-2", "stock": -1}"#,
+            "\n",
+            r#"{"sku": "A-3", "stock": 7}"#,
+            "\r\n",
+            r#"{"sku": 4}"#,
+            "\n",
+        );
+        let NdJson(lines) = NdJson::<Product>::from_request(request(body), &())
+            .await
+            .unwrap();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(
+            lines[0].as_ref().unwrap(),
+            &Product {
+                sku: "A-1".into(),
+                stock: 10
+            }
+        );
+        assert_eq!(lines[1].as_ref().unwrap_err().line(), 2);
+        assert_eq!(lines[2].as_ref().unwrap().sku, "A-3");
+        let error = lines[3].as_ref().unwrap_err();
+        assert_eq!(error.line(), 4);
+        // A single line can have several problems.
+        assert_eq!(error.errors().len(), 2);
+    }
+
+    #[test]
+    fn test_empty_lines_are_skipped() {
+        let NdJson(lines) =
+            NdJson::<Product>::from_bytes(b"\n{\"sku\": \"A-1\", \"stock\": 1}\n\n{}\n");
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].is_ok());
+        assert_eq!(lines[1].as_ref().unwrap_err().line(), 4);
+    }
+
+    #[test]
+    fn test_fail_fast_stops_at_the_first_error_of_a_line() {
+        let config = Config::new().fail_fast(true);
+        let NdJson(lines) = NdJson::<Product>::from_bytes_with_config(b"{\"sku\": 4}\n", &config);
+        assert_eq!(lines[0].as_ref().unwrap_err().errors().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_content_type_mismatch() {
+        let request = Request::builder()
+            .method("POST")
+            .uri("/")
+            .header(CONTENT_TYPE, "application/json")
+            .body(axum_core::body::Body::from("{}"))
+            .unwrap();
+        let rejection = NdJson::<Product>::from_request(request, &())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            rejection,
+            NdJsonRejection::NdJsonContentTypeMismatch(_)
+        ));
+    }
+}
@@ -0,0 +1,281 @@
+use axum_core::extract::rejection::BytesRejection;
+use http::{header::CONTENT_TYPE, StatusCode};
+
+use crate::config::Config;
+use crate::details::{
+    bytes_rejection_response, InvalidRequest, ProblemDetails, Source, UnsupportedMediaType,
+    ValidationError, ValidationErrors,
+};
+
+#[doc(hidden)]
+macro_rules! __log_rejection {
+    (
+        rejection_type = $ty:ident,
+        status = $status:expr,
+    ) => {
+        {
+            tracing::event!(
+                target: "eserde_axum::ndjson::rejection",
+                tracing::Level::TRACE,
+                status = $status.as_u16(),
+                rejection_type = ::std::any::type_name::<$ty>(),
+                "rejecting request",
+            );
+        }
+    };
+}
+
+#[derive(Debug, Default)]
+#[non_exhaustive]
+#[cfg_attr(docsrs, doc(cfg(feature = "ndjson")))]
+/// Rejection type for [`NdJson`](super::NdJson) used if the `Content-Type`
+/// header is missing.
+pub struct MissingNdJsonContentType {
+    pub(crate) config: Config,
+}
+
+impl axum_core::response::IntoResponse for MissingNdJsonContentType {
+    fn into_response(self) -> axum_core::response::Response {
+        let error = ValidationError::new(
+            Source::Header {
+                name: CONTENT_TYPE.as_str().into(),
+            },
+            "missing_content_type",
+            "Expected request with `Content-Type: application/x-ndjson`, but no `Content-Type` header was found",
+        );
+        let response = InvalidRequest::new(ValidationErrors {
+            errors: vec![error],
+            by_pointer: false,
+        })
+        .configure(&self.config);
+        __log_rejection!(
+            rejection_type = MissingNdJsonContentType,
+            status = InvalidRequest::status(),
+        );
+        response.into_response()
+    }
+}
+
+impl std::fmt::Display for MissingNdJsonContentType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Expected request with `Content-Type: application/x-ndjson`"
+        )
+    }
+}
+
+impl std::error::Error for MissingNdJsonContentType {}
+
+#[derive(Debug)]
+#[non_exhaustive]
+#[cfg_attr(docsrs, doc(cfg(feature = "ndjson")))]
+/// Rejection type for [`NdJson`](super::NdJson) used if the `Content-Type`
+/// header has an incorrect value.
+///
+/// It results in a `415 Unsupported Media Type` problem, listing the accepted
+/// media types in the `expected` member and the header value in the `received` one.
+pub struct NdJsonContentTypeMismatch {
+    pub(crate) actual: String,
+    pub(crate) config: Config,
+}
+
+/// The media types accepted by [`NdJson`](super::NdJson), as listed in a [`NdJsonContentTypeMismatch`] problem.
+const EXPECTED_CONTENT_TYPES: &[&str] = &["application/x-ndjson"];
+
+impl NdJsonContentTypeMismatch {
+    /// The media types accepted by the extractor.
+    pub fn expected(&self) -> &'static [&'static str] {
+        EXPECTED_CONTENT_TYPES
+    }
+
+    /// The value of the `Content-Type` header of the request.
+    pub fn received(&self) -> &str {
+        &self.actual
+    }
+}
+
+impl axum_core::response::IntoResponse for NdJsonContentTypeMismatch {
+    fn into_response(self) -> axum_core::response::Response {
+        let message = self.to_string();
+        let response = UnsupportedMediaType::mismatch(
+            EXPECTED_CONTENT_TYPES,
+            self.actual,
+            message,
+            "The request body must be newline-delimited JSON",
+        )
+        .configure(&self.config);
+        __log_rejection!(
+            rejection_type = NdJsonContentTypeMismatch,
+            status = UnsupportedMediaType::status(),
+        );
+        response.into_response()
+    }
+}
+
+impl std::fmt::Display for NdJsonContentTypeMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Expected request with `Content-Type: application/x-ndjson`, but found `{}`",
+            self.actual
+        )
+    }
+}
+
+impl std::error::Error for NdJsonContentTypeMismatch {}
+
+#[derive(Debug)]
+#[non_exhaustive]
+#[cfg_attr(docsrs, doc(cfg(feature = "ndjson")))]
+/// Rejection type for [`NdJson`](super::NdJson) used if the request has
+/// more than one `Content-Type` header, e.g. because of a misconfigured proxy.
+pub struct AmbiguousNdJsonContentType {
+    pub(crate) count: usize,
+    pub(crate) config: Config,
+}
+
+impl axum_core::response::IntoResponse for AmbiguousNdJsonContentType {
+    fn into_response(self) -> axum_core::response::Response {
+        let status = StatusCode::BAD_REQUEST;
+        let problem = ProblemDetails::ambiguous_content_type(self.count).configure(&self.config);
+        let problem = ProblemDetails {
+            extensions: problem
+                .extensions
+                .map(|errors| errors.configure(&self.config)),
+            ..problem
+        };
+        __log_rejection!(rejection_type = AmbiguousNdJsonContentType, status = status,);
+        problem.into_response()
+    }
+}
+
+impl std::fmt::Display for AmbiguousNdJsonContentType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Expected a single `Content-Type` header, but found {}",
+            self.count
+        )
+    }
+}
+
+impl std::error::Error for AmbiguousNdJsonContentType {}
+
+/// Rejection used for [`NdJson`](super::NdJson).
+///
+/// Contains one variant for each way the [`NdJson`](super::NdJson) extractor
+/// can fail.
+///
+/// All error responses follow the problem details specification,
+/// as outlined in [RFC 9457](https://www.rfc-editor.org/rfc/rfc9457.html).
+#[derive(Debug)]
+#[non_exhaustive]
+#[cfg_attr(docsrs, doc(cfg(feature = "ndjson")))]
+pub enum NdJsonRejection {
+    #[allow(missing_docs)]
+    MissingNdJsonContentType(MissingNdJsonContentType),
+    #[allow(missing_docs)]
+    AmbiguousNdJsonContentType(AmbiguousNdJsonContentType),
+    #[allow(missing_docs)]
+    NdJsonContentTypeMismatch(NdJsonContentTypeMismatch),
+    #[allow(missing_docs)]
+    BytesRejection(BytesRejection),
+    #[allow(missing_docs)]
+    ContentTooLarge(crate::body::ContentTooLarge),
+    #[allow(missing_docs)]
+    Overloaded(crate::body::Overloaded),
+    #[allow(missing_docs)]
+    InvalidContentLength(crate::body::InvalidContentLength),
+    #[cfg(feature = "timeout")]
+    #[allow(missing_docs)]
+    RequestTimeout(crate::timeout::RequestTimeout),
+}
+
+impl axum_core::response::IntoResponse for NdJsonRejection {
+    fn into_response(self) -> axum_core::response::Response {
+        match self {
+            Self::MissingNdJsonContentType(inner) => inner.into_response(),
+            Self::AmbiguousNdJsonContentType(inner) => inner.into_response(),
+            Self::NdJsonContentTypeMismatch(inner) => inner.into_response(),
+            Self::BytesRejection(inner) => bytes_rejection_response(inner),
+            Self::ContentTooLarge(inner) => inner.into_response(),
+            Self::Overloaded(inner) => inner.into_response(),
+            Self::InvalidContentLength(inner) => inner.into_response(),
+            #[cfg(feature = "timeout")]
+            Self::RequestTimeout(inner) => inner.into_response(),
+        }
+    }
+}
+
+impl From<MissingNdJsonContentType> for NdJsonRejection {
+    fn from(inner: MissingNdJsonContentType) -> Self {
+        Self::MissingNdJsonContentType(inner)
+    }
+}
+impl From<AmbiguousNdJsonContentType> for NdJsonRejection {
+    fn from(inner: AmbiguousNdJsonContentType) -> Self {
+        Self::AmbiguousNdJsonContentType(inner)
+    }
+}
+impl From<NdJsonContentTypeMismatch> for NdJsonRejection {
+    fn from(inner: NdJsonContentTypeMismatch) -> Self {
+        Self::NdJsonContentTypeMismatch(inner)
+    }
+}
+impl From<BytesRejection> for NdJsonRejection {
+    fn from(inner: BytesRejection) -> Self {
+        Self::BytesRejection(inner)
+    }
+}
+impl From<crate::body::ContentTooLarge> for NdJsonRejection {
+    fn from(inner: crate::body::ContentTooLarge) -> Self {
+        Self::ContentTooLarge(inner)
+    }
+}
+impl From<crate::body::Overloaded> for NdJsonRejection {
+    fn from(inner: crate::body::Overloaded) -> Self {
+        Self::Overloaded(inner)
+    }
+}
+impl From<crate::body::InvalidContentLength> for NdJsonRejection {
+    fn from(inner: crate::body::InvalidContentLength) -> Self {
+        Self::InvalidContentLength(inner)
+    }
+}
+#[cfg(feature = "timeout")]
+impl From<crate::timeout::RequestTimeout> for NdJsonRejection {
+    fn from(inner: crate::timeout::RequestTimeout) -> Self {
+        Self::RequestTimeout(inner)
+    }
+}
+impl std::fmt::Display for NdJsonRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingNdJsonContentType(inner) => write!(f, "{inner}"),
+            Self::AmbiguousNdJsonContentType(inner) => write!(f, "{inner}"),
+            Self::NdJsonContentTypeMismatch(inner) => write!(f, "{inner}"),
+            Self::BytesRejection(inner) => write!(f, "{inner}"),
+            Self::ContentTooLarge(inner) => write!(f, "{inner}"),
+            Self::Overloaded(inner) => write!(f, "{inner}"),
+            Self::InvalidContentLength(inner) => write!(f, "{inner}"),
+            #[cfg(feature = "timeout")]
+            Self::RequestTimeout(inner) => write!(f, "{inner}"),
+        }
+    }
+}
+impl std::error::Error for NdJsonRejection {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::MissingNdJsonContentType(inner) => inner.source(),
+            Self::AmbiguousNdJsonContentType(inner) => inner.source(),
+            Self::NdJsonContentTypeMismatch(inner) => inner.source(),
+            Self::BytesRejection(inner) => inner.source(),
+            Self::ContentTooLarge(inner) => inner.source(),
+            Self::Overloaded(inner) => inner.source(),
+            Self::InvalidContentLength(inner) => inner.source(),
+            #[cfg(feature = "timeout")]
+            Self::RequestTimeout(inner) => inner.source(),
+        }
+    }
+}
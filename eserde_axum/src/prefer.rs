@@ -0,0 +1,207 @@
+//! Honor the `Prefer` header of a request.
+//!
+//! See [`Prefer`] for more details.
+use std::convert::Infallible;
+
+use axum_core::body::Body;
+use axum_core::extract::FromRequestParts;
+use axum_core::response::{IntoResponse, Response};
+use http::header::{HeaderName, CONTENT_LENGTH, CONTENT_TYPE};
+use http::request::Parts;
+use http::{HeaderMap, HeaderValue};
+
+/// The `Prefer` request header, defined by
+/// [RFC 7240](https://www.rfc-editor.org/rfc/rfc7240#section-2).
+const PREFER: HeaderName = HeaderName::from_static("prefer");
+
+/// The `Preference-Applied` response header, defined by
+/// [RFC 7240](https://www.rfc-editor.org/rfc/rfc7240#section-3).
+const PREFERENCE_APPLIED: HeaderName = HeaderName::from_static("preference-applied");
+
+/// Extractor for the preferences expressed via the `Prefer` header,
+/// as defined by [RFC 7240](https://www.rfc-editor.org/rfc/rfc7240).
+///
+/// Only the `return` preference is supported: use [`Prefer::apply`] (or
+/// [`Created::prefer`](crate::Created::prefer)) to drop the body of successful
+/// responses when the client asked for `return=minimal`.
+/// Problem responses are never affected: clients need them to figure out what went wrong.
+///
+/// Extraction never fails: preferences are hints, so unknown or malformed
+/// ones are ignored.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{response::Response, routing::put, Router};
+/// use eserde_axum::{Json, Prefer};
+///
+/// #[derive(eserde::Deserialize, serde::Serialize)]
+/// struct Profile {
+///     name: String,
+/// }
+///
+/// async fn update_profile(prefer: Prefer, Json(profile): Json<Profile>) -> Response {
+///     // Store the profile...
+///     prefer.apply(Json(profile))
+/// }
+///
+/// let app = Router::new().route("/profile", put(update_profile));
+/// # let _: Router = app;
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Prefer {
+    return_: Option<Return>,
+}
+
+/// The values of the `return` preference.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Return {
+    /// `return=minimal`: the client doesn't need the resource in the response body.
+    Minimal,
+    /// `return=representation`: the client wants the resource in the response body.
+    Representation,
+}
+
+impl Prefer {
+    /// The `return` preference of the client, if any.
+    pub fn return_preference(&self) -> Option<Return> {
+        self.return_
+    }
+
+    /// Shape a response according to the preferences of the client.
+    ///
+    /// With `return=minimal`, the body of successful responses is dropped, along with
+    /// their `Content-Type`—the status code and all other headers are preserved.
+    /// A `Preference-Applied` header is added to the successful responses
+    /// for which the `return` preference was honored.
+    pub fn apply(&self, response: impl IntoResponse) -> Response {
+        let mut response = response.into_response();
+        let Some(return_) = self.return_ else {
+            return response;
+        };
+        if !response.status().is_success() {
+            return response;
+        }
+        let applied = match return_ {
+            Return::Minimal => {
+                let headers = response.headers_mut();
+                headers.remove(CONTENT_TYPE);
+                headers.remove(CONTENT_LENGTH);
+                *response.body_mut() = Body::empty();
+                "return=minimal"
+            }
+            Return::Representation => "return=representation",
+        };
+        response
+            .headers_mut()
+            .insert(PREFERENCE_APPLIED, HeaderValue::from_static(applied));
+        response
+    }
+
+    /// Parse the `Prefer` headers of a request.
+    ///
+    /// As prescribed by the RFC, only the first occurrence of each preference is considered.
+    pub(crate) fn from_headers(headers: &HeaderMap) -> Self {
+        let return_ = headers
+            .get_all(PREFER)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .filter_map(|preference| {
+                // Parameters (after `;`) don't matter for the `return` preference.
+                let token = preference.split(';').next()?;
+                let (name, value) = token.split_once('=').unwrap_or((token, ""));
+                name.trim()
+                    .eq_ignore_ascii_case("return")
+                    .then(|| value.trim().trim_matches('"'))
+            })
+            .next()
+            .and_then(|value| {
+                if value.eq_ignore_ascii_case("minimal") {
+                    Some(Return::Minimal)
+                } else if value.eq_ignore_ascii_case("representation") {
+                    Some(Return::Representation)
+                } else {
+                    None
+                }
+            });
+        Self { return_ }
+    }
+}
+
+impl<S> FromRequestParts<S> for Prefer
+where
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(Self::from_headers(&parts.headers))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::StatusCode;
+
+    fn prefer(values: &[&'static str]) -> Prefer {
+        let mut headers = HeaderMap::new();
+        for value in values {
+            headers.append(PREFER, HeaderValue::from_static(value));
+        }
+        Prefer::from_headers(&headers)
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(prefer(&[]).return_preference(), None);
+        assert_eq!(
+            prefer(&["return=minimal"]).return_preference(),
+            Some(Return::Minimal)
+        );
+        assert_eq!(
+            prefer(&["respond-async, wait=10", "RETURN = \"representation\"; foo"])
+                .return_preference(),
+            Some(Return::Representation)
+        );
+        // Only the first occurrence counts.
+        assert_eq!(
+            prefer(&["return=minimal, return=representation"]).return_preference(),
+            Some(Return::Minimal)
+        );
+        assert_eq!(prefer(&["return=everything"]).return_preference(), None);
+    }
+
+    #[tokio::test]
+    async fn test_problems_are_untouched() {
+        let response = prefer(&["return=minimal"])
+            .apply(crate::Conflict::new("/email", "The email is already taken"));
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+        assert!(!response.headers().contains_key(PREFERENCE_APPLIED));
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert!(!body.is_empty());
+    }
+
+    #[cfg(feature = "json")]
+    #[tokio::test]
+    async fn test_minimal_created() {
+        #[derive(serde::Serialize)]
+        struct User {
+            id: u64,
+        }
+
+        let created = crate::Created::new("/users/42", User { id: 42 }).unwrap();
+        let response = created.prefer(prefer(&["return=minimal"])).into_response();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        assert_eq!(response.headers()[http::header::LOCATION], "/users/42");
+        assert_eq!(response.headers()[PREFERENCE_APPLIED], "return=minimal");
+        assert!(!response.headers().contains_key(CONTENT_TYPE));
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert!(body.is_empty());
+    }
+}
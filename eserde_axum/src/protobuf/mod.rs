@@ -0,0 +1,7 @@
+//! Supporting types for the [`Protobuf`] extractor.
+mod protobuf_;
+mod rejections;
+
+#[doc(hidden)]
+pub use protobuf_::Protobuf;
+pub use rejections::*;
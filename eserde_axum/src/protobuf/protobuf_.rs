@@ -0,0 +1,237 @@
+use std::ops::Deref;
+use std::ops::DerefMut;
+
+use super::*;
+use crate::config::Config;
+use axum_core::extract::FromRequest;
+use axum_core::extract::Request;
+use http::header::HeaderMap;
+use prost::Message;
+
+/// Protobuf extractor, for endpoints sharing their messages with gRPC services.
+///
+/// The request body is decoded into some type that implements [`prost::Message`],
+/// e.g. one generated by `prost-build`.
+///
+/// The request will be rejected (and a [`ProtobufRejection`] will be returned) if:
+///
+/// - The request doesn't have a `Content-Type: application/x-protobuf`
+///   (or `application/protobuf`) header.
+/// - The body can't be decoded into the target message.
+/// - Buffering the request body fails.
+///
+/// Unlike the JSON extractors, decoding stops at the first problem:
+/// the response describes a single error, in its `detail` member.
+///
+/// ⚠️ Since parsing the body requires consuming it, the `Protobuf` extractor must be
+/// *last* if there are multiple extractors in a handler.
+/// See ["the order of extractors"][order-of-extractors]
+///
+/// [order-of-extractors]: https://docs.rs/axum/latest/axum/extract/index.html#the-order-of-extractors
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{routing::post, Router};
+/// use eserde_axum::Protobuf;
+///
+/// #[derive(Clone, PartialEq, prost::Message)]
+/// struct CreateOrder {
+///     #[prost(string, tag = "1")]
+///     sku: String,
+///     #[prost(uint32, tag = "2")]
+///     quantity: u32,
+/// }
+///
+/// async fn create_order(Protobuf(order): Protobuf<CreateOrder>) -> String {
+///     format!("Ordered {} units of {}", order.quantity, order.sku)
+/// }
+///
+/// let app = Router::new().route("/orders", post(create_order));
+/// # let _: Router = app;
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(docsrs, doc(cfg(feature = "protobuf")))]
+#[must_use]
+pub struct Protobuf<T>(pub T);
+
+impl<T, S> FromRequest<S> for Protobuf<T>
+where
+    T: Message + Default,
+    S: Send + Sync,
+{
+    type Rejection = ProtobufRejection;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let config = Config::from_request(&req);
+        check_protobuf_content_type(req.headers(), &config)?;
+        let (bytes, _permit) =
+            crate::body::read_body::<_, Self::Rejection>(req, state, &config).await?;
+        Self::from_bytes_with_config(&bytes, &config)
+    }
+}
+
+impl<T> Protobuf<T>
+where
+    T: Message + Default,
+{
+    /// Construct a `Protobuf<T>` from a byte slice. Most users should prefer to use the `FromRequest` impl
+    /// but special cases may require first extracting a `Request` into `Bytes` then optionally
+    /// constructing a `Protobuf<T>`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ProtobufRejection> {
+        Self::from_bytes_with_config(bytes, &Config::default())
+    }
+
+    fn from_bytes_with_config(bytes: &[u8], config: &Config) -> Result<Self, ProtobufRejection> {
+        match T::decode(bytes) {
+            Ok(value) => Ok(Protobuf(value)),
+            Err(error) => Err(InvalidProtobuf {
+                error,
+                config: config.clone(),
+            }
+            .into()),
+        }
+    }
+}
+
+/// Check that the `Content-Type` header is set to `application/x-protobuf`
+/// or `application/protobuf`.
+///
+/// Return an error otherwise.
+fn check_protobuf_content_type(
+    headers: &HeaderMap,
+    config: &Config,
+) -> Result<(), ProtobufRejection> {
+    let missing = || {
+        MissingProtobufContentType {
+            config: config.clone(),
+        }
+        .into()
+    };
+    let count = headers.get_all(http::header::CONTENT_TYPE).iter().count();
+    if count > 1 {
+        return Err(AmbiguousProtobufContentType {
+            count,
+            config: config.clone(),
+        }
+        .into());
+    }
+    let Some(content_type) = headers.get(http::header::CONTENT_TYPE) else {
+        return Err(missing());
+    };
+    let Ok(content_type) = content_type.to_str() else {
+        return Err(missing());
+    };
+
+    let is_protobuf_content_type = content_type.parse::<mime::Mime>().is_ok_and(|mime| {
+        matches!(
+            mime.essence_str(),
+            "application/x-protobuf" | "application/protobuf"
+        )
+    });
+    if !is_protobuf_content_type {
+        return Err(ProtobufContentTypeMismatch {
+            actual: content_type.to_string(),
+            config: config.clone(),
+        }
+        .into());
+    }
+    Ok(())
+}
+
+impl<T> Deref for Protobuf<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Protobuf<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T> From<T> for Protobuf<T> {
+    fn from(inner: T) -> Self {
+        Self(inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils;
+    use http::header::CONTENT_TYPE;
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    struct CreateOrder {
+        #[prost(string, tag = "1")]
+        sku: String,
+        #[prost(uint32, tag = "2")]
+        quantity: u32,
+    }
+
+    fn request(content_type: &'static str, body: Vec<u8>) -> Request {
+        Request::builder()
+            .method("POST")
+            .uri("/")
+            .header(CONTENT_TYPE, content_type)
+            .body(axum_core::body::Body::from(body))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_valid_protobuf() {
+        let order = CreateOrder {
+            sku: "A-1".into(),
+            quantity: 3,
+        };
+        for content_type in ["application/x-protobuf", "application/protobuf"] {
+            let Protobuf(decoded) = Protobuf::<CreateOrder>::from_request(
+                request(content_type, order.encode_to_vec()),
+                &(),
+            )
+            .await
+            .unwrap();
+            assert_eq!(decoded, order);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_corrupt_protobuf() {
+        let mut body = CreateOrder {
+            sku: "A-1".into(),
+            quantity: 3,
+        }
+        .encode_to_vec();
+        // Claim that the string is longer than the rest of the body.
+        body[1] = 0x7f;
+        let rejection =
+            Protobuf::<CreateOrder>::from_request(request("application/x-protobuf", body), &())
+                .await
+                .unwrap_err();
+        assert!(matches!(rejection, ProtobufRejection::InvalidProtobuf(_)));
+
+        let (status, body) = test_utils::problem(rejection).await;
+        assert_eq!(status, 400);
+        assert_eq!(body["type"], "invalid_protobuf");
+        assert!(body["detail"]
+            .as_str()
+            .unwrap()
+            .starts_with("failed to decode Protobuf message"));
+    }
+
+    #[tokio::test]
+    async fn test_content_type_mismatch() {
+        let rejection =
+            Protobuf::<CreateOrder>::from_request(request("application/json", Vec::new()), &())
+                .await
+                .unwrap_err();
+        assert!(matches!(
+            rejection,
+            ProtobufRejection::ProtobufContentTypeMismatch(_)
+        ));
+    }
+}
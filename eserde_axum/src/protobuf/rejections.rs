@@ -0,0 +1,334 @@
+use axum_core::extract::rejection::BytesRejection;
+use http::{header::CONTENT_TYPE, StatusCode};
+
+use crate::config::Config;
+use crate::details::{
+    bytes_rejection_response, InvalidRequest, ProblemDetails, Source, UnsupportedMediaType,
+    ValidationError, ValidationErrors,
+};
+
+#[doc(hidden)]
+macro_rules! __log_rejection {
+    (
+        rejection_type = $ty:ident,
+        status = $status:expr,
+    ) => {
+        {
+            tracing::event!(
+                target: "eserde_axum::protobuf::rejection",
+                tracing::Level::TRACE,
+                status = $status.as_u16(),
+                rejection_type = ::std::any::type_name::<$ty>(),
+                "rejecting request",
+            );
+        }
+    };
+}
+
+#[derive(Debug, Default)]
+#[non_exhaustive]
+#[cfg_attr(docsrs, doc(cfg(feature = "protobuf")))]
+/// Rejection type for [`Protobuf`](super::Protobuf) used if the `Content-Type`
+/// header is missing.
+pub struct MissingProtobufContentType {
+    pub(crate) config: Config,
+}
+
+impl axum_core::response::IntoResponse for MissingProtobufContentType {
+    fn into_response(self) -> axum_core::response::Response {
+        let error = ValidationError::new(
+            Source::Header {
+                name: CONTENT_TYPE.as_str().into(),
+            },
+            "missing_content_type",
+            "Expected request with `Content-Type: application/x-protobuf` (or `application/protobuf`), but no `Content-Type` header was found",
+        );
+        let response = InvalidRequest::new(ValidationErrors {
+            errors: vec![error],
+            by_pointer: false,
+        })
+        .configure(&self.config);
+        __log_rejection!(
+            rejection_type = MissingProtobufContentType,
+            status = InvalidRequest::status(),
+        );
+        response.into_response()
+    }
+}
+
+impl std::fmt::Display for MissingProtobufContentType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Expected request with `Content-Type: application/x-protobuf` (or `application/protobuf`)"
+        )
+    }
+}
+
+impl std::error::Error for MissingProtobufContentType {}
+
+#[derive(Debug)]
+#[non_exhaustive]
+#[cfg_attr(docsrs, doc(cfg(feature = "protobuf")))]
+/// Rejection type for [`Protobuf`](super::Protobuf) used if the `Content-Type`
+/// header has an incorrect value.
+///
+/// It results in a `415 Unsupported Media Type` problem, listing the accepted
+/// media types in the `expected` member and the header value in the `received` one.
+pub struct ProtobufContentTypeMismatch {
+    pub(crate) actual: String,
+    pub(crate) config: Config,
+}
+
+/// The media types accepted by [`Protobuf`](super::Protobuf), as listed in a [`ProtobufContentTypeMismatch`] problem.
+const EXPECTED_CONTENT_TYPES: &[&str] = &["application/x-protobuf", "application/protobuf"];
+
+impl ProtobufContentTypeMismatch {
+    /// The media types accepted by the extractor.
+    pub fn expected(&self) -> &'static [&'static str] {
+        EXPECTED_CONTENT_TYPES
+    }
+
+    /// The value of the `Content-Type` header of the request.
+    pub fn received(&self) -> &str {
+        &self.actual
+    }
+}
+
+impl axum_core::response::IntoResponse for ProtobufContentTypeMismatch {
+    fn into_response(self) -> axum_core::response::Response {
+        let message = self.to_string();
+        let response = UnsupportedMediaType::mismatch(
+            EXPECTED_CONTENT_TYPES,
+            self.actual,
+            message,
+            "The request body must be a Protobuf message",
+        )
+        .configure(&self.config);
+        __log_rejection!(
+            rejection_type = ProtobufContentTypeMismatch,
+            status = UnsupportedMediaType::status(),
+        );
+        response.into_response()
+    }
+}
+
+impl std::fmt::Display for ProtobufContentTypeMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Expected request with `Content-Type: application/x-protobuf` (or `application/protobuf`), but found `{}`",
+            self.actual
+        )
+    }
+}
+
+impl std::error::Error for ProtobufContentTypeMismatch {}
+
+#[derive(Debug)]
+#[non_exhaustive]
+#[cfg_attr(docsrs, doc(cfg(feature = "protobuf")))]
+/// Rejection type for [`Protobuf`](super::Protobuf) used if the request has
+/// more than one `Content-Type` header, e.g. because of a misconfigured proxy.
+pub struct AmbiguousProtobufContentType {
+    pub(crate) count: usize,
+    pub(crate) config: Config,
+}
+
+impl axum_core::response::IntoResponse for AmbiguousProtobufContentType {
+    fn into_response(self) -> axum_core::response::Response {
+        let status = StatusCode::BAD_REQUEST;
+        let problem = ProblemDetails::ambiguous_content_type(self.count).configure(&self.config);
+        let problem = ProblemDetails {
+            extensions: problem
+                .extensions
+                .map(|errors| errors.configure(&self.config)),
+            ..problem
+        };
+        __log_rejection!(
+            rejection_type = AmbiguousProtobufContentType,
+            status = status,
+        );
+        problem.into_response()
+    }
+}
+
+impl std::fmt::Display for AmbiguousProtobufContentType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Expected a single `Content-Type` header, but found {}",
+            self.count
+        )
+    }
+}
+
+impl std::error::Error for AmbiguousProtobufContentType {}
+
+#[derive(Debug)]
+#[non_exhaustive]
+#[cfg_attr(docsrs, doc(cfg(feature = "protobuf")))]
+/// Rejection type for [`Protobuf`](super::Protobuf) used if the request body
+/// can't be decoded into the target message.
+///
+/// Protobuf decoding stops at the first problem, so the `detail` of the problem
+/// describes a single error.
+pub struct InvalidProtobuf {
+    pub(crate) error: prost::DecodeError,
+    pub(crate) config: Config,
+}
+
+impl axum_core::response::IntoResponse for InvalidProtobuf {
+    fn into_response(self) -> axum_core::response::Response {
+        let status = StatusCode::BAD_REQUEST;
+        let problem: ProblemDetails<()> = ProblemDetails::new(
+            "invalid_protobuf",
+            status,
+            "The request body is not a valid protobuf message",
+            self.error.to_string(),
+        )
+        .configure(&self.config);
+        __log_rejection!(rejection_type = InvalidProtobuf, status = status,);
+        problem.into_response()
+    }
+}
+
+impl std::fmt::Display for InvalidProtobuf {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Failed to decode the request body: {}", self.error)
+    }
+}
+
+impl std::error::Error for InvalidProtobuf {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// Rejection used for [`Protobuf`](super::Protobuf).
+///
+/// Contains one variant for each way the [`Protobuf`](super::Protobuf) extractor
+/// can fail.
+///
+/// All error responses follow the problem details specification,
+/// as outlined in [RFC 9457](https://www.rfc-editor.org/rfc/rfc9457.html).
+#[derive(Debug)]
+#[non_exhaustive]
+#[cfg_attr(docsrs, doc(cfg(feature = "protobuf")))]
+pub enum ProtobufRejection {
+    #[allow(missing_docs)]
+    MissingProtobufContentType(MissingProtobufContentType),
+    #[allow(missing_docs)]
+    AmbiguousProtobufContentType(AmbiguousProtobufContentType),
+    #[allow(missing_docs)]
+    ProtobufContentTypeMismatch(ProtobufContentTypeMismatch),
+    #[allow(missing_docs)]
+    InvalidProtobuf(InvalidProtobuf),
+    #[allow(missing_docs)]
+    BytesRejection(BytesRejection),
+    #[allow(missing_docs)]
+    ContentTooLarge(crate::body::ContentTooLarge),
+    #[allow(missing_docs)]
+    Overloaded(crate::body::Overloaded),
+    #[allow(missing_docs)]
+    InvalidContentLength(crate::body::InvalidContentLength),
+    #[cfg(feature = "timeout")]
+    #[allow(missing_docs)]
+    RequestTimeout(crate::timeout::RequestTimeout),
+}
+
+impl axum_core::response::IntoResponse for ProtobufRejection {
+    fn into_response(self) -> axum_core::response::Response {
+        match self {
+            Self::MissingProtobufContentType(inner) => inner.into_response(),
+            Self::AmbiguousProtobufContentType(inner) => inner.into_response(),
+            Self::ProtobufContentTypeMismatch(inner) => inner.into_response(),
+            Self::InvalidProtobuf(inner) => inner.into_response(),
+            Self::BytesRejection(inner) => bytes_rejection_response(inner),
+            Self::ContentTooLarge(inner) => inner.into_response(),
+            Self::Overloaded(inner) => inner.into_response(),
+            Self::InvalidContentLength(inner) => inner.into_response(),
+            #[cfg(feature = "timeout")]
+            Self::RequestTimeout(inner) => inner.into_response(),
+        }
+    }
+}
+
+impl From<MissingProtobufContentType> for ProtobufRejection {
+    fn from(inner: MissingProtobufContentType) -> Self {
+        Self::MissingProtobufContentType(inner)
+    }
+}
+impl From<AmbiguousProtobufContentType> for ProtobufRejection {
+    fn from(inner: AmbiguousProtobufContentType) -> Self {
+        Self::AmbiguousProtobufContentType(inner)
+    }
+}
+impl From<ProtobufContentTypeMismatch> for ProtobufRejection {
+    fn from(inner: ProtobufContentTypeMismatch) -> Self {
+        Self::ProtobufContentTypeMismatch(inner)
+    }
+}
+impl From<InvalidProtobuf> for ProtobufRejection {
+    fn from(inner: InvalidProtobuf) -> Self {
+        Self::InvalidProtobuf(inner)
+    }
+}
+impl From<BytesRejection> for ProtobufRejection {
+    fn from(inner: BytesRejection) -> Self {
+        Self::BytesRejection(inner)
+    }
+}
+impl From<crate::body::ContentTooLarge> for ProtobufRejection {
+    fn from(inner: crate::body::ContentTooLarge) -> Self {
+        Self::ContentTooLarge(inner)
+    }
+}
+impl From<crate::body::Overloaded> for ProtobufRejection {
+    fn from(inner: crate::body::Overloaded) -> Self {
+        Self::Overloaded(inner)
+    }
+}
+impl From<crate::body::InvalidContentLength> for ProtobufRejection {
+    fn from(inner: crate::body::InvalidContentLength) -> Self {
+        Self::InvalidContentLength(inner)
+    }
+}
+#[cfg(feature = "timeout")]
+impl From<crate::timeout::RequestTimeout> for ProtobufRejection {
+    fn from(inner: crate::timeout::RequestTimeout) -> Self {
+        Self::RequestTimeout(inner)
+    }
+}
+impl std::fmt::Display for ProtobufRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingProtobufContentType(inner) => write!(f, "{inner}"),
+            Self::AmbiguousProtobufContentType(inner) => write!(f, "{inner}"),
+            Self::ProtobufContentTypeMismatch(inner) => write!(f, "{inner}"),
+            Self::InvalidProtobuf(inner) => write!(f, "{inner}"),
+            Self::BytesRejection(inner) => write!(f, "{inner}"),
+            Self::ContentTooLarge(inner) => write!(f, "{inner}"),
+            Self::Overloaded(inner) => write!(f, "{inner}"),
+            Self::InvalidContentLength(inner) => write!(f, "{inner}"),
+            #[cfg(feature = "timeout")]
+            Self::RequestTimeout(inner) => write!(f, "{inner}"),
+        }
+    }
+}
+impl std::error::Error for ProtobufRejection {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::MissingProtobufContentType(inner) => inner.source(),
+            Self::AmbiguousProtobufContentType(inner) => inner.source(),
+            Self::ProtobufContentTypeMismatch(inner) => inner.source(),
+            Self::InvalidProtobuf(inner) => inner.source(),
+            Self::BytesRejection(inner) => inner.source(),
+            Self::ContentTooLarge(inner) => inner.source(),
+            Self::Overloaded(inner) => inner.source(),
+            Self::InvalidContentLength(inner) => inner.source(),
+            #[cfg(feature = "timeout")]
+            Self::RequestTimeout(inner) => inner.source(),
+        }
+    }
+}
@@ -0,0 +1,346 @@
+//! Extract nested values from the query string of a request.
+//!
+//! See [`Query`] for more details.
+use std::ops::{Deref, DerefMut};
+
+use axum_core::extract::FromRequestParts;
+use axum_core::response::{IntoResponse, Response};
+use eserde::urlencoded::Document;
+use eserde::{DeserializationErrors, EDeserialize};
+use http::request::Parts;
+use serde::de::DeserializeOwned;
+
+use crate::config::Config;
+use crate::details::{InvalidRequest, ValidationError, ValidationErrors};
+
+#[doc(hidden)]
+macro_rules! __log_rejection {
+    (
+        rejection_type = $ty:ident,
+        status = $status:expr,
+    ) => {
+        {
+            tracing::event!(
+                target: "eserde_axum::query::rejection",
+                tracing::Level::TRACE,
+                status = $status.as_u16(),
+                rejection_type = ::std::any::type_name::<$ty>(),
+                "rejecting request",
+            );
+        }
+    };
+    (
+        rejection_type = $ty:ident,
+        status = $status:expr,
+        config = $config:expr,
+    ) => {
+        {
+            let (method, target) = $config.request_target();
+            tracing::event!(
+                target: "eserde_axum::query::rejection",
+                tracing::Level::TRACE,
+                status = $status.as_u16(),
+                rejection_type = ::std::any::type_name::<$ty>(),
+                http.method = method,
+                http.target = target,
+                "rejecting request",
+            );
+        }
+    };
+}
+
+/// Query string extractor, with support for nested parameters.
+///
+/// It deserializes the query string into some type that implements
+/// [`serde::de::DeserializeOwned`] and [`eserde::EDeserialize`].
+/// Keys using the bracket syntax are nested under their parent key:
+/// `filter[status]=open&filter[price][min]=10` populates the `status` field and the
+/// `price` struct of a `filter` field.
+/// Like for [`Form`](crate::Form), repeated keys (e.g. `tag=a&tag=b`, optionally
+/// written `tag[]=a&tag[]=b`) are collected into sequence fields.
+/// See [`eserde::urlencoded`] for more details on the syntax.
+///
+/// A missing query string is treated as an empty one.
+/// The request will be rejected (and a [`QueryRejection`] will be returned) if the
+/// query string can't be deserialized into the target type.
+/// Errors point at the offending parameter with the bracket syntax, e.g. `filter[price][min]`.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{routing::get, Router};
+/// use eserde_axum::Query;
+///
+/// #[derive(eserde::Deserialize)]
+/// struct ListOrders {
+///     #[serde(default)]
+///     filter: Filter,
+///     page: Option<u32>,
+/// }
+///
+/// #[derive(eserde::Deserialize, Default)]
+/// struct Filter {
+///     status: Option<String>,
+///     min_total: Option<u64>,
+/// }
+///
+/// // e.g. `GET /orders?filter[status]=shipped&filter[min_total]=100&page=2`
+/// async fn list_orders(Query(query): Query<ListOrders>) {
+///     // query is a `ListOrders`
+/// }
+///
+/// let app = Router::new().route("/orders", get(list_orders));
+/// # let _: Router = app;
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(docsrs, doc(cfg(feature = "query")))]
+#[must_use]
+pub struct Query<T>(pub T);
+
+impl<T, S> FromRequestParts<S> for Query<T>
+where
+    T: DeserializeOwned,
+    T: for<'de> EDeserialize<'de>,
+    S: Send + Sync,
+{
+    type Rejection = QueryRejection;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let config = Config::from_request(parts);
+        let query = parts.uri.query().unwrap_or_default();
+        let document = Document::parse_nested(query.as_bytes())
+            .flexible_booleans(config.settings.flexible_query_booleans);
+        match eserde::from_deserializer(|| document.deserializer()) {
+            Ok(value) => Ok(Query(value)),
+            Err(errors) => Err(QueryError::new(errors, config, query.as_bytes()).into()),
+        }
+    }
+}
+
+impl<T> Deref for Query<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Query<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T> From<T> for Query<T> {
+    fn from(inner: T) -> Self {
+        Self(inner)
+    }
+}
+
+#[derive(Debug)]
+#[cfg_attr(docsrs, doc(cfg(feature = "query")))]
+/// Rejection type for [`Query`].
+///
+/// This rejection is used if the query string couldn't be deserialized
+/// into the target type.
+pub struct QueryError {
+    pub(crate) errors: DeserializationErrors,
+    pub(crate) config: Config,
+    pub(crate) debug_raw: Option<String>,
+}
+
+impl QueryError {
+    pub(crate) fn new(errors: DeserializationErrors, config: Config, query: &[u8]) -> Self {
+        Self {
+            debug_raw: config.debug_raw(query),
+            errors,
+            config,
+        }
+    }
+}
+
+impl IntoResponse for QueryError {
+    fn into_response(self) -> Response {
+        let errors = self.errors.iter().map(ValidationError::query).collect();
+        let response = InvalidRequest::new(ValidationErrors {
+            errors,
+            by_pointer: false,
+        })
+        .debug_raw(self.debug_raw)
+        .configure(&self.config);
+        __log_rejection!(
+            rejection_type = QueryError,
+            status = InvalidRequest::status(),
+            config = self.config,
+        );
+        response.into_response()
+    }
+}
+
+impl std::fmt::Display for QueryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Failed to deserialize the query string into the target schema:\n")?;
+        for e in self.errors.iter() {
+            writeln!(f, "- {}", e)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for QueryError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.errors)
+    }
+}
+
+/// Rejection used for [`Query`].
+///
+/// Contains one variant for each way the [`Query`] extractor
+/// can fail.
+///
+/// All error responses follow the problem details specification,
+/// as outlined in [RFC 9457](https://www.rfc-editor.org/rfc/rfc9457.html).
+#[derive(Debug)]
+#[non_exhaustive]
+#[cfg_attr(docsrs, doc(cfg(feature = "query")))]
+pub enum QueryRejection {
+    #[allow(missing_docs)]
+    QueryError(QueryError),
+}
+
+impl IntoResponse for QueryRejection {
+    fn into_response(self) -> Response {
+        match self {
+            Self::QueryError(inner) => inner.into_response(),
+        }
+    }
+}
+
+impl From<QueryError> for QueryRejection {
+    fn from(inner: QueryError) -> Self {
+        Self::QueryError(inner)
+    }
+}
+
+impl std::fmt::Display for QueryRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::QueryError(inner) => write!(f, "{inner}"),
+        }
+    }
+}
+
+impl std::error::Error for QueryRejection {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::QueryError(inner) => inner.source(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils;
+    use axum_core::extract::Request;
+
+    #[derive(Debug, PartialEq, eserde::Deserialize)]
+    struct Search {
+        filter: Filter,
+        #[serde(default)]
+        tag: Vec<String>,
+    }
+
+    #[derive(Debug, PartialEq, eserde::Deserialize)]
+    struct Filter {
+        status: String,
+        price: Option<Price>,
+    }
+
+    #[derive(Debug, PartialEq, eserde::Deserialize)]
+    struct Price {
+        min: u32,
+        max: Option<u32>,
+    }
+
+    async fn query(uri: &str) -> Result<Query<Search>, QueryRejection> {
+        let (mut parts, _) = Request::builder().uri(uri).body(()).unwrap().into_parts();
+        Query::<Search>::from_request_parts(&mut parts, &()).await
+    }
+
+    #[tokio::test]
+    async fn test_nested_parameters() {
+        let Query(search) = query("/?filter[status]=open&filter[price][min]=10&tag[]=a&tag[]=b")
+            .await
+            .unwrap();
+        assert_eq!(
+            search,
+            Search {
+                filter: Filter {
+                    status: "open".into(),
+                    price: Some(Price { min: 10, max: None }),
+                },
+                tag: vec!["a".into(), "b".into()],
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_nested_parameters_error() {
+        let rejection = query("/?filter[price][min]=cheap&tag=a&tag[x]=b")
+            .await
+            .unwrap_err();
+
+        let (status, body) = test_utils::problem(rejection).await;
+        assert_eq!(status, 400);
+        assert_eq!(body["type"], "invalid_request");
+        let errors = body["errors"].as_array().unwrap();
+        assert_eq!(errors[0]["source"], "query");
+        assert_eq!(errors[0]["parameter"], "filter[price][min]");
+        assert_eq!(errors[0]["code"], "invalid_type");
+        assert_eq!(errors[1]["parameter"], "filter");
+        assert_eq!(errors[1]["code"], "missing_field");
+        assert_eq!(errors[2]["parameter"], "tag");
+        assert_eq!(errors[2]["code"], "duplicate_field");
+    }
+
+    #[derive(Debug, eserde::Deserialize)]
+    struct Flags {
+        active: bool,
+    }
+
+    async fn flags(uri: &str) -> Result<Query<Flags>, QueryRejection> {
+        let (mut parts, _) = Request::builder()
+            .uri(uri)
+            .extension(Config::new().flexible_query_booleans(true))
+            .body(())
+            .unwrap()
+            .into_parts();
+        Query::<Flags>::from_request_parts(&mut parts, &()).await
+    }
+
+    #[tokio::test]
+    async fn test_flexible_booleans() {
+        let Query(flags) = flags("/?active=1").await.unwrap();
+        assert!(flags.active);
+    }
+
+    #[tokio::test]
+    async fn test_presence_only_flag() {
+        let Query(flags) = flags("/?active").await.unwrap();
+        assert!(flags.active);
+    }
+
+    #[tokio::test]
+    async fn test_unrecognized_boolean() {
+        let rejection = flags("/?active=maybe").await.unwrap_err();
+
+        let (status, body) = test_utils::problem(rejection).await;
+        assert_eq!(status, 400);
+        let errors = body["errors"].as_array().unwrap();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0]["source"], "query");
+        assert_eq!(errors[0]["parameter"], "active");
+        assert_eq!(errors[0]["code"], "invalid_type");
+    }
+}
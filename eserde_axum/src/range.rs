@@ -0,0 +1,500 @@
+//! Extract and validate the `Range` header of a request.
+//!
+//! See [`RangeHeader`] for more details.
+use std::ops::RangeInclusive;
+
+use axum_core::extract::{FromRequestParts, OptionalFromRequestParts};
+use axum_core::response::{IntoResponse, Response};
+use http::header::{CONTENT_RANGE, RANGE};
+use http::request::Parts;
+use http::{HeaderValue, StatusCode};
+
+use crate::config::Config;
+use crate::details::{InvalidRequest, ProblemDetails, Source, ValidationError, ValidationErrors};
+
+#[doc(hidden)]
+macro_rules! __log_rejection {
+    (
+        rejection_type = $ty:ident,
+        status = $status:expr,
+    ) => {
+        {
+            tracing::event!(
+                target: "eserde_axum::range::rejection",
+                tracing::Level::TRACE,
+                status = $status.as_u16(),
+                rejection_type = ::std::any::type_name::<$ty>(),
+                "rejecting request",
+            );
+        }
+    };
+}
+
+/// Extractor for the byte ranges requested via the `Range` header.
+///
+/// The request will be rejected (and a [`RangeRejection`] will be returned) if
+/// the header is missing or if it isn't a syntactically valid `bytes` range set,
+/// as defined in [RFC 9110](https://www.rfc-editor.org/rfc/rfc9110#name-range).
+/// Use `Option<RangeHeader>` if the header is optional—as is usually the case.
+///
+/// Whether the ranges can be satisfied depends on the size of the resource,
+/// which is only known by the handler: use [`RangeHeader::satisfiable`] to
+/// resolve them. It fails with a `416 Range Not Satisfiable` problem if none of the
+/// requested ranges overlaps with the resource.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{routing::get, Router};
+/// use eserde_axum::range::{RangeHeader, RangeNotSatisfiable};
+///
+/// async fn download(range: Option<RangeHeader>) -> Result<Vec<u8>, RangeNotSatisfiable> {
+///     let content = b"Hello, world!".to_vec();
+///     let Some(range) = range else {
+///         return Ok(content);
+///     };
+///     // Only serve the first requested range, for the sake of simplicity.
+///     let first = range.satisfiable(content.len() as u64)?.remove(0);
+///     Ok(content[*first.start() as usize..=*first.end() as usize].to_vec())
+/// }
+///
+/// let app = Router::new().route("/download", get(download));
+/// # let _: Router = app;
+/// ```
+#[derive(Debug, Clone)]
+pub struct RangeHeader {
+    ranges: Vec<ByteRange>,
+    config: Config,
+}
+
+/// A single range within a `Range` header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ByteRange {
+    /// `bytes=<start>-<end>`, both ends included.
+    Bounded {
+        /// The position of the first byte.
+        start: u64,
+        /// The position of the last byte.
+        end: u64,
+    },
+    /// `bytes=<start>-`, up until the end of the resource.
+    From {
+        /// The position of the first byte.
+        start: u64,
+    },
+    /// `bytes=-<length>`, the last `length` bytes of the resource.
+    Suffix {
+        /// The number of bytes.
+        length: u64,
+    },
+}
+
+impl ByteRange {
+    /// Resolve the range against a resource of the given size,
+    /// returning the positions of the first and the last byte.
+    ///
+    /// Returns `None` if the range doesn't overlap with the resource.
+    fn resolve(self, size: u64) -> Option<RangeInclusive<u64>> {
+        let last = size.checked_sub(1)?;
+        match self {
+            Self::Bounded { start, end } if start <= last => Some(start..=end.min(last)),
+            Self::From { start } if start <= last => Some(start..=last),
+            Self::Suffix { length } if length > 0 => Some(size - length.min(size)..=last),
+            _ => None,
+        }
+    }
+}
+
+impl RangeHeader {
+    /// The ranges requested by the client, in the order they were specified.
+    pub fn ranges(&self) -> &[ByteRange] {
+        &self.ranges
+    }
+
+    /// Resolve the requested ranges against a resource of the given size,
+    /// discarding the ones that don't overlap with it.
+    ///
+    /// Each range is returned as the positions of its first and last byte.
+    /// Fails if none of the requested ranges can be satisfied.
+    pub fn satisfiable(&self, size: u64) -> Result<Vec<RangeInclusive<u64>>, RangeNotSatisfiable> {
+        let ranges: Vec<_> = self
+            .ranges
+            .iter()
+            .filter_map(|range| range.resolve(size))
+            .collect();
+        if ranges.is_empty() {
+            Err(RangeNotSatisfiable {
+                size,
+                config: self.config.clone(),
+            })
+        } else {
+            Ok(ranges)
+        }
+    }
+
+    /// Parse the value of a `Range` header.
+    fn parse(value: &str) -> Option<Vec<ByteRange>> {
+        let (unit, set) = value.trim().split_once('=')?;
+        if !unit.eq_ignore_ascii_case("bytes") {
+            return None;
+        }
+        set.split(',')
+            .map(str::trim)
+            // Empty list elements are allowed by the grammar.
+            .filter(|range| !range.is_empty())
+            .map(|range| {
+                let (start, end) = range.split_once('-')?;
+                let parse = |s: &str| {
+                    if s.bytes().all(|b| b.is_ascii_digit()) {
+                        s.parse::<u64>().ok()
+                    } else {
+                        None
+                    }
+                };
+                match (start, end) {
+                    ("", length) => Some(ByteRange::Suffix {
+                        length: parse(length)?,
+                    }),
+                    (start, "") => Some(ByteRange::From {
+                        start: parse(start)?,
+                    }),
+                    (start, end) => {
+                        let (start, end) = (parse(start)?, parse(end)?);
+                        (start <= end).then_some(ByteRange::Bounded { start, end })
+                    }
+                }
+            })
+            .collect::<Option<Vec<_>>>()
+            .filter(|ranges| !ranges.is_empty())
+    }
+}
+
+impl<S> FromRequestParts<S> for RangeHeader
+where
+    S: Send + Sync,
+{
+    type Rejection = RangeRejection;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        match <Self as OptionalFromRequestParts<S>>::from_request_parts(parts, state).await? {
+            Some(range) => Ok(range),
+            None => Err(MissingRange {
+                config: Config::from_request(parts),
+            }
+            .into()),
+        }
+    }
+}
+
+impl<S> OptionalFromRequestParts<S> for RangeHeader
+where
+    S: Send + Sync,
+{
+    type Rejection = RangeRejection;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        _state: &S,
+    ) -> Result<Option<Self>, Self::Rejection> {
+        let Some(value) = parts.headers.get(RANGE) else {
+            return Ok(None);
+        };
+        let config = Config::from_request(parts);
+        let Some(ranges) = value.to_str().ok().and_then(Self::parse) else {
+            return Err(InvalidRange {
+                actual: String::from_utf8_lossy(value.as_bytes()).into_owned(),
+                config,
+            }
+            .into());
+        };
+        Ok(Some(Self { ranges, config }))
+    }
+}
+
+fn range_error(detail: String, code: &'static str) -> ValidationError {
+    ValidationError::new(
+        Source::Header {
+            name: RANGE.as_str().into(),
+        },
+        code,
+        detail,
+    )
+}
+
+#[derive(Debug)]
+#[non_exhaustive]
+/// Rejection type for [`RangeHeader`] used if the `Range` header is missing.
+pub struct MissingRange {
+    pub(crate) config: Config,
+}
+
+impl IntoResponse for MissingRange {
+    fn into_response(self) -> Response {
+        let error = range_error(
+            "Expected request with a `Range` header, but none was found".into(),
+            "missing_range",
+        );
+        let response = InvalidRequest::new(ValidationErrors {
+            errors: vec![error],
+            by_pointer: false,
+        })
+        .configure(&self.config);
+        __log_rejection!(
+            rejection_type = MissingRange,
+            status = InvalidRequest::status(),
+        );
+        response.into_response()
+    }
+}
+
+impl std::fmt::Display for MissingRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Expected request with a `Range` header")
+    }
+}
+
+impl std::error::Error for MissingRange {}
+
+#[derive(Debug)]
+#[non_exhaustive]
+/// Rejection type for [`RangeHeader`] used if the `Range` header
+/// is not a valid `bytes` range set.
+pub struct InvalidRange {
+    pub(crate) actual: String,
+    pub(crate) config: Config,
+}
+
+impl IntoResponse for InvalidRange {
+    fn into_response(self) -> Response {
+        let error = range_error(
+            format!(
+                "Expected a `Range` header with one or more byte ranges (e.g. `bytes=0-499`), but found `{}`",
+                self.actual
+            ),
+            "invalid_range",
+        );
+        let response = InvalidRequest::new(ValidationErrors {
+            errors: vec![error],
+            by_pointer: false,
+        })
+        .configure(&self.config);
+        __log_rejection!(
+            rejection_type = InvalidRange,
+            status = InvalidRequest::status(),
+        );
+        response.into_response()
+    }
+}
+
+impl std::fmt::Display for InvalidRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Expected a `Range` header with one or more byte ranges, but found `{}`",
+            self.actual
+        )
+    }
+}
+
+impl std::error::Error for InvalidRange {}
+
+/// Rejection used for [`RangeHeader`].
+///
+/// Contains one variant for each way the [`RangeHeader`] extractor
+/// can fail.
+///
+/// All error responses follow the problem details specification,
+/// as outlined in [RFC 9457](https://www.rfc-editor.org/rfc/rfc9457.html).
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum RangeRejection {
+    #[allow(missing_docs)]
+    MissingRange(MissingRange),
+    #[allow(missing_docs)]
+    InvalidRange(InvalidRange),
+}
+
+impl IntoResponse for RangeRejection {
+    fn into_response(self) -> Response {
+        match self {
+            Self::MissingRange(inner) => inner.into_response(),
+            Self::InvalidRange(inner) => inner.into_response(),
+        }
+    }
+}
+
+impl From<MissingRange> for RangeRejection {
+    fn from(inner: MissingRange) -> Self {
+        Self::MissingRange(inner)
+    }
+}
+impl From<InvalidRange> for RangeRejection {
+    fn from(inner: InvalidRange) -> Self {
+        Self::InvalidRange(inner)
+    }
+}
+impl std::fmt::Display for RangeRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingRange(inner) => write!(f, "{inner}"),
+            Self::InvalidRange(inner) => write!(f, "{inner}"),
+        }
+    }
+}
+impl std::error::Error for RangeRejection {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::MissingRange(inner) => inner.source(),
+            Self::InvalidRange(inner) => inner.source(),
+        }
+    }
+}
+
+/// None of the ranges requested via the `Range` header overlaps with the resource.
+///
+/// Returned by [`RangeHeader::satisfiable`].
+/// When converted into a response, it becomes a `416 Range Not Satisfiable` problem,
+/// with a `Content-Range` header specifying the size of the resource.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct RangeNotSatisfiable {
+    pub(crate) size: u64,
+    pub(crate) config: Config,
+}
+
+impl RangeNotSatisfiable {
+    /// The size of the resource, in bytes.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+}
+
+impl IntoResponse for RangeNotSatisfiable {
+    fn into_response(self) -> Response {
+        let error = range_error(
+            format!(
+                "None of the requested ranges overlaps with the resource, which is {} bytes long",
+                self.size
+            ),
+            "range_not_satisfiable",
+        );
+        let status = StatusCode::RANGE_NOT_SATISFIABLE;
+        let problem = ProblemDetails {
+            extensions: Some(
+                ValidationErrors {
+                    errors: vec![error],
+                    by_pointer: false,
+                }
+                .configure(&self.config),
+            ),
+            ..ProblemDetails::new(
+                "range_not_satisfiable",
+                status,
+                "The requested range is not satisfiable",
+                format!(
+                    "The requested ranges must overlap with the resource, which is {} bytes long",
+                    self.size
+                ),
+            )
+        }
+        .configure(&self.config);
+        __log_rejection!(rejection_type = RangeNotSatisfiable, status = status,);
+
+        let mut response = problem.into_response();
+        let content_range = format!("bytes */{}", self.size);
+        if let Ok(content_range) = HeaderValue::try_from(content_range) {
+            response.headers_mut().insert(CONTENT_RANGE, content_range);
+        }
+        response
+    }
+}
+
+impl std::fmt::Display for RangeNotSatisfiable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "None of the requested ranges overlaps with the resource, which is {} bytes long",
+            self.size
+        )
+    }
+}
+
+impl std::error::Error for RangeNotSatisfiable {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils;
+
+    async fn extract(value: &'static str) -> Result<RangeHeader, RangeRejection> {
+        let (mut parts, _) = http::Request::builder()
+            .header(RANGE, value)
+            .body(())
+            .unwrap()
+            .into_parts();
+        <RangeHeader as FromRequestParts<()>>::from_request_parts(&mut parts, &()).await
+    }
+
+    #[tokio::test]
+    async fn test_parse() {
+        let range = extract("bytes=0-499, 1000-, -200").await.unwrap();
+        assert_eq!(
+            range.ranges(),
+            [
+                ByteRange::Bounded { start: 0, end: 499 },
+                ByteRange::From { start: 1000 },
+                ByteRange::Suffix { length: 200 },
+            ]
+        );
+        assert_eq!(
+            range.satisfiable(1200).unwrap(),
+            [0..=499, 1000..=1199, 1000..=1199]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_malformed() {
+        for value in ["bytes=", "bytes=10-5", "bytes=a-b", "items=0-1", "0-499"] {
+            let rejection = extract(value).await.unwrap_err();
+            assert!(
+                matches!(rejection, RangeRejection::InvalidRange(_)),
+                "{value}"
+            );
+
+            let (status, body) = test_utils::problem(rejection).await;
+            assert_eq!(status, 400);
+            assert_eq!(body["errors"][0]["source"], "header");
+            assert_eq!(body["errors"][0]["name"], "range");
+            assert_eq!(body["errors"][0]["code"], "invalid_range");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_missing() {
+        let (mut parts, _) = http::Request::new(()).into_parts();
+        let range =
+            <RangeHeader as OptionalFromRequestParts<()>>::from_request_parts(&mut parts, &())
+                .await
+                .unwrap();
+        assert!(range.is_none());
+
+        let rejection = <RangeHeader as FromRequestParts<()>>::from_request_parts(&mut parts, &())
+            .await
+            .unwrap_err();
+        assert!(matches!(rejection, RangeRejection::MissingRange(_)));
+    }
+
+    #[tokio::test]
+    async fn test_unsatisfiable() {
+        let range = extract("bytes=500-999").await.unwrap();
+        let error = range.satisfiable(100).unwrap_err();
+        assert_eq!(error.size(), 100);
+
+        let response = error.into_response();
+        assert_eq!(response.headers()[CONTENT_RANGE], "bytes */100");
+        let (status, body) = test_utils::problem(response).await;
+        assert_eq!(status, StatusCode::RANGE_NOT_SATISFIABLE);
+        assert_eq!(body["type"], "range_not_satisfiable");
+        assert_eq!(body["errors"][0]["code"], "range_not_satisfiable");
+    }
+}
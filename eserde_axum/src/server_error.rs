@@ -0,0 +1,234 @@
+//! Turn errors returned by request handlers into a generic 500 problem response.
+//!
+//! Handlers return [`ServerError`] (usually via `?` on an [`anyhow::Result`]) and
+//! the [`ServerErrorLayer`] takes care of logging the full error chain and
+//! of replacing the response body with a standardized problem, without leaking
+//! any internal detail to the client.
+//!
+//! ```rust,no_run
+//! use axum::{routing::get, Router};
+//! use eserde_axum::{ServerError, ServerErrorLayer};
+//!
+//! async fn handler() -> Result<String, ServerError> {
+//!     let config = std::fs::read_to_string("config.toml")?;
+//!     Ok(config)
+//! }
+//!
+//! let app = Router::new()
+//!     .route("/", get(handler))
+//!     .layer(ServerErrorLayer::new());
+//! # let _: Router = app;
+//! ```
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use axum_core::response::{IntoResponse, Response};
+use http::{Request, StatusCode};
+
+use crate::config::Config;
+use crate::details::INTERNAL_SERVER_ERROR;
+
+/// An error returned by a request handler.
+///
+/// It can be built from any error that can be converted into an [`anyhow::Error`],
+/// so you can use `?` in handlers returning `Result<_, ServerError>`.
+///
+/// When converted into a response, it becomes a 500 problem with a generic detail.
+/// The underlying error is stored in the response extensions, where [`ServerErrorLayer`]
+/// picks it up to log it.
+#[derive(Debug)]
+#[cfg_attr(docsrs, doc(cfg(feature = "anyhow")))]
+pub struct ServerError(anyhow::Error);
+
+impl ServerError {
+    /// Borrow the underlying error.
+    pub fn inner(&self) -> &anyhow::Error {
+        &self.0
+    }
+}
+
+impl<E> From<E> for ServerError
+where
+    E: Into<anyhow::Error>,
+{
+    fn from(error: E) -> Self {
+        Self(error.into())
+    }
+}
+
+impl IntoResponse for ServerError {
+    fn into_response(self) -> Response {
+        let mut response = INTERNAL_SERVER_ERROR.into_response();
+        response
+            .extensions_mut()
+            .insert(HandlerError(Arc::new(self.0)));
+        response
+    }
+}
+
+/// The response extension used to carry the error returned by a handler.
+#[derive(Clone)]
+struct HandlerError(Arc<anyhow::Error>);
+
+/// A [`Layer`](tower_layer::Layer) that converts the responses of failed handlers
+/// into a standardized 500 problem response.
+///
+/// Check out [`ServerError`] for more details.
+///
+/// The problem honors the [`Config`](crate::Config) found in the request extensions,
+/// e.g. its [`type_prefix`](crate::Config::type_prefix), so make sure to add it
+/// *outside* of this layer.
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(docsrs, doc(cfg(feature = "anyhow")))]
+pub struct ServerErrorLayer;
+
+impl ServerErrorLayer {
+    /// Create a new `ServerErrorLayer`.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl<S> tower_layer::Layer<S> for ServerErrorLayer {
+    type Service = ServerErrorService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ServerErrorService { inner }
+    }
+}
+
+/// The [`Service`](tower_service::Service) returned by [`ServerErrorLayer`].
+#[derive(Debug, Clone)]
+#[cfg_attr(docsrs, doc(cfg(feature = "anyhow")))]
+pub struct ServerErrorService<S> {
+    inner: S,
+}
+
+impl<S, B> tower_service::Service<Request<B>> for ServerErrorService<S>
+where
+    S: tower_service::Service<Request<B>, Response = Response>,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = ResponseFuture<S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        let config = Config::from_request(&request);
+        ResponseFuture {
+            inner: self.inner.call(request),
+            config,
+        }
+    }
+}
+
+pin_project_lite::pin_project! {
+    /// The response future of [`ServerErrorService`].
+    #[cfg_attr(docsrs, doc(cfg(feature = "anyhow")))]
+    pub struct ResponseFuture<F> {
+        #[pin]
+        inner: F,
+        config: Config,
+    }
+}
+
+impl<F, E> Future for ResponseFuture<F>
+where
+    F: Future<Output = Result<Response, E>>,
+{
+    type Output = Result<Response, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let response = std::task::ready!(this.inner.poll(cx))?;
+        let Some(HandlerError(error)) = response.extensions().get::<HandlerError>().cloned() else {
+            return Poll::Ready(Ok(response));
+        };
+        tracing::event!(
+            target: "eserde_axum::server_error",
+            tracing::Level::ERROR,
+            status = StatusCode::INTERNAL_SERVER_ERROR.as_u16(),
+            error.message = %error,
+            error.chain = ?error.chain().map(ToString::to_string).collect::<Vec<_>>(),
+            "request handler failed",
+        );
+        let mut problem = this.config.internal_server_error_response();
+        *problem.extensions_mut() = response.into_parts().0.extensions;
+        Poll::Ready(Ok(problem))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{routing::get, Router};
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::test_utils;
+
+    async fn handler() -> Result<String, ServerError> {
+        let error = anyhow::anyhow!("connection refused").context("failed to query the database");
+        Err(error.into())
+    }
+
+    #[tokio::test]
+    async fn test_handler_error() {
+        let app = Router::new()
+            .route("/", get(handler))
+            .layer(ServerErrorLayer::new());
+        let request = Request::builder()
+            .uri("/")
+            .body(axum::body::Body::empty())
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(
+            response.headers()[http::header::CONTENT_TYPE],
+            "application/problem+json"
+        );
+
+        let (status, body) = test_utils::problem(response).await;
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(body["type"], "internal_server_error");
+        let detail = body["detail"].as_str().unwrap();
+        assert!(!detail.contains("database"), "{detail}");
+        assert!(!detail.contains("connection refused"), "{detail}");
+    }
+
+    #[cfg(feature = "json")]
+    #[derive(Debug, eserde::Deserialize)]
+    #[allow(dead_code)]
+    struct User {
+        name: String,
+    }
+
+    #[cfg(feature = "json")]
+    async fn create_user(_user: crate::Json<User>) -> Result<String, ServerError> {
+        handler().await
+    }
+
+    #[cfg(feature = "json")]
+    #[tokio::test]
+    async fn test_type_prefix() {
+        let app = Router::new()
+            .route("/", axum::routing::post(create_user))
+            .layer(ServerErrorLayer::new())
+            .layer(axum::Extension(Config::new().type_prefix("myapi:")));
+
+        let request = test_utils::json_request(r#"{"name": 1}"#).body();
+        let (status, body) = test_utils::problem(app.clone().oneshot(request).await.unwrap()).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["type"], "myapi:invalid_request");
+        // Error codes aren't problem types, they're left untouched.
+        assert_eq!(body["errors"][0]["code"], "invalid_type");
+
+        let request = test_utils::json_request(r#"{"name": "Alice"}"#).body();
+        let (status, body) = test_utils::problem(app.oneshot(request).await.unwrap()).await;
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(body["type"], "myapi:internal_server_error");
+    }
+}
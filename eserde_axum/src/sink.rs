@@ -0,0 +1,461 @@
+//! Accumulate validation errors from your own extractors.
+//!
+//! See [`ErrorSink`] for more details.
+use std::borrow::Cow;
+use std::convert::Infallible;
+
+use axum_core::extract::FromRequestParts;
+use axum_core::response::IntoResponse;
+use eserde::DeserializationErrors;
+use http::request::Parts;
+use http::HeaderValue;
+
+use crate::config::Config;
+use crate::details::{CustomSource, InvalidRequest, Source, ValidationError, ValidationErrors};
+
+/// An accumulator of validation errors, rendered as a single
+/// `invalid_request` problem—the same one returned by the extractors of this crate.
+///
+/// Use it to run your own validation logic in a custom extractor, while
+/// reporting its failures alongside the deserialization errors detected by `eserde`.
+/// Start from an empty sink (either via [`ErrorSink::new`] or by extracting it from
+/// the request, to honor the [`Config`] installed for the route), push errors into it
+/// and turn it into a rejection with [`ErrorSink::into_result`].
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::extract::{FromRequest, Request};
+/// use axum::response::{IntoResponse, Response};
+/// use eserde_axum::{ErrorSink, Json, JsonRejection};
+///
+/// #[derive(eserde::Deserialize)]
+/// struct Signup {
+///     password: String,
+///     confirmation: String,
+/// }
+///
+/// struct ValidSignup(Signup);
+///
+/// impl<S: Send + Sync> FromRequest<S> for ValidSignup {
+///     type Rejection = Response;
+///
+///     async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+///         let (parts, body) = req.into_parts();
+///         let mut sink = ErrorSink::from_parts(&parts);
+///         let req = Request::from_parts(parts, body);
+///         match Json::<Signup>::from_request(req, state).await {
+///             Ok(Json(signup)) => {
+///                 if signup.password != signup.confirmation {
+///                     sink.push_body_error(
+///                         "/confirmation",
+///                         "password_mismatch",
+///                         "The confirmation doesn't match the password",
+///                     );
+///                 }
+///                 sink.into_result().map_err(IntoResponse::into_response)?;
+///                 Ok(ValidSignup(signup))
+///             }
+///             Err(JsonRejection::JsonError(error)) => {
+///                 sink.extend_body_errors(error.errors());
+///                 Err(sink.into_response())
+///             }
+///             // Other rejections (e.g. a missing `Content-Type`) have nothing
+///             // to do with validation: return them as they are.
+///             Err(rejection) => Err(rejection.into_response()),
+///         }
+///     }
+/// }
+/// ```
+#[derive(Debug, Default)]
+#[must_use]
+pub struct ErrorSink {
+    errors: Vec<ValidationError>,
+    content_location: Option<HeaderValue>,
+    config: Config,
+}
+
+impl ErrorSink {
+    /// Create an empty sink, with the default [`Config`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create an empty sink, honoring the [`Config`] stored in the request extensions.
+    pub fn from_parts(parts: &Parts) -> Self {
+        Self {
+            errors: Vec::new(),
+            content_location: None,
+            config: Config::from_request(parts),
+        }
+    }
+
+    /// Report a problem with the request body.
+    ///
+    /// `pointer` is a [JSON pointer](https://www.rfc-editor.org/info/rfc6901) targeted
+    /// at the problematic property (e.g. `/user/email`), while `code` is a
+    /// machine-readable identifier for the kind of problem (e.g. `email_taken`).
+    pub fn push_body_error(
+        &mut self,
+        pointer: impl Into<String>,
+        code: impl Into<Cow<'static, str>>,
+        detail: impl Into<String>,
+    ) -> &mut Self {
+        self.push(
+            Source::Body {
+                pointer: Some(pointer.into()),
+                key: false,
+            },
+            code.into(),
+            detail.into(),
+        )
+    }
+
+    /// Report a problem with a request header.
+    pub fn push_header_error(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+        code: impl Into<Cow<'static, str>>,
+        detail: impl Into<String>,
+    ) -> &mut Self {
+        self.push(
+            Source::Header { name: name.into() },
+            code.into(),
+            detail.into(),
+        )
+    }
+
+    /// Report a problem with a part of the request this crate doesn't know about,
+    /// e.g. a GraphQL variable or a gRPC field path.
+    ///
+    /// See [`ErrorSource`] for more details.
+    pub fn push_error(
+        &mut self,
+        source: impl ErrorSource,
+        code: impl Into<Cow<'static, str>>,
+        detail: impl Into<String>,
+    ) -> &mut Self {
+        self.push(
+            Source::Custom(CustomSource(Box::new(source))),
+            code.into(),
+            detail.into(),
+        )
+    }
+
+    /// Report the errors encountered by `eserde` while deserializing the request body.
+    pub fn extend_body_errors(&mut self, errors: &DeserializationErrors) -> &mut Self {
+        self.errors.extend(errors.iter().map(ValidationError::body));
+        self
+    }
+
+    /// Report the outcome of custom validation logic, as a map from dotted field names
+    /// (e.g. `address.city`) to messages—e.g. a `HashMap<String, String>`.
+    ///
+    /// Each field is reported as a problem with the request body, at the JSON pointer
+    /// matching its name (e.g. `/address/city`), with the `invalid` code.
+    pub fn extend_field_messages<K, V>(
+        &mut self,
+        messages: impl IntoIterator<Item = (K, V)>,
+    ) -> &mut Self
+    where
+        K: AsRef<str>,
+        V: Into<String>,
+    {
+        self.errors
+            .extend(ValidationErrors::from_field_messages(messages).errors);
+        self
+    }
+
+    fn push(&mut self, source: Source, code: Cow<'static, str>, detail: String) -> &mut Self {
+        self.errors.push(ValidationError::new(source, code, detail));
+        self
+    }
+
+    /// Point at the canonical resource the request is about (e.g. `/users/42`),
+    /// via the `Content-Location` header of the problem response.
+    pub fn content_location(&mut self, location: HeaderValue) -> &mut Self {
+        self.content_location = Some(location);
+        self
+    }
+
+    /// The number of errors reported so far.
+    pub fn len(&self) -> usize {
+        self.errors.len()
+    }
+
+    /// Returns `true` if no errors have been reported.
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Returns `Ok(())` if no errors have been reported, the sink itself otherwise—ready
+    /// to be returned as a rejection.
+    pub fn into_result(self) -> Result<(), Self> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl<S> FromRequestParts<S> for ErrorSink
+where
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(Self::from_parts(parts))
+    }
+}
+
+impl ErrorSink {
+    /// The `invalid_request` problem listing the errors reported so far.
+    pub(crate) fn into_problem(self) -> InvalidRequest {
+        InvalidRequest::new(ValidationErrors {
+            errors: self.errors,
+            by_pointer: false,
+        })
+        .configure(&self.config)
+        .content_location(self.content_location)
+    }
+}
+
+impl IntoResponse for ErrorSink {
+    fn into_response(self) -> axum_core::response::Response {
+        let response = self.into_problem();
+        tracing::event!(
+            target: "eserde_axum::sink::rejection",
+            tracing::Level::TRACE,
+            status = InvalidRequest::status().as_u16(),
+            rejection_type = ::std::any::type_name::<Self>(),
+            "rejecting request",
+        );
+        response.into_response()
+    }
+}
+
+impl std::fmt::Display for ErrorSink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("The request failed validation:\n")?;
+        for e in &self.errors {
+            writeln!(f, "- {}", e.detail)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ErrorSink {}
+
+/// A custom location for the errors reported via [`ErrorSink::push_error`].
+///
+/// The errors of the `invalid_request` problem come with a `source` member
+/// (e.g. `body` or `header`) and a member locating the problem within it (e.g. `pointer`).
+/// Implement this trait to report errors from other places, without waiting for
+/// this crate to support them: the error is serialized with [`ErrorSource::name`]
+/// as its `source` member and [`ErrorSource::location`] as its `location` member.
+///
+/// # Example
+///
+/// ```rust
+/// use eserde_axum::{ErrorSink, ErrorSource};
+///
+/// /// A variable of a GraphQL operation.
+/// #[derive(Debug)]
+/// struct GraphQlVariable(String);
+///
+/// impl ErrorSource for GraphQlVariable {
+///     fn name(&self) -> &str {
+///         "graphql_variable"
+///     }
+///
+///     fn location(&self) -> &str {
+///         &self.0
+///     }
+/// }
+///
+/// let mut sink = ErrorSink::new();
+/// sink.push_error(
+///     GraphQlVariable("input.email".into()),
+///     "invalid_email",
+///     "The email is not valid",
+/// );
+/// // Renders as `{"source": "graphql_variable", "location": "input.email", ...}`.
+/// ```
+pub trait ErrorSource: std::fmt::Debug + Send + Sync + 'static {
+    /// The discriminator of the source, e.g. `graphql_variable`.
+    ///
+    /// Pick a name that doesn't clash with the sources of this crate:
+    /// `body`, `header`, `form`, `query`, `path` and `response`.
+    fn name(&self) -> &str;
+
+    /// The location of the problem within the source, e.g. the path to a GraphQL variable.
+    ///
+    /// It's also used to sort the errors, if [`Config::error_order`] asks for it.
+    fn location(&self) -> &str;
+}
+
+#[cfg(all(test, feature = "json"))]
+mod tests {
+    use axum_core::extract::{FromRequest, Request};
+
+    use super::*;
+    use crate::{test_utils, Json, JsonRejection};
+
+    #[derive(Debug, eserde::Deserialize)]
+    #[allow(dead_code)]
+    struct Signup {
+        username: String,
+        age: u8,
+    }
+
+    /// Reject usernames that are already taken, on top of the schema validation.
+    struct ValidSignup;
+
+    impl<S: Send + Sync> FromRequest<S> for ValidSignup {
+        type Rejection = ErrorSink;
+
+        async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+            let (parts, body) = req.into_parts();
+            let mut sink = ErrorSink::from_parts(&parts);
+            if parts.headers.get("x-tenant").is_none() {
+                sink.push_header_error("x-tenant", "missing_tenant", "The tenant is required");
+            }
+            let req = Request::from_parts(parts, body);
+            match Json::<Signup>::from_request(req, state).await {
+                Ok(Json(signup)) => {
+                    if signup.username == "admin" {
+                        sink.push_body_error(
+                            "/username",
+                            "username_taken",
+                            "The username is already taken",
+                        );
+                    }
+                }
+                Err(JsonRejection::JsonError(error)) => {
+                    sink.extend_body_errors(error.errors());
+                }
+                Err(e) => panic!("Unexpected rejection: {e}"),
+            }
+            sink.into_result().map(|()| ValidSignup)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_business_rule_error() {
+        let request = test_utils::json_request(r#"{"username": "admin", "age": 30}"#)
+            .header("x-tenant", "acme");
+        let rejection = ValidSignup::from_request(request.body(), &())
+            .await
+            .err()
+            .unwrap();
+
+        let (status, body) = test_utils::problem(rejection).await;
+        assert_eq!(status, 400);
+        assert_eq!(body["type"], "invalid_request");
+        assert_eq!(body["errors"].as_array().unwrap().len(), 1);
+        assert_eq!(body["errors"][0]["code"], "username_taken");
+        assert_eq!(body["errors"][0]["source"], "body");
+        assert_eq!(body["errors"][0]["pointer"], "/username");
+    }
+
+    #[tokio::test]
+    async fn test_merged_with_deserialization_errors() {
+        let request = test_utils::json_request(r#"{"username": "alice", "age": "30"}"#);
+        let rejection = ValidSignup::from_request(request.body(), &())
+            .await
+            .err()
+            .unwrap();
+
+        let (_, body) = test_utils::problem(rejection).await;
+        let codes: Vec<_> = body["errors"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|error| error["code"].as_str().unwrap())
+            .collect();
+        assert_eq!(codes, ["missing_tenant", "invalid_type"]);
+    }
+
+    #[tokio::test]
+    async fn test_no_errors() {
+        let request = test_utils::json_request(r#"{"username": "alice", "age": 30}"#)
+            .header("x-tenant", "acme");
+        assert!(ValidSignup::from_request(request.body(), &()).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_field_messages() {
+        let messages = std::collections::HashMap::from([
+            ("email".to_owned(), "The email is not valid".to_owned()),
+            ("address.city".to_owned(), "The city is required".to_owned()),
+        ]);
+        let mut sink = ErrorSink::new();
+        sink.push_header_error("x-tenant", "missing_tenant", "The tenant is required")
+            .extend_field_messages(messages);
+
+        let (status, body) = test_utils::problem(sink).await;
+        assert_eq!(status, 400);
+        assert_eq!(
+            body["errors"],
+            serde_json::json!([
+                {
+                    "detail": "The tenant is required",
+                    "code": "missing_tenant",
+                    "source": "header",
+                    "name": "x-tenant",
+                },
+                {
+                    "detail": "The city is required",
+                    "code": "invalid",
+                    "source": "body",
+                    "pointer": "/address/city",
+                },
+                {
+                    "detail": "The email is not valid",
+                    "code": "invalid",
+                    "source": "body",
+                    "pointer": "/email",
+                },
+            ])
+        );
+    }
+
+    #[derive(Debug)]
+    struct GrpcField(&'static str);
+
+    impl ErrorSource for GrpcField {
+        fn name(&self) -> &str {
+            "grpc_field"
+        }
+
+        fn location(&self) -> &str {
+            self.0
+        }
+    }
+
+    #[tokio::test]
+    async fn test_custom_source() {
+        let mut sink = ErrorSink::new();
+        sink.push_error(
+            GrpcField("user.email"),
+            "invalid_email",
+            "The email is not valid",
+        )
+        .push_body_error("/name", "invalid_type", "The name must be a string");
+
+        let (status, body) = test_utils::problem(sink).await;
+        assert_eq!(status, 400);
+        assert_eq!(
+            body["errors"][0],
+            serde_json::json!({
+                "detail": "The email is not valid",
+                "code": "invalid_email",
+                "source": "grpc_field",
+                "location": "user.email",
+            })
+        );
+        assert_eq!(body["errors"][1]["source"], "body");
+    }
+}
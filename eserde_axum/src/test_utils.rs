@@ -0,0 +1,128 @@
+//! Helpers shared by the unit tests of this crate.
+use std::borrow::Cow;
+use std::pin::Pin;
+#[cfg(feature = "stream")]
+use std::sync::atomic::{AtomicUsize, Ordering};
+#[cfg(feature = "stream")]
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use axum_core::body::Body;
+use axum_core::response::IntoResponse;
+use bytes::Bytes;
+use http::{header::CONTENT_TYPE, request::Builder, Request, StatusCode};
+use http_body::Frame;
+
+/// Start building a request carrying the given JSON body.
+///
+/// Call `.body()` on the returned value to get a [`Request`].
+pub(crate) fn json_request(body: impl Into<Cow<'static, str>>) -> RequestBuilder {
+    RequestBuilder {
+        builder: Request::builder()
+            .method("POST")
+            .uri("/")
+            .header(CONTENT_TYPE, "application/json"),
+        body: body.into(),
+    }
+}
+
+/// Start building a request carrying the given URL encoded form body.
+///
+/// Call `.body()` on the returned value to get a [`Request`].
+#[cfg(feature = "form")]
+pub(crate) fn form_request(body: impl Into<Cow<'static, str>>) -> RequestBuilder {
+    RequestBuilder {
+        builder: Request::builder()
+            .method("POST")
+            .uri("/")
+            .header(CONTENT_TYPE, "application/x-www-form-urlencoded"),
+        body: body.into(),
+    }
+}
+
+pub(crate) struct RequestBuilder {
+    builder: Builder,
+    body: Cow<'static, str>,
+}
+
+impl RequestBuilder {
+    pub(crate) fn extension<T: Clone + Send + Sync + 'static>(self, extension: T) -> Self {
+        Self {
+            builder: self.builder.extension(extension),
+            ..self
+        }
+    }
+
+    pub(crate) fn header(self, name: &'static str, value: &'static str) -> Self {
+        Self {
+            builder: self.builder.header(name, value),
+            ..self
+        }
+    }
+
+    pub(crate) fn body(self) -> Request<Body> {
+        self.builder.body(Body::from(self.body)).unwrap()
+    }
+
+    /// Like [`body`](Self::body), but the body panics if it's ever polled.
+    pub(crate) fn untouchable(self) -> Request<Body> {
+        self.builder.body(Body::new(Untouchable)).unwrap()
+    }
+}
+
+/// A body that panics if it's ever polled.
+pub(crate) struct Untouchable;
+
+impl http_body::Body for Untouchable {
+    type Data = Bytes;
+    type Error = std::convert::Infallible;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        panic!("The body shouldn't be polled");
+    }
+}
+
+/// Convert the given value into a response, returning its status and its decoded body.
+pub(crate) async fn problem(response: impl IntoResponse) -> (StatusCode, serde_json::Value) {
+    let (parts, body) = crate::details::read_problem(response).await;
+    (parts.status, body)
+}
+
+/// A body made of the given chunks, along with the number of chunks pulled so far.
+#[cfg(feature = "stream")]
+pub(crate) fn chunked_body(chunks: &[&str]) -> (Body, Arc<AtomicUsize>) {
+    let pulled = Arc::new(AtomicUsize::new(0));
+    let chunks = chunks
+        .iter()
+        .map(|chunk| Bytes::copy_from_slice(chunk.as_bytes()))
+        .collect::<Vec<_>>()
+        .into_iter();
+    let body = Body::from_stream(Chunks {
+        chunks,
+        pulled: pulled.clone(),
+    });
+    (body, pulled)
+}
+
+/// A body made of the given chunks, counting how many have been pulled.
+#[cfg(feature = "stream")]
+struct Chunks {
+    chunks: std::vec::IntoIter<Bytes>,
+    pulled: Arc<AtomicUsize>,
+}
+
+#[cfg(feature = "stream")]
+impl futures_core::Stream for Chunks {
+    type Item = Result<Bytes, std::convert::Infallible>;
+
+    fn poll_next(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let chunk = self.chunks.next();
+        if chunk.is_some() {
+            self.pulled.fetch_add(1, Ordering::SeqCst);
+        }
+        Poll::Ready(chunk.map(Ok))
+    }
+}
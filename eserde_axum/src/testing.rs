@@ -0,0 +1,157 @@
+//! Inspect problem responses in unit tests, without running a server.
+//!
+//! See [`problem`] for more details.
+use axum_core::response::IntoResponse;
+use http::header::CONTENT_TYPE;
+use http::{HeaderMap, StatusCode};
+
+use crate::details::{read_problem, APPLICATION_PROBLEM_JSON};
+
+/// A problem response, parsed back for the sake of assertions.
+///
+/// The standard members of [RFC 9457](https://www.rfc-editor.org/rfc/rfc9457.html)
+/// get their own field, while the extension members (e.g. `errors`) are kept as JSON values.
+#[derive(Debug, Clone)]
+#[cfg_attr(docsrs, doc(cfg(feature = "testing")))]
+#[non_exhaustive]
+pub struct ParsedProblem {
+    /// The status code of the response.
+    pub status: StatusCode,
+    /// The headers of the response.
+    pub headers: HeaderMap,
+    /// The `type` member, e.g. `invalid_request`.
+    pub type_: String,
+    /// The `title` member.
+    pub title: String,
+    /// The `detail` member.
+    pub detail: String,
+    /// The extension members, i.e. all members but `type`, `status`, `title` and `detail`.
+    pub extensions: serde_json::Map<String, serde_json::Value>,
+}
+
+impl ParsedProblem {
+    /// The extension member called `name`, if any—e.g. `errors`.
+    pub fn extension(&self, name: &str) -> Option<&serde_json::Value> {
+        self.extensions.get(name)
+    }
+}
+
+/// Collect the body of a problem response and parse it into a [`ParsedProblem`].
+///
+/// Call your handlers (or extractors) directly, then use it to assert on their
+/// error responses.
+/// Problems served as CBOR, if the `cbor` feature is enabled and the request asked for it,
+/// are parsed as well.
+///
+/// # Panics
+///
+/// It panics if the response isn't a problem, i.e. if it doesn't have a
+/// `Content-Type: application/problem+json` (or `application/problem+cbor`) header
+/// or if its body isn't an object with the standard members—failing the test at hand.
+///
+/// # Example
+///
+/// ```rust
+/// use eserde_axum::{testing, Json};
+///
+/// #[derive(Debug, eserde::Deserialize)]
+/// struct CreateUser {
+///     name: String,
+/// }
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let rejection = Json::<CreateUser>::from_bytes(br#"{"name": 1}"#).unwrap_err();
+/// let problem = testing::problem(rejection).await;
+/// assert_eq!(problem.status, 400);
+/// assert_eq!(problem.type_, "invalid_request");
+/// assert_eq!(problem.extension("errors").unwrap()[0]["pointer"], "/name");
+/// # }
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "testing")))]
+pub async fn problem(response: impl IntoResponse) -> ParsedProblem {
+    let response = response.into_response();
+    let content_type = response.headers().get(CONTENT_TYPE);
+    let is_problem = content_type == Some(&APPLICATION_PROBLEM_JSON);
+    #[cfg(feature = "cbor")]
+    let is_problem = is_problem || content_type == Some(&crate::details::APPLICATION_PROBLEM_CBOR);
+    assert!(
+        is_problem,
+        "Expected a problem response, with `Content-Type: application/problem+json`, found {content_type:?}"
+    );
+    let (parts, body) = read_problem(response).await;
+    let serde_json::Value::Object(mut members) = body else {
+        panic!("The body of the problem response is not an object: {body}");
+    };
+    let mut take = |name: &str| match members.remove(name) {
+        Some(serde_json::Value::String(value)) => value,
+        other => panic!("Expected a string `{name}` member in the problem, found {other:?}"),
+    };
+    let type_ = take("type");
+    let title = take("title");
+    let detail = take("detail");
+    members.remove("status");
+    ParsedProblem {
+        status: parts.status,
+        headers: parts.headers,
+        type_,
+        title,
+        detail,
+        extensions: members,
+    }
+}
+
+#[cfg(all(test, feature = "json"))]
+mod tests {
+    use super::*;
+    use crate::{Config, Json, NotFound};
+
+    #[derive(Debug, eserde::Deserialize)]
+    #[allow(dead_code)]
+    struct User {
+        name: String,
+        age: u8,
+    }
+
+    #[tokio::test]
+    async fn test_invalid_request() {
+        let rejection = Json::<User>::from_bytes(br#"{"name": 1}"#).unwrap_err();
+        let problem = problem(rejection).await;
+        assert_eq!(problem.status, StatusCode::BAD_REQUEST);
+        assert_eq!(problem.type_, "invalid_request");
+        assert_eq!(problem.title, "The request is invalid");
+        let errors = problem.extension("errors").unwrap().as_array().unwrap();
+        assert_eq!(errors[0]["pointer"], "/name");
+        assert_eq!(errors[1]["code"], "missing_field");
+        assert!(problem.extension("status").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_status_as_string() {
+        let problem =
+            problem(NotFound::new("user", 42).configure(&Config::new().status_as_string(true)))
+                .await;
+        assert_eq!(problem.status, StatusCode::NOT_FOUND);
+        assert_eq!(problem.extension("id").unwrap(), "42");
+    }
+
+    #[cfg(feature = "cbor")]
+    #[tokio::test]
+    async fn test_cbor() {
+        let request = http::Request::builder()
+            .header(http::header::ACCEPT, "application/problem+cbor")
+            .body(())
+            .unwrap();
+        let problem =
+            problem(NotFound::new("user", 42).configure(&Config::from_request(&request))).await;
+        assert_eq!(problem.status, StatusCode::NOT_FOUND);
+        assert_eq!(problem.type_, "not_found");
+        assert_eq!(problem.extension("id").unwrap(), "42");
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "Expected a problem response")]
+    async fn test_not_a_problem() {
+        problem(StatusCode::NO_CONTENT).await;
+    }
+}
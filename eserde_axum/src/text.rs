@@ -0,0 +1,327 @@
+//! Extract a single value from a plain text request body.
+//!
+//! See [`Text`] for more details.
+use std::fmt::Display;
+use std::ops::{Deref, DerefMut};
+use std::str::FromStr;
+
+use axum_core::extract::rejection::BytesRejection;
+use axum_core::extract::{FromRequest, Request};
+use axum_core::response::{IntoResponse, Response};
+use http::StatusCode;
+
+use crate::config::Config;
+use crate::details::{bytes_rejection_response, ProblemDetails};
+
+#[doc(hidden)]
+macro_rules! __log_rejection {
+    (
+        rejection_type = $ty:ident,
+        status = $status:expr,
+    ) => {
+        {
+            tracing::event!(
+                target: "eserde_axum::text::rejection",
+                tracing::Level::TRACE,
+                status = $status.as_u16(),
+                rejection_type = ::std::any::type_name::<$ty>(),
+                "rejecting request",
+            );
+        }
+    };
+}
+
+/// Extractor for request bodies holding a single value as plain text, e.g. a token or a number.
+///
+/// The body is read as UTF-8 and parsed via [`FromStr`], as is: surrounding whitespace
+/// (including a trailing newline) is not trimmed.
+/// Like axum's `String` extractor, `Text` doesn't look at the `Content-Type` header.
+///
+/// The request will be rejected (and a [`TextRejection`] will be returned) if:
+///
+/// - The body isn't valid UTF-8.
+/// - The body can't be parsed into the target type.
+/// - Buffering the request body fails.
+///
+/// ⚠️ Since parsing the body requires consuming it, the `Text` extractor must be
+/// *last* if there are multiple extractors in a handler.
+/// See ["the order of extractors"][order-of-extractors]
+///
+/// [order-of-extractors]: https://docs.rs/axum/latest/axum/extract/index.html#the-order-of-extractors
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{routing::put, Router};
+/// use eserde_axum::Text;
+///
+/// async fn set_quota(Text(quota): Text<u64>) -> String {
+///     format!("The quota is now {quota}")
+/// }
+///
+/// let app = Router::new().route("/quota", put(set_quota));
+/// # let _: Router = app;
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+#[must_use]
+pub struct Text<T>(pub T);
+
+impl<T, S> FromRequest<S> for Text<T>
+where
+    T: FromStr,
+    T::Err: Display,
+    S: Send + Sync,
+{
+    type Rejection = TextRejection;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let config = Config::from_request(&req);
+        let (bytes, _permit) =
+            crate::body::read_body::<_, Self::Rejection>(req, state, &config).await?;
+        let Ok(text) = std::str::from_utf8(&bytes) else {
+            return Err(InvalidUtf8 { config }.into());
+        };
+        match text.parse() {
+            Ok(value) => Ok(Text(value)),
+            Err(e) => Err(ParseError {
+                detail: e.to_string(),
+                config,
+            }
+            .into()),
+        }
+    }
+}
+
+impl<T> Deref for Text<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Text<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T> From<T> for Text<T> {
+    fn from(inner: T) -> Self {
+        Self(inner)
+    }
+}
+
+#[derive(Debug)]
+#[non_exhaustive]
+/// Rejection type for [`Text`] used if the request body isn't valid UTF-8.
+pub struct InvalidUtf8 {
+    pub(crate) config: Config,
+}
+
+impl IntoResponse for InvalidUtf8 {
+    fn into_response(self) -> Response {
+        let status = StatusCode::BAD_REQUEST;
+        let problem: ProblemDetails<()> = ProblemDetails::new(
+            "invalid_encoding",
+            status,
+            "The request body is not valid UTF-8",
+            self.to_string(),
+        )
+        .configure(&self.config);
+        __log_rejection!(rejection_type = InvalidUtf8, status = status,);
+        problem.into_response()
+    }
+}
+
+impl std::fmt::Display for InvalidUtf8 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Expected a UTF-8 encoded request body")
+    }
+}
+
+impl std::error::Error for InvalidUtf8 {}
+
+#[derive(Debug)]
+#[non_exhaustive]
+/// Rejection type for [`Text`] used if the request body can't be parsed
+/// into the target type.
+///
+/// The error returned by [`FromStr::from_str`] is used as the `detail`
+/// of the problem.
+pub struct ParseError {
+    pub(crate) detail: String,
+    pub(crate) config: Config,
+}
+
+impl IntoResponse for ParseError {
+    fn into_response(self) -> Response {
+        let status = StatusCode::BAD_REQUEST;
+        let problem: ProblemDetails<()> = ProblemDetails::new(
+            "parse_error",
+            status,
+            "The request body couldn't be parsed",
+            self.detail,
+        )
+        .configure(&self.config);
+        __log_rejection!(rejection_type = ParseError, status = status,);
+        problem.into_response()
+    }
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Failed to parse the request body: {}", self.detail)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Rejection used for [`Text`].
+///
+/// Contains one variant for each way the [`Text`] extractor
+/// can fail.
+///
+/// All error responses follow the problem details specification,
+/// as outlined in [RFC 9457](https://www.rfc-editor.org/rfc/rfc9457.html).
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum TextRejection {
+    #[allow(missing_docs)]
+    InvalidUtf8(InvalidUtf8),
+    #[allow(missing_docs)]
+    ParseError(ParseError),
+    #[allow(missing_docs)]
+    BytesRejection(BytesRejection),
+    #[allow(missing_docs)]
+    ContentTooLarge(crate::body::ContentTooLarge),
+    #[allow(missing_docs)]
+    Overloaded(crate::body::Overloaded),
+    #[allow(missing_docs)]
+    InvalidContentLength(crate::body::InvalidContentLength),
+    #[cfg(feature = "timeout")]
+    #[allow(missing_docs)]
+    RequestTimeout(crate::timeout::RequestTimeout),
+}
+
+impl IntoResponse for TextRejection {
+    fn into_response(self) -> Response {
+        match self {
+            Self::InvalidUtf8(inner) => inner.into_response(),
+            Self::ParseError(inner) => inner.into_response(),
+            Self::BytesRejection(inner) => bytes_rejection_response(inner),
+            Self::ContentTooLarge(inner) => inner.into_response(),
+            Self::Overloaded(inner) => inner.into_response(),
+            Self::InvalidContentLength(inner) => inner.into_response(),
+            #[cfg(feature = "timeout")]
+            Self::RequestTimeout(inner) => inner.into_response(),
+        }
+    }
+}
+
+impl From<InvalidUtf8> for TextRejection {
+    fn from(inner: InvalidUtf8) -> Self {
+        Self::InvalidUtf8(inner)
+    }
+}
+impl From<ParseError> for TextRejection {
+    fn from(inner: ParseError) -> Self {
+        Self::ParseError(inner)
+    }
+}
+impl From<BytesRejection> for TextRejection {
+    fn from(inner: BytesRejection) -> Self {
+        Self::BytesRejection(inner)
+    }
+}
+impl From<crate::body::ContentTooLarge> for TextRejection {
+    fn from(inner: crate::body::ContentTooLarge) -> Self {
+        Self::ContentTooLarge(inner)
+    }
+}
+impl From<crate::body::Overloaded> for TextRejection {
+    fn from(inner: crate::body::Overloaded) -> Self {
+        Self::Overloaded(inner)
+    }
+}
+impl From<crate::body::InvalidContentLength> for TextRejection {
+    fn from(inner: crate::body::InvalidContentLength) -> Self {
+        Self::InvalidContentLength(inner)
+    }
+}
+#[cfg(feature = "timeout")]
+impl From<crate::timeout::RequestTimeout> for TextRejection {
+    fn from(inner: crate::timeout::RequestTimeout) -> Self {
+        Self::RequestTimeout(inner)
+    }
+}
+impl std::fmt::Display for TextRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidUtf8(inner) => write!(f, "{inner}"),
+            Self::ParseError(inner) => write!(f, "{inner}"),
+            Self::BytesRejection(inner) => write!(f, "{inner}"),
+            Self::ContentTooLarge(inner) => write!(f, "{inner}"),
+            Self::Overloaded(inner) => write!(f, "{inner}"),
+            Self::InvalidContentLength(inner) => write!(f, "{inner}"),
+            #[cfg(feature = "timeout")]
+            Self::RequestTimeout(inner) => write!(f, "{inner}"),
+        }
+    }
+}
+impl std::error::Error for TextRejection {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InvalidUtf8(inner) => inner.source(),
+            Self::ParseError(inner) => inner.source(),
+            Self::BytesRejection(inner) => inner.source(),
+            Self::ContentTooLarge(inner) => inner.source(),
+            Self::Overloaded(inner) => inner.source(),
+            Self::InvalidContentLength(inner) => inner.source(),
+            #[cfg(feature = "timeout")]
+            Self::RequestTimeout(inner) => inner.source(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils;
+    use axum_core::body::Body;
+
+    fn request(body: impl Into<Body>) -> Request {
+        Request::builder()
+            .method("PUT")
+            .uri("/")
+            .header(http::header::CONTENT_TYPE, "text/plain")
+            .body(body.into())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_valid_text() {
+        let Text(quota) = Text::<u64>::from_request(request("42"), &()).await.unwrap();
+        assert_eq!(quota, 42);
+    }
+
+    #[tokio::test]
+    async fn test_parse_error() {
+        let rejection = Text::<u64>::from_request(request("forty-two"), &())
+            .await
+            .unwrap_err();
+
+        let (status, body) = test_utils::problem(rejection).await;
+        assert_eq!(status, 400);
+        assert_eq!(body["type"], "parse_error");
+        assert_eq!(body["detail"], "invalid digit found in string");
+    }
+
+    #[tokio::test]
+    async fn test_invalid_utf8() {
+        let rejection = Text::<String>::from_request(request(&b"\xff\xfe"[..]), &())
+            .await
+            .unwrap_err();
+        assert!(matches!(rejection, TextRejection::InvalidUtf8(_)));
+    }
+}
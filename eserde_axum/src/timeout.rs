@@ -0,0 +1,165 @@
+//! Bound the time spent reading request bodies.
+//!
+//! See [`Config::body_read_timeout`](crate::Config::body_read_timeout) for more details.
+use std::time::Duration;
+
+use axum_core::extract::rejection::BytesRejection;
+use axum_core::extract::{FromRequest, Request};
+use axum_core::response::{IntoResponse, Response};
+use bytes::Bytes;
+use http::header::CONNECTION;
+use http::{HeaderValue, StatusCode};
+
+use crate::config::Config;
+use crate::details::ProblemDetails;
+
+#[doc(hidden)]
+macro_rules! __log_rejection {
+    (
+        rejection_type = $ty:ident,
+        status = $status:expr,
+    ) => {
+        {
+            tracing::event!(
+                target: "eserde_axum::timeout::rejection",
+                tracing::Level::TRACE,
+                status = $status.as_u16(),
+                rejection_type = ::std::any::type_name::<$ty>(),
+                "rejecting request",
+            );
+        }
+    };
+}
+
+/// Buffer the request body, giving up if it takes longer than the
+/// [configured timeout](Config::body_read_timeout).
+pub(crate) async fn buffer<S, R>(req: Request, state: &S, config: &Config) -> Result<Bytes, R>
+where
+    S: Send + Sync,
+    R: From<BytesRejection> + From<RequestTimeout>,
+{
+    let Some(timeout) = config.settings.body_read_timeout else {
+        return Ok(Bytes::from_request(req, state).await?);
+    };
+    match tokio::time::timeout(timeout, Bytes::from_request(req, state)).await {
+        Ok(bytes) => Ok(bytes?),
+        Err(_) => Err(RequestTimeout {
+            timeout,
+            config: config.clone(),
+        }
+        .into()),
+    }
+}
+
+#[derive(Debug)]
+#[non_exhaustive]
+/// Rejection type used by the extractors of this crate if the request body
+/// isn't received within [`Config::body_read_timeout`].
+///
+/// The response asks for the connection to be closed, since the unread part
+/// of the body may still be on its way.
+pub struct RequestTimeout {
+    pub(crate) timeout: Duration,
+    pub(crate) config: Config,
+}
+
+impl IntoResponse for RequestTimeout {
+    fn into_response(self) -> Response {
+        let status = StatusCode::REQUEST_TIMEOUT;
+        let problem: ProblemDetails<()> = ProblemDetails::new(
+            "request_timeout",
+            status,
+            "The request body took too long to arrive",
+            self.to_string(),
+        )
+        .configure(&self.config);
+        __log_rejection!(rejection_type = RequestTimeout, status = status,);
+        let mut response = problem.into_response();
+        response
+            .headers_mut()
+            .insert(CONNECTION, HeaderValue::from_static("close"));
+        response
+    }
+}
+
+impl std::fmt::Display for RequestTimeout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "The request body wasn't received within {}ms",
+            self.timeout.as_millis()
+        )
+    }
+}
+
+impl std::error::Error for RequestTimeout {}
+
+#[cfg(all(test, feature = "json"))]
+mod tests {
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+
+    use axum_core::body::Body;
+    use http_body::Frame;
+
+    use super::*;
+    use crate::{test_utils, Json};
+
+    /// A body that sends its first chunk, then never completes.
+    struct Trickle(Option<Bytes>);
+
+    impl http_body::Body for Trickle {
+        type Data = Bytes;
+        type Error = std::convert::Infallible;
+
+        fn poll_frame(
+            mut self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+        ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+            match self.0.take() {
+                Some(chunk) => Poll::Ready(Some(Ok(Frame::data(chunk)))),
+                None => Poll::Pending,
+            }
+        }
+    }
+
+    fn slow_request(config: Config) -> Request {
+        Request::builder()
+            .method("POST")
+            .uri("/")
+            .header(http::header::CONTENT_TYPE, "application/json")
+            .extension(config)
+            .body(Body::new(Trickle(Some(Bytes::from_static(b"{\"name\": ")))))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_slow_body() {
+        let config = Config::new().body_read_timeout(Duration::from_millis(20));
+        let rejection = Json::<serde_json::Value>::from_request(slow_request(config), &())
+            .await
+            .unwrap_err();
+        assert!(matches!(rejection, crate::JsonRejection::RequestTimeout(_)));
+
+        let response = rejection.into_response();
+        assert_eq!(response.headers()[CONNECTION], "close");
+        let (status, body) = test_utils::problem(response).await;
+        assert_eq!(status, StatusCode::REQUEST_TIMEOUT);
+        assert_eq!(body["type"], "request_timeout");
+        assert_eq!(body["status"], 408);
+        assert_eq!(
+            body["detail"],
+            "The request body wasn't received within 20ms"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_body_within_timeout() {
+        let request = test_utils::json_request(r#"{"name": "Alice"}"#)
+            .extension(Config::new().body_read_timeout(Duration::from_secs(5)));
+        let Json(value) = Json::<serde_json::Value>::from_request(request.body(), &())
+            .await
+            .unwrap();
+        assert_eq!(value["name"], "Alice");
+    }
+}
@@ -0,0 +1,144 @@
+//! Turn the panics raised while deserializing request bodies into a 500 problem response.
+//!
+//! See [`DeserializationPanicked`] for more details.
+use std::panic::AssertUnwindSafe;
+
+use axum_core::response::{IntoResponse, Response};
+use http::StatusCode;
+
+use crate::config::Config;
+
+#[doc(hidden)]
+macro_rules! __log_rejection {
+    (
+        rejection_type = $ty:ident,
+        status = $status:expr,
+    ) => {
+        {
+            tracing::event!(
+                target: "eserde_axum::unwind::rejection",
+                tracing::Level::TRACE,
+                status = $status.as_u16(),
+                rejection_type = ::std::any::type_name::<$ty>(),
+                "rejecting request",
+            );
+        }
+    };
+}
+
+/// Rejection type for the JSON extractors used if deserializing the request body panicked,
+/// e.g. because of a bug in a custom `Deserialize` implementation or in the
+/// [`DeserializeSeed`](serde::de::DeserializeSeed) behind a
+/// [`SeededJson`](crate::json::SeededJson).
+///
+/// The panic is caught and logged, as an `ERROR` event with the `eserde_axum::unwind`
+/// target, and the client gets a generic `500 Internal Server Error` problem instead
+/// of a dropped connection. No detail about the panic is leaked to the client.
+///
+/// Panics can only be caught if the binary is compiled with `panic = "unwind"`,
+/// the default. The panic hook still runs as usual.
+#[derive(Debug)]
+#[non_exhaustive]
+#[cfg_attr(docsrs, doc(cfg(feature = "catch-unwind")))]
+pub struct DeserializationPanicked {
+    pub(crate) message: String,
+    pub(crate) config: Config,
+}
+
+impl DeserializationPanicked {
+    /// The message the deserializer panicked with.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl IntoResponse for DeserializationPanicked {
+    fn into_response(self) -> Response {
+        __log_rejection!(
+            rejection_type = DeserializationPanicked,
+            status = StatusCode::INTERNAL_SERVER_ERROR,
+        );
+        self.config.internal_server_error_response()
+    }
+}
+
+impl std::fmt::Display for DeserializationPanicked {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "The deserialization of the request panicked: {}",
+            self.message
+        )
+    }
+}
+
+impl std::error::Error for DeserializationPanicked {}
+
+/// Run `f`, turning a panic into a [`DeserializationPanicked`] error.
+pub(crate) fn catch_unwind<R>(
+    config: &Config,
+    f: impl FnOnce() -> R,
+) -> Result<R, DeserializationPanicked> {
+    std::panic::catch_unwind(AssertUnwindSafe(f)).map_err(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|message| (*message).to_owned())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "Box<dyn Any>".to_owned());
+        tracing::event!(
+            target: "eserde_axum::unwind",
+            tracing::Level::ERROR,
+            panic.message = %message,
+            "deserialization panicked",
+        );
+        DeserializationPanicked {
+            message,
+            config: config.clone(),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use axum_core::extract::FromRequest;
+
+    use super::*;
+    use crate::{test_utils, Json, JsonRejection};
+
+    /// A type whose `Deserialize` implementation is buggy.
+    #[derive(Debug)]
+    struct Buggy;
+
+    impl<'de> serde::Deserialize<'de> for Buggy {
+        fn deserialize<D>(_deserializer: D) -> Result<Self, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            panic!("unreachable code reached");
+        }
+    }
+
+    #[derive(Debug, eserde::Deserialize)]
+    #[allow(dead_code)]
+    struct Payload {
+        #[eserde(compat)]
+        buggy: Buggy,
+    }
+
+    #[tokio::test]
+    async fn test_panic_becomes_problem() {
+        let request = test_utils::json_request(r#"{"buggy": 1}"#);
+        let rejection = Json::<Payload>::from_request(request.body(), &())
+            .await
+            .unwrap_err();
+        let JsonRejection::DeserializationPanicked(panicked) = &rejection else {
+            panic!("Expected a panic rejection, got {rejection:?}");
+        };
+        assert_eq!(panicked.message(), "unreachable code reached");
+
+        let (status, body) = test_utils::problem(rejection).await;
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(body["type"], "internal_server_error");
+        assert!(!body["detail"].as_str().unwrap().contains("unreachable"));
+    }
+}
@@ -0,0 +1,225 @@
+//! Extract and validate the API version from the request path.
+//!
+//! See [`ApiVersion`] for more details.
+use std::borrow::Cow;
+use std::sync::Arc;
+
+use axum_core::extract::FromRequestParts;
+use axum_core::response::{IntoResponse, Response};
+use http::request::Parts;
+use http::StatusCode;
+
+use crate::config::Config;
+use crate::details::ProblemDetails;
+
+#[doc(hidden)]
+macro_rules! __log_rejection {
+    (
+        rejection_type = $ty:ident,
+        status = $status:expr,
+    ) => {
+        {
+            tracing::event!(
+                target: "eserde_axum::version::rejection",
+                tracing::Level::TRACE,
+                status = $status.as_u16(),
+                rejection_type = ::std::any::type_name::<$ty>(),
+                "rejecting request",
+            );
+        }
+    };
+}
+
+/// Extractor for the API version specified in the request path, e.g. `v1` in `/v1/users`.
+///
+/// The version is read from a path segment—the first one, unless specified otherwise via
+/// [`Config::api_version_segment`]—and checked against the versions listed via
+/// [`Config::supported_api_versions`].
+/// The request will be rejected (and an [`UnsupportedVersion`] will be returned) if the
+/// segment is missing or if it doesn't match any of the supported versions.
+///
+/// The extractor doesn't affect routing: the version segment must still be
+/// matched by your routes, e.g. via a `/{version}` prefix.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{routing::get, Extension, Router};
+/// use eserde_axum::{ApiVersion, Config};
+///
+/// async fn list_users(version: ApiVersion) -> String {
+///     format!("Listing users with the {} API", version.as_str())
+/// }
+///
+/// let app = Router::new()
+///     .route("/{version}/users", get(list_users))
+///     .layer(Extension(Config::new().supported_api_versions(["v1", "v2"])));
+/// # let _: Router = app;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiVersion(Cow<'static, str>);
+
+impl ApiVersion {
+    /// The requested version, e.g. `v1`.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl PartialEq<str> for ApiVersion {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for ApiVersion {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+impl<S> FromRequestParts<S> for ApiVersion
+where
+    S: Send + Sync,
+{
+    type Rejection = UnsupportedVersion;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let config = Config::from_request(parts);
+        let segment = parts
+            .uri
+            .path()
+            .trim_start_matches('/')
+            .split('/')
+            .nth(config.settings.api_version_segment)
+            .filter(|segment| !segment.is_empty());
+        let supported = segment.and_then(|segment| {
+            config
+                .settings
+                .supported_api_versions
+                .iter()
+                .find(|version| *version == segment)
+        });
+        match supported {
+            Some(version) => Ok(Self(version.clone())),
+            None => Err(UnsupportedVersion {
+                actual: segment.map(ToOwned::to_owned),
+                supported: config.settings.supported_api_versions.clone(),
+                config,
+            }),
+        }
+    }
+}
+
+#[derive(Debug)]
+#[non_exhaustive]
+/// Rejection type for [`ApiVersion`].
+///
+/// This rejection is used if the version segment of the path is missing
+/// or if it doesn't match any of the supported versions.
+/// It is rendered as a `404 Not Found` problem, listing the supported
+/// versions in the `supported_versions` member.
+pub struct UnsupportedVersion {
+    pub(crate) actual: Option<String>,
+    pub(crate) supported: Arc<[Cow<'static, str>]>,
+    pub(crate) config: Config,
+}
+
+impl UnsupportedVersion {
+    /// The versions accepted by the extractor.
+    pub fn supported(&self) -> impl Iterator<Item = &str> {
+        self.supported.iter().map(|version| version.as_ref())
+    }
+}
+
+#[derive(serde::Serialize)]
+struct SupportedVersions {
+    supported_versions: Arc<[Cow<'static, str>]>,
+}
+
+impl IntoResponse for UnsupportedVersion {
+    fn into_response(self) -> Response {
+        let status = StatusCode::NOT_FOUND;
+        let detail = self.to_string();
+        let problem = ProblemDetails {
+            extensions: Some(SupportedVersions {
+                supported_versions: self.supported,
+            }),
+            ..ProblemDetails::new(
+                "unsupported_version",
+                status,
+                "The API version is not supported",
+                detail,
+            )
+        }
+        .configure(&self.config);
+        __log_rejection!(rejection_type = UnsupportedVersion, status = status,);
+        problem.into_response()
+    }
+}
+
+impl std::fmt::Display for UnsupportedVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.actual {
+            Some(actual) => write!(f, "The API version `{actual}` is not supported"),
+            None => write!(f, "Expected an API version in the request path"),
+        }
+    }
+}
+
+impl std::error::Error for UnsupportedVersion {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils;
+    use http::Request;
+
+    async fn extract(uri: &str, config: Config) -> Result<ApiVersion, UnsupportedVersion> {
+        let request = Request::builder()
+            .uri(uri)
+            .extension(config)
+            .body(())
+            .unwrap();
+        let (mut parts, _) = request.into_parts();
+        ApiVersion::from_request_parts(&mut parts, &()).await
+    }
+
+    #[tokio::test]
+    async fn test_supported_version() {
+        let config = Config::new().supported_api_versions(["v1", "v2"]);
+        let version = extract("/v2/users", config).await.unwrap();
+        assert_eq!(version, "v2");
+
+        let config = Config::new()
+            .supported_api_versions(["v1"])
+            .api_version_segment(1);
+        let version = extract("/api/v1/users", config).await.unwrap();
+        assert_eq!(version.as_str(), "v1");
+    }
+
+    #[tokio::test]
+    async fn test_unsupported_version() {
+        let config = Config::new().supported_api_versions(["v1", "v2"]);
+        let rejection = extract("/v3/users", config).await.unwrap_err();
+
+        let (status, body) = test_utils::problem(rejection).await;
+        assert_eq!(status, 404);
+        assert_eq!(body["type"], "unsupported_version");
+        assert_eq!(body["detail"], "The API version `v3` is not supported");
+        assert_eq!(body["supported_versions"], serde_json::json!(["v1", "v2"]));
+    }
+
+    #[tokio::test]
+    async fn test_missing_version() {
+        let config = Config::new().supported_api_versions(["v1"]);
+        let rejection = extract("/", config).await.unwrap_err();
+
+        let (status, body) = test_utils::problem(rejection).await;
+        assert_eq!(status, 404);
+        assert_eq!(
+            body["detail"],
+            "Expected an API version in the request path"
+        );
+    }
+}
@@ -0,0 +1,32 @@
+#![cfg(feature = "json")]
+//! Check that handlers using `Json` and its rejection type satisfy `#[axum::debug_handler]`.
+#![allow(dead_code)]
+
+use axum::{routing::post, Router};
+use eserde_axum::{Json, JsonRejection};
+
+#[derive(eserde::Deserialize)]
+struct CreateUser {
+    email: String,
+    password: String,
+}
+
+#[axum::debug_handler]
+async fn infallible(Json(payload): Json<CreateUser>) -> Json<String> {
+    Json(payload.email)
+}
+
+#[axum::debug_handler]
+async fn fallible(
+    payload: Result<Json<CreateUser>, JsonRejection>,
+) -> Result<Json<String>, JsonRejection> {
+    let Json(payload) = payload?;
+    Ok(Json(payload.email))
+}
+
+#[test]
+fn handlers_compile() {
+    let _: Router = Router::new()
+        .route("/infallible", post(infallible))
+        .route("/fallible", post(fallible));
+}
@@ -0,0 +1,55 @@
+#![cfg(feature = "thiserror")]
+//! Check that `#[derive(IntoProblem)]` maps each variant to its problem.
+
+use axum::response::IntoResponse;
+use eserde_axum::IntoProblem;
+use http_body_util::BodyExt;
+
+#[derive(Debug, thiserror::Error, IntoProblem)]
+enum OrderError {
+    #[error("Order {id} was already shipped")]
+    #[problem(status = 409, type = "order_shipped")]
+    Shipped { id: u64 },
+    #[error("The payment provider is down: {0}")]
+    #[problem(
+        status = 503,
+        type = "payment_unavailable",
+        title = "Payments are unavailable",
+        detail = "Try again later"
+    )]
+    PaymentUnavailable(String),
+}
+
+async fn problem(error: OrderError) -> (u16, serde_json::Value) {
+    let response = error.into_response();
+    let status = response.status().as_u16();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+#[tokio::test]
+async fn test_variants_map_to_their_problem() {
+    let (status, body) = problem(OrderError::Shipped { id: 42 }).await;
+    assert_eq!(status, 409);
+    assert_eq!(
+        body,
+        serde_json::json!({
+            "type": "order_shipped",
+            "status": 409,
+            "title": "Conflict",
+            "detail": "Order 42 was already shipped",
+        })
+    );
+
+    let (status, body) = problem(OrderError::PaymentUnavailable("timeout".into())).await;
+    assert_eq!(status, 503);
+    assert_eq!(
+        body,
+        serde_json::json!({
+            "type": "payment_unavailable",
+            "status": 503,
+            "title": "Payments are unavailable",
+            "detail": "Try again later",
+        })
+    );
+}
@@ -0,0 +1,136 @@
+//! The `Combine` derive macro, see `eserde_axum::combined::Combine`.
+use proc_macro2::Span;
+use quote::quote;
+use syn::spanned::Spanned;
+use syn::{Attribute, Data, DeriveInput, Error, Field, Fields, LitStr, Result};
+
+pub(crate) fn expand(input: &DeriveInput) -> Result<proc_macro2::TokenStream> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new(
+                    Span::call_site(),
+                    "`Combine` can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new(
+                Span::call_site(),
+                "`Combine` can only be derived for structs",
+            ))
+        }
+    };
+    if let Some(attr) = find_serde_meta(&input.attrs, "rename_all")? {
+        return Err(Error::new_spanned(
+            attr,
+            "`Combine` doesn't support `#[serde(rename_all = \"...\")]`, \
+            rename the fields one by one via `#[serde(rename = \"...\")]`",
+        ));
+    }
+    let entries = fields
+        .iter()
+        .map(|field| {
+            let part = part(field)?;
+            let name = serde_name(field)?;
+            Ok(quote! { (#name, ::eserde_axum::combined::Part::#part) })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics ::eserde_axum::combined::Combine for #name #ty_generics
+        #where_clause
+        {
+            const FIELDS: &'static [(&'static str, ::eserde_axum::combined::Part)] = &[
+                #(#entries),*
+            ];
+        }
+    })
+}
+
+/// The request part a field is marked with, e.g. `#[combined(query)]`.
+fn part(field: &Field) -> Result<proc_macro2::Ident> {
+    let mut part = None;
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("combined"))
+    {
+        attr.parse_nested_meta(|meta| {
+            let variant = if meta.path.is_ident("path") {
+                "Path"
+            } else if meta.path.is_ident("query") {
+                "Query"
+            } else if meta.path.is_ident("body") {
+                "Body"
+            } else {
+                return Err(meta.error("expected `path`, `query` or `body`"));
+            };
+            if part.is_some() {
+                return Err(meta.error("a field can only be marked with one request part"));
+            }
+            part = Some(proc_macro2::Ident::new(variant, meta.path.span()));
+            Ok(())
+        })?;
+    }
+    part.ok_or_else(|| {
+        Error::new_spanned(
+            field,
+            "missing `#[combined(...)]`, e.g. `#[combined(path)]`, `#[combined(query)]` \
+            or `#[combined(body)]`",
+        )
+    })
+}
+
+/// The name `serde` knows the field by: its identifier,
+/// unless it's renamed via `#[serde(rename = "...")]`.
+fn serde_name(field: &Field) -> Result<LitStr> {
+    let ident = field.ident.as_ref().expect("the fields are named");
+    if let Some(name) = find_serde_meta(&field.attrs, "rename")? {
+        return Ok(name);
+    }
+    let name = ident.to_string();
+    let name = name.strip_prefix("r#").unwrap_or(&name);
+    Ok(LitStr::new(name, ident.span()))
+}
+
+/// The value of `#[serde(key = "...")]`, or of `#[serde(key(deserialize = "..."))]`.
+fn find_serde_meta(attrs: &[Attribute], key: &str) -> Result<Option<LitStr>> {
+    let mut value = None;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("serde")) {
+        attr.parse_nested_meta(|meta| {
+            if !meta.path.is_ident(key) {
+                // Skip the value of the attributes we aren't interested in, if any.
+                if meta.input.peek(syn::Token![=]) {
+                    meta.value()?.parse::<syn::Expr>()?;
+                } else if meta.input.peek(syn::token::Paren) {
+                    meta.parse_nested_meta(|nested| {
+                        if nested.input.peek(syn::Token![=]) {
+                            nested.value()?.parse::<syn::Expr>()?;
+                        }
+                        Ok(())
+                    })?;
+                }
+                return Ok(());
+            }
+            if meta.input.peek(syn::Token![=]) {
+                value = Some(meta.value()?.parse()?);
+            } else {
+                meta.parse_nested_meta(|nested| {
+                    if nested.path.is_ident("deserialize") {
+                        value = Some(nested.value()?.parse()?);
+                    } else {
+                        nested.value()?.parse::<LitStr>()?;
+                    }
+                    Ok(())
+                })?;
+            }
+            Ok(())
+        })?;
+    }
+    Ok(value)
+}
@@ -0,0 +1,159 @@
+//! The `eserde_axum_derive` crate provides the `IntoProblem` and `Combine` derive macros
+//! for the [`eserde_axum`](https://crates.io/crates/eserde_axum) crate.
+//!
+//! You most likely don't want to use `eserde_axum_derive` directly. Instead, enable
+//! the `thiserror` (or `combined`) feature of `eserde_axum` and use its `IntoProblem`
+//! (or `Combine`) re-export.
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, LitInt, LitStr, Result, Variant};
+
+mod combine;
+
+#[proc_macro_derive(IntoProblem, attributes(problem))]
+pub fn derive_into_problem(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+#[proc_macro_derive(Combine, attributes(combined))]
+pub fn derive_combine(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    combine::expand(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: &DeriveInput) -> Result<proc_macro2::TokenStream> {
+    let Data::Enum(data) = &input.data else {
+        return Err(Error::new(
+            Span::call_site(),
+            "`IntoProblem` can only be derived for enums",
+        ));
+    };
+    let name = &input.ident;
+    let arms = data
+        .variants
+        .iter()
+        .map(|variant| {
+            let ProblemAttr {
+                status,
+                type_,
+                title,
+                detail,
+            } = ProblemAttr::parse(variant)?;
+            let ident = &variant.ident;
+            let pattern = match &variant.fields {
+                Fields::Named(_) => quote! { #name::#ident { .. } },
+                Fields::Unnamed(_) => quote! { #name::#ident(..) },
+                Fields::Unit => quote! { #name::#ident },
+            };
+            let detail = match detail {
+                Some(detail) => quote! { #detail },
+                None => quote! { ::std::string::ToString::to_string(&error) },
+            };
+            let title = title.map(|title| quote! { .title(#title) });
+            Ok(quote! {
+                #pattern => ::eserde_axum::Problem::new(
+                    ::eserde_axum::_http::StatusCode::from_u16(#status)
+                        .expect("the status code was validated by `IntoProblem`"),
+                    #type_,
+                    #detail,
+                )
+                #title,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    // An empty enum can't be matched on by reference.
+    let scrutinee = if arms.is_empty() {
+        quote! { error }
+    } else {
+        quote! { &error }
+    };
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics ::core::convert::From<#name #ty_generics> for ::eserde_axum::Problem
+        #where_clause
+        {
+            fn from(error: #name #ty_generics) -> Self {
+                match #scrutinee {
+                    #(#arms)*
+                }
+            }
+        }
+
+        #[automatically_derived]
+        impl #impl_generics ::eserde_axum::_axum_core::response::IntoResponse for #name #ty_generics
+        #where_clause
+        {
+            fn into_response(self) -> ::eserde_axum::_axum_core::response::Response {
+                ::eserde_axum::_axum_core::response::IntoResponse::into_response(
+                    ::eserde_axum::Problem::from(self),
+                )
+            }
+        }
+    })
+}
+
+/// The content of the `#[problem(...)]` attribute of a variant, e.g.
+/// `#[problem(status = 409, type = "conflict")]`.
+struct ProblemAttr {
+    status: u16,
+    type_: LitStr,
+    title: Option<LitStr>,
+    detail: Option<LitStr>,
+}
+
+impl ProblemAttr {
+    fn parse(variant: &Variant) -> Result<Self> {
+        let mut status = None;
+        let mut type_ = None;
+        let mut title = None;
+        let mut detail = None;
+        for attr in variant
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("problem"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("status") {
+                    let lit: LitInt = meta.value()?.parse()?;
+                    let code = lit.base10_parse::<u16>()?;
+                    if !(100..=999).contains(&code) {
+                        return Err(Error::new(
+                            lit.span(),
+                            "the status code must be between 100 and 999",
+                        ));
+                    }
+                    status = Some(code);
+                } else if meta.path.is_ident("type") {
+                    type_ = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("title") {
+                    title = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("detail") {
+                    detail = Some(meta.value()?.parse()?);
+                } else {
+                    return Err(meta.error("expected `status`, `type`, `title` or `detail`"));
+                }
+                Ok(())
+            })?;
+        }
+        let missing = |key: &str| {
+            Error::new_spanned(
+                &variant.ident,
+                format!("missing `{key}` in `#[problem(...)]`, e.g. `#[problem(status = 409, type = \"conflict\")]`"),
+            )
+        };
+        Ok(Self {
+            status: status.ok_or_else(|| missing("status"))?,
+            type_: type_.ok_or_else(|| missing("type"))?,
+            title,
+            detail,
+        })
+    }
+}
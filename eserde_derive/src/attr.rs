@@ -134,3 +134,18 @@ pub fn remove_attr_meta(
         }
     })
 }
+
+/// Returns the machine code set via `#[eserde(code = "..")]` on the field, if any.
+///
+/// Values other than string literals are ignored here; they're rejected
+/// upfront, see `reject_unsupported_inputs`.
+pub fn field_code(field: &syn::Field) -> Option<syn::LitStr> {
+    let (_eq, expr) = find_attr_meta(&field.attrs, "eserde", "code")?.value?;
+    match expr {
+        Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Str(lit_str),
+            ..
+        }) => Some(lit_str),
+        _ => None,
+    }
+}
@@ -2,6 +2,7 @@ use indexmap::IndexSet;
 use quote::{format_ident, quote, ToTokens};
 use syn::{Data, DeriveInput, GenericParam, Generics, Lifetime};
 
+use crate::attr::{field_code, find_attr_meta};
 use crate::model::{PermissiveCompanionType, ShadowType};
 
 impl ToTokens for ShadowType {
@@ -161,6 +162,32 @@ pub fn initialize_from_shadow(
     }
 }
 
+/// The method used to report a missing value for the given field.
+///
+/// `serde` lets fields like `Option<T>` be omitted, unless they use a custom
+/// `deserialize_with` function.
+fn push_error_if_missing(field: &syn::Field) -> syn::Ident {
+    if find_attr_meta(&field.attrs, "serde", "deserialize_with").is_some()
+        || find_attr_meta(&field.attrs, "serde", "with").is_some()
+    {
+        format_ident!("push_error_if_missing")
+    } else {
+        format_ident!("push_error_if_missing_and_required")
+    }
+}
+
+/// Assign the `#[eserde(code = "..")]` of the field, if any, to the errors reported by `report`.
+fn with_code(field: &syn::Field, report: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    match field_code(field) {
+        Some(code) => quote! {
+            ::eserde::_macro_impl::with_code(#code, || #report);
+        },
+        None => quote! {
+            #report;
+        },
+    }
+}
+
 /// Walk all fields on the companion types to report errors about missing values, if any.
 pub fn collect_missing_errors(
     input: &Data,
@@ -170,15 +197,19 @@ pub fn collect_missing_errors(
 ) -> proc_macro2::TokenStream {
     match input {
         Data::Struct(data) => {
-            let accumulate = data.fields.members().map(|field| {
+            let accumulate = data.fields.members().zip(&data.fields).map(|(field, f)| {
+                let push_error = push_error_if_missing(f);
                 let field_str = match &field {
                     syn::Member::Named(ident) => ident.to_string(),
                     // TODO: Improve naming for unnamed fields
                     syn::Member::Unnamed(index) => format!("{}", index.index),
                 };
-                quote! {
-                    #companion_binding.#field.push_error_if_missing(#field_str);
-                }
+                with_code(
+                    f,
+                    quote! {
+                        #companion_binding.#field.#push_error(#field_str)
+                    },
+                )
             });
             quote! {
                 #(#accumulate)*
@@ -218,16 +249,21 @@ pub fn collect_missing_errors(
                 let accumulate = variant
                     .fields
                     .members()
+                    .zip(&variant.fields)
                     .zip(bindings.iter())
-                    .map(|(field, v)| {
+                    .map(|((field, f), v)| {
+                        let push_error = push_error_if_missing(f);
                         let field_str = match &field {
                             syn::Member::Named(ident) => ident.to_string(),
                             // TODO: Improve naming for unnamed fields
                             syn::Member::Unnamed(index) => format!("{}", index.index),
                         };
-                        quote! {
-                            #v.push_error_if_missing(#field_str);
-                        }
+                        with_code(
+                            f,
+                            quote! {
+                                #v.#push_error(#field_str)
+                            },
+                        )
                     });
                 quote! {
                     #companion_type::#variant_ident { #(#destructure),* } => {
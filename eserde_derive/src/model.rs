@@ -1,7 +1,7 @@
 use std::collections::HashSet;
 
 use crate::{
-    attr::{find_attr_meta, remove_attr_meta},
+    attr::{field_code, find_attr_meta, remove_attr_meta},
     filter_attributes::FilterAttributes,
 };
 use indexmap::IndexSet;
@@ -71,6 +71,7 @@ impl PermissiveCompanionType {
                 // they are not valid `serde` attributes.
                 let is_eserde_compatible =
                     find_attr_meta(&field.attrs, "eserde", "compat").is_none();
+                let code = field_code(field);
                 field.attrs.retain(keep_serde_attributes);
 
                 if is_eserde_compatible {
@@ -119,7 +120,12 @@ impl PermissiveCompanionType {
                     syn::parse_quote_spanned!(field_ty.span()=> ::eserde::_macro_impl::MaybeInvalidOrMissing::<#field_ty>)
                 };
 
-                if is_eserde_compatible {
+                // `#[eserde(compat)]` fields are left to `serde`, unless they have a custom
+                // `deserialize_with` function: its errors must be reported like the others.
+                let has_deserialize_with =
+                    find_attr_meta(&field.attrs, "serde", "deserialize_with").is_some()
+                        || find_attr_meta(&field.attrs, "serde", "with").is_some();
+                if is_eserde_compatible || has_deserialize_with {
                     // Add or replace `#[serde(deserialize_with = "..")]` for our wrapper.
 
                     // Handle user `#[serde(deserialize_with = "..")]` or `#[serde(with = "..')]` attributes.
@@ -139,14 +145,7 @@ impl PermissiveCompanionType {
                         // User specified a custom `deserialize_with` function.
                         // We need to wrap it in our own function.
 
-                        let fn_name = format!(
-                            "__eserde_deserialize_with_{}",
-                            field
-                                .ident
-                                .as_ref()
-                                .map(|ident| ident.to_string())
-                                .unwrap_or_else(|| i.to_string()),
-                        );
+                        let fn_name = format!("__eserde_deserialize_with_{}", field_name(field, i));
                         let fn_ident = syn::Ident::new(&fn_name, field.span());
 
                         // Add the method to `deserialize_withs`.
@@ -173,6 +172,14 @@ impl PermissiveCompanionType {
                             field.span(),
                         );
                         syn::parse_quote!(#[serde(deserialize_with = #new_path)])
+                    } else if find_attr_meta(&field.attrs, "serde", "flatten").is_some() {
+                        // `serde` feeds flattened fields with the buffered entries of the parent,
+                        // out of reach of the path tracking set up for the parent.
+                        if has_default {
+                            syn::parse_quote_spanned!(span=> #[serde(deserialize_with = "::eserde::_macro_impl::flattened_maybe_invalid")])
+                        } else {
+                            syn::parse_quote_spanned!(span=> #[serde(deserialize_with = "::eserde::_macro_impl::flattened_maybe_invalid_or_missing")])
+                        }
                     } else if has_default {
                         syn::parse_quote_spanned!(span=> #[serde(deserialize_with = "::eserde::_macro_impl::maybe_invalid")])
                     } else {
@@ -181,6 +188,44 @@ impl PermissiveCompanionType {
                     field.attrs.push(attr);
                 }
 
+                if let Some(code) = code {
+                    // Wrap whatever deserializes the field to assign `code` to the errors it reports.
+                    let deserialize =
+                        remove_attr_meta(&mut field.attrs, "serde", "deserialize_with")
+                            .and_then(|meta_item| meta_str_value(&meta_item))
+                            .and_then(|s| syn::parse_str::<syn::ExprPath>(&s).ok());
+                    let deserialize = match deserialize {
+                        Some(path) => quote::quote!((#path)(deserializer)),
+                        None => {
+                            quote::quote!(<#wrapper_ty as ::eserde::_serde::Deserialize>::deserialize(deserializer))
+                        }
+                    };
+
+                    // Suffixed with a counter, since fields of different variants may share a name.
+                    let fn_name = format!(
+                        "__eserde_code_{}_{}",
+                        field_name(field, i),
+                        impl_items.len()
+                    );
+                    let fn_ident = syn::Ident::new(&fn_name, field.span());
+                    impl_items.push(syn::parse_quote_spanned! {field.span()=>
+                        fn #fn_ident<'de, D>(deserializer: D) -> ::core::result::Result<#wrapper_ty, D::Error>
+                        where
+                            D: ::eserde::_serde::Deserializer<'de>,
+                        {
+                            ::eserde::_macro_impl::with_code(#code, || #deserialize)
+                        }
+                    });
+
+                    let new_path = syn::LitStr::new(
+                        &format!("{}::{}", companion.ident, fn_name),
+                        field.span(),
+                    );
+                    field
+                        .attrs
+                        .push(syn::parse_quote!(#[serde(deserialize_with = #new_path)]));
+                }
+
                 // Done last for ownership.
                 field.ty = wrapper_ty;
             }
@@ -290,6 +335,15 @@ fn collect_generic_type_params(
     }
 }
 
+/// The name of the field, or its index for tuple-like fields.
+fn field_name(field: &syn::Field, index: usize) -> String {
+    field
+        .ident
+        .as_ref()
+        .map(|ident| ident.to_string())
+        .unwrap_or_else(|| index.to_string())
+}
+
 /// If the `MetaItem` has a string literal value, return it as `Some(String)`, otherwise return `None`.
 fn meta_str_value(meta: &crate::attr::MetaItem) -> Option<String> {
     let (_eq, expr) = meta.value.as_ref()?;
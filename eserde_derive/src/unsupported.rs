@@ -1,12 +1,17 @@
 use syn::DeriveInput;
 
-use crate::{attr::find_attr_meta, filter_attributes::FilterAttributes};
+use crate::{
+    attr::{field_code, find_attr_meta},
+    filter_attributes::FilterAttributes,
+};
 
 /// Return a compiler error if the input contains data types or
 /// `serde` attributes that are not supported by our custom derive.
 pub fn reject_unsupported_inputs(input: &DeriveInput) -> Result<(), syn::Error> {
     let mut errors = Vec::new();
 
+    reject_invalid_eserde_attributes(&mut errors, input);
+
     let input = input.filter_attributes(|a| a.meta.path().is_ident("serde"));
     reject_container_attributes(&mut errors, &input.attrs);
 
@@ -40,6 +45,29 @@ pub fn reject_unsupported_inputs(input: &DeriveInput) -> Result<(), syn::Error>
     }
 }
 
+/// Reject `#[eserde(..)]` field attributes with a malformed value.
+fn reject_invalid_eserde_attributes(errors: &mut Vec<syn::Error>, input: &DeriveInput) {
+    let fields: Vec<&syn::Field> = match &input.data {
+        syn::Data::Struct(data_struct) => data_struct.fields.iter().collect(),
+        syn::Data::Enum(data_enum) => data_enum
+            .variants
+            .iter()
+            .flat_map(|variant| variant.fields.iter())
+            .collect(),
+        syn::Data::Union(_) => Vec::new(),
+    };
+    for field in fields {
+        if let Some(meta_item) = find_attr_meta(&field.attrs, "eserde", "code") {
+            if field_code(field).is_none() {
+                errors.push(syn::Error::new_spanned(
+                    meta_item,
+                    "`#[eserde(code = \"..\")]` expects a string literal, e.g. `#[eserde(code = \"AGE_INVALID\")]`.",
+                ));
+            }
+        }
+    }
+}
+
 /// Attributes from <https://serde.rs/container-attrs.html> that we either
 /// can't support or haven't implemented yet.
 fn reject_container_attributes(errors: &mut Vec<syn::Error>, attrs: &[syn::Attribute]) {